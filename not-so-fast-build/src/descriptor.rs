@@ -0,0 +1,103 @@
+//! Subset of `google/protobuf/descriptor.proto` needed to find messages,
+//! fields and their PGV rules. Unlike `prost_types`, [FieldOptions] keeps the
+//! `validate.rules` extension.
+
+use prost::Message;
+
+use crate::rules::FieldRules;
+
+pub(crate) const TYPE_MESSAGE: i32 = 11;
+pub(crate) const LABEL_OPTIONAL: i32 = 1;
+pub(crate) const LABEL_REPEATED: i32 = 3;
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct FileDescriptorSet {
+    #[prost(message, repeated, tag = "1")]
+    pub file: Vec<FileDescriptorProto>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct FileDescriptorProto {
+    #[prost(string, optional, tag = "2")]
+    pub package: Option<String>,
+    #[prost(message, repeated, tag = "4")]
+    pub message_type: Vec<DescriptorProto>,
+    #[prost(string, optional, tag = "12")]
+    pub syntax: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct DescriptorProto {
+    #[prost(string, optional, tag = "1")]
+    pub name: Option<String>,
+    #[prost(message, repeated, tag = "2")]
+    pub field: Vec<FieldDescriptorProto>,
+    #[prost(message, repeated, tag = "3")]
+    pub nested_type: Vec<DescriptorProto>,
+    #[prost(message, optional, tag = "7")]
+    pub options: Option<MessageOptions>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct MessageOptions {
+    #[prost(bool, optional, tag = "7")]
+    pub map_entry: Option<bool>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct FieldDescriptorProto {
+    #[prost(string, optional, tag = "1")]
+    pub name: Option<String>,
+    #[prost(int32, optional, tag = "3")]
+    pub number: Option<i32>,
+    #[prost(int32, optional, tag = "4")]
+    pub label: Option<i32>,
+    #[prost(int32, optional, tag = "5")]
+    pub r#type: Option<i32>,
+    #[prost(string, optional, tag = "6")]
+    pub type_name: Option<String>,
+    #[prost(message, optional, tag = "8")]
+    pub options: Option<FieldOptions>,
+    #[prost(int32, optional, tag = "9")]
+    pub oneof_index: Option<i32>,
+    #[prost(bool, optional, tag = "17")]
+    pub proto3_optional: Option<bool>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct FieldOptions {
+    /// Encoded `validate.rules` extension, kept as bytes to detect rules that
+    /// [FieldRules] doesn't know.
+    #[prost(bytes = "vec", optional, tag = "1071")]
+    pub rules: Option<Vec<u8>>,
+}
+
+impl DescriptorProto {
+    pub(crate) fn is_map_entry(&self) -> bool {
+        self.options
+            .as_ref()
+            .and_then(|options| options.map_entry)
+            .unwrap_or(false)
+    }
+}
+
+impl FieldDescriptorProto {
+    pub(crate) fn is_message(&self) -> bool {
+        self.r#type == Some(TYPE_MESSAGE)
+    }
+
+    /// Decodes PGV rules of the field. The flag tells whether all rules were
+    /// recognized.
+    pub(crate) fn rules(&self) -> Result<Option<(FieldRules, bool)>, prost::DecodeError> {
+        let Some(bytes) = self
+            .options
+            .as_ref()
+            .and_then(|options| options.rules.as_ref())
+        else {
+            return Ok(None);
+        };
+        let rules = FieldRules::decode(bytes.as_slice())?;
+        let complete = rules.encoded_len() == bytes.len();
+        Ok(Some((rules, complete)))
+    }
+}
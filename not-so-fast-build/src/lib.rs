@@ -0,0 +1,477 @@
+//! Build-time support for validating messages generated by `prost-build`
+//! with `not-so-fast`.
+//!
+//! [Builder] derives `Validate` for generated messages and annotates their
+//! fields with derive validators, translated from [PGV] rules
+//! (`(validate.rules)` field options) and added by hand. Validation of decoded
+//! messages then reports the same [ValidationNode] trees as validation of
+//! hand-written structs.
+//!
+//! ```no_run
+//! // build.rs
+//! fn main() -> std::io::Result<()> {
+//!     not_so_fast_build::Builder::new()
+//!         .field(".shop.Order.note", "custom = crate::validators::no_html")
+//!         .compile_protos(
+//!             &mut prost_build::Config::new(),
+//!             &["proto/shop.proto"],
+//!             &["proto"],
+//!         )
+//! }
+//! ```
+//!
+//! Generated code requires `not-so-fast` with the `derive` feature. PGV rules
+//! without derive counterpart, like `pattern`, `in` or `required`, are
+//! reported as `cargo:warning`s and ignored. So are `min_len` and `max_len`
+//! (or `min_bytes` and `max_bytes`) of fields with exact `len` (`len_bytes`).
+//! Fields of oneofs are not validated.
+//!
+//! [PGV]: https://github.com/bufbuild/protoc-gen-validate
+//! [ValidationNode]: https://docs.rs/not-so-fast/latest/not_so_fast/struct.ValidationNode.html
+
+mod descriptor;
+mod rules;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use prost::Message;
+
+use descriptor::{
+    DescriptorProto, FieldDescriptorProto, FileDescriptorSet, LABEL_OPTIONAL, LABEL_REPEATED,
+};
+use rules::{Bounds, FieldRules};
+
+/// Attribute deriving `Validate` for generated messages.
+pub const DERIVE_VALIDATE: &str = "#[derive(::not_so_fast::Validate)]";
+
+/// Configures `prost-build` to generate messages implementing `Validate`.
+/// ```
+/// use prost::Message;
+/// use prost_types::{field_descriptor_proto::Type, DescriptorProto, FieldDescriptorProto};
+/// use prost_types::{FileDescriptorProto, FileDescriptorSet};
+///
+/// let descriptor_set = FileDescriptorSet {
+///     file: vec![FileDescriptorProto {
+///         package: Some("shop".into()),
+///         message_type: vec![DescriptorProto {
+///             name: Some("User".into()),
+///             field: vec![FieldDescriptorProto {
+///                 name: Some("name".into()),
+///                 number: Some(1),
+///                 r#type: Some(Type::String.into()),
+///                 ..Default::default()
+///             }],
+///             ..Default::default()
+///         }],
+///         ..Default::default()
+///     }],
+/// };
+///
+/// let attributes = not_so_fast_build::Builder::new()
+///     .field(".shop.User.name", "char_length(max = 30)")
+///     .attributes(&descriptor_set.encode_to_vec())
+///     .unwrap();
+///
+/// assert_eq!(&[".shop".to_owned()], attributes.derives());
+/// assert_eq!(
+///     &[(".shop.User.name".to_owned(), "#[validate(char_length(max = 30))]".to_owned())],
+///     attributes.fields(),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Builder {
+    fields: Vec<(String, String)>,
+    pgv: bool,
+}
+
+/// Attributes of generated code, computed by [Builder::attributes].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attributes {
+    derives: Vec<String>,
+    fields: Vec<(String, String)>,
+    warnings: Vec<String>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    /// Creates a builder translating PGV rules.
+    pub fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+            pgv: true,
+        }
+    }
+
+    /// Adds derive validators to a field, identified by its fully-qualified
+    /// Protobuf path. Validators follow the ones translated from PGV rules.
+    /// ```
+    /// # use prost::Message;
+    /// # use prost_types::FileDescriptorSet;
+    /// let attributes = not_so_fast_build::Builder::new()
+    ///     .field(".shop.User.age", "range(max = 150)")
+    ///     .field(".shop.User.age", "custom = crate::adult")
+    ///     .attributes(&FileDescriptorSet::default().encode_to_vec())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     &[(".shop.User.age".to_owned(), "#[validate(range(max = 150), custom = crate::adult)]".to_owned())],
+    ///     attributes.fields(),
+    /// );
+    /// ```
+    pub fn field(mut self, path: impl Into<String>, validators: impl Into<String>) -> Self {
+        self.fields.push((path.into(), validators.into()));
+        self
+    }
+
+    /// Enables or disables translation of PGV rules, enabled by default.
+    /// Messages derive `Validate` either way.
+    /// ```
+    /// # use prost::Message;
+    /// # use prost_types::FileDescriptorSet;
+    /// let attributes = not_so_fast_build::Builder::new()
+    ///     .pgv(false)
+    ///     .attributes(&FileDescriptorSet::default().encode_to_vec())
+    ///     .unwrap();
+    /// assert!(attributes.fields().is_empty());
+    /// ```
+    pub fn pgv(mut self, enabled: bool) -> Self {
+        self.pgv = enabled;
+        self
+    }
+
+    /// Computes attributes of messages described by an encoded
+    /// `FileDescriptorSet`, as written by `protoc -o`. Descriptors must keep
+    /// field options for PGV rules to be found.
+    /// ```
+    /// # use prost::Message;
+    /// # use prost_types::FileDescriptorSet;
+    /// let attributes = not_so_fast_build::Builder::new()
+    ///     .attributes(&FileDescriptorSet::default().encode_to_vec())
+    ///     .unwrap();
+    /// assert!(attributes.derives().is_empty());
+    ///
+    /// assert!(not_so_fast_build::Builder::new().attributes(b"\xff").is_err());
+    /// ```
+    pub fn attributes(&self, descriptor_set: &[u8]) -> Result<Attributes, prost::DecodeError> {
+        let set = FileDescriptorSet::decode(descriptor_set)?;
+
+        let mut messages = HashMap::new();
+        for file in &set.file {
+            let package = file.package();
+            let prefix = if package.is_empty() {
+                String::new()
+            } else {
+                format!(".{package}")
+            };
+            index_messages(&prefix, &file.message_type, &mut messages);
+        }
+
+        let mut scopes = BTreeSet::new();
+        let mut fields = BTreeMap::<String, Vec<String>>::new();
+        let mut warnings = Vec::new();
+        for file in &set.file {
+            let package = file.package();
+            if package == "google.protobuf" {
+                // Well-known types come from `prost-types`.
+                continue;
+            }
+            let prefix = if package.is_empty() {
+                for message in &file.message_type {
+                    scopes.insert(format!(".{}", message.name()));
+                }
+                String::new()
+            } else {
+                scopes.insert(format!(".{package}"));
+                format!(".{package}")
+            };
+            if self.pgv {
+                let mut context = Context {
+                    messages: &messages,
+                    proto3: file.syntax() == "proto3",
+                    fields: &mut fields,
+                    warnings: &mut warnings,
+                };
+                context.messages(&prefix, &file.message_type)?;
+            }
+        }
+        for (path, validators) in &self.fields {
+            fields
+                .entry(path.clone())
+                .or_default()
+                .push(validators.clone());
+        }
+
+        // Attributes of a scope apply to all messages inside it, so nested
+        // scopes would derive `Validate` twice.
+        let derives = scopes
+            .iter()
+            .filter(|scope| {
+                !scopes.iter().any(|outer| {
+                    scope
+                        .strip_prefix(outer.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+                })
+            })
+            .cloned()
+            .collect();
+        let fields = fields
+            .into_iter()
+            .map(|(path, validators)| (path, format!("#[validate({})]", validators.join(", "))))
+            .collect();
+
+        Ok(Attributes {
+            derives,
+            fields,
+            warnings,
+        })
+    }
+
+    /// Adds attributes of messages described by an encoded
+    /// `FileDescriptorSet` to `config`, and prints warnings about ignored
+    /// rules for Cargo.
+    /// ```
+    /// # use prost::Message;
+    /// # use prost_types::FileDescriptorSet;
+    /// let descriptor_set = FileDescriptorSet::default().encode_to_vec();
+    /// let mut config = prost_build::Config::new();
+    /// not_so_fast_build::Builder::new()
+    ///     .configure(&mut config, &descriptor_set)
+    ///     .unwrap();
+    /// ```
+    pub fn configure(
+        &self,
+        config: &mut prost_build::Config,
+        descriptor_set: &[u8],
+    ) -> Result<(), prost::DecodeError> {
+        let attributes = self.attributes(descriptor_set)?;
+        for warning in attributes.warnings() {
+            println!("cargo:warning={warning}");
+        }
+        attributes.apply(config);
+        Ok(())
+    }
+
+    /// Compiles `.proto` files with `protoc`, like
+    /// `prost_build::Config::compile_protos`, generating messages that
+    /// implement `Validate`. The descriptor set is written to
+    /// `not-so-fast-descriptors.bin` in `OUT_DIR`.
+    /// ```no_run
+    /// not_so_fast_build::Builder::new()
+    ///     .compile_protos(&mut prost_build::Config::new(), &["proto/shop.proto"], &["proto"])
+    ///     .unwrap();
+    /// ```
+    pub fn compile_protos(
+        &self,
+        config: &mut prost_build::Config,
+        protos: &[impl AsRef<Path>],
+        includes: &[impl AsRef<Path>],
+    ) -> io::Result<()> {
+        let out_dir = std::env::var_os("OUT_DIR")
+            .ok_or_else(|| io::Error::other("OUT_DIR environment variable is not set"))?;
+        let path = PathBuf::from(out_dir).join("not-so-fast-descriptors.bin");
+        config.file_descriptor_set_path(&path);
+        let descriptor_set = config.load_fds(protos, includes)?;
+        let bytes = std::fs::read(&path)?;
+        self.configure(config, &bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        config.compile_fds(descriptor_set)
+    }
+}
+
+impl Attributes {
+    /// Returns paths of packages, or messages without package, whose messages
+    /// derive `Validate`.
+    pub fn derives(&self) -> &[String] {
+        &self.derives
+    }
+
+    /// Returns paths of fields and their `validate` attributes.
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+
+    /// Returns descriptions of PGV rules that couldn't be translated.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Adds the attributes to `config`.
+    /// ```
+    /// # use prost::Message;
+    /// # use prost_types::FileDescriptorSet;
+    /// let attributes = not_so_fast_build::Builder::new()
+    ///     .attributes(&FileDescriptorSet::default().encode_to_vec())
+    ///     .unwrap();
+    /// attributes.apply(&mut prost_build::Config::new());
+    /// ```
+    pub fn apply(&self, config: &mut prost_build::Config) {
+        for scope in &self.derives {
+            config.message_attribute(scope, DERIVE_VALIDATE);
+        }
+        for (path, attribute) in &self.fields {
+            config.field_attribute(path, attribute);
+        }
+    }
+}
+
+fn index_messages<'a>(
+    prefix: &str,
+    messages: &'a [DescriptorProto],
+    index: &mut HashMap<String, &'a DescriptorProto>,
+) {
+    for message in messages {
+        let path = format!("{prefix}.{}", message.name());
+        index_messages(&path, &message.nested_type, index);
+        index.insert(path, message);
+    }
+}
+
+/// State of translation of PGV rules of one file.
+struct Context<'a> {
+    messages: &'a HashMap<String, &'a DescriptorProto>,
+    proto3: bool,
+    fields: &'a mut BTreeMap<String, Vec<String>>,
+    warnings: &'a mut Vec<String>,
+}
+
+impl Context<'_> {
+    fn messages(
+        &mut self,
+        prefix: &str,
+        messages: &[DescriptorProto],
+    ) -> Result<(), prost::DecodeError> {
+        for message in messages {
+            if message.is_map_entry() {
+                // Map entries are not generated as structs.
+                continue;
+            }
+            let path = format!("{prefix}.{}", message.name());
+            for field in &message.field {
+                let field_path = format!("{path}.{}", field.name());
+                let validators = self.field(&field_path, field)?;
+                if !validators.is_empty() {
+                    self.fields
+                        .entry(field_path)
+                        .or_default()
+                        .extend(validators);
+                }
+            }
+            self.messages(&path, &message.nested_type)?;
+        }
+        Ok(())
+    }
+
+    fn field(
+        &mut self,
+        path: &str,
+        field: &FieldDescriptorProto,
+    ) -> Result<Vec<String>, prost::DecodeError> {
+        let rules = match field.rules()? {
+            Some((rules, complete)) => {
+                if !complete {
+                    self.warnings.push(format!(
+                        "{path}: rules other than lengths, bounds and message rules are not supported"
+                    ));
+                }
+                Some(rules)
+            }
+            None => None,
+        };
+        let proto3_optional = field.proto3_optional.unwrap_or(false);
+
+        if field.oneof_index.is_some() && !proto3_optional {
+            if rules.is_some() {
+                self.warnings
+                    .push(format!("{path}: rules of oneof fields are not supported"));
+            }
+            return Ok(Vec::new());
+        }
+
+        let mut validators = Vec::new();
+        if field.label == Some(LABEL_REPEATED) {
+            let messages = self.messages;
+            if let Some(entry) = messages.get(field.type_name()).filter(|m| m.is_map_entry()) {
+                let map_rules = rules.as_ref().and_then(|rules| {
+                    self.expect_kind(path, rules, rules.map().is_some());
+                    rules.map()
+                });
+                if let Some(map_rules) = map_rules {
+                    Bounds::lengths(map_rules.min_pairs, map_rules.max_pairs, None)
+                        .push("length", &mut validators);
+                }
+                if let Some(value) = entry.field.iter().find(|field| field.number == Some(2)) {
+                    let value_rules = map_rules.and_then(|rules| rules.values.as_deref());
+                    let values = self.value(path, value, value_rules);
+                    if !values.is_empty() {
+                        validators.push(format!("fields({})", values.join(", ")));
+                    }
+                }
+            } else {
+                let repeated_rules = rules.as_ref().and_then(|rules| {
+                    self.expect_kind(path, rules, rules.repeated().is_some());
+                    rules.repeated()
+                });
+                if let Some(repeated_rules) = repeated_rules {
+                    Bounds::lengths(repeated_rules.min_items, repeated_rules.max_items, None)
+                        .push("length", &mut validators);
+                }
+                let item_rules = repeated_rules.and_then(|rules| rules.items.as_deref());
+                let items = self.value(path, field, item_rules);
+                if !items.is_empty() {
+                    validators.push(format!("items({})", items.join(", ")));
+                }
+            }
+        } else {
+            let values = self.value(path, field, rules.as_ref());
+            let optional = field.is_message()
+                || proto3_optional
+                || (!self.proto3 && field.label == Some(LABEL_OPTIONAL));
+            if optional && !values.is_empty() {
+                validators.push(format!("some({})", values.join(", ")));
+            } else {
+                validators.extend(values);
+            }
+        }
+        Ok(validators)
+    }
+
+    /// Returns validators of a single value of `field`.
+    fn value(
+        &mut self,
+        path: &str,
+        field: &FieldDescriptorProto,
+        rules: Option<&FieldRules>,
+    ) -> Vec<String> {
+        let mut validators = Vec::new();
+        if let Some(rules) = rules {
+            rules.push_value_validators(path, &mut validators, self.warnings);
+        }
+        // Well-known types come from `prost-types` and don't implement
+        // `Validate`.
+        if field.is_message() && !field.type_name().starts_with(".google.protobuf.") {
+            let message_rules = rules.and_then(|rules| rules.message.as_ref());
+            if message_rules.and_then(|rules| rules.required) == Some(true) {
+                self.warnings
+                    .push(format!("{path}: required message rule is not supported"));
+            }
+            if message_rules.and_then(|rules| rules.skip) != Some(true) {
+                validators.push("nested".to_owned());
+            }
+        }
+        validators
+    }
+
+    fn expect_kind(&mut self, path: &str, rules: &FieldRules, matches: bool) {
+        if !matches && rules.r#type.is_some() {
+            self.warnings
+                .push(format!("{path}: rules don't match the field type"));
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Subset of PGV `validate/validate.proto` rules which map to derive
+//! validators. Rules missing here are dropped when decoding, which
+//! [FieldDescriptorProto::rules](crate::descriptor::FieldDescriptorProto::rules)
+//! detects and reports.
+
+use prost::{Message, Oneof};
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct FieldRules {
+    #[prost(message, optional, tag = "17")]
+    pub message: Option<MessageRules>,
+    #[prost(
+        oneof = "Type",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 14, 15, 18, 19"
+    )]
+    pub r#type: Option<Type>,
+}
+
+#[derive(Clone, PartialEq, Oneof)]
+pub(crate) enum Type {
+    #[prost(message, tag = "1")]
+    Float(FloatRules),
+    #[prost(message, tag = "2")]
+    Double(DoubleRules),
+    #[prost(message, tag = "3")]
+    Int32(Int32Rules),
+    #[prost(message, tag = "4")]
+    Int64(Int64Rules),
+    #[prost(message, tag = "5")]
+    Uint32(UInt32Rules),
+    #[prost(message, tag = "6")]
+    Uint64(UInt64Rules),
+    #[prost(message, tag = "7")]
+    Sint32(SInt32Rules),
+    #[prost(message, tag = "8")]
+    Sint64(SInt64Rules),
+    #[prost(message, tag = "9")]
+    Fixed32(Fixed32Rules),
+    #[prost(message, tag = "10")]
+    Fixed64(Fixed64Rules),
+    #[prost(message, tag = "11")]
+    Sfixed32(SFixed32Rules),
+    #[prost(message, tag = "12")]
+    Sfixed64(SFixed64Rules),
+    #[prost(message, tag = "14")]
+    String(StringRules),
+    #[prost(message, tag = "15")]
+    Bytes(BytesRules),
+    #[prost(message, tag = "18")]
+    Repeated(Box<RepeatedRules>),
+    #[prost(message, tag = "19")]
+    Map(Box<MapRules>),
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct MessageRules {
+    #[prost(bool, optional, tag = "1")]
+    pub skip: Option<bool>,
+    #[prost(bool, optional, tag = "2")]
+    pub required: Option<bool>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct StringRules {
+    #[prost(uint64, optional, tag = "19")]
+    pub len: Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub min_len: Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub max_len: Option<u64>,
+    #[prost(uint64, optional, tag = "20")]
+    pub len_bytes: Option<u64>,
+    #[prost(uint64, optional, tag = "4")]
+    pub min_bytes: Option<u64>,
+    #[prost(uint64, optional, tag = "5")]
+    pub max_bytes: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct BytesRules {
+    #[prost(uint64, optional, tag = "13")]
+    pub len: Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub min_len: Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub max_len: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct RepeatedRules {
+    #[prost(uint64, optional, tag = "1")]
+    pub min_items: Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub max_items: Option<u64>,
+    #[prost(message, optional, boxed, tag = "4")]
+    pub items: Option<Box<FieldRules>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct MapRules {
+    #[prost(uint64, optional, tag = "1")]
+    pub min_pairs: Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub max_pairs: Option<u64>,
+    #[prost(message, optional, boxed, tag = "5")]
+    pub values: Option<Box<FieldRules>>,
+}
+
+macro_rules! int_rules {
+    ($name:ident, $kind:ident, $ty:ty) => {
+        #[derive(Clone, PartialEq, Message)]
+        pub(crate) struct $name {
+            #[prost($kind, optional, tag = "1")]
+            pub r#const: Option<$ty>,
+            #[prost($kind, optional, tag = "2")]
+            pub lt: Option<$ty>,
+            #[prost($kind, optional, tag = "3")]
+            pub lte: Option<$ty>,
+            #[prost($kind, optional, tag = "4")]
+            pub gt: Option<$ty>,
+            #[prost($kind, optional, tag = "5")]
+            pub gte: Option<$ty>,
+        }
+
+        impl $name {
+            /// Exclusive bounds become inclusive ones, since integers are
+            /// discrete.
+            fn bounds(&self, path: &str, warnings: &mut Vec<String>) -> Bounds {
+                let min = [self.r#const, self.gte]
+                    .into_iter()
+                    .flatten()
+                    .map(i128::from)
+                    .chain(self.gt.map(|gt| i128::from(gt) + 1))
+                    .max();
+                let max = [self.r#const, self.lte]
+                    .into_iter()
+                    .flatten()
+                    .map(i128::from)
+                    .chain(self.lt.map(|lt| i128::from(lt) - 1))
+                    .min();
+                Bounds {
+                    min: min.and_then(|min| int_bound(min, path, warnings)),
+                    max: max.and_then(|max| int_bound(max, path, warnings)),
+                    equal: None,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! float_rules {
+    ($name:ident, $kind:ident, $ty:ty) => {
+        #[derive(Clone, PartialEq, Message)]
+        pub(crate) struct $name {
+            #[prost($kind, optional, tag = "1")]
+            pub r#const: Option<$ty>,
+            #[prost($kind, optional, tag = "2")]
+            pub lt: Option<$ty>,
+            #[prost($kind, optional, tag = "3")]
+            pub lte: Option<$ty>,
+            #[prost($kind, optional, tag = "4")]
+            pub gt: Option<$ty>,
+            #[prost($kind, optional, tag = "5")]
+            pub gte: Option<$ty>,
+        }
+
+        impl $name {
+            fn bounds(&self, path: &str, warnings: &mut Vec<String>) -> Bounds {
+                if self.lt.is_some() || self.gt.is_some() {
+                    warnings.push(format!(
+                        "{path}: exclusive bounds (lt, gt) of floating point numbers are not supported"
+                    ));
+                }
+                let min = [self.r#const, self.gte].into_iter().flatten().reduce(<$ty>::max);
+                let max = [self.r#const, self.lte].into_iter().flatten().reduce(<$ty>::min);
+                Bounds {
+                    min: min.and_then(|min| float_bound(min, path, warnings)),
+                    max: max.and_then(|max| float_bound(max, path, warnings)),
+                    equal: None,
+                }
+            }
+        }
+    };
+}
+
+float_rules!(FloatRules, float, f32);
+float_rules!(DoubleRules, double, f64);
+int_rules!(Int32Rules, int32, i32);
+int_rules!(Int64Rules, int64, i64);
+int_rules!(UInt32Rules, uint32, u32);
+int_rules!(UInt64Rules, uint64, u64);
+int_rules!(SInt32Rules, sint32, i32);
+int_rules!(SInt64Rules, sint64, i64);
+int_rules!(Fixed32Rules, fixed32, u32);
+int_rules!(Fixed64Rules, fixed64, u64);
+int_rules!(SFixed32Rules, sfixed32, i32);
+int_rules!(SFixed64Rules, sfixed64, i64);
+
+/// Arguments of `range`, `length` and `char_length` validators, formatted as
+/// Rust literals.
+#[derive(Default)]
+pub(crate) struct Bounds {
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub equal: Option<String>,
+}
+
+impl Bounds {
+    pub(crate) fn lengths(min: Option<u64>, max: Option<u64>, equal: Option<u64>) -> Self {
+        Self {
+            min: min.map(|min| min.to_string()),
+            max: max.map(|max| max.to_string()),
+            equal: equal.map(|equal| equal.to_string()),
+        }
+    }
+
+    /// Bounds of lengths, where exact length set by rule `equal_rule` makes
+    /// min and max redundant or unsatisfiable, so only `equal` is kept then.
+    fn exact_lengths(
+        min: Option<u64>,
+        max: Option<u64>,
+        equal: Option<u64>,
+        equal_rule: &str,
+        path: &str,
+        warnings: &mut Vec<String>,
+    ) -> Self {
+        if equal.is_none() {
+            return Self::lengths(min, max, None);
+        }
+        if min.is_some() || max.is_some() {
+            warnings.push(format!(
+                "{path}: min and max lengths are ignored, because {equal_rule} is set"
+            ));
+        }
+        Self::lengths(None, None, equal)
+    }
+
+    /// Appends validator `name` to `validators`, if any bound is set.
+    pub(crate) fn push(self, name: &str, validators: &mut Vec<String>) {
+        let arguments = [("min", self.min), ("max", self.max), ("equal", self.equal)]
+            .into_iter()
+            .filter_map(|(argument, value)| Some(format!("{argument} = {}", value?)))
+            .collect::<Vec<_>>();
+        if !arguments.is_empty() {
+            validators.push(format!("{name}({})", arguments.join(", ")));
+        }
+    }
+}
+
+/// Derive macro doesn't accept negative literals.
+fn int_bound(value: i128, path: &str, warnings: &mut Vec<String>) -> Option<String> {
+    if value < 0 {
+        warnings.push(format!("{path}: negative bound {value} is not supported"));
+        return None;
+    }
+    Some(value.to_string())
+}
+
+fn float_bound<T>(value: T, path: &str, warnings: &mut Vec<String>) -> Option<String>
+where
+    T: Into<f64> + Copy + std::fmt::Debug,
+{
+    let number: f64 = value.into();
+    if number == 0.0 {
+        // Covers -0.0 too.
+        return Some("0.0".to_owned());
+    }
+    if !number.is_finite() || number < 0.0 {
+        warnings.push(format!("{path}: bound {value:?} is not supported"));
+        return None;
+    }
+    Some(format!("{value:?}"))
+}
+
+impl FieldRules {
+    /// Appends validators of a single value: a singular field, an item of a
+    /// repeated field or a value of a map.
+    pub(crate) fn push_value_validators(
+        &self,
+        path: &str,
+        validators: &mut Vec<String>,
+        warnings: &mut Vec<String>,
+    ) {
+        let bounds = match &self.r#type {
+            None => return,
+            Some(Type::Float(rules)) => rules.bounds(path, warnings),
+            Some(Type::Double(rules)) => rules.bounds(path, warnings),
+            Some(Type::Int32(rules)) => rules.bounds(path, warnings),
+            Some(Type::Int64(rules)) => rules.bounds(path, warnings),
+            Some(Type::Uint32(rules)) => rules.bounds(path, warnings),
+            Some(Type::Uint64(rules)) => rules.bounds(path, warnings),
+            Some(Type::Sint32(rules)) => rules.bounds(path, warnings),
+            Some(Type::Sint64(rules)) => rules.bounds(path, warnings),
+            Some(Type::Fixed32(rules)) => rules.bounds(path, warnings),
+            Some(Type::Fixed64(rules)) => rules.bounds(path, warnings),
+            Some(Type::Sfixed32(rules)) => rules.bounds(path, warnings),
+            Some(Type::Sfixed64(rules)) => rules.bounds(path, warnings),
+            Some(Type::String(rules)) => {
+                Bounds::exact_lengths(
+                    rules.min_len,
+                    rules.max_len,
+                    rules.len,
+                    "len",
+                    path,
+                    warnings,
+                )
+                .push("char_length", validators);
+                Bounds::exact_lengths(
+                    rules.min_bytes,
+                    rules.max_bytes,
+                    rules.len_bytes,
+                    "len_bytes",
+                    path,
+                    warnings,
+                )
+                .push("length", validators);
+                return;
+            }
+            Some(Type::Bytes(rules)) => {
+                Bounds::exact_lengths(
+                    rules.min_len,
+                    rules.max_len,
+                    rules.len,
+                    "len",
+                    path,
+                    warnings,
+                )
+                .push("length", validators);
+                return;
+            }
+            Some(Type::Repeated(_) | Type::Map(_)) => {
+                warnings.push(format!(
+                    "{path}: repeated and map rules of a single value are ignored"
+                ));
+                return;
+            }
+        };
+        bounds.push("range", validators);
+    }
+
+    pub(crate) fn repeated(&self) -> Option<&RepeatedRules> {
+        match &self.r#type {
+            Some(Type::Repeated(rules)) => Some(rules),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn map(&self) -> Option<&MapRules> {
+        match &self.r#type {
+            Some(Type::Map(rules)) => Some(rules),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast_build::*;
+use protobuf::Message as _;
+
+fn descriptor_set() -> Vec<u8> {
+    protobuf_parse::Parser::new()
+        .pure()
+        .include("tests/protos")
+        .input("tests/protos/shop.proto")
+        .file_descriptor_set()
+        .unwrap()
+        .write_to_bytes()
+        .unwrap()
+}
+
+fn fields(attributes: &Attributes) -> Vec<(&str, &str)> {
+    attributes
+        .fields()
+        .iter()
+        .map(|(path, attribute)| (path.as_str(), attribute.as_str()))
+        .collect()
+}
+
+#[test]
+fn translates_pgv_rules() {
+    let attributes = Builder::new().attributes(&descriptor_set()).unwrap();
+
+    assert_eq!(&[".shop".to_owned()], attributes.derives());
+    assert_eq!(
+        vec![
+            (".shop.Order.address", "#[validate(some(nested))]"),
+            (
+                ".shop.Order.code",
+                "#[validate(char_length(equal = 4), length(equal = 4))]"
+            ),
+            (
+                ".shop.Order.labels",
+                "#[validate(fields(char_length(max = 10)))]"
+            ),
+            (
+                ".shop.User.Address.city",
+                "#[validate(char_length(min = 1))]"
+            ),
+            (
+                ".shop.User.addresses",
+                "#[validate(length(max = 3), fields(nested))]"
+            ),
+            (".shop.User.age", "#[validate(range(min = 13, max = 150))]"),
+            (".shop.User.avatar", "#[validate(length(max = 1024))]"),
+            (".shop.User.home", "#[validate(some(nested))]"),
+            (".shop.User.level", "#[validate(range(min = 3, max = 3))]"),
+            (
+                ".shop.User.name",
+                "#[validate(char_length(min = 1, max = 30), length(max = 120))]"
+            ),
+            (
+                ".shop.User.nick",
+                "#[validate(some(char_length(equal = 8)))]"
+            ),
+            (".shop.User.previous", "#[validate(items(nested))]"),
+            (
+                ".shop.User.score",
+                "#[validate(range(min = 0.0, max = 1.5))]"
+            ),
+            (
+                ".shop.User.tags",
+                "#[validate(length(max = 5), items(char_length(min = 1)))]"
+            ),
+        ],
+        fields(&attributes),
+    );
+}
+
+#[test]
+fn reports_unsupported_rules() {
+    let attributes = Builder::new().attributes(&descriptor_set()).unwrap();
+
+    assert_eq!(
+        &[
+            ".shop.Order.note: rules other than lengths, bounds and message rules are not supported",
+            ".shop.Order.delta: negative bound -10 is not supported",
+            ".shop.Order.ratio: exclusive bounds (lt, gt) of floating point numbers are not supported",
+            ".shop.Order.address: required message rule is not supported",
+            ".shop.Order.card: rules of oneof fields are not supported",
+            ".shop.Order.quantities: rules don't match the field type",
+            ".shop.Order.code: min and max lengths are ignored, because len is set",
+            ".shop.Order.code: min and max lengths are ignored, because len_bytes is set",
+        ][..],
+        attributes.warnings(),
+    );
+}
+
+#[test]
+fn combines_pgv_and_manual_validators() {
+    let builder = Builder::new()
+        .field(".shop.User.name", "custom = crate::no_spaces")
+        .field(".shop.Order.note", "char_length(max = 200)");
+
+    let attributes = builder.attributes(&descriptor_set()).unwrap();
+    let annotated = fields(&attributes);
+    assert!(annotated.contains(&(
+        ".shop.User.name",
+        "#[validate(char_length(min = 1, max = 30), length(max = 120), custom = crate::no_spaces)]"
+    )));
+    assert!(annotated.contains(&(".shop.Order.note", "#[validate(char_length(max = 200))]")));
+
+    let attributes = builder.pgv(false).attributes(&descriptor_set()).unwrap();
+    assert_eq!(&[".shop".to_owned()], attributes.derives());
+    assert_eq!(
+        vec![
+            (".shop.Order.note", "#[validate(char_length(max = 200))]"),
+            (".shop.User.name", "#[validate(custom = crate::no_spaces)]"),
+        ],
+        fields(&attributes),
+    );
+    assert!(attributes.warnings().is_empty());
+}
+
+#[test]
+fn configures_prost_build() {
+    let bytes = descriptor_set();
+    let out_dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("pgv");
+    std::fs::create_dir_all(&out_dir).unwrap();
+
+    let mut config = prost_build::Config::new();
+    config.out_dir(&out_dir);
+    Builder::new().configure(&mut config, &bytes).unwrap();
+    config
+        .compile_fds(prost::Message::decode(bytes.as_slice()).unwrap())
+        .unwrap();
+
+    let code = std::fs::read_to_string(out_dir.join("shop.rs")).unwrap();
+    assert_eq!(3, code.matches(DERIVE_VALIDATE).count());
+    assert!(code.contains("#[validate(range(min = 13, max = 150))]\n    pub age: u32,"));
+    assert!(code.contains(
+        "#[validate(some(char_length(equal = 8)))]\n    pub nick: ::core::option::Option<"
+    ));
+}
@@ -0,0 +1,29 @@
+//! Validates user documents, e.g.
+//! `cargo run -p not-so-fast-cli --example validate -- user not-so-fast-cli/tests/fixtures/*`.
+
+use std::process::ExitCode;
+
+use not_so_fast::{Severity, Validate, ValidationError, ValidationNode};
+use serde::Deserialize;
+
+#[derive(Deserialize, Validate)]
+struct User {
+    #[validate(char_length(min = 1, max = 10))]
+    name: String,
+    #[validate(custom = warn_if_young)]
+    age: u8,
+    #[validate(length(max = 3), items(char_length(max = 10)))]
+    tags: Vec<String>,
+}
+
+fn warn_if_young(age: &u8) -> ValidationNode {
+    ValidationNode::error_if(*age < 18, || {
+        ValidationError::with_code("young").and_severity(Severity::Warning)
+    })
+}
+
+fn main() -> ExitCode {
+    not_so_fast_cli::Cli::new("validate")
+        .register::<User>("user")
+        .run()
+}
@@ -0,0 +1,344 @@
+//! Command-line validation of JSON and YAML documents against types
+//! validated with `not-so-fast`, e.g. to check fixture files in CI with the
+//! exact rules used in production.
+//!
+//! Rust can't load types at run time, so the command is a small binary
+//! registering the types with [Cli]:
+//!
+//! ```no_run
+//! use not_so_fast::Validate;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, Validate)]
+//! struct User {
+//!     #[validate(char_length(min = 1, max = 30))]
+//!     name: String,
+//! }
+//!
+//! fn main() -> std::process::ExitCode {
+//!     not_so_fast_cli::Cli::new("validate")
+//!         .register::<User>("user")
+//!         .run()
+//! }
+//! ```
+//!
+//! Running `validate user fixtures/*.json` prints errors of invalid documents
+//! with `jq`-like paths, one per line:
+//!
+//! ```text
+//! fixtures/bob.json: .name: char_length: Invalid character length: max=30, min=1, value=0
+//! ```
+//!
+//! Warnings and infos are printed with their severity, e.g.
+//! `fixtures/tom.json: .age: warning: young`, but don't fail validation
+//! unless `--deny-warnings` is passed. Exit status is 0 if all documents are
+//! valid, 1 if some are invalid or can't be deserialized, and 2 on usage and
+//! I/O errors.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use not_so_fast::{Severity, Validate, ValidationNode};
+use serde::de::DeserializeOwned;
+
+/// Deserializes a document and validates it. Deserialization errors are
+/// returned as `Err`.
+type ValidateDocument = fn(&str, Format) -> Result<ValidationNode, ValidationNode>;
+
+/// Validation command with registered types.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(serde::Deserialize, Validate)]
+/// struct Order {
+///     #[validate(range(min = 1))]
+///     quantity: u32,
+/// }
+///
+/// let cli = not_so_fast_cli::Cli::new("validate").register::<Order>("order");
+///
+/// let mut out = Vec::new();
+/// let status = cli.run_with(["--list"], &mut out, &mut std::io::sink());
+/// assert_eq!(not_so_fast_cli::Status::Valid, status);
+/// assert_eq!("order\n", String::from_utf8(out).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cli {
+    name: String,
+    types: BTreeMap<String, ValidateDocument>,
+}
+
+/// Format of validated documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Yaml,
+}
+
+/// Outcome of a command run, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    /// All documents are valid.
+    Valid,
+    /// Some documents are invalid or can't be deserialized.
+    Invalid,
+    /// Arguments are wrong or documents can't be read.
+    Failed,
+}
+
+impl Format {
+    /// Detects format from file extension: `.yaml` and `.yml` files are YAML,
+    /// other files are JSON.
+    /// ```
+    /// # use not_so_fast_cli::Format;
+    /// assert_eq!(Format::Yaml, Format::from_path("fixtures/user.yml".as_ref()));
+    /// assert_eq!(Format::Json, Format::from_path("fixtures/user.json".as_ref()));
+    /// ```
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml" | "yml") => Format::Yaml,
+            _ => Format::Json,
+        }
+    }
+}
+
+impl Status {
+    /// Returns the process exit code: 0, 1 or 2.
+    pub fn code(self) -> u8 {
+        match self {
+            Status::Valid => 0,
+            Status::Invalid => 1,
+            Status::Failed => 2,
+        }
+    }
+}
+
+impl From<Status> for ExitCode {
+    fn from(status: Status) -> Self {
+        ExitCode::from(status.code())
+    }
+}
+
+impl Cli {
+    /// Creates a command without types. `name` is shown in the usage message.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            types: BTreeMap::new(),
+        }
+    }
+
+    /// Registers type `T` under `name`, which selects the type on the
+    /// command line.
+    /// ```
+    /// # use not_so_fast::*;
+    /// #[derive(serde::Deserialize, Validate)]
+    /// struct Tag(#[validate(char_length(max = 3))] String);
+    ///
+    /// let cli = not_so_fast_cli::Cli::new("validate").register::<Tag>("tag");
+    /// let mut out = Vec::new();
+    /// cli.run_with(["--list"], &mut out, &mut std::io::sink());
+    /// assert_eq!("tag\n", String::from_utf8(out).unwrap());
+    /// ```
+    pub fn register<T: DeserializeOwned + Validate>(mut self, name: impl Into<String>) -> Self {
+        self.types.insert(name.into(), validate::<T>);
+        self
+    }
+
+    /// Runs the command with arguments of the process, printing to standard
+    /// output and error.
+    pub fn run(&self) -> ExitCode {
+        let args = std::env::args().skip(1);
+        let status = self.run_with(args, &mut std::io::stdout(), &mut std::io::stderr());
+        status.into()
+    }
+
+    /// Runs the command with `args`, excluding the program name. Errors of
+    /// documents are written to `out`, usage and I/O errors to `err`.
+    /// ```
+    /// let cli = not_so_fast_cli::Cli::new("validate");
+    /// let mut err = Vec::new();
+    /// let status = cli.run_with(["user", "user.json"], &mut std::io::sink(), &mut err);
+    ///
+    /// assert_eq!(not_so_fast_cli::Status::Failed, status);
+    /// assert!(String::from_utf8(err).unwrap().starts_with("error: unknown type user"));
+    /// ```
+    pub fn run_with<I, S>(&self, args: I, out: &mut dyn Write, err: &mut dyn Write) -> Status
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        match self.try_run(args.into_iter().map(Into::into), out, err) {
+            Ok(status) => status,
+            Err(error) => {
+                let _ = writeln!(err, "error: {error}\n\n{}", self.usage());
+                Status::Failed
+            }
+        }
+    }
+
+    fn try_run(
+        &self,
+        args: impl Iterator<Item = String>,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+    ) -> Result<Status, String> {
+        let options = match parse_args(args)? {
+            Command::Help => {
+                let _ = writeln!(out, "{}", self.usage());
+                return Ok(Status::Valid);
+            }
+            Command::List => {
+                for name in self.types.keys() {
+                    let _ = writeln!(out, "{name}");
+                }
+                return Ok(Status::Valid);
+            }
+            Command::Validate(options) => options,
+        };
+        let validate = self
+            .types
+            .get(&options.type_name)
+            .ok_or_else(|| format!("unknown type {}", options.type_name))?;
+
+        let mut status = Status::Valid;
+        for file in &options.files {
+            let text = match read(file) {
+                Ok(text) => text,
+                Err(error) => {
+                    let _ = writeln!(err, "{file}: {error}");
+                    status = status.max(Status::Failed);
+                    continue;
+                }
+            };
+            let format = options
+                .format
+                .unwrap_or_else(|| Format::from_path(file.as_ref()));
+            let errors = match validate(&text, format) {
+                Ok(errors) => {
+                    if errors.has_errors() || (options.deny_warnings && errors.has_warnings()) {
+                        status = status.max(Status::Invalid);
+                    }
+                    errors
+                }
+                Err(errors) => {
+                    status = status.max(Status::Invalid);
+                    errors
+                }
+            };
+            for (path, error) in &errors {
+                let _ = match error.severity() {
+                    Severity::Error => writeln!(out, "{file}: {path}: {error}"),
+                    severity => writeln!(out, "{file}: {path}: {}: {error}", severity.as_str()),
+                };
+            }
+        }
+        Ok(status)
+    }
+
+    fn usage(&self) -> String {
+        format!(
+            "\
+Usage: {} [OPTIONS] <TYPE> <FILE>...
+
+Validates JSON or YAML documents against a registered type. FILE - reads
+standard input.
+
+Options:
+      --format <FORMAT>  Format of documents, json or yaml, detected from file
+                         extensions by default
+      --deny-warnings    Fail validation of documents with warnings
+      --list             List registered types
+  -h, --help             Print help
+
+Exit status is 0 if all documents are valid, 1 if some are invalid or can't be
+deserialized, and 2 on usage and I/O errors.",
+            self.name
+        )
+    }
+}
+
+enum Command {
+    Help,
+    List,
+    Validate(Options),
+}
+
+struct Options {
+    format: Option<Format>,
+    deny_warnings: bool,
+    type_name: String,
+    files: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut format = None;
+    let mut deny_warnings = false;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--list" => return Ok(Command::List),
+            "--deny-warnings" => deny_warnings = true,
+            "--format" => {
+                let value = args.next().ok_or("--format requires a value")?;
+                format = Some(parse_format(&value)?);
+            }
+            "--" => {
+                positional.extend(args.by_ref());
+            }
+            _ => {
+                if let Some(value) = arg.strip_prefix("--format=") {
+                    format = Some(parse_format(value)?);
+                } else if arg.starts_with('-') && arg != "-" {
+                    return Err(format!("unknown option {arg}"));
+                } else {
+                    positional.push(arg);
+                }
+            }
+        }
+    }
+    let mut positional = positional.into_iter();
+    let type_name = positional.next().ok_or("missing type")?;
+    let files: Vec<_> = positional.collect();
+    if files.is_empty() {
+        return Err("missing files".to_owned());
+    }
+    Ok(Command::Validate(Options {
+        format,
+        deny_warnings,
+        type_name,
+        files,
+    }))
+}
+
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value {
+        "json" => Ok(Format::Json),
+        "yaml" => Ok(Format::Yaml),
+        _ => Err(format!("unknown format {value}, expected json or yaml")),
+    }
+}
+
+fn read(file: &str) -> std::io::Result<String> {
+    if file == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        Ok(text)
+    } else {
+        std::fs::read_to_string(file)
+    }
+}
+
+fn validate<T: DeserializeOwned + Validate>(
+    text: &str,
+    format: Format,
+) -> Result<ValidationNode, ValidationNode> {
+    match format {
+        Format::Json => not_so_fast::validate_json_str::<T>(text),
+        Format::Yaml => {
+            not_so_fast::deserialize_and_validate::<T, _>(serde_yaml::Deserializer::from_str(text))
+        }
+    }
+}
@@ -0,0 +1,139 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+use not_so_fast_cli::{Cli, Status};
+use serde::Deserialize;
+
+#[derive(Deserialize, Validate)]
+struct User {
+    #[validate(char_length(min = 1, max = 10))]
+    name: String,
+    #[validate(custom = warn_if_young)]
+    age: u8,
+    #[validate(length(max = 3), items(char_length(max = 10)))]
+    tags: Vec<String>,
+}
+
+fn warn_if_young(age: &u8) -> ValidationNode {
+    ValidationNode::error_if(*age < 18, || {
+        ValidationError::with_code("young").and_severity(Severity::Warning)
+    })
+}
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// Runs the command with `args`, replacing `{}` with the fixtures directory.
+/// Returns status, output and error output.
+fn run(args: &[&str]) -> (Status, String, String) {
+    let cli = Cli::new("validate").register::<User>("user");
+    let args = args.iter().map(|arg| arg.replace("{}", FIXTURES));
+    let (mut out, mut err) = (Vec::new(), Vec::new());
+    let status = cli.run_with(args, &mut out, &mut err);
+    let out = String::from_utf8(out).unwrap().replace(FIXTURES, "{}");
+    let err = String::from_utf8(err).unwrap().replace(FIXTURES, "{}");
+    (status, out, err)
+}
+
+#[test]
+fn accepts_valid_documents() {
+    assert_eq!(
+        (Status::Valid, String::new(), String::new()),
+        run(&["user", "{}/valid.json", "{}/valid.yaml"]),
+    );
+    assert_eq!(0, Status::Valid.code());
+}
+
+#[test]
+fn prints_errors_of_invalid_documents() {
+    let (status, out, err) = run(&["user", "{}/valid.json", "{}/invalid.json"]);
+    assert_eq!(Status::Invalid, status);
+    assert_eq!(1, status.code());
+    assert_eq!(
+        "\
+{}/invalid.json: .name: char_length: Invalid character length: max=10, min=1, value=0
+{}/invalid.json: .tags[1]: char_length: Invalid character length: max=10, value=13
+",
+        out,
+    );
+    assert_eq!("", err);
+}
+
+#[test]
+fn prints_deserialization_errors() {
+    let (status, out, _) = run(&["user", "{}/malformed.json"]);
+    assert_eq!(Status::Invalid, status);
+    assert_eq!(
+        "{}/malformed.json: .age: deserialize: invalid type: string \"old\", expected u8 at line 1 column 28\n",
+        out,
+    );
+
+    let (status, out, _) = run(&["--format", "yaml", "user", "{}/valid.json"]);
+    assert_eq!(Status::Valid, status, "JSON is valid YAML");
+    assert_eq!("", out);
+
+    let (status, out, _) = run(&["--format=json", "user", "{}/valid.yaml"]);
+    assert_eq!(Status::Invalid, status);
+    assert!(out.starts_with("{}/valid.yaml: .: deserialize: "), "{out}");
+}
+
+#[test]
+fn fails_on_warnings_when_denied() {
+    assert_eq!(
+        (
+            Status::Valid,
+            "{}/young.yml: .age: warning: young\n".to_owned(),
+            String::new()
+        ),
+        run(&["user", "{}/young.yml"]),
+    );
+    assert_eq!(
+        (
+            Status::Invalid,
+            "{}/young.yml: .age: warning: young\n".to_owned(),
+            String::new()
+        ),
+        run(&["--deny-warnings", "user", "{}/young.yml"]),
+    );
+}
+
+#[test]
+fn reports_usage_and_io_errors() {
+    let (status, out, err) = run(&["user", "{}/missing.json", "{}/invalid.json"]);
+    assert_eq!(Status::Failed, status, "I/O errors take precedence");
+    assert_eq!(2, status.code());
+    assert_eq!(2, out.lines().count());
+    assert!(err.starts_with("{}/missing.json: "), "{err}");
+
+    for (args, error) in [
+        (&["admin", "{}/valid.json"][..], "error: unknown type admin"),
+        (&["user"][..], "error: missing files"),
+        (&[][..], "error: missing type"),
+        (
+            &["--format", "toml", "user", "a"][..],
+            "error: unknown format toml",
+        ),
+        (
+            &["--verbose", "user", "a"][..],
+            "error: unknown option --verbose",
+        ),
+    ] {
+        let (status, out, err) = run(args);
+        assert_eq!(Status::Failed, status);
+        assert_eq!("", out);
+        assert!(err.starts_with(error), "{err}");
+        assert!(
+            err.contains("Usage: validate [OPTIONS] <TYPE> <FILE>..."),
+            "{err}"
+        );
+    }
+
+    let (status, out, _) = run(&["--help"]);
+    assert_eq!(Status::Valid, status);
+    assert!(out.starts_with("Usage: validate"));
+
+    assert_eq!(
+        (Status::Valid, "user\n".to_owned(), String::new()),
+        run(&["--list"]),
+    );
+}
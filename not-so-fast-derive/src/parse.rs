@@ -1,3 +1,7 @@
+// Identifiers of parsed arguments are kept for error spans, even if the
+// expansion does not read all of them.
+#![allow(dead_code)]
+
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::parse::{Parse, ParseStream};
@@ -32,6 +36,16 @@ impl Parse for TypeValidateArguments {
                 {
                     Err(syn::Error::new_spanned(ident, "\"args\" already defined"))
                 }
+                TypeValidateArgument::Context(ident, _)
+                    if acc
+                        .iter()
+                        .any(|a| matches!(a, TypeValidateArgument::Context(_, _))) =>
+                {
+                    Err(syn::Error::new_spanned(
+                        ident,
+                        "\"context\" already defined",
+                    ))
+                }
                 _ => {
                     acc.push(argument);
                     Ok(acc)
@@ -47,10 +61,34 @@ impl Parse for TypeValidateArguments {
 /// - `custom = path::to::function`
 /// - `custom(function = path::to::function)`
 /// - `custom(function = path::to::function, args(100, true))`
+/// - `custom_async = path::to::async_function`
+/// - `context = path::to::Context`
+/// - `declaration_order`
+/// - `dynamic`
+/// - `incremental`
+/// - `proptest`
+/// - `remote = "path::to::Type"`
+/// - `sample`
+/// - `schemars`
+/// - `transparent`
+/// - `trace`
+/// - `wasm = validate_function_name`
 #[derive(Debug)]
 pub enum TypeValidateArgument {
     Args(Ident, ArgsArguments),
+    Context(Ident, Type),
+    DeclarationOrder(Ident),
+    Dynamic(Ident),
+    Incremental(Ident),
+    Proptest(Ident),
+    Remote(Ident, LitStr),
+    Sample(Ident),
+    Schemars(Ident),
+    Transparent(Ident),
+    Trace(Ident),
+    Wasm(Ident, Ident),
     Custom(Ident, CustomArguments),
+    CustomAsync(Ident, CustomArguments),
 }
 
 impl Parse for TypeValidateArgument {
@@ -61,13 +99,40 @@ impl Parse for TypeValidateArgument {
                 let args_arguments: ArgsArguments = input.parse()?;
                 Ok(Self::Args(ident, args_arguments))
             }
+            "context" => {
+                let _: Token![=] = input.parse()?;
+                let type_: Type = input.parse()?;
+                Ok(Self::Context(ident, type_))
+            }
+            "declaration_order" => Ok(Self::DeclarationOrder(ident)),
+            "dynamic" => Ok(Self::Dynamic(ident)),
+            "incremental" => Ok(Self::Incremental(ident)),
+            "proptest" => Ok(Self::Proptest(ident)),
+            "remote" => {
+                let _: Token![=] = input.parse()?;
+                let path: LitStr = input.parse()?;
+                Ok(Self::Remote(ident, path))
+            }
+            "sample" => Ok(Self::Sample(ident)),
+            "schemars" => Ok(Self::Schemars(ident)),
+            "transparent" => Ok(Self::Transparent(ident)),
+            "trace" => Ok(Self::Trace(ident)),
+            "wasm" => {
+                let _: Token![=] = input.parse()?;
+                let function: Ident = input.parse()?;
+                Ok(Self::Wasm(ident, function))
+            }
             "custom" => {
                 let custom_arguments: CustomArguments = input.parse()?;
                 Ok(Self::Custom(ident, custom_arguments))
             }
+            "custom_async" => {
+                let custom_arguments: CustomArguments = input.parse()?;
+                Ok(Self::CustomAsync(ident, custom_arguments))
+            }
             _ => Err(syn::Error::new_spanned(
                 ident,
-                r#"Unknown argument. Expected "args" or "custom""#,
+                r#"Unknown argument. Expected "args", "context", "custom", "custom_async", "declaration_order", "dynamic", "incremental", "proptest", "remote", "sample", "schemars", "transparent", "trace" or "wasm""#,
             )),
         }
     }
@@ -124,12 +189,15 @@ impl Parse for ArgsArgument {
 /// - `= validator::path`
 /// - `(function = validator::path)`
 /// - `(function = validator::path, args(a, b, c))`
+/// - `(function = validator::path, context)`
+/// - `(name = "registered_name")`
 #[derive(Debug)]
 pub struct CustomArguments {
     pub function_ident: Option<Ident>,
-    pub function: Path,
+    pub function: CustomFunction,
     pub args_ident: Option<Ident>,
     pub args: Vec<Arg>,
+    pub context: Option<Ident>,
 }
 
 impl Parse for CustomArguments {
@@ -140,9 +208,10 @@ impl Parse for CustomArguments {
             let path: Path = input.parse()?;
             Ok(Self {
                 function_ident: None,
-                function: path,
+                function: CustomFunction::Path(path),
                 args_ident: None,
                 args: Vec::new(),
+                context: None,
             })
         } else {
             let input_span = input.span();
@@ -150,7 +219,9 @@ impl Parse for CustomArguments {
             let _ = parenthesized!(content in input);
 
             let mut function = None;
+            let mut name = None;
             let mut args = None;
+            let mut context = None;
 
             let arguments = Punctuated::<CustomArgument, Token![,]>::parse_terminated(&content)?;
             for argument in arguments {
@@ -164,38 +235,95 @@ impl Parse for CustomArguments {
                             "\"function\" already defined",
                         ))
                     }
+                    CustomArgument::Name(ident, lit) if name.is_none() => {
+                        name = Some((ident, lit));
+                    }
+                    CustomArgument::Name(ident, _) => {
+                        return Err(syn::Error::new_spanned(ident, "\"name\" already defined"))
+                    }
                     CustomArgument::Args(ident, a) if args.is_none() => {
                         args = Some((ident, a));
                     }
                     CustomArgument::Args(ident, _) => {
                         return Err(syn::Error::new_spanned(ident, "\"args\" already defined"))
                     }
+                    CustomArgument::Context(ident) if context.is_none() => {
+                        context = Some(ident);
+                    }
+                    CustomArgument::Context(ident) => {
+                        return Err(syn::Error::new_spanned(
+                            ident,
+                            "\"context\" already defined",
+                        ))
+                    }
                 }
             }
 
-            match function {
-                Some((ident, path)) => {
+            match (function, name) {
+                (Some((ident, path)), None) => {
                     let (args_ident, args) =
                         args.map_or((None, Vec::new()), |(_, args)| (None, args));
                     Ok(Self {
                         function_ident: Some(ident),
-                        function: path,
+                        function: CustomFunction::Path(path),
                         args_ident,
                         args,
+                        context,
                     })
                 }
-                None => Err(syn::Error::new(input_span, "\"function\" not defined")),
+                (None, Some((ident, lit))) => {
+                    if let Some((args_ident, _)) = args {
+                        return Err(syn::Error::new_spanned(
+                            args_ident,
+                            "\"args\" can't be used with \"name\"",
+                        ));
+                    }
+                    if let Some(context) = context {
+                        return Err(syn::Error::new_spanned(
+                            context,
+                            "\"context\" can't be used with \"name\"",
+                        ));
+                    }
+                    Ok(Self {
+                        function_ident: Some(ident),
+                        function: CustomFunction::Name(lit),
+                        args_ident: None,
+                        args: Vec::new(),
+                        context: None,
+                    })
+                }
+                (Some(_), Some((ident, _))) => Err(syn::Error::new_spanned(
+                    ident,
+                    "\"function\" and \"name\" can't be used together",
+                )),
+                (None, None) => Err(syn::Error::new(
+                    input_span,
+                    "\"function\" or \"name\" not defined",
+                )),
             }
         }
     }
 }
 
+/// Validator called by custom validator arguments.
+#[derive(Debug)]
+pub enum CustomFunction {
+    /// Function at the path.
+    Path(Path),
+    /// Validator registered under the name in the global registry.
+    Name(LitStr),
+}
+
 /// Parses custom validator argument, e.g.
 /// - `function = validator::path`
+/// - `name = "registered_name"`
 /// - `args(a, b, c)`
+/// - `context`
 pub enum CustomArgument {
     Function(Ident, Path),
+    Name(Ident, LitStr),
     Args(Ident, Vec<Arg>),
+    Context(Ident),
 }
 
 impl Parse for CustomArgument {
@@ -205,15 +333,21 @@ impl Parse for CustomArgument {
             let _: Token![=] = input.parse()?;
             let path: Path = input.parse()?;
             Ok(Self::Function(ident, path))
+        } else if ident == "name" {
+            let _: Token![=] = input.parse()?;
+            let name: LitStr = input.parse()?;
+            Ok(Self::Name(ident, name))
         } else if ident == "args" {
             let content;
             let _ = parenthesized!(content in input);
             let args = Punctuated::<Arg, Token![,]>::parse_terminated(&content)?;
             Ok(Self::Args(ident, args.into_iter().collect()))
+        } else if ident == "context" {
+            Ok(Self::Context(ident))
         } else {
             Err(syn::Error::new_spanned(
                 ident,
-                "Illegal argument for custom argument: expected \"function\" or \"args\"",
+                "Illegal argument for custom argument: expected \"function\", \"name\", \"args\" or \"context\"",
             ))
         }
     }
@@ -296,7 +430,11 @@ impl FieldValidateArguments {
         Self {
             arguments: vec![FieldValidateArgument::Nested(
                 None,
-                NestedArguments { args: vec![] },
+                NestedArguments {
+                    args: vec![],
+                    context: None,
+                    cached: None,
+                },
             )],
         }
     }
@@ -331,17 +469,49 @@ impl Parse for OptParenFieldValidateArguments {
 ///
 /// Examples:
 /// - `custom = myfunc`
+/// - `custom_async = my_async_func`
 /// - `length(min=20, max=100)`
+/// - `order = 2`
+/// - `flatten`
+/// - `sample = fake::faker::internet::en::SafeEmail()`
+/// - `before = now`
+/// - `within_last(days = 30)`
+/// - `ord_range(min = START, max = end())`
+/// - `uuid_version = 4`
+/// - `scheme_in("https")`
+/// - `no_credentials`
+/// - `cidr`
+/// - `prefix_len(max = 24)`
+/// - `language_tag`
+/// - `country_code`
 #[derive(Debug)]
 pub enum FieldValidateArgument {
     Some(Ident, Box<FieldValidateArguments>),
-    Items(Ident, Box<FieldValidateArguments>),
-    Fields(Ident, Box<FieldValidateArguments>),
+    Items(Ident, ItemsArguments),
+    Fields(Ident, FieldsArguments),
     Nested(Option<Ident>, NestedArguments),
     Custom(Ident, CustomArguments),
+    CustomAsync(Ident, CustomArguments),
     Length(Ident, LengthArguments),
     CharLength(Ident, LengthArguments),
     Range(Ident, RangeArguments),
+    OrdRange(Ident, OrdRangeArguments),
+    Before(Ident, TimeLimit),
+    After(Ident, TimeLimit),
+    WithinLast(Ident, WithinLastArguments),
+    UuidVersion(Ident, LengthArgumentValue),
+    SchemeIn(Ident, InArguments),
+    HostIn(Ident, InArguments),
+    PortIn(Ident, InArguments),
+    NoCredentials(Ident),
+    Cidr(Ident),
+    PrefixLen(Ident, LengthArguments),
+    LanguageTag(Ident),
+    CountryCode(Ident),
+    CurrencyCode(Ident),
+    Order(Ident, LitInt),
+    Flatten(Ident),
+    Sample(Ident, Expr),
 }
 
 impl Parse for FieldValidateArgument {
@@ -352,32 +522,254 @@ impl Parse for FieldValidateArgument {
                 ident,
                 Box::new(OptParenFieldValidateArguments::parse(input)?.0),
             )),
-            "items" => Ok(Self::Items(
-                ident,
-                Box::new(OptParenFieldValidateArguments::parse(input)?.0),
-            )),
-            "fields" => Ok(Self::Fields(
-                ident,
-                Box::new(OptParenFieldValidateArguments::parse(input)?.0),
-            )),
+            "items" => Ok(Self::Items(ident, input.parse()?)),
+            "fields" => Ok(Self::Fields(ident, input.parse()?)),
             "nested" => Ok(Self::Nested(Some(ident), input.parse()?)),
             "custom" => Ok(Self::Custom(ident, input.parse()?)),
+            "custom_async" => Ok(Self::CustomAsync(ident, input.parse()?)),
             "length" => Ok(Self::Length(ident, input.parse()?)),
             "char_length" => Ok(Self::CharLength(ident, input.parse()?)),
             "range" => Ok(Self::Range(ident, input.parse()?)),
+            "ord_range" => Ok(Self::OrdRange(ident, input.parse()?)),
+            "before" => {
+                let _: Token![=] = input.parse()?;
+                Ok(Self::Before(ident, input.parse()?))
+            }
+            "after" => {
+                let _: Token![=] = input.parse()?;
+                Ok(Self::After(ident, input.parse()?))
+            }
+            "within_last" => Ok(Self::WithinLast(ident, input.parse()?)),
+            "uuid_version" => {
+                let _: Token![=] = input.parse()?;
+                Ok(Self::UuidVersion(ident, input.parse()?))
+            }
+            "scheme_in" => Ok(Self::SchemeIn(ident, input.parse()?)),
+            "host_in" => Ok(Self::HostIn(ident, input.parse()?)),
+            "port_in" => Ok(Self::PortIn(ident, input.parse()?)),
+            "no_credentials" => Ok(Self::NoCredentials(ident)),
+            "cidr" => Ok(Self::Cidr(ident)),
+            "prefix_len" => Ok(Self::PrefixLen(ident, input.parse()?)),
+            "language_tag" => Ok(Self::LanguageTag(ident)),
+            "country_code" => Ok(Self::CountryCode(ident)),
+            "currency_code" => Ok(Self::CurrencyCode(ident)),
+            "order" => {
+                let _: Token![=] = input.parse()?;
+                Ok(Self::Order(ident, input.parse()?))
+            }
+            "flatten" => Ok(Self::Flatten(ident)),
+            "sample" => {
+                let _: Token![=] = input.parse()?;
+                Ok(Self::Sample(ident, input.parse()?))
+            }
             _ => Err(syn::Error::new_spanned(
                 ident,
-                r#"Unknown argument. Expected "some", "items", "fields", "nested", "custom", "length", "char_length" or "range""#,
+                r#"Unknown argument. Expected "some", "items", "fields", "nested", "custom", "custom_async", "length", "char_length", "range", "ord_range", "before", "after", "within_last", "uuid_version", "scheme_in", "host_in", "port_in", "no_credentials", "cidr", "prefix_len", "language_tag", "country_code", "currency_code", "order", "flatten" or "sample""#,
             )),
         }
     }
 }
 
+/// Arguments to `items` validator. Accepts all field arguments and optional
+/// `key` or `key_field`, or `parallel`.
+///
+/// - ``
+/// - `(range(max = 10))`
+/// - `(key = |item| item.id.clone(), nested)`
+/// - `(key_field = id)`
+/// - `(parallel, nested)`
+#[derive(Debug)]
+pub struct ItemsArguments {
+    pub key: Option<ItemKey>,
+    pub parallel: Option<Ident>,
+    pub arguments: Box<FieldValidateArguments>,
+}
+
+impl Parse for ItemsArguments {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if !input.peek(Paren) {
+            return Ok(Self {
+                key: None,
+                parallel: None,
+                arguments: Box::new(FieldValidateArguments::empty()),
+            });
+        }
+
+        let content;
+        let _ = parenthesized!(content in input);
+        let mut key = None;
+        let mut parallel = None;
+        let mut arguments = Vec::new();
+
+        for argument in Punctuated::<ItemsArgument, Token![,]>::parse_terminated(&content)? {
+            match argument {
+                ItemsArgument::Key(key_argument) => {
+                    if key.is_some() {
+                        let ident = match key_argument {
+                            ItemKey::Expr(ident, _) | ItemKey::Field(ident, _) => ident,
+                        };
+                        return Err(syn::Error::new_spanned(ident, "item key already defined"));
+                    }
+                    key = Some(key_argument);
+                }
+                ItemsArgument::Parallel(ident) => {
+                    if parallel.is_some() {
+                        return Err(syn::Error::new_spanned(ident, "parallel already defined"));
+                    }
+                    parallel = Some(ident);
+                }
+                ItemsArgument::Validator(argument) => {
+                    arguments.push(*argument);
+                }
+            }
+        }
+
+        if let (Some(_), Some(ident)) = (&key, &parallel) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "parallel cannot be combined with item keys",
+            ));
+        }
+
+        // `items(key = ...)` validates items like `items`.
+        Ok(Self {
+            arguments: Box::new(if arguments.is_empty() && key.is_some() {
+                FieldValidateArguments::empty()
+            } else {
+                FieldValidateArguments { arguments }
+            }),
+            key,
+            parallel,
+        })
+    }
+}
+
+/// Source of item keys.
+///
+/// - `key = |item| item.id.clone()`
+/// - `key_field = id`
+#[derive(Debug)]
+pub enum ItemKey {
+    Expr(Ident, Box<Expr>),
+    Field(Ident, Member),
+}
+
+/// - `key = |item| item.id.clone()`
+/// - `key_field = id`
+/// - `parallel`
+/// - `range(max = 10)`
+pub enum ItemsArgument {
+    Key(ItemKey),
+    Parallel(Ident),
+    Validator(Box<FieldValidateArgument>),
+}
+
+impl Parse for ItemsArgument {
+    fn parse(input: ParseStream) -> Result<Self> {
+        match input.fork().parse::<Ident>() {
+            Ok(ident) if ident == "key" => {
+                let ident: Ident = input.parse()?;
+                let _: Token![=] = input.parse()?;
+                Ok(Self::Key(ItemKey::Expr(ident, Box::new(input.parse()?))))
+            }
+            Ok(ident) if ident == "key_field" => {
+                let ident: Ident = input.parse()?;
+                let _: Token![=] = input.parse()?;
+                Ok(Self::Key(ItemKey::Field(ident, input.parse()?)))
+            }
+            Ok(ident) if ident == "parallel" => Ok(Self::Parallel(input.parse()?)),
+            _ => Ok(Self::Validator(Box::new(input.parse()?))),
+        }
+    }
+}
+
+/// Arguments to `fields` validator. Accepts all field arguments and optional
+/// `key_display`.
+///
+/// - ``
+/// - `(range(max = 10))`
+/// - `(key_display = path::to::function, range(max = 10))`
+#[derive(Debug)]
+pub struct FieldsArguments {
+    pub key_display: Option<Path>,
+    pub arguments: FieldValidateArguments,
+}
+
+impl Parse for FieldsArguments {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if !input.peek(Paren) {
+            return Ok(Self {
+                key_display: None,
+                arguments: FieldValidateArguments::empty(),
+            });
+        }
+
+        let content;
+        let _ = parenthesized!(content in input);
+        let mut key_display = None;
+        let mut arguments = Vec::new();
+
+        for argument in Punctuated::<FieldsArgument, Token![,]>::parse_terminated(&content)? {
+            match argument {
+                FieldsArgument::KeyDisplay(ident, _) if key_display.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "key_display already defined",
+                    ));
+                }
+                FieldsArgument::KeyDisplay(_, path) => {
+                    key_display = Some(path);
+                }
+                FieldsArgument::Validator(argument) => {
+                    arguments.push(*argument);
+                }
+            }
+        }
+
+        // `fields(key_display = ...)` validates values like `fields`.
+        Ok(Self {
+            arguments: if arguments.is_empty() && key_display.is_some() {
+                FieldValidateArguments::empty()
+            } else {
+                FieldValidateArguments { arguments }
+            },
+            key_display,
+        })
+    }
+}
+
+/// - `key_display = path::to::function`
+/// - `range(max = 10)`
+pub enum FieldsArgument {
+    KeyDisplay(Ident, Path),
+    Validator(Box<FieldValidateArgument>),
+}
+
+impl Parse for FieldsArgument {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input
+            .fork()
+            .parse::<Ident>()
+            .is_ok_and(|ident| ident == "key_display")
+        {
+            let ident: Ident = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self::KeyDisplay(ident, input.parse()?))
+        } else {
+            Ok(Self::Validator(Box::new(input.parse()?)))
+        }
+    }
+}
+
 /// - ``
 /// - `(args(a, b, c))`
+/// - `(context)`
+/// - `(cached)`
 #[derive(Debug)]
 pub struct NestedArguments {
     pub args: Vec<Arg>,
+    pub context: Option<Ident>,
+    pub cached: Option<Ident>,
 }
 
 impl Parse for NestedArguments {
@@ -387,6 +779,8 @@ impl Parse for NestedArguments {
             let _ = parenthesized!(content in input);
             let arguments = Punctuated::<NestedArgument, Token![,]>::parse_terminated(&content)?;
             let mut args = None;
+            let mut context = None;
+            let mut cached = None;
             for argument in arguments {
                 match argument {
                     NestedArgument::Args(ident, _) if args.is_some() => {
@@ -395,21 +789,55 @@ impl Parse for NestedArguments {
                     NestedArgument::Args(_, a) => {
                         args = Some(a);
                     }
+                    NestedArgument::Context(ident) if context.is_some() => {
+                        return Err(syn::Error::new_spanned(ident, "context already defined"));
+                    }
+                    NestedArgument::Context(ident) => {
+                        context = Some(ident);
+                    }
+                    NestedArgument::Cached(ident) if cached.is_some() => {
+                        return Err(syn::Error::new_spanned(ident, "cached already defined"));
+                    }
+                    NestedArgument::Cached(ident) => {
+                        cached = Some(ident);
+                    }
                 }
             }
+            if let (Some(_), Some(ident)) = (&args, &context) {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "specify either args or context",
+                ));
+            }
+            if let (true, Some(ident)) = (args.is_some() || context.is_some(), &cached) {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "cached can not be combined with args or context",
+                ));
+            }
             Ok(Self {
                 args: args.unwrap_or_default(),
+                context,
+                cached,
             })
         } else {
-            Ok(Self { args: Vec::new() })
+            Ok(Self {
+                args: Vec::new(),
+                context: None,
+                cached: None,
+            })
         }
     }
 }
 
 /// - `args(a, b, c)`
+/// - `context`
+/// - `cached`
 #[derive(Debug)]
 pub enum NestedArgument {
     Args(Ident, Vec<Arg>),
+    Context(Ident),
+    Cached(Ident),
 }
 
 impl Parse for NestedArgument {
@@ -420,10 +848,14 @@ impl Parse for NestedArgument {
             let _ = parenthesized!(content in input);
             let args = Punctuated::<Arg, Token![,]>::parse_terminated(&content)?;
             Ok(Self::Args(ident, args.into_iter().collect()))
+        } else if ident == "context" {
+            Ok(Self::Context(ident))
+        } else if ident == "cached" {
+            Ok(Self::Cached(ident))
         } else {
             Err(syn::Error::new_spanned(
                 ident,
-                "Unsupported argument, expected \"args\"",
+                "Unsupported argument, expected \"args\", \"context\" or \"cached\"",
             ))
         }
     }
@@ -645,3 +1077,167 @@ impl ToTokens for RangeArgumentValue {
         }
     }
 }
+
+/// - (min = START)
+/// - (max = end())
+/// - (min = START, max = end())
+#[derive(Debug)]
+pub struct OrdRangeArguments {
+    pub min: Option<(Ident, Box<Expr>)>,
+    pub max: Option<(Ident, Box<Expr>)>,
+}
+
+impl Parse for OrdRangeArguments {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut min = None;
+        let mut max = None;
+
+        let content;
+        let _ = parenthesized!(content in input);
+        let content_span_start = content.span();
+        let args = Punctuated::<OrdRangeArgument, Token![,]>::parse_terminated(&content)?;
+
+        for OrdRangeArgument { ident, value } in args {
+            let bound = if ident == "min" {
+                &mut min
+            } else if ident == "max" {
+                &mut max
+            } else {
+                return Err(syn::Error::new(ident.span(), "unknown ord_range argument"));
+            };
+            if bound.is_some() {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("{ident} already declared"),
+                ));
+            }
+            *bound = Some((ident, value));
+        }
+
+        if min.is_none() && max.is_none() {
+            return Err(syn::Error::new(content_span_start, "specify min or max"));
+        }
+
+        Ok(Self { min, max })
+    }
+}
+
+/// - `min = START`
+/// - `max = end()`
+struct OrdRangeArgument {
+    ident: Ident,
+    value: Box<Expr>,
+}
+
+impl Parse for OrdRangeArgument {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        let value = Box::new(input.parse()?);
+        Ok(Self { ident, value })
+    }
+}
+
+/// Allowed values of `scheme_in`, `host_in` and `port_in` validators.
+///
+/// - `("https")`
+/// - `("example.com", ALLOWED_HOST)`
+/// - `(443, 8443)`
+#[derive(Debug)]
+pub struct InArguments {
+    pub values: Vec<Expr>,
+}
+
+impl Parse for InArguments {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let _ = parenthesized!(content in input);
+        let content_span_start = content.span();
+        let values: Vec<Expr> = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+        if values.is_empty() {
+            return Err(syn::Error::new(
+                content_span_start,
+                "specify allowed values",
+            ));
+        }
+        Ok(Self { values })
+    }
+}
+
+/// Limit of `before` and `after` validators.
+///
+/// - `now`
+/// - `LAUNCH_DATE`
+/// - `NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()`
+#[derive(Debug)]
+pub enum TimeLimit {
+    Now(Ident),
+    Expr(Box<Expr>),
+}
+
+impl Parse for TimeLimit {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fork = input.fork();
+        if let Ok(ident) = fork.parse::<Ident>() {
+            if ident == "now" && (fork.is_empty() || fork.peek(Token![,])) {
+                return Ok(Self::Now(input.parse()?));
+            }
+        }
+        Ok(Self::Expr(Box::new(input.parse()?)))
+    }
+}
+
+/// - (days = 30)
+/// - (hours = 12, minutes = 30)
+/// - (seconds = path::to::VAR_OR_CONST)
+#[derive(Debug)]
+pub struct WithinLastArguments {
+    pub units: Vec<LengthArgument>,
+}
+
+impl WithinLastArguments {
+    /// Names of accepted units with their lengths in seconds.
+    pub const UNITS: [(&'static str, u64); 4] = [
+        ("days", 24 * 60 * 60),
+        ("hours", 60 * 60),
+        ("minutes", 60),
+        ("seconds", 1),
+    ];
+}
+
+impl Parse for WithinLastArguments {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let _ = parenthesized!(content in input);
+        let content_span_start = content.span();
+        let args = Punctuated::<LengthArgument, Token![,]>::parse_terminated(&content)?;
+
+        let mut units: Vec<LengthArgument> = Vec::new();
+        for arg in args {
+            if !Self::UNITS.iter().any(|(unit, _)| arg.ident == unit) {
+                return Err(syn::Error::new(
+                    arg.ident.span(),
+                    r#"unknown within_last argument. Expected "days", "hours", "minutes" or "seconds""#,
+                ));
+            }
+            if units.iter().any(|unit| unit.ident == arg.ident) {
+                return Err(syn::Error::new(
+                    arg.ident.span(),
+                    format!("{} already declared", arg.ident),
+                ));
+            }
+            units.push(arg);
+        }
+
+        if units.is_empty() {
+            return Err(syn::Error::new(
+                content_span_start,
+                "specify days, hours, minutes or seconds",
+            ));
+        }
+
+        Ok(Self { units })
+    }
+}
@@ -0,0 +1,39 @@
+#ifndef NOT_SO_FAST_H
+#define NOT_SO_FAST_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Document is valid. Errors may contain warnings and infos. */
+#define NSF_VALID 0
+/* Document has errors of severity "error". */
+#define NSF_INVALID 1
+/* Document can't be deserialized into the type. */
+#define NSF_MALFORMED 2
+/* No type is registered under the name. */
+#define NSF_UNKNOWN_TYPE 3
+/* Arguments are null or not UTF-8. */
+#define NSF_INVALID_ARGUMENT 4
+/* Validation panicked. */
+#define NSF_PANIC 5
+
+/*
+ * Validates a JSON document of json_len bytes against the type registered
+ * under type_name. Returns one of NSF_* status codes. With NSF_VALID,
+ * NSF_INVALID and NSF_MALFORMED statuses, stores a JSON array of errors in
+ * *errors, unless errors is NULL. The array must be released with
+ * nsf_free_string.
+ */
+int nsf_validate_json(const char *type_name, const char *json, size_t json_len, char **errors);
+
+/* Releases a string returned by not-so-fast. Does nothing if string is NULL. */
+void nsf_free_string(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
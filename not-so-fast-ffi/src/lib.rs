@@ -0,0 +1,206 @@
+//! C ABI for validating JSON documents with `not-so-fast`, for embedding Rust
+//! validation in C and C++ programs.
+//!
+//! Rust code registers types under names with [register]. C code then
+//! validates JSON documents against registered types with
+//! `nsf_validate_json`, which returns a status code and the errors serialized
+//! as [NodeAsFlatList](not_so_fast::NodeAsFlatList), and releases the
+//! returned strings with `nsf_free_string`. Declarations of the C functions
+//! are in `include/not_so_fast.h`.
+//!
+//! Link the functions into a `staticlib` or `cdylib` crate that depends on
+//! `not-so-fast-ffi` and exports a function registering its types:
+//!
+//! ```
+//! use not_so_fast::Validate;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, Validate)]
+//! struct User {
+//!     #[validate(char_length(min = 1, max = 30))]
+//!     name: String,
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn my_service_register_types() {
+//!     not_so_fast_ffi::register::<User>("User");
+//! }
+//! ```
+//!
+//! ```c
+//! #include "not_so_fast.h"
+//!
+//! my_service_register_types();
+//! char *errors = NULL;
+//! int status = nsf_validate_json("User", json, json_len, &errors);
+//! if (status == NSF_INVALID) {
+//!     // errors: [{"path":"name","code":"char_length",...}]
+//! }
+//! nsf_free_string(errors);
+//! ```
+
+use std::collections::BTreeMap;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::RwLock;
+
+use not_so_fast::{Validate, ValidationNode};
+use serde::de::DeserializeOwned;
+
+/// Document is valid. Errors may contain warnings and infos.
+pub const NSF_VALID: c_int = 0;
+/// Document has errors of `Severity::Error`.
+pub const NSF_INVALID: c_int = 1;
+/// Document can't be deserialized into the type. Errors have the
+/// `deserialize` code.
+pub const NSF_MALFORMED: c_int = 2;
+/// No type is registered under the name.
+pub const NSF_UNKNOWN_TYPE: c_int = 3;
+/// Arguments are null or not UTF-8.
+pub const NSF_INVALID_ARGUMENT: c_int = 4;
+/// Validation panicked.
+pub const NSF_PANIC: c_int = 5;
+
+/// Deserializes a JSON document and validates it. Deserialization errors are
+/// returned as `Err`.
+type ValidateJson = fn(&str) -> Result<ValidationNode, ValidationNode>;
+
+static REGISTRY: RwLock<BTreeMap<String, ValidateJson>> = RwLock::new(BTreeMap::new());
+
+/// Error of [validate_json].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// No type is registered under the name.
+    UnknownType(String),
+    /// Document can't be deserialized into the type. Errors are placed at
+    /// paths of the values that failed to deserialize.
+    Malformed(ValidationNode),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownType(name) => write!(f, "type {name:?} is not registered"),
+            Error::Malformed(errors) => write!(f, "malformed document:\n{errors}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Registers type `T` under `name`, replacing the type registered under the
+/// same name before.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(serde::Deserialize, Validate)]
+/// struct Order {
+///     #[validate(range(min = 1))]
+///     quantity: u32,
+/// }
+///
+/// not_so_fast_ffi::register::<Order>("Order");
+/// assert!(not_so_fast_ffi::validate_json("Order", r#"{"quantity": 0}"#).unwrap().has_errors());
+/// ```
+pub fn register<T: DeserializeOwned + Validate>(name: impl Into<String>) {
+    REGISTRY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(name.into(), not_so_fast::validate_json_str::<T>);
+}
+
+/// Validates a JSON document against the type registered under `type_name`,
+/// like `nsf_validate_json` does.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(serde::Deserialize, Validate)]
+/// struct Tag(#[validate(char_length(max = 3))] String);
+///
+/// not_so_fast_ffi::register::<Tag>("Tag");
+/// assert!(not_so_fast_ffi::validate_json("Tag", r#""new""#).unwrap().is_ok());
+/// assert!(matches!(
+///     not_so_fast_ffi::validate_json("Tag", "7"),
+///     Err(not_so_fast_ffi::Error::Malformed(_)),
+/// ));
+/// assert!(matches!(
+///     not_so_fast_ffi::validate_json("Label", r#""new""#),
+///     Err(not_so_fast_ffi::Error::UnknownType(_)),
+/// ));
+/// ```
+pub fn validate_json(type_name: &str, json: &str) -> Result<ValidationNode, Error> {
+    let validate = REGISTRY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(type_name)
+        .copied()
+        .ok_or_else(|| Error::UnknownType(type_name.to_owned()))?;
+    validate(json).map_err(Error::Malformed)
+}
+
+/// Validates a JSON document of `json_len` bytes against the type registered
+/// under `type_name`. Returns one of `NSF_*` status codes. With
+/// [NSF_VALID], [NSF_INVALID] and [NSF_MALFORMED] statuses, stores a JSON
+/// array of errors in `errors`, unless `errors` is null. The array must be
+/// released with [nsf_free_string].
+///
+/// # Safety
+///
+/// `type_name` must be a null-terminated string, `json` must point to
+/// `json_len` readable bytes and `errors` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn nsf_validate_json(
+    type_name: *const c_char,
+    json: *const c_char,
+    json_len: usize,
+    errors: *mut *mut c_char,
+) -> c_int {
+    if !errors.is_null() {
+        *errors = std::ptr::null_mut();
+    }
+    if type_name.is_null() || json.is_null() {
+        return NSF_INVALID_ARGUMENT;
+    }
+    let Ok(type_name) = CStr::from_ptr(type_name).to_str() else {
+        return NSF_INVALID_ARGUMENT;
+    };
+    let json = std::slice::from_raw_parts(json.cast::<u8>(), json_len);
+    let Ok(json) = std::str::from_utf8(json) else {
+        return NSF_INVALID_ARGUMENT;
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let (status, node) = match validate_json(type_name, json) {
+            Ok(node) if node.has_errors() => (NSF_INVALID, node),
+            Ok(node) => (NSF_VALID, node),
+            Err(Error::Malformed(node)) => (NSF_MALFORMED, node),
+            Err(Error::UnknownType(_)) => return (NSF_UNKNOWN_TYPE, None),
+        };
+        let list = serde_json::to_string(&node.as_flat_list())
+            .expect("Serialization of errors into JSON should not fail");
+        (status, Some(list))
+    }));
+    let Ok((status, list)) = result else {
+        return NSF_PANIC;
+    };
+    if let (Some(list), false) = (list, errors.is_null()) {
+        // JSON strings escape null characters.
+        *errors = CString::new(list)
+            .expect("JSON should not contain null characters")
+            .into_raw();
+    }
+    status
+}
+
+/// Releases a string returned by `not-so-fast-ffi`. Does nothing if `string`
+/// is null.
+///
+/// # Safety
+///
+/// `string` must be null or returned by a function of this library, and must
+/// not be used after the call.
+#[no_mangle]
+pub unsafe extern "C" fn nsf_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
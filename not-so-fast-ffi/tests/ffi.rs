@@ -0,0 +1,145 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use std::ffi::{c_char, CStr, CString};
+
+use not_so_fast::*;
+use not_so_fast_ffi::Error;
+use not_so_fast_ffi::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Validate)]
+struct User {
+    #[validate(char_length(min = 1, max = 5))]
+    name: String,
+    #[validate(custom = warn_if_young)]
+    age: u8,
+}
+
+fn warn_if_young(age: &u8) -> ValidationNode {
+    ValidationNode::error_if(*age < 18, || {
+        ValidationError::with_code("young").and_severity(Severity::Warning)
+    })
+}
+
+#[derive(Deserialize, Validate)]
+struct Panicking {
+    #[validate(custom = panic)]
+    value: u8,
+}
+
+fn panic(_: &u8) -> ValidationNode {
+    panic!("validator failed")
+}
+
+/// Calls `nsf_validate_json` and returns its status with the errors.
+fn call(type_name: &str, json: &str) -> (i32, Option<String>) {
+    let type_name = CString::new(type_name).unwrap();
+    let mut errors: *mut c_char = std::ptr::null_mut();
+    unsafe {
+        let status = nsf_validate_json(
+            type_name.as_ptr(),
+            json.as_ptr().cast(),
+            json.len(),
+            &mut errors,
+        );
+        let list = (!errors.is_null()).then(|| CStr::from_ptr(errors).to_str().unwrap().to_owned());
+        nsf_free_string(errors);
+        (status, list)
+    }
+}
+
+#[test]
+fn validates_registered_types() {
+    register::<User>("User");
+
+    assert_eq!(
+        (NSF_VALID, Some("[]".to_owned())),
+        call("User", r#"{"name": "tom", "age": 30}"#),
+    );
+    assert_eq!(
+        (
+            NSF_VALID,
+            Some(r#"[{"path":"age","code":"young","severity":"warning"}]"#.to_owned())
+        ),
+        call("User", r#"{"name": "tom", "age": 10}"#),
+    );
+    assert_eq!(
+        (
+            NSF_INVALID,
+            Some(r#"[{"path":"name","code":"char_length","message":"Invalid character length","params":{"max":5,"min":1,"value":6}}]"#.to_owned())
+        ),
+        call("User", r#"{"name": "thomas", "age": 30}"#),
+    );
+    assert_eq!((NSF_UNKNOWN_TYPE, None), call("Admin", "{}"));
+}
+
+#[test]
+fn reports_malformed_documents() {
+    register::<User>("User");
+
+    assert_eq!(
+        (
+            NSF_MALFORMED,
+            Some(r#"[{"path":"age","code":"deserialize","message":"invalid type: string \"old\", expected u8 at line 1 column 28"}]"#.to_owned())
+        ),
+        call("User", r#"{"name": "tom", "age": "old"}"#),
+    );
+    assert_eq!(
+        NSF_MALFORMED,
+        call("User", r#"{"name": "tom", "age": 30} []"#).0,
+    );
+    assert_eq!(
+        Err(Error::UnknownType("Admin".to_owned())),
+        validate_json("Admin", "{}"),
+    );
+}
+
+#[test]
+fn rejects_invalid_arguments() {
+    register::<User>("User");
+
+    let json = b"{\"name\": \"\xff\", \"age\": 30}";
+    let mut errors: *mut c_char = std::ptr::null_mut();
+    unsafe {
+        let type_name = CString::new("User").unwrap();
+        assert_eq!(
+            NSF_INVALID_ARGUMENT,
+            nsf_validate_json(
+                type_name.as_ptr(),
+                json.as_ptr().cast(),
+                json.len(),
+                &mut errors
+            ),
+        );
+        assert!(errors.is_null());
+        assert_eq!(
+            NSF_INVALID_ARGUMENT,
+            nsf_validate_json(
+                std::ptr::null(),
+                json.as_ptr().cast(),
+                json.len(),
+                &mut errors
+            ),
+        );
+        // Errors are optional.
+        let json = br#"{"name": "", "age": 30}"#;
+        assert_eq!(
+            NSF_INVALID,
+            nsf_validate_json(
+                type_name.as_ptr(),
+                json.as_ptr().cast(),
+                json.len(),
+                std::ptr::null_mut()
+            ),
+        );
+        nsf_free_string(std::ptr::null_mut());
+    }
+}
+
+#[test]
+fn catches_panics() {
+    register::<Panicking>("Panicking");
+
+    assert_eq!((NSF_PANIC, None), call("Panicking", r#"{"value": 1}"#));
+}
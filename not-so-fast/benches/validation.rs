@@ -0,0 +1,220 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+use not_so_fast::*;
+
+#[derive(Validate)]
+struct Address {
+    #[validate(char_length(min = 1, max = 50))]
+    city: String,
+    #[validate(char_length(max = 100))]
+    street: String,
+    #[validate(length(equal = 6))]
+    postal_code: String,
+}
+
+#[derive(Validate)]
+struct User {
+    #[validate(char_length(min = 3, max = 30), custom = alpha_only)]
+    nick: String,
+    #[validate(range(min = 13, max = 150))]
+    age: u8,
+    #[validate(length(max = 5), items(char_length(max = 20)))]
+    tags: Vec<String>,
+    #[validate(nested)]
+    address: Address,
+    #[validate(items(nested))]
+    previous_addresses: Vec<Address>,
+}
+
+fn alpha_only(value: &str) -> ValidationNode {
+    ValidationNode::error_if(value.chars().any(|c| !c.is_alphanumeric()), || {
+        ValidationError::with_code("alpha_only")
+    })
+}
+
+fn address(valid: bool) -> Address {
+    Address {
+        city: if valid { "Warsaw" } else { "" }.into(),
+        street: "Marszałkowska".repeat(if valid { 1 } else { 10 }),
+        postal_code: if valid { "00-001" } else { "1" }.into(),
+    }
+}
+
+fn user(valid: bool) -> User {
+    User {
+        nick: if valid { "tom1980" } else { "**tom1980**" }.into(),
+        age: if valid { 44 } else { 200 },
+        tags: (0..if valid { 3 } else { 8 })
+            .map(|i| format!("tag{i}").repeat(if valid { 1 } else { 5 }))
+            .collect(),
+        address: address(valid),
+        previous_addresses: (0..10).map(|_| address(valid)).collect(),
+    }
+}
+
+fn users(count: usize, valid: bool) -> Vec<User> {
+    (0..count).map(|_| user(valid)).collect()
+}
+
+fn validate_users(users: &[User]) -> ValidationNode {
+    ValidationNode::items(users.iter(), |_, user| user.validate())
+}
+
+fn bench_validate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate");
+    let valid = users(100, true);
+    let invalid = users(100, false);
+    group.bench_function("valid_users", |b| {
+        b.iter(|| validate_users(black_box(&valid)))
+    });
+    group.bench_function("invalid_users", |b| {
+        b.iter(|| validate_users(black_box(&invalid)))
+    });
+    group.finish();
+}
+
+fn validate_address_in<'bump>(arena: Arena<'bump>, address: &Address) -> ArenaNode<'bump> {
+    let city = address.city.chars().count();
+    let street = address.street.chars().count();
+    arena
+        .ok()
+        .and_field(
+            "city",
+            arena.error_if(!(1..=50).contains(&city), || {
+                arena
+                    .with_code(codes::CHAR_LENGTH)
+                    .and_message("Invalid character length")
+                    .and_param("value", city)
+                    .and_param("min", 1)
+                    .and_param("max", 50)
+            }),
+        )
+        .and_field(
+            "street",
+            arena.error_if(street > 100, || {
+                arena
+                    .with_code(codes::CHAR_LENGTH)
+                    .and_message("Invalid character length")
+                    .and_param("value", street)
+                    .and_param("max", 100)
+            }),
+        )
+        .and_field(
+            "postal_code",
+            arena.error_if(address.postal_code.len() != 6, || {
+                arena
+                    .with_code(codes::LENGTH)
+                    .and_message("Invalid length")
+                    .and_param("value", address.postal_code.len())
+                    .and_param("equal", 6)
+            }),
+        )
+}
+
+fn bench_arena(c: &mut Criterion) {
+    let mut group = c.benchmark_group("arena");
+    let addresses: Vec<_> = (0..1000).map(|_| address(false)).collect();
+    group.bench_function("heap", |b| {
+        b.iter(|| ValidationNode::items(black_box(&addresses).iter(), |_, a| a.validate()).is_err())
+    });
+    let mut bump = bumpalo::Bump::new();
+    group.bench_function("bump", |b| {
+        b.iter(|| {
+            let arena = Arena::new(&bump);
+            let invalid = black_box(&addresses)
+                .iter()
+                .enumerate()
+                .fold(arena.ok(), |node, (index, address)| {
+                    node.and_item(index, validate_address_in(arena, address))
+                })
+                .is_err();
+            bump.reset();
+            invalid
+        })
+    });
+    group.finish();
+}
+
+fn bench_errors(c: &mut Criterion) {
+    let mut group = c.benchmark_group("errors");
+    let values: Vec<u32> = (0..1000).collect();
+    group.bench_function("single_error_items", |b| {
+        b.iter(|| {
+            ValidationNode::items(black_box(&values).iter(), |_, value| {
+                ValidationNode::error_if(*value % 2 == 0, || ValidationError::with_code("odd"))
+            })
+        })
+    });
+    group.bench_function("single_error_items_with_params", |b| {
+        b.iter(|| {
+            ValidationNode::items(black_box(&values).iter(), |_, value| {
+                ValidationNode::error_if(*value > 10, || {
+                    ValidationError::with_code("range")
+                        .and_param("max", 10)
+                        .and_param("value", *value)
+                })
+            })
+        })
+    });
+    group.bench_function("lazy_items_with_limits", |b| {
+        let limits = ValidationLimits::new().with_max_errors(usize::MAX);
+        b.iter(|| {
+            limits.run(|| {
+                black_box(&values)
+                    .iter()
+                    .fold(ValidationNode::ok(), |node, value| {
+                        node.and_item_with(*value as usize, || {
+                            ValidationNode::error(ValidationError::with_code("abc"))
+                        })
+                    })
+            })
+        })
+    });
+    group.finish();
+}
+
+fn bench_merge(c: &mut Criterion) {
+    let first = validate_users(&users(50, false));
+    let second =
+        ValidationNode::items(users(100, false).iter().skip(25), |_, user| user.validate());
+    c.bench_function("merge", |b| {
+        b.iter(|| black_box(first.clone()).merge(black_box(second.clone())))
+    });
+}
+
+fn bench_render(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render");
+    let errors = validate_users(&users(100, false)).and_keyed_item(
+        "quoted \"key\"",
+        ValidationNode::field(
+            "with space",
+            ValidationNode::error(ValidationError::with_code("abc")),
+        ),
+    );
+    group.bench_function("display", |b| b.iter(|| black_box(&errors).to_string()));
+    group.bench_function("display_json_pointer", |b| {
+        b.iter(|| {
+            black_box(&errors)
+                .display_with(DisplayOptions::new().with_path_style(PathStyle::JsonPointer))
+                .to_string()
+        })
+    });
+    group.bench_function("serde_json", |b| {
+        b.iter(|| serde_json::to_string(black_box(&errors)).unwrap())
+    });
+    group.bench_function("serde_json_tree", |b| {
+        b.iter(|| serde_json::to_string(&black_box(&errors).as_tree()).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_validate,
+    bench_arena,
+    bench_errors,
+    bench_merge,
+    bench_render
+);
+criterion_main!(benches);
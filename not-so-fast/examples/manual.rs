@@ -291,6 +291,7 @@ fn main() {
 
     // We could report errors on the object that has invalid keys.
 
+    #[allow(clippy::filter_map_bool_then)]
     fn validate_product_prices_alt_1(
         product_prices: &std::collections::HashMap<String, f32>,
     ) -> ValidationNode {
@@ -0,0 +1,280 @@
+//! Validation trees allocated in a `bumpalo` arena, for services validating
+//! many values per request. Nodes, names, codes, messages and params of one
+//! validation pass are bump-allocated and freed at once when the arena is
+//! reset or dropped. Trees that must outlive the arena are copied out with
+//! [ArenaNode::to_node].
+
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use core::fmt::Write;
+
+use bumpalo::collections::{String as BumpString, Vec as BumpVec};
+use bumpalo::Bump;
+
+use crate::{ParamValue, ValidationError, ValidationNode};
+
+/// Handle of a `bumpalo::Bump` creating [ArenaNode]s and [ArenaError]s
+/// allocated in it.
+/// ```
+/// # use not_so_fast::*;
+/// fn validate_name<'bump>(arena: Arena<'bump>, name: &str) -> ArenaNode<'bump> {
+///     arena.error_if(name.is_empty(), || {
+///         arena.with_code("length").and_param("min", 1)
+///     })
+/// }
+///
+/// let mut bump = bumpalo::Bump::new();
+/// for names in [["Alice", ""], ["Bob", "Eve"]] {
+///     {
+///         let arena = Arena::new(&bump);
+///         let errors = names.iter().enumerate().fold(arena.ok(), |node, (index, name)| {
+///             node.and_item(index, validate_name(arena, name))
+///         });
+///         if errors.is_err() {
+///             assert_eq!(".[1]: length: min=1", errors.to_string());
+///         }
+///     }
+///     // Frees all nodes of the pass at once.
+///     bump.reset();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Arena<'bump> {
+    bump: &'bump Bump,
+}
+
+impl<'bump> Arena<'bump> {
+    /// Creates a handle allocating in `bump`.
+    pub fn new(bump: &'bump Bump) -> Self {
+        Self { bump }
+    }
+
+    /// Returns the underlying `bumpalo::Bump`.
+    pub fn bump(self) -> &'bump Bump {
+        self.bump
+    }
+
+    /// Creates a node without errors, like [ValidationNode::ok] does. Empty
+    /// nodes allocate nothing.
+    pub fn ok(self) -> ArenaNode<'bump> {
+        ArenaNode {
+            arena: self,
+            errors: BumpVec::new_in(self.bump),
+            fields: BumpVec::new_in(self.bump),
+            items: BumpVec::new_in(self.bump),
+        }
+    }
+
+    /// Creates a node with `error`, like [ValidationNode::error] does.
+    pub fn error(self, error: ArenaError<'bump>) -> ArenaNode<'bump> {
+        self.ok().and_error(error)
+    }
+
+    /// Creates a node with the error returned by `f` if `condition` is true,
+    /// like [ValidationNode::error_if] does.
+    pub fn error_if(
+        self,
+        condition: bool,
+        f: impl FnOnce() -> ArenaError<'bump>,
+    ) -> ArenaNode<'bump> {
+        if condition {
+            self.error(f())
+        } else {
+            self.ok()
+        }
+    }
+
+    /// Creates an error with `code`, like [ValidationError::with_code] does.
+    pub fn with_code(self, code: &str) -> ArenaError<'bump> {
+        ArenaError {
+            arena: self,
+            code: self.bump.alloc_str(code),
+            message: None,
+            params: BumpVec::new_in(self.bump),
+        }
+    }
+
+    /// Formats `args` into a string allocated in the arena, e.g. for
+    /// messages or keys of items.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let bump = bumpalo::Bump::new();
+    /// let arena = Arena::new(&bump);
+    /// assert_eq!("user-7", arena.format(format_args!("user-{}", 7)));
+    /// ```
+    pub fn format(self, args: core::fmt::Arguments) -> &'bump str {
+        let mut string = BumpString::new_in(self.bump);
+        // Writing to a string never fails.
+        let _ = string.write_fmt(args);
+        string.into_bump_str()
+    }
+}
+
+/// [ValidationError] allocated in an [Arena]. Created by
+/// [Arena::with_code].
+#[derive(Debug)]
+pub struct ArenaError<'bump> {
+    arena: Arena<'bump>,
+    code: &'bump str,
+    message: Option<&'bump str>,
+    params: BumpVec<'bump, (&'bump str, ParamValue)>,
+}
+
+impl<'bump> ArenaError<'bump> {
+    /// Sets message of the error, like [ValidationError::and_message] does.
+    pub fn and_message(mut self, message: &str) -> Self {
+        self.message = Some(self.arena.bump.alloc_str(message));
+        self
+    }
+
+    /// Adds param to the error, like [ValidationError::and_param] does.
+    /// Params of numbers and static strings allocate nothing outside the
+    /// arena.
+    pub fn and_param(mut self, name: &str, value: impl Into<ParamValue>) -> Self {
+        let name = self.arena.bump.alloc_str(name);
+        let value = value.into();
+        match self.params.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.params.push((name, value)),
+        }
+        self
+    }
+
+    /// Returns code of the error.
+    pub fn code(&self) -> &'bump str {
+        self.code
+    }
+
+    /// Returns message of the error.
+    pub fn message(&self) -> Option<&'bump str> {
+        self.message
+    }
+
+    /// Copies the error out of the arena.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let bump = bumpalo::Bump::new();
+    /// let error = Arena::new(&bump)
+    ///     .with_code("range")
+    ///     .and_message("Number not in range")
+    ///     .and_param("max", 10);
+    /// assert_eq!(
+    ///     ValidationError::with_code("range")
+    ///         .and_message("Number not in range")
+    ///         .and_param("max", 10),
+    ///     error.to_error(),
+    /// );
+    /// ```
+    pub fn to_error(&self) -> ValidationError {
+        let error = ValidationError::with_code(self.code.to_string());
+        let error = match self.message {
+            Some(message) => error.and_message(message.to_string()),
+            None => error,
+        };
+        self.params.iter().fold(error, |error, (name, value)| {
+            error.and_param(name.to_string(), value.clone())
+        })
+    }
+}
+
+/// [ValidationNode] allocated in an [Arena]. Created by [Arena::ok],
+/// [Arena::error] and [Arena::error_if]. Fields and items keep the order in
+/// which they were added, and are merged and sorted like in
+/// [ValidationNode] when the tree is copied out with
+/// [to_node](ArenaNode::to_node).
+#[derive(Debug)]
+pub struct ArenaNode<'bump> {
+    arena: Arena<'bump>,
+    errors: BumpVec<'bump, ArenaError<'bump>>,
+    fields: BumpVec<'bump, (&'bump str, ArenaNode<'bump>)>,
+    items: BumpVec<'bump, (ArenaItemKey<'bump>, ArenaNode<'bump>)>,
+}
+
+/// Identifies an item of [ArenaNode], like `ItemKey` of [ValidationNode].
+#[derive(Debug, Clone, Copy)]
+enum ArenaItemKey<'bump> {
+    Index(usize),
+    Key(&'bump str),
+}
+
+impl<'bump> ArenaNode<'bump> {
+    /// Checks if the node has no errors.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty() && self.fields.is_empty() && self.items.is_empty()
+    }
+
+    /// Checks if the node has errors.
+    pub fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+
+    /// Adds `error` to the node, like [ValidationNode::and_error] does.
+    pub fn and_error(mut self, error: ArenaError<'bump>) -> Self {
+        self.errors.push(error);
+        self
+    }
+
+    /// Adds errors of field `name`, like [ValidationNode::and_field] does.
+    /// Ok nodes are skipped.
+    pub fn and_field(mut self, name: &str, node: ArenaNode<'bump>) -> Self {
+        if node.is_err() {
+            let name = self.arena.bump.alloc_str(name);
+            self.fields.push((name, node));
+        }
+        self
+    }
+
+    /// Adds errors of item `index`, like [ValidationNode::and_item] does.
+    /// Ok nodes are skipped.
+    pub fn and_item(mut self, index: usize, node: ArenaNode<'bump>) -> Self {
+        if node.is_err() {
+            self.items.push((ArenaItemKey::Index(index), node));
+        }
+        self
+    }
+
+    /// Adds errors of item identified by `key`, like
+    /// [ValidationNode::and_keyed_item] does. Ok nodes are skipped.
+    pub fn and_keyed_item(mut self, key: &str, node: ArenaNode<'bump>) -> Self {
+        if node.is_err() {
+            let key = self.arena.bump.alloc_str(key);
+            self.items.push((ArenaItemKey::Key(key), node));
+        }
+        self
+    }
+
+    /// Copies the tree out of the arena.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let bump = bumpalo::Bump::new();
+    /// let arena = Arena::new(&bump);
+    /// let errors = arena
+    ///     .ok()
+    ///     .and_field("b", arena.error(arena.with_code("abc")))
+    ///     .and_field("a", arena.ok().and_keyed_item("x", arena.error(arena.with_code("def"))));
+    ///
+    /// assert_eq!(
+    ///     ValidationNode::field("a", ValidationNode::keyed_item("x", ValidationNode::error(ValidationError::with_code("def"))))
+    ///         .and_field("b", ValidationNode::error(ValidationError::with_code("abc"))),
+    ///     errors.to_node(),
+    /// );
+    /// ```
+    pub fn to_node(&self) -> ValidationNode {
+        let node = ValidationNode::errors(self.errors.iter().map(ArenaError::to_error));
+        let node = self.fields.iter().fold(node, |node, (name, field)| {
+            node.and_field(name.to_string(), field.to_node())
+        });
+        self.items.iter().fold(node, |node, (key, item)| match key {
+            ArenaItemKey::Index(index) => node.and_item(*index, item.to_node()),
+            ArenaItemKey::Key(key) => node.and_keyed_item(key.to_string(), item.to_node()),
+        })
+    }
+}
+
+/// Displays the tree like [ValidationNode] does. Copies the tree out of the
+/// arena first.
+impl core::fmt::Display for ArenaNode<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.to_node(), f)
+    }
+}
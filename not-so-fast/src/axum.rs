@@ -0,0 +1,174 @@
+//! Axum extractors deserializing request data and validating it before it
+//! reaches the handler.
+//!
+//! Extractors reject requests whose data has errors of [Severity::Error],
+//! warnings and infos don't fail the request. The default rejection responds
+//! with a [ValidationResponse], `422 Unprocessable Entity` with errors in the
+//! format of [error_body_format]. To respond differently, match on
+//! [ValidationRejection] in a custom error type, e.g. with
+//! `axum_extra::extract::WithRejection`.
+//!
+//! [Severity::Error]: crate::Severity::Error
+//! [ValidationResponse]: crate::ValidationResponse
+//! [error_body_format]: crate::error_body_format
+
+use ::axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use ::axum::extract::{FromRequest, FromRequestParts, Path, Query, Request};
+use ::axum::http::request::Parts;
+use ::axum::http::{header, StatusCode};
+use ::axum::response::{IntoResponse, Response};
+use ::axum::Json;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error_body_format, IntoValidationResponse, TryFromUnvalidated, Validate, ValidationRejection,
+    ValidationResponse,
+};
+
+impl<R: IntoResponse> IntoResponse for ValidationRejection<R> {
+    /// Responds with the inner rejection, or with the [ValidationResponse] of
+    /// errors in the format of [error_body_format].
+    fn into_response(self) -> Response {
+        match self {
+            ValidationRejection::Extract(rejection) => rejection.into_response(),
+            ValidationRejection::Invalid(errors) => errors
+                .into_validation_response(error_body_format())
+                .into_response(),
+        }
+    }
+}
+
+impl IntoResponse for ValidationResponse {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::UNPROCESSABLE_ENTITY);
+        (
+            status,
+            [(header::CONTENT_TYPE, self.content_type)],
+            self.body,
+        )
+            .into_response()
+    }
+}
+
+/// Extracts and validates a JSON body, like [Json].
+/// ```
+/// # use not_so_fast::*;
+/// use axum::{routing::post, Router};
+///
+/// #[derive(serde::Deserialize, Validate)]
+/// struct CreateUser {
+///     #[validate(char_length(min = 1, max = 30))]
+///     name: String,
+/// }
+///
+/// async fn create_user(ValidatedJson(user): ValidatedJson<CreateUser>) -> String {
+///     user.name
+/// }
+///
+/// let app: Router = Router::new().route("/users", post(create_user));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> TryFromUnvalidated<T> for ValidatedJson<T> {
+    fn from_validated(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ValidationRejection<JsonRejection>;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(ValidationRejection::Extract)?;
+        Self::try_from_unvalidated(value).map_err(ValidationRejection::Invalid)
+    }
+}
+
+/// Extracts and validates a query string, like [Query].
+/// ```
+/// # use not_so_fast::*;
+/// use axum::{routing::get, Router};
+///
+/// #[derive(serde::Deserialize, Validate)]
+/// struct Pagination {
+///     #[validate(range(min = 1, max = 100))]
+///     per_page: u32,
+/// }
+///
+/// async fn list_users(ValidatedQuery(page): ValidatedQuery<Pagination>) -> String {
+///     page.per_page.to_string()
+/// }
+///
+/// let app: Router = Router::new().route("/users", get(list_users));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T> TryFromUnvalidated<T> for ValidatedQuery<T> {
+    fn from_validated(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ValidationRejection<QueryRejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(ValidationRejection::Extract)?;
+        Self::try_from_unvalidated(value).map_err(ValidationRejection::Invalid)
+    }
+}
+
+/// Extracts and validates path parameters, like [Path].
+/// ```
+/// # use not_so_fast::*;
+/// use axum::{routing::get, Router};
+///
+/// #[derive(serde::Deserialize, Validate)]
+/// struct UserPath {
+///     #[validate(char_length(max = 30))]
+///     name: String,
+/// }
+///
+/// async fn get_user(ValidatedPath(path): ValidatedPath<UserPath>) -> String {
+///     path.name
+/// }
+///
+/// let app: Router = Router::new().route("/users/{name}", get(get_user));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedPath<T>(pub T);
+
+impl<T> TryFromUnvalidated<T> for ValidatedPath<T> {
+    fn from_validated(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, S> FromRequestParts<S> for ValidatedPath<T>
+where
+    T: DeserializeOwned + Validate + Send,
+    S: Send + Sync,
+{
+    type Rejection = ValidationRejection<PathRejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(ValidationRejection::Extract)?;
+        Self::try_from_unvalidated(value).map_err(ValidationRejection::Invalid)
+    }
+}
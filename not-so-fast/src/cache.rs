@@ -0,0 +1,172 @@
+//! Memoization of validation results of values identified by user-supplied
+//! keys.
+
+use alloc::collections::BTreeMap;
+use core::any::TypeId;
+
+use crate::{Validate, ValidationNode};
+
+/// Key of a value, identifying its content among values of its type, e.g. a
+/// hash of the content or a version of an immutable document. Values with
+/// equal keys must have equal validation results. See
+/// [Validate::validate_cached](crate::Validate::validate_cached).
+pub trait ValidationKey {
+    fn validation_key(&self) -> u64;
+}
+
+/// Validation results of values, by type and [ValidationKey] of the values.
+/// Results are kept until the cache is cleared or dropped.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationCache {
+    nodes: BTreeMap<(TypeId, u64), ValidationNode>,
+}
+
+impl ValidationCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Tells if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// Validates `value`, reusing the result cached under the key of
+    /// `value`, or caching the result. Nested fields validated with
+    /// `nested(cached)` use the cache too, like in
+    /// [Validate::validate_cached](crate::Validate::validate_cached).
+    /// ```
+    /// # use not_so_fast::*;
+    /// #[derive(Validate)]
+    /// struct Snapshot {
+    ///     id: u64,
+    ///     #[validate(length(max = 2))]
+    ///     items: Vec<u32>,
+    /// }
+    ///
+    /// impl ValidationKey for Snapshot {
+    ///     fn validation_key(&self) -> u64 {
+    ///         self.id
+    ///     }
+    /// }
+    ///
+    /// let mut cache = ValidationCache::new();
+    /// let snapshot = Snapshot { id: 1, items: vec![1, 2, 3] };
+    /// assert!(cache.validate(&snapshot).is_err());
+    ///
+    /// // Values with equal keys are expected to have equal results.
+    /// let snapshot = Snapshot { id: 1, items: vec![] };
+    /// assert!(cache.validate(&snapshot).is_err());
+    /// cache.clear();
+    /// assert!(cache.validate(&snapshot).is_ok());
+    /// ```
+    pub fn validate<T>(&mut self, value: &T) -> ValidationNode
+    where
+        T: Validate + ValidationKey + ?Sized + 'static,
+    {
+        let key = cache_key(value);
+        if let Some(node) = self.nodes.get(&key) {
+            return node.clone();
+        }
+        let node = with_current_cache(self, || value.validate());
+        self.nodes.insert(key, node.clone());
+        node
+    }
+}
+
+fn cache_key<T: ValidationKey + ?Sized + 'static>(value: &T) -> (TypeId, u64) {
+    (TypeId::of::<T>(), value.validation_key())
+}
+
+/// Validates a nested value with `validate`, reusing the result cached under
+/// its key in the cache of the running
+/// [Validate::validate_cached](crate::Validate::validate_cached) call, if
+/// any. Used by the derive macro.
+#[doc(hidden)]
+pub fn validate_cached_nested<T: ValidationKey + ?Sized + 'static>(
+    value: &T,
+    validate: impl FnOnce() -> ValidationNode,
+) -> ValidationNode {
+    #[cfg(feature = "std")]
+    {
+        let key = cache_key(value);
+        let cached = CACHE.with(|cache| {
+            let cache = cache.borrow();
+            cache.as_ref().map(|cache| cache.nodes.get(&key).cloned())
+        });
+        match cached {
+            Some(Some(node)) => node,
+            Some(None) => {
+                let node = validate();
+                CACHE.with(|cache| {
+                    if let Some(cache) = cache.borrow_mut().as_mut() {
+                        cache.nodes.insert(key, node.clone());
+                    }
+                });
+                node
+            }
+            None => validate(),
+        }
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = value;
+        validate()
+    }
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static CACHE: core::cell::RefCell<Option<ValidationCache>> = const { core::cell::RefCell::new(None) };
+}
+
+/// Runs `f` with `cache` as the cache of the current thread, used by nested
+/// values. Without the `std` feature, just runs `f`.
+pub(crate) fn with_current_cache(
+    cache: &mut ValidationCache,
+    f: impl FnOnce() -> ValidationNode,
+) -> ValidationNode {
+    #[cfg(feature = "std")]
+    {
+        let previous = CACHE.with(|current| current.replace(Some(core::mem::take(cache))));
+        let _guard = CacheGuard {
+            cache,
+            previous: Some(previous),
+        };
+        f()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = cache;
+        f()
+    }
+}
+
+/// Moves the cache of the current thread back to the cache passed to
+/// [with_current_cache], restoring the previous one, also on panic.
+#[cfg(feature = "std")]
+struct CacheGuard<'a> {
+    cache: &'a mut ValidationCache,
+    previous: Option<Option<ValidationCache>>,
+}
+
+#[cfg(feature = "std")]
+impl Drop for CacheGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            let current = CACHE.with(|current| current.replace(previous));
+            *self.cache = current.unwrap_or_default();
+        }
+    }
+}
@@ -0,0 +1,477 @@
+//! Canonical, versioned encoding of [ValidationNode], for storing
+//! validation results, e.g. in audit logs, and reading them back later.
+
+use alloc::{borrow::Cow, collections::BTreeMap};
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, string::String, string::ToString, vec::Vec};
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sorted_map::SortedMap;
+use crate::{ItemKey, Message, ParamValue, Severity, ValidationError, ValidationNode};
+
+/// Version of the canonical encoding written by [NodeAsCanonical]. Encodings
+/// of other versions are rejected by [CanonicalNode].
+pub const CANONICAL_VERSION: u32 = 1;
+
+/// Wrapper serializing [ValidationNode] into the canonical encoding, which
+/// can be read back with [CanonicalNode]. Unlike other
+/// formats, the canonical encoding keeps everything needed to restore an
+/// equal node, and is stable: equal nodes are encoded the same way, and
+/// versions of the crate writing the same [CANONICAL_VERSION] write the same
+/// output. Works with text and binary serde formats, e.g. JSON or CBOR.
+///
+/// The encoding is an object with `version` and `node` members, in this
+/// order. Nodes list all their members in a fixed order: `errors`, `fields`
+/// sorted by name, `items` sorted by key (indexes before keys), `order` and
+/// `insertion_order`. Errors list `code`, `message`, `params` sorted by name,
+/// `severity` and `tags` sorted by name. Parameters are tagged with their
+/// type.
+///
+/// ```json
+/// {
+///     "version": 1,
+///     "node": {
+///         "errors": [],
+///         "fields": {
+///             "abc": {
+///                 "errors": [],
+///                 "fields": {},
+///                 "items": [
+///                     [
+///                         { "index": 4 },
+///                         {
+///                             "errors": [
+///                                 {
+///                                     "code": "length",
+///                                     "message": null,
+///                                     "params": { "max": { "u32": 20 } },
+///                                     "severity": "error",
+///                                     "tags": []
+///                                 }
+///                             ],
+///                             "fields": {},
+///                             "items": [],
+///                             "order": null,
+///                             "insertion_order": false
+///                         }
+///                     ]
+///                 ],
+///                 "order": null,
+///                 "insertion_order": false
+///             }
+///         },
+///         "items": [],
+///         "order": null,
+///         "insertion_order": false
+///     }
+/// }
+/// ```
+///
+/// Lazy messages are stored as rendered text, and error sources are not
+/// stored. Non-finite floats can't be represented in JSON. JSON parameters
+/// are stored as JSON text. UUIDs and decimals are stored as text, and read
+/// back as strings if the matching feature is disabled.
+///
+/// Example:
+///
+/// ```
+/// # use not_so_fast::*;
+/// let errors = ValidationNode::field(
+///     "abc",
+///     ValidationNode::item(4, ValidationNode::error(ValidationError::with_code("length").and_param("max", 20_u32))),
+/// );
+///
+/// let json = serde_json::to_string(&errors.as_canonical()).unwrap();
+/// assert!(json.starts_with(r#"{"version":1,"node":{"errors":[],"fields":{"abc":"#));
+/// let restored: CanonicalNode = serde_json::from_str(&json).unwrap();
+/// assert_eq!(errors, restored.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NodeAsCanonical<'a>(pub &'a ValidationNode);
+
+impl Serialize for NodeAsCanonical<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut envelope = serializer.serialize_struct("Canonical", 2)?;
+        envelope.serialize_field("version", &CANONICAL_VERSION)?;
+        envelope.serialize_field("node", &EncodedNode::from(self.0))?;
+        envelope.end()
+    }
+}
+
+impl ValidationNode {
+    /// Returns a wrapper serializing the node into the canonical encoding.
+    /// See [NodeAsCanonical] for details.
+    pub fn as_canonical(&self) -> NodeAsCanonical<'_> {
+        NodeAsCanonical(self)
+    }
+
+    /// Encodes the node into canonical JSON. See [NodeAsCanonical] for
+    /// details.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("conflict").and_tag("audit"));
+    /// let json = errors.to_canonical_json();
+    /// assert_eq!(errors, ValidationNode::from_canonical_json(&json).unwrap());
+    /// ```
+    pub fn to_canonical_json(&self) -> String {
+        serde_json::to_string(&self.as_canonical())
+            .expect("canonical encoding is serializable to JSON")
+    }
+
+    /// Decodes a node from canonical JSON written by
+    /// [ValidationNode::to_canonical_json].
+    pub fn from_canonical_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str::<CanonicalNode>(json).map(|node| node.0)
+    }
+}
+
+/// Node deserialized from the canonical encoding written by
+/// [NodeAsCanonical]. Deserialization fails if the encoding has a version
+/// other than [CANONICAL_VERSION].
+/// ```
+/// # use not_so_fast::*;
+/// let errors = ValidationNode::field("abc", ValidationNode::error(ValidationError::with_code("length")));
+///
+/// let json = serde_json::to_string(&errors.as_canonical()).unwrap();
+/// let restored: CanonicalNode = serde_json::from_str(&json).unwrap();
+/// assert_eq!(errors, restored.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanonicalNode(pub ValidationNode);
+
+impl Serialize for CanonicalNode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        NodeAsCanonical(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CanonicalNode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_struct("Canonical", &["version", "node"], EnvelopeVisitor)
+            .map(CanonicalNode)
+    }
+}
+
+/// Reads the version before the node, so that nodes of unknown versions
+/// are rejected before being parsed.
+struct EnvelopeVisitor;
+
+impl EnvelopeVisitor {
+    fn check_version<E: serde::de::Error>(version: u32) -> Result<(), E> {
+        if version == CANONICAL_VERSION {
+            Ok(())
+        } else {
+            Err(E::custom(format_args!(
+                "unsupported canonical encoding version {version}, expected {CANONICAL_VERSION}"
+            )))
+        }
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for EnvelopeVisitor {
+    type Value = ValidationNode;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("canonical validation node")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        let version = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        Self::check_version(version)?;
+        let node = seq
+            .next_element::<EncodedNode>()?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        Ok(node.into())
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        let mut version = None;
+        let mut node = None;
+        while let Some(key) = map.next_key::<Cow<'de, str>>()? {
+            match key.as_ref() {
+                "version" => {
+                    let value = map.next_value::<u32>()?;
+                    Self::check_version(value)?;
+                    version = Some(value);
+                }
+                "node" if version.is_none() => return Err(A::Error::missing_field("version")),
+                "node" => node = Some(map.next_value::<EncodedNode>()?),
+                other => return Err(A::Error::unknown_field(other, &["version", "node"])),
+            }
+        }
+        version.ok_or_else(|| A::Error::missing_field("version"))?;
+        Ok(node.ok_or_else(|| A::Error::missing_field("node"))?.into())
+    }
+}
+
+// Types below define the encoding of version 1. Changing them, other than
+// appending variants to enums, requires a new version.
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EncodedNode {
+    errors: Vec<EncodedError>,
+    fields: BTreeMap<String, EncodedNode>,
+    items: Vec<(EncodedItemKey, EncodedNode)>,
+    order: Option<u32>,
+    insertion_order: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EncodedItemKey {
+    Index(u64),
+    Key(String),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EncodedError {
+    code: String,
+    message: Option<String>,
+    params: BTreeMap<String, EncodedParam>,
+    severity: EncodedSeverity,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EncodedSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EncodedParam {
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(u64),
+    F32(f32),
+    F64(f64),
+    Char(char),
+    String(String),
+    Raw(String),
+    Bytes(Vec<u8>),
+    List(Vec<EncodedParam>),
+    Map(BTreeMap<String, EncodedParam>),
+    Duration { secs: u64, nanos: u32 },
+    Json(String),
+    Uuid(String),
+    Decimal(String),
+    BigDecimal(String),
+}
+
+impl From<&ValidationNode> for EncodedNode {
+    fn from(node: &ValidationNode) -> Self {
+        Self {
+            errors: node.errors.iter().map(EncodedError::from).collect(),
+            fields: node
+                .fields
+                .iter()
+                .map(|(name, field)| (name.as_ref().to_owned(), field.into()))
+                .collect(),
+            items: node
+                .items
+                .iter()
+                .map(|(key, item)| {
+                    let key = match key {
+                        ItemKey::Index(index) => EncodedItemKey::Index(*index as u64),
+                        ItemKey::Key(key) => EncodedItemKey::Key(key.as_ref().to_owned()),
+                    };
+                    (key, item.into())
+                })
+                .collect(),
+            order: node.order,
+            insertion_order: node.insertion_order,
+        }
+    }
+}
+
+impl From<EncodedNode> for ValidationNode {
+    fn from(node: EncodedNode) -> Self {
+        let mut fields = SortedMap::new();
+        for (name, field) in node.fields {
+            fields.insert(Cow::Owned(name), field.into());
+        }
+        let mut items = SortedMap::new();
+        for (key, item) in node.items {
+            let key = match key {
+                EncodedItemKey::Index(index) => ItemKey::Index(index as usize),
+                EncodedItemKey::Key(key) => ItemKey::Key(Cow::Owned(key)),
+            };
+            items.insert(key, item.into());
+        }
+        Self {
+            errors: node.errors.into_iter().map(Into::into).collect(),
+            fields,
+            items,
+            order: node.order,
+            insertion_order: node.insertion_order,
+        }
+    }
+}
+
+impl From<&ValidationError> for EncodedError {
+    fn from(error: &ValidationError) -> Self {
+        Self {
+            code: error.code.as_ref().to_owned(),
+            message: error.message().map(ToOwned::to_owned),
+            params: error
+                .param_map()
+                .iter()
+                .map(|(name, value)| (name.as_ref().to_owned(), value.into()))
+                .collect(),
+            severity: match error.severity() {
+                Severity::Info => EncodedSeverity::Info,
+                Severity::Warning => EncodedSeverity::Warning,
+                Severity::Error => EncodedSeverity::Error,
+            },
+            tags: error.tags().map(ToOwned::to_owned).collect(),
+        }
+    }
+}
+
+impl From<EncodedError> for ValidationError {
+    fn from(error: EncodedError) -> Self {
+        Self::from_parts(
+            Cow::Owned(error.code),
+            error
+                .message
+                .map(|message| Message::Text(Cow::Owned(message))),
+            error
+                .params
+                .into_iter()
+                .map(|(name, value)| (Cow::Owned(name), value.into()))
+                .collect(),
+            match error.severity {
+                EncodedSeverity::Info => Severity::Info,
+                EncodedSeverity::Warning => Severity::Warning,
+                EncodedSeverity::Error => Severity::Error,
+            },
+            error.tags.into_iter().map(Cow::Owned).collect(),
+        )
+    }
+}
+
+impl From<&ParamValue> for EncodedParam {
+    fn from(value: &ParamValue) -> Self {
+        match value {
+            ParamValue::Bool(value) => Self::Bool(*value),
+            ParamValue::I8(value) => Self::I8(*value),
+            ParamValue::I16(value) => Self::I16(*value),
+            ParamValue::I32(value) => Self::I32(*value),
+            ParamValue::I64(value) => Self::I64(*value),
+            ParamValue::I128(value) => Self::I128(*value),
+            ParamValue::U8(value) => Self::U8(*value),
+            ParamValue::U16(value) => Self::U16(*value),
+            ParamValue::U32(value) => Self::U32(*value),
+            ParamValue::U64(value) => Self::U64(*value),
+            ParamValue::U128(value) => Self::U128(*value),
+            ParamValue::Usize(value) => Self::Usize(*value as u64),
+            ParamValue::F32(value) => Self::F32(*value),
+            ParamValue::F64(value) => Self::F64(*value),
+            ParamValue::Char(value) => Self::Char(*value),
+            ParamValue::String(value) => Self::String(value.as_ref().to_owned()),
+            ParamValue::Raw(value) => Self::Raw(value.as_ref().to_owned()),
+            ParamValue::Bytes(value) => Self::Bytes(value.as_ref().to_owned()),
+            ParamValue::List(values) => Self::List(values.iter().map(Into::into).collect()),
+            ParamValue::Map(entries) => Self::Map(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.as_ref().to_owned(), value.into()))
+                    .collect(),
+            ),
+            ParamValue::Duration(value) => Self::Duration {
+                secs: value.as_secs(),
+                nanos: value.subsec_nanos(),
+            },
+            ParamValue::Json(value) => Self::Json(value.to_string()),
+            #[cfg(feature = "uuid")]
+            ParamValue::Uuid(value) => Self::Uuid(value.to_string()),
+            #[cfg(feature = "rust_decimal")]
+            ParamValue::Decimal(value) => Self::Decimal(value.to_string()),
+            #[cfg(feature = "bigdecimal")]
+            ParamValue::BigDecimal(value) => Self::BigDecimal(value.to_string()),
+        }
+    }
+}
+
+impl From<EncodedParam> for ParamValue {
+    fn from(value: EncodedParam) -> Self {
+        match value {
+            EncodedParam::Bool(value) => Self::Bool(value),
+            EncodedParam::I8(value) => Self::I8(value),
+            EncodedParam::I16(value) => Self::I16(value),
+            EncodedParam::I32(value) => Self::I32(value),
+            EncodedParam::I64(value) => Self::I64(value),
+            EncodedParam::I128(value) => Self::I128(value),
+            EncodedParam::U8(value) => Self::U8(value),
+            EncodedParam::U16(value) => Self::U16(value),
+            EncodedParam::U32(value) => Self::U32(value),
+            EncodedParam::U64(value) => Self::U64(value),
+            EncodedParam::U128(value) => Self::U128(value),
+            EncodedParam::Usize(value) => Self::Usize(value as usize),
+            EncodedParam::F32(value) => Self::F32(value),
+            EncodedParam::F64(value) => Self::F64(value),
+            EncodedParam::Char(value) => Self::Char(value),
+            EncodedParam::String(value) => Self::String(Cow::Owned(value)),
+            EncodedParam::Raw(value) => Self::Raw(Cow::Owned(value)),
+            EncodedParam::Bytes(value) => Self::Bytes(Cow::Owned(value)),
+            EncodedParam::List(values) => Self::List(values.into_iter().map(Into::into).collect()),
+            EncodedParam::Map(entries) => Self::Map(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (Cow::Owned(key), value.into()))
+                    .collect(),
+            ),
+            EncodedParam::Duration { secs, nanos } => Self::Duration(Duration::new(secs, nanos)),
+            EncodedParam::Json(value) => match serde_json::from_str(&value) {
+                Ok(value) => Self::Json(value),
+                Err(_) => Self::String(Cow::Owned(value)),
+            },
+            EncodedParam::Uuid(value) => {
+                #[cfg(feature = "uuid")]
+                if let Ok(uuid) = value.parse() {
+                    return Self::Uuid(uuid);
+                }
+                Self::String(Cow::Owned(value))
+            }
+            EncodedParam::Decimal(value) => {
+                #[cfg(feature = "rust_decimal")]
+                if let Ok(decimal) = value.parse() {
+                    return Self::Decimal(decimal);
+                }
+                Self::String(Cow::Owned(value))
+            }
+            EncodedParam::BigDecimal(value) => {
+                #[cfg(feature = "bigdecimal")]
+                if let Ok(decimal) = value.parse() {
+                    return Self::BigDecimal(decimal);
+                }
+                Self::String(Cow::Owned(value))
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Validation of command-line arguments parsed with `clap`.
+
+use std::ffi::OsString;
+
+use ::clap::error::{ContextKind, ContextValue, ErrorKind};
+use ::clap::{ArgMatches, Command, Parser};
+
+use crate::{PathSegment, Severity, Validate, ValidationNode};
+
+/// Extension of [clap::Parser](::clap::Parser) validating parsed arguments.
+/// Errors of [Severity::Error] are reported as `clap` errors of kind
+/// `ValueValidation`, attributed to the arguments of the invalid fields.
+/// ```
+/// # use not_so_fast::*;
+/// use clap::Parser;
+///
+/// #[derive(Parser, Validate)]
+/// struct Args {
+///     #[arg(long)]
+///     #[validate(char_length(max = 5))]
+///     name: String,
+/// }
+///
+/// let error = Args::try_parse_validated_from(["greet", "--name", "Thomas"]).err().unwrap();
+/// assert!(error.to_string().starts_with(
+///     "error: invalid value 'Thomas' for '--name <NAME>': char_length: Invalid character length: max=5, value=6"
+/// ));
+/// ```
+pub trait ParseValidated: Parser + Validate {
+    /// Parses and validates arguments of the process, exiting on errors like
+    /// [Parser::parse] does.
+    fn parse_validated() -> Self {
+        Self::try_parse_validated().unwrap_or_else(|error| error.exit())
+    }
+
+    /// Parses and validates arguments of the process.
+    fn try_parse_validated() -> Result<Self, ::clap::Error> {
+        Self::try_parse_validated_from(std::env::args_os())
+    }
+
+    /// Parses and validates `args`, the first of which is the program name.
+    /// Returns the first error of the arguments.
+    /// ```
+    /// # use not_so_fast::*;
+    /// use clap::Parser;
+    ///
+    /// #[derive(Parser, Validate)]
+    /// struct Args {
+    ///     #[validate(range(min = 1))]
+    ///     count: u32,
+    /// }
+    ///
+    /// assert_eq!(2, Args::try_parse_validated_from(["repeat", "2"]).unwrap().count);
+    /// assert!(Args::try_parse_validated_from(["repeat", "0"]).is_err());
+    /// ```
+    fn try_parse_validated_from<I, T>(args: I) -> Result<Self, ::clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut command = Self::command();
+        let matches = command.try_get_matches_from_mut(args)?;
+        let value = Self::from_arg_matches(&matches).map_err(|error| error.format(&mut command))?;
+        match clap_errors(&mut command, &matches, &value.validate())
+            .into_iter()
+            .next()
+        {
+            Some(error) => Err(error),
+            None => Ok(value),
+        }
+    }
+}
+
+impl<T: Parser + Validate> ParseValidated for T {}
+
+/// Converts errors of [Severity::Error] into `clap` errors of kind
+/// `ValueValidation`. An error is attributed to the argument whose id equals a
+/// field name on the error's path, which holds for arguments derived with
+/// `clap` and fields of flattened structs. Index following the field selects
+/// a value of a multi-value argument. Errors of attributed arguments carry
+/// `InvalidArg` and `InvalidValue` contexts.
+/// ```
+/// # use not_so_fast::*;
+/// use clap::{Arg, ArgAction, Command};
+///
+/// let mut command = Command::new("tag").arg(Arg::new("tags").action(ArgAction::Append));
+/// let matches = command.clone().get_matches_from(["tag", "new", "important"]);
+/// let errors = ValidationNode::field(
+///     "tags",
+///     ValidationNode::item(1, ValidationNode::error(ValidationError::with_code("too_long"))),
+/// );
+///
+/// let errors = clap_errors(&mut command, &matches, &errors);
+/// assert!(errors[0].to_string().starts_with("error: invalid value 'important' for '[tags]...': too_long"));
+/// ```
+pub fn clap_errors(
+    command: &mut Command,
+    matches: &ArgMatches,
+    errors: &ValidationNode,
+) -> Vec<::clap::Error> {
+    // Rendering of arguments needs value names set by building.
+    command.build();
+    errors
+        .iter()
+        .filter(|(_, error)| error.severity() == Severity::Error)
+        .map(|(path, error)| {
+            let mut segments = path.segments().iter();
+            let arg = segments.by_ref().find_map(|segment| match segment {
+                PathSegment::Field(name) => command
+                    .get_arguments()
+                    .find(|arg| arg.get_id() == name.as_ref())
+                    .map(|arg| (arg.get_id().clone(), arg.to_string())),
+                _ => None,
+            });
+            let Some((id, arg)) = arg else {
+                if path.segments().is_empty() {
+                    return command.error(ErrorKind::ValueValidation, error);
+                }
+                return command.error(ErrorKind::ValueValidation, format!("{path}: {error}"));
+            };
+            let index = match segments.next() {
+                Some(PathSegment::Index(index)) => Some(*index),
+                _ => None,
+            };
+            let values: Vec<_> = matches
+                .try_get_raw(id.as_str())
+                .ok()
+                .flatten()
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .filter(|(i, _)| index.is_none_or(|index| index == *i))
+                .map(|(_, value)| value.to_string_lossy())
+                .collect();
+            if values.is_empty() {
+                return command.error(
+                    ErrorKind::ValueValidation,
+                    format!("invalid '{arg}': {error}"),
+                );
+            }
+            let value = values.join(" ");
+            let mut clap_error = command.error(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{value}' for '{arg}': {error}"),
+            );
+            clap_error.insert(ContextKind::InvalidArg, ContextValue::String(arg));
+            clap_error.insert(ContextKind::InvalidValue, ContextValue::String(value));
+            clap_error
+        })
+        .collect()
+}
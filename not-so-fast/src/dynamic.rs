@@ -0,0 +1,447 @@
+//! Validation rules loaded at runtime, e.g. from JSON or YAML documents, so
+//! limits can change without recompiling.
+//!
+//! A document lists rules of fields of the validated object:
+//! ```json
+//! {
+//!     "fields": {
+//!         "name": ["required", { "char_length": { "min": 1, "max": 30 } }],
+//!         "age": [{ "type": "integer" }, { "range": { "min": 18, "max": 100 } }],
+//!         "tags": [{ "length": { "max": 5 } }, { "items": [{ "char_length": { "max": 10 } }] }]
+//!     }
+//! }
+//! ```
+//! Rules report the same codes, messages and params as the validators of the
+//! derive macro. Rules not applicable to the type of the value, like `range`
+//! of a string, are skipped; use `type` to require a type. Missing and null
+//! fields are only checked by `required`. `custom` rules call validators of
+//! [serde_json::Value] registered in a [ValidatorRegistry].
+//!
+//! Types deriving `Validate` with the `dynamic` flag implement
+//! [ValidateValue], applying their validators to untyped values as rules.
+
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+use crate::{codes, ValidationError, ValidationNode, ValidatorRegistry};
+
+/// Validator of [serde_json::Value] inputs built from a rule document, see
+/// [module documentation](self).
+/// ```
+/// # use not_so_fast::*;
+/// use not_so_fast::dynamic::DynValidator;
+/// use serde_json::json;
+///
+/// let validator = DynValidator::from_json(r#"{ "fields": { "age": [{ "range": { "max": 100 } }] } }"#).unwrap();
+///
+/// assert!(validator.validate(&json!({ "age": 30 })).is_ok());
+/// assert_eq!(
+///     ".age: range: Number not in range: max=100, value=120",
+///     validator.validate(&json!({ "age": 120 })).to_string(),
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DynValidator {
+    /// Rules of the validated value itself.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<Rule>,
+    /// Rules of fields of the validated object.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, Vec<Rule>>,
+}
+
+/// Rule of a value. Serialized like in rule documents, e.g. `"required"` or
+/// `{ "range": { "max": 100 } }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    /// The value must be present and not null. Reports [codes::REQUIRED].
+    Required,
+    /// The value must be of the JSON type. Reports [codes::TYPE].
+    Type(JsonType),
+    /// Number must be in range. Reports [codes::RANGE].
+    Range {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<Number>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<Number>,
+    },
+    /// Byte length of a string, or the number of items of an array or fields
+    /// of an object, must be in bounds. Reports [codes::LENGTH].
+    Length(Bounds),
+    /// Number of characters of a string must be in bounds. Reports
+    /// [codes::CHAR_LENGTH].
+    CharLength(Bounds),
+    /// The value must be equal to one of the values. Reports
+    /// [codes::ONE_OF].
+    OneOf(Vec<Value>),
+    /// Rules of items of an array.
+    Items(Vec<Rule>),
+    /// Rules of fields of an object.
+    Fields(BTreeMap<String, Vec<Rule>>),
+    /// Rules of values of all fields of an object, like a map.
+    Values(Vec<Rule>),
+    /// Validator of [serde_json::Value] registered under the name, e.g.
+    /// `{ "custom": "no_profanity" }`.
+    Custom(String),
+}
+
+/// Bounds of `length` and `char_length` rules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bounds {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equal: Option<usize>,
+}
+
+/// JSON type of `type` rule. `integer` matches numbers without fractional
+/// part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonType {
+    Null,
+    Bool,
+    Number,
+    Integer,
+    String,
+    Array,
+    Object,
+}
+
+impl DynValidator {
+    /// Creates a validator of values with the rules of `T`. Can be stored to
+    /// validate many values without collecting the rules again.
+    pub fn of<T: ValidateValue + ?Sized>() -> Self {
+        Self {
+            rules: T::value_rules(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Parses a JSON rule document. Documents in other formats, like YAML,
+    /// can be deserialized with their `serde` libraries.
+    pub fn from_json(document: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(document)
+    }
+
+    /// Validates `value` with the rules of the document. `custom` rules use
+    /// the global registry of
+    /// [set_validator_registry](crate::set_validator_registry), or report
+    /// unknown validators without `std`.
+    pub fn validate(&self, value: &Value) -> ValidationNode {
+        #[cfg(feature = "std")]
+        let registry = crate::validator_registry();
+        #[cfg(not(feature = "std"))]
+        let registry = &ValidatorRegistry::new();
+        self.validate_with(value, registry)
+    }
+
+    /// Validates `value` with the rules of the document, calling validators
+    /// of `custom` rules from `registry`.
+    /// ```
+    /// # use not_so_fast::*;
+    /// use not_so_fast::dynamic::DynValidator;
+    /// use serde_json::{json, Value};
+    ///
+    /// let mut registry = ValidatorRegistry::new();
+    /// registry.register("no_profanity", |value: &Value| {
+    ///     ValidationNode::error_if(value.as_str().is_some_and(|s| s.contains("heck")), || {
+    ///         ValidationError::with_code("profanity")
+    ///     })
+    /// });
+    /// let validator = DynValidator::from_json(r#"{ "fields": { "bio": [{ "custom": "no_profanity" }] } }"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     ".bio: profanity",
+    ///     validator.validate_with(&json!({ "bio": "oh heck" }), &registry).to_string(),
+    /// );
+    /// ```
+    pub fn validate_with(&self, value: &Value, registry: &ValidatorRegistry) -> ValidationNode {
+        validate_rules(value, &self.rules, registry).merge(validate_fields(
+            value,
+            &self.fields,
+            registry,
+        ))
+    }
+}
+
+/// Types whose validators can be applied to untyped [serde_json::Value]
+/// inputs, e.g. by gateways forwarding values they don't deserialize.
+/// Implemented by the derive macro for types with the `dynamic` flag.
+/// ```
+/// # use not_so_fast::*;
+/// use serde_json::json;
+///
+/// #[derive(Validate)]
+/// #[validate(dynamic)]
+/// struct Comment {
+///     #[validate(char_length(max = 10))]
+///     text: String,
+///     #[validate(length(max = 2), items(range(max = 5)))]
+///     ratings: Vec<u8>,
+/// }
+///
+/// let value = json!({ "text": "Nice!", "ratings": [4, 6], "forwarded": true });
+/// assert_eq!(
+///     ".ratings[1]: range: Number not in range: max=5, value=6",
+///     Comment::validate_value(&value).to_string(),
+/// );
+/// ```
+pub trait ValidateValue {
+    /// Rules of values of the type.
+    fn value_rules() -> Vec<Rule>;
+
+    /// Validates `value` with the rules of the type. Fields missing from
+    /// `value` are not validated.
+    fn validate_value(value: &Value) -> ValidationNode {
+        DynValidator::of::<Self>().validate(value)
+    }
+}
+
+/// Types of collections validated by `some`, `items` and `fields`.
+#[doc(hidden)]
+pub trait ValueElement {
+    type Element: ?Sized;
+}
+
+impl<T> ValueElement for Option<T> {
+    type Element = T;
+}
+
+impl<T> ValueElement for [T] {
+    type Element = T;
+}
+
+impl<T, const N: usize> ValueElement for [T; N] {
+    type Element = T;
+}
+
+impl<T> ValueElement for Vec<T> {
+    type Element = T;
+}
+
+impl<T> ValueElement for VecDeque<T> {
+    type Element = T;
+}
+
+impl<T> ValueElement for BTreeSet<T> {
+    type Element = T;
+}
+
+impl<K, V> ValueElement for BTreeMap<K, V> {
+    type Element = V;
+}
+
+#[cfg(feature = "std")]
+impl<T, S> ValueElement for std::collections::HashSet<T, S> {
+    type Element = T;
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> ValueElement for std::collections::HashMap<K, V, S> {
+    type Element = V;
+}
+
+/// Concatenates rules of validators.
+#[doc(hidden)]
+pub fn dyn_rules<const N: usize>(rules: [Vec<Rule>; N]) -> Vec<Rule> {
+    rules.into_iter().flatten().collect()
+}
+
+/// Returns `fields` rule of rules of fields.
+#[doc(hidden)]
+pub fn dyn_fields<const N: usize>(fields: [(&str, Vec<Rule>); N]) -> Vec<Rule> {
+    let fields = fields
+        .into_iter()
+        .map(|(name, rules)| (name.to_string(), rules))
+        .collect();
+    Vec::from([Rule::Fields(fields)])
+}
+
+/// Returns bound of `range` rule, if it serializes to a number.
+#[doc(hidden)]
+pub fn dyn_number(bound: &impl Serialize) -> Option<Number> {
+    match serde_json::to_value(bound) {
+        Ok(Value::Number(number)) => Some(number),
+        _ => None,
+    }
+}
+
+impl JsonType {
+    fn as_str(self) -> &'static str {
+        match self {
+            JsonType::Null => "null",
+            JsonType::Bool => "bool",
+            JsonType::Number => "number",
+            JsonType::Integer => "integer",
+            JsonType::String => "string",
+            JsonType::Array => "array",
+            JsonType::Object => "object",
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            JsonType::Null => value.is_null(),
+            JsonType::Bool => value.is_boolean(),
+            JsonType::Number => value.is_number(),
+            JsonType::Integer => match value {
+                Value::Number(number) => {
+                    number.is_i64()
+                        || number.is_u64()
+                        || number.as_f64().is_some_and(|n| n % 1.0 == 0.0)
+                }
+                _ => false,
+            },
+            JsonType::String => value.is_string(),
+            JsonType::Array => value.is_array(),
+            JsonType::Object => value.is_object(),
+        }
+    }
+}
+
+fn validate_rules(value: &Value, rules: &[Rule], registry: &ValidatorRegistry) -> ValidationNode {
+    ValidationNode::all(
+        rules
+            .iter()
+            .map(|rule| validate_rule(value, rule, registry)),
+    )
+}
+
+fn validate_fields(
+    value: &Value,
+    fields: &BTreeMap<String, Vec<Rule>>,
+    registry: &ValidatorRegistry,
+) -> ValidationNode {
+    let Value::Object(object) = value else {
+        return ValidationNode::ok();
+    };
+    fields
+        .iter()
+        .fold(ValidationNode::ok(), |node, (name, rules)| {
+            let field_node = match object.get(name) {
+                Some(value) if !value.is_null() => validate_rules(value, rules, registry),
+                _ if rules.contains(&Rule::Required) => ValidationNode::error(required_error()),
+                _ => ValidationNode::ok(),
+            };
+            node.and_field(name.clone(), field_node)
+        })
+}
+
+fn validate_rule(value: &Value, rule: &Rule, registry: &ValidatorRegistry) -> ValidationNode {
+    match rule {
+        Rule::Required => ValidationNode::error_if(value.is_null(), required_error),
+        Rule::Type(type_) => ValidationNode::error_if(!type_.matches(value), || {
+            ValidationError::with_code(codes::TYPE)
+                .and_message("Invalid type")
+                .and_param("expected", type_.as_str())
+        }),
+        Rule::Range { min, max } => {
+            let Some(number) = value.as_f64() else {
+                return ValidationNode::ok();
+            };
+            let below = min
+                .as_ref()
+                .and_then(Number::as_f64)
+                .is_some_and(|min| number < min);
+            let above = max
+                .as_ref()
+                .and_then(Number::as_f64)
+                .is_some_and(|max| number > max);
+            ValidationNode::error_if(below || above, || {
+                let mut error = ValidationError::with_code(codes::RANGE)
+                    .and_message("Number not in range")
+                    .and_param("value", value.clone());
+                if let Some(min) = min {
+                    error = error.and_param("min", Value::Number(min.clone()));
+                }
+                if let Some(max) = max {
+                    error = error.and_param("max", Value::Number(max.clone()));
+                }
+                error
+            })
+        }
+        Rule::Length(bounds) => {
+            let length = match value {
+                Value::String(string) => string.len(),
+                Value::Array(array) => array.len(),
+                Value::Object(object) => object.len(),
+                _ => return ValidationNode::ok(),
+            };
+            validate_bounds(length, bounds, codes::LENGTH, "Invalid length")
+        }
+        Rule::CharLength(bounds) => {
+            let Value::String(string) = value else {
+                return ValidationNode::ok();
+            };
+            validate_bounds(
+                string.chars().count(),
+                bounds,
+                codes::CHAR_LENGTH,
+                "Invalid character length",
+            )
+        }
+        Rule::OneOf(allowed) => ValidationNode::error_if(!allowed.contains(value), || {
+            ValidationError::with_code(codes::ONE_OF)
+                .and_message("Value not allowed")
+                .and_param("value", value.clone())
+                .and_param("allowed", Value::Array(allowed.clone()))
+        }),
+        Rule::Items(rules) => match value {
+            Value::Array(array) => ValidationNode::items(array.iter(), |_, item| {
+                validate_rules(item, rules, registry)
+            }),
+            _ => ValidationNode::ok(),
+        },
+        Rule::Fields(fields) => validate_fields(value, fields, registry),
+        Rule::Values(rules) => match value {
+            Value::Object(object) => ValidationNode::fields(object.iter(), |_, value| {
+                validate_rules(value, rules, registry)
+            }),
+            _ => ValidationNode::ok(),
+        },
+        Rule::Custom(name) => registry.validate(name, value),
+    }
+}
+
+fn validate_bounds(
+    length: usize,
+    bounds: &Bounds,
+    code: &'static str,
+    message: &'static str,
+) -> ValidationNode {
+    let invalid = bounds.min.is_some_and(|min| length < min)
+        || bounds.max.is_some_and(|max| length > max)
+        || bounds.equal.is_some_and(|equal| length != equal);
+    ValidationNode::error_if(invalid, || {
+        let mut error = ValidationError::with_code(code)
+            .and_message(message)
+            .and_param("value", length);
+        for (name, bound) in [
+            ("min", bounds.min),
+            ("max", bounds.max),
+            ("equal", bounds.equal),
+        ] {
+            if let Some(bound) = bound {
+                error = error.and_param(name, bound);
+            }
+        }
+        error
+    })
+}
+
+fn required_error() -> ValidationError {
+    ValidationError::with_code(codes::REQUIRED).and_message("Value is required")
+}
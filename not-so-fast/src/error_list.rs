@@ -0,0 +1,152 @@
+//! List of errors of a node, storing a single error inline. Most nodes have
+//! no errors or one error, which then take no allocation of their own.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use crate::ValidationError;
+
+/// List of errors, supporting the subset of `Vec` API used by
+/// [ValidationNode](crate::ValidationNode). Derefs to a slice.
+#[derive(Clone, Default)]
+pub(crate) struct ErrorList(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    One(ValidationError),
+    Many(Vec<ValidationError>),
+}
+
+impl Default for Repr {
+    fn default() -> Self {
+        Repr::Many(Vec::new())
+    }
+}
+
+impl ErrorList {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn one(error: ValidationError) -> Self {
+        Self(Repr::One(error))
+    }
+
+    pub(crate) fn push(&mut self, error: ValidationError) {
+        match &mut self.0 {
+            Repr::Many(errors) if !errors.is_empty() => errors.push(error),
+            Repr::Many(_) => self.0 = Repr::One(error),
+            Repr::One(_) => {
+                let Repr::One(first) = core::mem::take(&mut self.0) else {
+                    unreachable!();
+                };
+                self.0 = Repr::Many(vec![first, error]);
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, index: usize) -> ValidationError {
+        match &mut self.0 {
+            Repr::Many(errors) => errors.remove(index),
+            Repr::One(_) => {
+                assert_eq!(0, index, "index out of bounds");
+                let Repr::One(error) = core::mem::take(&mut self.0) else {
+                    unreachable!();
+                };
+                error
+            }
+        }
+    }
+
+    pub(crate) fn retain(&mut self, mut f: impl FnMut(&ValidationError) -> bool) {
+        match &mut self.0 {
+            Repr::Many(errors) => errors.retain(f),
+            Repr::One(error) => {
+                if !f(error) {
+                    self.0 = Repr::default();
+                }
+            }
+        }
+    }
+
+    pub(crate) fn truncate(&mut self, len: usize) {
+        match &mut self.0 {
+            Repr::Many(errors) => errors.truncate(len),
+            Repr::One(_) if len == 0 => self.0 = Repr::default(),
+            Repr::One(_) => {}
+        }
+    }
+}
+
+impl Deref for ErrorList {
+    type Target = [ValidationError];
+
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            Repr::One(error) => core::slice::from_ref(error),
+            Repr::Many(errors) => errors,
+        }
+    }
+}
+
+impl DerefMut for ErrorList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.0 {
+            Repr::One(error) => core::slice::from_mut(error),
+            Repr::Many(errors) => errors,
+        }
+    }
+}
+
+impl PartialEq for ErrorList {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl core::fmt::Debug for ErrorList {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Extend<ValidationError> for ErrorList {
+    fn extend<T: IntoIterator<Item = ValidationError>>(&mut self, iter: T) {
+        for error in iter {
+            self.push(error);
+        }
+    }
+}
+
+impl FromIterator<ValidationError> for ErrorList {
+    fn from_iter<T: IntoIterator<Item = ValidationError>>(iter: T) -> Self {
+        let mut errors = Self::new();
+        errors.extend(iter);
+        errors
+    }
+}
+
+impl IntoIterator for ErrorList {
+    type Item = ValidationError;
+    type IntoIter = core::iter::Chain<
+        core::option::IntoIter<ValidationError>,
+        alloc::vec::IntoIter<ValidationError>,
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self.0 {
+            Repr::One(error) => Some(error).into_iter().chain(Vec::new()),
+            Repr::Many(errors) => None.into_iter().chain(errors),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ErrorList {
+    type Item = &'a ValidationError;
+    type IntoIter = core::slice::Iter<'a, ValidationError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,193 @@
+//! Validation of configuration extracted with `figment`.
+
+use std::fmt::{Display, Formatter};
+
+use ::figment::value::{Dict, Value};
+use ::figment::{Figment, Metadata};
+use serde::de::DeserializeOwned;
+
+use crate::{Path, PathSegment, Severity, Validate, ValidationError, ValidationNode};
+
+/// Extension of [Figment](::figment::Figment) validating extracted
+/// configuration.
+/// ```
+/// # use not_so_fast::*;
+/// use figment::{providers::Serialized, Figment};
+///
+/// #[derive(serde::Deserialize, Validate)]
+/// struct Config {
+///     #[validate(range(min = 1))]
+///     port: u16,
+/// }
+///
+/// let figment = Figment::from(Serialized::default("port", 0));
+/// let error = figment.extract_validated::<Config>().err().unwrap();
+/// assert!(error.to_string().starts_with(
+///     r#"range: Number not in range: min=1, value=0 for key "default.port" in "#
+/// ));
+/// ```
+pub trait ExtractValidated {
+    /// Extracts configuration into `T` and validates it. Errors of
+    /// [Severity::Error] are returned as [ConfigError::Invalid].
+    fn extract_validated<T: DeserializeOwned + Validate>(&self) -> Result<T, ConfigError>;
+}
+
+impl ExtractValidated for Figment {
+    fn extract_validated<T: DeserializeOwned + Validate>(&self) -> Result<T, ConfigError> {
+        let config: T = self
+            .extract()
+            .map_err(|error| ConfigError::Extract(Box::new(error)))?;
+        let errors = config.validate();
+        if errors.has_errors() {
+            return Err(ConfigError::Invalid(invalid_keys(self, &errors)));
+        }
+        Ok(config)
+    }
+}
+
+/// Error of [ExtractValidated::extract_validated].
+#[derive(Debug, Clone)]
+pub enum ConfigError {
+    /// Configuration can't be extracted into the type.
+    Extract(Box<::figment::Error>),
+    /// Extracted configuration is invalid.
+    Invalid(Vec<InvalidKey>),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Extract(error) => error.fmt(f),
+            ConfigError::Invalid(keys) => {
+                for (i, key) in keys.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str("\n")?;
+                    }
+                    key.fmt(f)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Validation error placed at a configuration key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidKey {
+    /// Keys leading to the value in configuration, e.g. `["server", "port"]`,
+    /// with indexes of array items.
+    pub path: Vec<String>,
+    /// Key as named by the source of the value, like in `figment` errors,
+    /// e.g. `default.server.port` in files of the default profile or
+    /// `SERVER.PORT` in environment variables.
+    pub key: String,
+    /// Metadata of the provider of the value, if the value is in
+    /// configuration.
+    pub metadata: Option<Metadata>,
+    /// The validation error.
+    pub error: ValidationError,
+}
+
+impl Display for InvalidKey {
+    /// Prints the error with key and source, like `figment` errors.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} for key {:?}", self.error, self.key)?;
+        if let Some(metadata) = &self.metadata {
+            match &metadata.source {
+                Some(source) => write!(f, " in {} {}", source, metadata.name)?,
+                None => write!(f, " in {}", metadata.name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Places errors of [Severity::Error] at configuration keys. Field names on
+/// error paths are matched with keys of the configuration exactly, or else
+/// ignoring case, underscores and hyphens, so renames like `kebab-case` or
+/// `camelCase` resolve to keys of the files. Metadata tells which provider,
+/// e.g. which file, set the value. `figment` doesn't track lines of values.
+/// ```
+/// # use not_so_fast::*;
+/// use figment::{providers::Serialized, util::map, Figment};
+///
+/// let figment = Figment::from(Serialized::default("server", map!["max-connections" => 0]));
+/// let errors = ValidationNode::field(
+///     "server",
+///     ValidationNode::field("max_connections", ValidationNode::error(ValidationError::with_code("range"))),
+/// );
+///
+/// let keys = invalid_keys(&figment, &errors);
+/// assert_eq!(vec!["server", "max-connections"], keys[0].path);
+/// assert_eq!("default.server.max-connections", keys[0].key);
+/// ```
+pub fn invalid_keys(figment: &Figment, errors: &ValidationNode) -> Vec<InvalidKey> {
+    let root = figment.find_value("").ok();
+    errors
+        .iter()
+        .filter(|(_, error)| error.severity() == Severity::Error)
+        .map(|(path, error)| {
+            let (path, value) = config_path(root.as_ref(), &path);
+            let metadata = value.and_then(|value| figment.get_metadata(value.tag()));
+            let key = match metadata {
+                Some(metadata) => metadata.interpolate(figment.profile(), &path),
+                None => path.join("."),
+            };
+            InvalidKey {
+                path,
+                key,
+                metadata: metadata.cloned(),
+                error: error.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Returns configuration keys of `path` and the value at `path`, if it's in
+/// configuration.
+fn config_path<'a>(mut value: Option<&'a Value>, path: &Path) -> (Vec<String>, Option<&'a Value>) {
+    let mut keys = Vec::new();
+    for segment in path.segments() {
+        let (key, next) = match segment {
+            PathSegment::Field(name) => match value.and_then(Value::as_dict) {
+                Some(dict) => match find_field(dict, name) {
+                    Some((key, next)) => (key.clone(), Some(next)),
+                    None => (name.to_string(), None),
+                },
+                None => (name.to_string(), None),
+            },
+            PathSegment::Key(key) => (
+                key.to_string(),
+                value
+                    .and_then(Value::as_dict)
+                    .and_then(|dict| dict.get(key.as_ref())),
+            ),
+            PathSegment::Index(index) => (
+                index.to_string(),
+                value
+                    .and_then(Value::as_array)
+                    .and_then(|items| items.get(*index)),
+            ),
+        };
+        keys.push(key);
+        value = next;
+    }
+    (keys, value)
+}
+
+fn find_field<'a>(dict: &'a Dict, name: &str) -> Option<(&'a String, &'a Value)> {
+    if let Some(entry) = dict.get_key_value(name) {
+        return Some(entry);
+    }
+    let name = normalize(name);
+    dict.iter().find(|(key, _)| normalize(key) == name)
+}
+
+fn normalize(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
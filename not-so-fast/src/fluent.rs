@@ -0,0 +1,155 @@
+//! Localization of error messages with [Fluent](https://projectfluent.org).
+//! Fluent bundles resolve error codes as message ids, with error params as
+//! message arguments.
+
+use std::borrow::{Borrow, Cow};
+use std::collections::BTreeMap;
+
+use fluent_bundle::bundle::FluentBundle;
+use fluent_bundle::memoizer::MemoizerKind;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+use crate::{MessageResolver, ParamValue};
+
+impl<R: Borrow<FluentResource>, M: MemoizerKind> MessageResolver for FluentBundle<R, M> {
+    /// Formats message with id equal to the error code. Numeric params are
+    /// passed to Fluent as numbers, other params as strings.
+    /// ```
+    /// # use not_so_fast::*;
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    ///
+    /// let resource = FluentResource::try_new(
+    ///     "length = Długość musi wynosić co najwyżej { $max }".to_owned(),
+    /// ).unwrap();
+    /// let mut bundle = FluentBundle::new(vec!["pl".parse().unwrap()]);
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(resource).unwrap();
+    ///
+    /// let errors = ValidationNode::field(
+    ///     "name",
+    ///     ValidationNode::error(ValidationError::with_code("length").and_param("max", 10)),
+    /// );
+    /// assert_eq!(
+    ///     ".name: length: Długość musi wynosić co najwyżej 10: max=10",
+    ///     errors.render_with(&bundle),
+    /// );
+    /// ```
+    fn resolve(
+        &self,
+        code: &str,
+        params: &BTreeMap<Cow<'static, str>, ParamValue>,
+    ) -> Option<String> {
+        let pattern = self.get_message(code)?.value()?;
+        let mut args = FluentArgs::with_capacity(params.len());
+        for (name, value) in params {
+            args.set(name.as_ref(), fluent_value(value));
+        }
+        // Fluent reports missing arguments and references, and renders
+        // their placeholders, so the message is usable anyway.
+        let mut errors = Vec::new();
+        Some(
+            self.format_pattern(pattern, Some(&args), &mut errors)
+                .into_owned(),
+        )
+    }
+}
+
+fn fluent_value(value: &ParamValue) -> FluentValue<'_> {
+    match value {
+        ParamValue::I8(value) => value.into(),
+        ParamValue::I16(value) => value.into(),
+        ParamValue::I32(value) => value.into(),
+        ParamValue::I64(value) => value.into(),
+        ParamValue::I128(value) => value.into(),
+        ParamValue::U8(value) => value.into(),
+        ParamValue::U16(value) => value.into(),
+        ParamValue::U32(value) => value.into(),
+        ParamValue::U64(value) => value.into(),
+        ParamValue::U128(value) => value.into(),
+        ParamValue::Usize(value) => value.into(),
+        ParamValue::F32(value) => value.into(),
+        ParamValue::F64(value) => value.into(),
+        ParamValue::String(value) | ParamValue::Raw(value) => value.as_ref().into(),
+        value => value.to_string().into(),
+    }
+}
+
+/// Fluent bundles of multiple languages, selecting the bundle for the
+/// user's language at render time.
+/// ```
+/// # use not_so_fast::*;
+/// use fluent_bundle::{FluentBundle, FluentResource};
+///
+/// fn bundle(language: &str, ftl: &str) -> FluentBundle<FluentResource> {
+///     let mut bundle = FluentBundle::new(vec![language.parse().unwrap()]);
+///     bundle.add_resource(FluentResource::try_new(ftl.to_owned()).unwrap()).unwrap();
+///     bundle
+/// }
+///
+/// let messages = FluentMessages::new()
+///     .with_bundle(bundle("en", "alpha_only = Only letters are allowed"))
+///     .with_bundle(bundle("de", "alpha_only = Nur Buchstaben sind erlaubt"));
+///
+/// let errors = ValidationNode::error(ValidationError::with_code("alpha_only"));
+/// let resolver = messages.for_language(&"de-AT".parse().unwrap()).unwrap();
+/// assert_eq!(".: alpha_only: Nur Buchstaben sind erlaubt", errors.render_with(resolver));
+/// assert!(messages.for_language(&"fr".parse().unwrap()).is_none());
+/// ```
+#[derive(Default)]
+pub struct FluentMessages {
+    bundles: Vec<fluent_bundle::FluentBundle<FluentResource>>,
+}
+
+impl FluentMessages {
+    /// Creates an empty set of bundles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `bundle` to the set.
+    pub fn with_bundle(mut self, bundle: fluent_bundle::FluentBundle<FluentResource>) -> Self {
+        self.insert(bundle);
+        self
+    }
+
+    /// Adds `bundle` to the set.
+    pub fn insert(&mut self, bundle: fluent_bundle::FluentBundle<FluentResource>) {
+        self.bundles.push(bundle);
+    }
+
+    /// Returns the first bundle with one of locales equal to `language`, or
+    /// if there is none, the first bundle with a locale of the same language
+    /// subtag, e.g. `de` for `de-AT`.
+    pub fn for_language(
+        &self,
+        language: &LanguageIdentifier,
+    ) -> Option<&fluent_bundle::FluentBundle<FluentResource>> {
+        self.bundles
+            .iter()
+            .find(|bundle| bundle.locales.contains(language))
+            .or_else(|| {
+                self.bundles.iter().find(|bundle| {
+                    bundle
+                        .locales
+                        .iter()
+                        .any(|locale| locale.language == language.language)
+                })
+            })
+    }
+}
+
+impl std::fmt::Debug for FluentMessages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FluentMessages")
+            .field(
+                "locales",
+                &self
+                    .bundles
+                    .iter()
+                    .map(|bundle| &bundle.locales)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,103 @@
+//! Conversions between [ValidationNode] and reports of the `garde` crate,
+//! for code bases using both libraries.
+//!
+//! Errors of `garde` carry only messages. Converted `garde` errors get code
+//! [codes::GARDE], and converted [ValidationError]s keep their message, or
+//! their code if they have no message. Params, severity, tags and sources
+//! have no `garde` counterpart and are dropped.
+
+use garde::error::Kind;
+
+use crate::{codes, Path, PathSegment, ValidationError, ValidationNode};
+
+impl From<&garde::Path> for Path {
+    /// Converts a path of `garde`. Keys become fields, components without
+    /// keys, e.g. of `Option`s, are skipped.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let path = garde::Path::new("users").join(2).join("name");
+    /// assert_eq!(".users[2].name", Path::from(&path).to_string());
+    /// ```
+    fn from(path: &garde::Path) -> Self {
+        // `garde` exposes path components only through this hidden method.
+        path.__iter()
+            .rev()
+            .fold(Path::root(), |path, (kind, component)| match kind {
+                Kind::Key => path.and_field(component.to_string()),
+                Kind::Index => match component.parse() {
+                    Ok(index) => path.and_index(index),
+                    Err(_) => path.and_key(component.to_string()),
+                },
+                Kind::None => path,
+            })
+    }
+}
+
+impl From<&Path> for garde::Path {
+    /// Converts a path to `garde`. Fields and keyed items become keys.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let path: Path = r#".users["abc"].tags[1]"#.parse().unwrap();
+    /// assert_eq!("users.abc.tags[1]", garde::Path::from(&path).to_string());
+    /// ```
+    fn from(path: &Path) -> Self {
+        path.segments()
+            .iter()
+            .fold(garde::Path::empty(), |path, segment| match segment {
+                PathSegment::Field(name) | PathSegment::Key(name) => path.join(name.as_ref()),
+                PathSegment::Index(index) => path.join(*index),
+            })
+    }
+}
+
+impl From<garde::Report> for ValidationNode {
+    /// Places errors of the report at their paths, with code [codes::GARDE]
+    /// and the errors' messages.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut report = garde::Report::new();
+    /// report.append(garde::Path::new("name"), garde::Error::new("length is lower than 3"));
+    /// report.append(garde::Path::empty(), garde::Error::new("invariant"));
+    ///
+    /// assert_eq!(
+    ///     ".: garde: invariant\n.name: garde: length is lower than 3",
+    ///     ValidationNode::from(report).to_string(),
+    /// );
+    /// ```
+    fn from(report: garde::Report) -> Self {
+        report
+            .into_inner()
+            .into_iter()
+            .fold(ValidationNode::ok(), |node, (path, error)| {
+                let error = ValidationError::with_code(codes::GARDE)
+                    .and_message(error.message().to_owned());
+                node.and_at(Path::from(&path), error)
+            })
+    }
+}
+
+impl From<ValidationNode> for garde::Report {
+    /// Converts a node to a report with the same paths, in the order of
+    /// [ValidationNode::iter].
+    /// ```
+    /// # use not_so_fast::*;
+    /// let node = ValidationNode::field(
+    ///     "tags",
+    ///     ValidationNode::item(1, ValidationNode::error(ValidationError::with_code("length"))),
+    /// )
+    /// .and_error(ValidationError::with_code("invariant").and_message("Invalid tags"));
+    ///
+    /// assert_eq!(
+    ///     "Invalid tags\ntags[1]: length\n",
+    ///     garde::Report::from(node).to_string(),
+    /// );
+    /// ```
+    fn from(node: ValidationNode) -> Self {
+        let mut report = garde::Report::new();
+        for (path, error) in node {
+            let error = garde::Error::new(error.message().unwrap_or(error.code()));
+            report.append(garde::Path::from(&path), error);
+        }
+        report
+    }
+}
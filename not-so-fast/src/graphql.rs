@@ -0,0 +1,80 @@
+//! Validation of `async-graphql` input values.
+
+use ::async_graphql::indexmap::IndexMap;
+use ::async_graphql::{CustomValidator, InputType, InputValueError, Name, Value};
+
+use crate::{PathSegment, Severity, Validate, ValidationNode};
+
+/// Custom `async-graphql` validator running [Validate] on input values.
+/// Values with errors of [Severity::Error] are rejected with these errors
+/// rendered as message, and a `validation` error extension listing `path`,
+/// `code` and `message` of each error. Paths are lists of field names and
+/// indexes, like paths of GraphQL errors.
+/// ```
+/// # use not_so_fast::*;
+/// use async_graphql::{EmptyMutation, EmptySubscription, InputObject, Object, Schema};
+///
+/// #[derive(InputObject, Validate)]
+/// struct UserInput {
+///     #[validate(char_length(max = 5))]
+///     name: String,
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn greet(&self, #[graphql(validator(custom = "ValidateInput"))] user: UserInput) -> String {
+///         format!("Hello, {}!", user.name)
+///     }
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let response = schema.execute(r#"{ greet(user: { name: "Thomas" }) }"#).await;
+/// let extensions = response.errors[0].extensions.as_ref().unwrap();
+/// assert_eq!(
+///     r#"[{path: ["name"], code: "char_length", message: "Invalid character length"}]"#,
+///     extensions.get("validation").unwrap().to_string(),
+/// );
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidateInput;
+
+impl<T: InputType + Validate> CustomValidator<T> for ValidateInput {
+    fn check(&self, value: &T) -> Result<(), InputValueError<T>> {
+        let mut errors = value.validate();
+        if !errors.has_errors() {
+            return Ok(());
+        }
+        errors.retain_severity(Severity::Error);
+        Err(InputValueError::custom(&errors).with_extension("validation", error_list(&errors)))
+    }
+}
+
+fn error_list(errors: &ValidationNode) -> Value {
+    let errors = errors
+        .iter()
+        .map(|(path, error)| {
+            let path = path
+                .segments()
+                .iter()
+                .map(|segment| match segment {
+                    PathSegment::Field(name) | PathSegment::Key(name) => {
+                        Value::String(name.to_string())
+                    }
+                    PathSegment::Index(index) => Value::Number((*index as u64).into()),
+                })
+                .collect();
+            let mut object = IndexMap::new();
+            object.insert(Name::new("path"), Value::List(path));
+            object.insert(Name::new("code"), Value::String(error.code().to_owned()));
+            if let Some(message) = error.message() {
+                object.insert(Name::new("message"), Value::String(message.to_owned()));
+            }
+            Value::Object(object)
+        })
+        .collect();
+    Value::List(errors)
+}
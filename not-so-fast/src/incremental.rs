@@ -0,0 +1,175 @@
+//! Incremental revalidation of values, recomputing errors of changed fields
+//! only.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{Path, PathSegment, ValidationNode};
+
+/// Type validated field by field, implemented by the derive macro for
+/// structs with `#[validate(incremental)]`. See [Revalidation].
+pub trait ValidateFields {
+    /// Names of validated fields, in declaration order. Flattened fields,
+    /// which report errors at the level of the struct, have no name.
+    const FIELDS: &'static [Option<&'static str>];
+
+    /// Validates `self` with type-level validators only, which check
+    /// invariants across fields.
+    fn validate_type(&self) -> ValidationNode;
+
+    /// Validates field `FIELDS[index]`, returning its errors attached to the
+    /// node of `self`.
+    fn validate_field(&self, index: usize) -> ValidationNode;
+
+    /// Validates fields affected by changes of values at `changed` paths,
+    /// and `self` with type-level validators, which can depend on any
+    /// field. Validates all fields if a changed path doesn't point at or
+    /// inside a field, e.g. if `self` changed as a whole.
+    /// ```
+    /// # use not_so_fast::*;
+    /// #[derive(Validate)]
+    /// #[validate(incremental)]
+    /// struct Range {
+    ///     #[validate(range(max = 10))]
+    ///     start: u32,
+    ///     #[validate(range(max = 10))]
+    ///     end: u32,
+    /// }
+    ///
+    /// let range = Range { start: 20, end: 30 };
+    /// assert_eq!(
+    ///     ".end: range: Number not in range: max=10, value=30",
+    ///     range.revalidate(&[".end".parse().unwrap()]).to_string(),
+    /// );
+    /// ```
+    fn revalidate(&self, changed: &[Path]) -> ValidationNode {
+        let affected = affected_fields(Self::FIELDS, changed);
+        ValidationNode::all(
+            core::iter::once(self.validate_type()).chain(
+                affected
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, affected)| **affected)
+                    .map(|(index, _)| self.validate_field(index)),
+            ),
+        )
+    }
+}
+
+/// Errors of a value kept per field, so that changes of some fields
+/// revalidate only those fields. Type-level validators, which check
+/// invariants across fields, are rerun on every change, replacing all
+/// errors they reported before.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(Validate)]
+/// #[validate(incremental, custom = check_dates)]
+/// struct Booking {
+///     #[validate(char_length(max = 5))]
+///     guest: String,
+///     nights: u32,
+///     #[validate(range(max = 4))]
+///     rooms: u32,
+/// }
+///
+/// fn check_dates(booking: &Booking) -> ValidationNode {
+///     ValidationNode::ok().and_field(
+///         "rooms",
+///         ValidationNode::error_if(booking.rooms > booking.nights, || {
+///             ValidationError::with_code("more_rooms_than_nights")
+///         }),
+///     )
+/// }
+///
+/// let mut booking = Booking { guest: "Alexander".into(), nights: 1, rooms: 2 };
+/// let mut revalidation = Revalidation::new(&booking);
+/// assert_eq!(
+///     ".guest: char_length: Invalid character length: max=5, value=9\n\
+///      .rooms: more_rooms_than_nights",
+///     revalidation.node().to_string(),
+/// );
+///
+/// booking.nights = 3;
+/// revalidation.revalidate(&booking, &[".nights".parse().unwrap()]);
+/// assert_eq!(
+///     ".guest: char_length: Invalid character length: max=5, value=9",
+///     revalidation.node().to_string(),
+/// );
+/// assert_eq!(&booking.validate(), revalidation.node());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revalidation {
+    type_node: ValidationNode,
+    fields: Vec<ValidationNode>,
+    node: ValidationNode,
+}
+
+impl Revalidation {
+    /// Validates all fields of `value`.
+    pub fn new<T: ValidateFields + ?Sized>(value: &T) -> Self {
+        let mut revalidation = Self {
+            type_node: value.validate_type(),
+            fields: (0..T::FIELDS.len())
+                .map(|index| value.validate_field(index))
+                .collect(),
+            node: ValidationNode::ok(),
+        };
+        revalidation.node = revalidation.merged();
+        revalidation
+    }
+
+    /// Revalidates fields of `value` affected by changes of values at
+    /// `changed` paths, and `value` with type-level validators, replacing
+    /// their previous errors. Revalidates all fields if a changed path
+    /// doesn't point at or inside a field. Returns errors of the whole value,
+    /// equal to errors of full validation.
+    pub fn revalidate<T: ValidateFields + ?Sized>(
+        &mut self,
+        value: &T,
+        changed: &[Path],
+    ) -> &ValidationNode {
+        let affected = affected_fields(T::FIELDS, changed);
+        self.type_node = value.validate_type();
+        for (index, affected) in affected.into_iter().enumerate() {
+            if affected {
+                self.fields[index] = value.validate_field(index);
+            }
+        }
+        self.node = self.merged();
+        &self.node
+    }
+
+    /// Returns errors of the whole value.
+    pub fn node(&self) -> &ValidationNode {
+        &self.node
+    }
+
+    /// Returns errors of the whole value.
+    pub fn into_node(self) -> ValidationNode {
+        self.node
+    }
+
+    /// Merges errors the way full validation does, type-level errors first,
+    /// then fields in declaration order.
+    fn merged(&self) -> ValidationNode {
+        ValidationNode::all(
+            core::iter::once(self.type_node.clone()).chain(self.fields.iter().cloned()),
+        )
+    }
+}
+
+/// Tells which of `fields` are affected by changes at `changed` paths.
+/// Flattened fields are affected by any change, as their values can't be
+/// told apart from other fields. Changes of values other than fields affect
+/// all fields.
+fn affected_fields(fields: &[Option<&str>], changed: &[Path]) -> Vec<bool> {
+    let mut affected = vec![false; fields.len()];
+    for path in changed {
+        let Some(PathSegment::Field(name)) = path.segments().first() else {
+            return vec![true; fields.len()];
+        };
+        for (affected, field) in affected.iter_mut().zip(fields) {
+            *affected |= field.is_none_or(|field| field == name.as_ref());
+        }
+    }
+    affected
+}
@@ -0,0 +1,122 @@
+//! Check of BCP 47 language tags for `language_tag` validator of the derive
+//! macro.
+
+/// Grandfathered tags not matching the syntax of other tags.
+const IRREGULAR: &[&str] = &[
+    "en-GB-oed",
+    "i-ami",
+    "i-bnn",
+    "i-default",
+    "i-enochian",
+    "i-hak",
+    "i-klingon",
+    "i-lux",
+    "i-mingo",
+    "i-navajo",
+    "i-pwn",
+    "i-tao",
+    "i-tay",
+    "i-tsu",
+    "sgn-BE-FR",
+    "sgn-BE-NL",
+    "sgn-CH-DE",
+];
+
+/// Checks if `tag` is a well-formed BCP 47 language tag, like `en`, `en-US`
+/// or `zh-Hant-TW`, ignoring case. With the `language-tags` feature, the tag
+/// must also be valid: its subtags must be registered in the IANA Language
+/// Subtag Registry and variants and extensions can't repeat.
+/// ```
+/// # use not_so_fast::*;
+/// assert!(is_language_tag("en-US"));
+/// assert!(is_language_tag("zh-Hant-TW"));
+/// assert!(is_language_tag("de-CH-1996"));
+/// assert!(is_language_tag("x-whatever"));
+/// assert!(!is_language_tag("en_US"));
+/// assert!(!is_language_tag("e"));
+/// assert!(!is_language_tag("en-"));
+/// ```
+pub fn is_language_tag(tag: &str) -> bool {
+    #[cfg(feature = "language-tags")]
+    if !language_tags::LanguageTag::parse(tag).is_ok_and(|tag| tag.is_valid()) {
+        return false;
+    }
+
+    if IRREGULAR
+        .iter()
+        .any(|irregular| irregular.eq_ignore_ascii_case(tag))
+    {
+        return true;
+    }
+
+    let mut subtags = tag.split('-').peekable();
+    let Some(language) = subtags.next() else {
+        return false;
+    };
+    if language.eq_ignore_ascii_case("x") {
+        return is_private_use(subtags);
+    }
+    if !is_alpha(language, 2, 8) {
+        return false;
+    }
+    if language.len() <= 3 {
+        for _ in 0..3 {
+            if subtags.next_if(|subtag| is_alpha(subtag, 3, 3)).is_none() {
+                break;
+            }
+        }
+    }
+    subtags.next_if(|subtag| is_alpha(subtag, 4, 4));
+    subtags.next_if(|subtag| is_alpha(subtag, 2, 2) || is_digit(subtag, 3));
+    while subtags.next_if(|subtag| is_variant(subtag)).is_some() {}
+    while subtags.next_if(|subtag| is_singleton(subtag)).is_some() {
+        if subtags
+            .next_if(|subtag| is_alphanumeric(subtag, 2, 8))
+            .is_none()
+        {
+            return false;
+        }
+        while subtags
+            .next_if(|subtag| is_alphanumeric(subtag, 2, 8))
+            .is_some()
+        {}
+    }
+    match subtags.next() {
+        Some(subtag) if subtag.eq_ignore_ascii_case("x") => is_private_use(subtags),
+        Some(_) => false,
+        None => true,
+    }
+}
+
+/// Checks subtags following `x`.
+fn is_private_use<'a>(subtags: impl Iterator<Item = &'a str>) -> bool {
+    let mut count = 0;
+    for subtag in subtags {
+        if !is_alphanumeric(subtag, 1, 8) {
+            return false;
+        }
+        count += 1;
+    }
+    count > 0
+}
+
+fn is_alpha(subtag: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_digit(subtag: &str, len: usize) -> bool {
+    subtag.len() == len && subtag.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_alphanumeric(subtag: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+fn is_variant(subtag: &str) -> bool {
+    is_alphanumeric(subtag, 5, 8)
+        || (is_alphanumeric(subtag, 4, 4) && subtag.as_bytes()[0].is_ascii_digit())
+}
+
+fn is_singleton(subtag: &str) -> bool {
+    is_alphanumeric(subtag, 1, 1) && !subtag.eq_ignore_ascii_case("x")
+}
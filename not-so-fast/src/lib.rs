@@ -46,27 +46,332 @@
 //! );
 //! ```
 
-use std::borrow::Cow;
-use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
-use std::fmt::Write;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::sync::Arc;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+use crate::error_list::ErrorList;
+use crate::sorted_map::{Entry, SortedMap};
+
+#[cfg(feature = "arena")]
+mod arena;
+#[cfg(feature = "axum")]
+mod axum;
+mod cache;
+#[cfg(feature = "serde")]
+mod canonical;
+#[cfg(feature = "clap")]
+mod clap;
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
+mod error_list;
+#[cfg(feature = "figment")]
+mod figment;
+#[cfg(feature = "fluent")]
+mod fluent;
+#[cfg(feature = "garde-compat")]
+mod garde_compat;
+#[cfg(feature = "async-graphql")]
+mod graphql;
+mod incremental;
+#[cfg(feature = "iso-codes")]
+mod iso;
+mod language_tag;
+mod macros;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "std")]
+mod observer;
+mod partial;
+#[cfg(feature = "serde_path_to_error")]
+mod path_to_error;
+#[cfg(feature = "serde")]
+mod problem;
+#[cfg(feature = "proptest")]
+mod proptest;
+mod range;
+mod registry;
+#[cfg(any(feature = "axum", feature = "rocket"))]
+mod rejection;
+#[cfg(all(feature = "std", feature = "serde"))]
+mod response;
+#[cfg(feature = "rocket")]
+mod rocket;
+pub mod rules;
+#[cfg(feature = "fake")]
+mod sample;
+#[cfg(feature = "schemars")]
+mod schemars;
+mod sink;
+mod sorted_map;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub mod temporal;
+#[cfg(feature = "test-util")]
+mod test_util;
+#[cfg(feature = "tracing")]
+mod tracing;
+mod validate_async;
+#[cfg(feature = "validator-compat")]
+mod validator_compat;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 #[cfg(feature = "derive")]
 pub use not_so_fast_derive::Validate;
 
+#[cfg(feature = "arena")]
+pub use crate::arena::{Arena, ArenaError, ArenaNode};
+#[cfg(feature = "axum")]
+pub use crate::axum::{ValidatedJson, ValidatedPath, ValidatedQuery};
+pub use crate::cache::{ValidationCache, ValidationKey};
+#[cfg(feature = "serde")]
+pub use crate::canonical::{CanonicalNode, NodeAsCanonical, CANONICAL_VERSION};
+#[cfg(feature = "clap")]
+pub use crate::clap::{clap_errors, ParseValidated};
+#[cfg(feature = "dynamic")]
+pub use crate::dynamic::ValidateValue;
+#[cfg(feature = "figment")]
+pub use crate::figment::{invalid_keys, ConfigError, ExtractValidated, InvalidKey};
+#[cfg(feature = "fluent")]
+pub use crate::fluent::FluentMessages;
+#[cfg(feature = "async-graphql")]
+pub use crate::graphql::ValidateInput;
+pub use crate::incremental::{Revalidation, ValidateFields};
+#[cfg(feature = "iso-codes")]
+pub use crate::iso::{is_country_code, is_currency_code};
+pub use crate::language_tag::is_language_tag;
+#[cfg(feature = "metrics")]
+pub use crate::metrics::MetricsObserver;
+#[cfg(feature = "std")]
+pub use crate::observer::{set_observer, ValidationObserver};
+pub use crate::partial::FieldMask;
+#[cfg(feature = "serde_path_to_error")]
+pub use crate::path_to_error::deserialize_and_validate;
+#[cfg(all(feature = "serde", feature = "serde_path_to_error"))]
+pub use crate::path_to_error::validate_json_str;
+#[cfg(feature = "serde")]
+pub use crate::problem::{Problem, ProblemError};
+#[cfg(feature = "proptest")]
+pub use crate::proptest::ArbitraryValid;
+pub use crate::registry::ValidatorRegistry;
+#[cfg(feature = "std")]
+pub use crate::registry::{set_validator_registry, validate_named, validator_registry};
+#[cfg(any(feature = "axum", feature = "rocket"))]
+pub use crate::rejection::ValidationRejection;
+#[cfg(all(feature = "std", feature = "serde"))]
+pub use crate::response::{
+    error_body_format, set_error_body_format, ErrorBodyFormat, IntoValidationResponse,
+    TryFromUnvalidated, ValidationResponse,
+};
+#[cfg(feature = "rocket")]
+pub use crate::rocket::{validation_errors, Validated};
+#[cfg(feature = "fake")]
+pub use crate::sample::Sample;
+#[cfg(feature = "schemars")]
+pub use crate::schemars::{augment_schema, ValidationSchema};
+#[cfg(feature = "serde")]
+pub use crate::serde::{NodeAsFlatList, NodeAsTree};
+pub use crate::validate_async::{join_all, FromSync, JoinAll, ValidateAsync, ValidateAsyncArgs};
+#[cfg(feature = "wasm")]
+pub use crate::wasm::validate_js;
+
+/// Former name of [ValidationNode], kept so code written before the rename
+/// compiles during migration.
+#[deprecated(since = "0.3.0", note = "renamed to `ValidationNode`")]
+pub type ValidationErrors = ValidationNode;
+
+/// Former name of [ValidationError], kept so code written before the rename
+/// compiles during migration.
+/// Crates glob-importing both `not_so_fast` and another crate exporting
+/// `Error` should import the other `Error` explicitly.
+#[deprecated(since = "0.3.0", note = "renamed to `ValidationError`")]
+pub type Error = ValidationError;
+
+/// Items used by code generated by the derive macro, which can't name
+/// `alloc` in `no_std` crates.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::cache::validate_cached_nested;
+    #[cfg(feature = "dynamic")]
+    pub use crate::dynamic::{dyn_fields, dyn_number, dyn_rules, ValueElement};
+    pub use crate::macros::{node_item, NodeItemKey};
+    #[cfg(feature = "proptest")]
+    pub use crate::proptest::{
+        pt_any, pt_char_length, pt_char_length_invalid, pt_items, pt_items_invalid, pt_just,
+        pt_length, pt_length_invalid, pt_nested, pt_nested_invalid, pt_one_of, pt_range,
+        pt_range_invalid, pt_some, pt_some_invalid,
+    };
+    pub use crate::range::{range_bound, RangeBound};
+    #[cfg(feature = "fake")]
+    pub use crate::sample::{
+        sa_any, sa_char_length, sa_fake, sa_items, sa_length, sa_nested, sa_range, sa_some,
+        sa_valid,
+    };
+    #[cfg(feature = "schemars")]
+    pub use crate::schemars::{
+        schema_char_length, schema_items, schema_length, schema_maximum, schema_minimum,
+        schema_property, schema_values,
+    };
+    pub use crate::sink::{drain_field, drain_item, drain_root, nested_into, PrefixedSink};
+    #[cfg(feature = "test-util")]
+    pub use crate::test_util::{assert_errors, assert_invalid, assert_valid};
+    #[cfg(feature = "tracing")]
+    pub use crate::tracing::TracedParams;
+    pub use crate::validate_async::{NestedAsync, NestedAsyncArgs, NestedSyncArgs};
+    pub use alloc::{boxed::Box, string::ToString, vec::Vec};
+    #[cfg(feature = "fake")]
+    pub use fake;
+    #[cfg(feature = "ipnet")]
+    pub use ipnet::IpNet;
+    #[cfg(feature = "proptest")]
+    pub use proptest;
+    #[cfg(feature = "fake")]
+    pub use rand;
+    #[cfg(feature = "rayon")]
+    pub use rayon::iter::IntoParallelRefIterator;
+    #[cfg(feature = "schemars")]
+    pub use schemars::Schema;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+    #[cfg(feature = "url")]
+    pub use url::Url;
+    #[cfg(feature = "wasm")]
+    pub use wasm_bindgen;
+}
+
 /// Describes what is wrong with the validated value. It contains code, an
 /// optional message, and a list of error parameters.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ValidationError {
     /// Tells what feature of the validated value is not ok, e.g. "length",
     /// "range", "invariant_xyz".
     code: Cow<'static, str>,
     /// Optional message explaining the error code, e.g. "Illegal array
     /// length".
-    message: Option<Cow<'static, str>>,
+    message: Option<Message>,
+    /// Params, severity, tags and source of the error, allocated only if
+    /// some of them are set. Keeps errors small, so that nodes can store a
+    /// single error inline.
+    details: Option<Box<ErrorDetails>>,
+}
+
+/// Parts of [ValidationError] other than code and message.
+#[derive(Debug, Clone, Default)]
+struct ErrorDetails {
     /// A list of params that provide further context about the error, e.g. for
     /// code "range": "min", "max", "value".
     params: BTreeMap<Cow<'static, str>, ParamValue>,
+    /// Tells how serious the error is. Errors are fatal by default.
+    severity: Severity,
+    /// Categories of the error, e.g. "security", "pii".
+    tags: BTreeSet<Cow<'static, str>>,
+    /// Underlying cause of the error, e.g. a parse error.
+    source: Option<Arc<dyn core::error::Error + Send + Sync>>,
+}
+
+/// Params of errors without details.
+static NO_PARAMS: BTreeMap<Cow<'static, str>, ParamValue> = BTreeMap::new();
+
+/// Tags of errors without details.
+static NO_TAGS: BTreeSet<Cow<'static, str>> = BTreeSet::new();
+
+/// Compares all properties of errors except sources, which can't be compared.
+impl PartialEq for ValidationError {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code
+            && self.message == other.message
+            && self.param_map() == other.param_map()
+            && self.severity() == other.severity()
+            && self.tag_set() == other.tag_set()
+    }
+}
+
+/// Message of [ValidationError], either a text or a function formatting the
+/// text on first use, set by [ValidationError::and_message_with].
+#[derive(Clone)]
+enum Message {
+    Text(Cow<'static, str>),
+    #[cfg(feature = "std")]
+    Lazy(Arc<LazyMessage>),
+}
+
+#[cfg(feature = "std")]
+struct LazyMessage {
+    format: Box<dyn Fn() -> String + Send + Sync>,
+    text: std::sync::OnceLock<String>,
+}
+
+impl Message {
+    fn as_str(&self) -> &str {
+        match self {
+            Message::Text(text) => text,
+            #[cfg(feature = "std")]
+            Message::Lazy(lazy) => lazy.text.get_or_init(|| (lazy.format)()),
+        }
+    }
+
+    #[cfg(feature = "validator-compat")]
+    fn into_text(self) -> Cow<'static, str> {
+        match self {
+            Message::Text(text) => text,
+            #[cfg(feature = "std")]
+            Message::Lazy(lazy) => Cow::Owned(lazy.text.get_or_init(|| (lazy.format)()).clone()),
+        }
+    }
+}
+
+impl PartialEq for Message {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl core::fmt::Debug for Message {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Severity of [ValidationError]. Severities are ordered from the least to
+/// the most serious.
+/// ```
+/// # use not_so_fast::*;
+/// assert!(Severity::Info < Severity::Warning);
+/// assert!(Severity::Warning < Severity::Error);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Informational note about the value.
+    Info,
+    /// Non-fatal problem, the value may still be accepted.
+    Warning,
+    /// Fatal problem, the value should be rejected.
+    #[default]
+    Error,
+}
+
+impl Severity {
+    /// Returns lowercase name of the severity, e.g. "warning".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
 }
 
 impl ValidationError {
@@ -80,10 +385,117 @@ impl ValidationError {
         Self {
             code: code.into(),
             message: None,
-            params: BTreeMap::new(),
+            details: None,
+        }
+    }
+
+    /// Creates an error from its parts, allocating details only if some of
+    /// them are set.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_parts(
+        code: Cow<'static, str>,
+        message: Option<Message>,
+        params: BTreeMap<Cow<'static, str>, ParamValue>,
+        severity: Severity,
+        tags: BTreeSet<Cow<'static, str>>,
+    ) -> Self {
+        let details =
+            (!params.is_empty() || severity != Severity::Error || !tags.is_empty()).then(|| {
+                Box::new(ErrorDetails {
+                    params,
+                    severity,
+                    tags,
+                    source: None,
+                })
+            });
+        Self {
+            code,
+            message,
+            details,
+        }
+    }
+
+    fn details_mut(&mut self) -> &mut ErrorDetails {
+        self.details.get_or_insert_with(Default::default)
+    }
+
+    pub(crate) fn param_map(&self) -> &BTreeMap<Cow<'static, str>, ParamValue> {
+        match &self.details {
+            Some(details) => &details.params,
+            None => &NO_PARAMS,
+        }
+    }
+
+    fn tag_set(&self) -> &BTreeSet<Cow<'static, str>> {
+        match &self.details {
+            Some(details) => &details.tags,
+            None => &NO_TAGS,
         }
     }
 
+    /// Former name of [ValidationError::with_code].
+    #[deprecated(since = "0.3.0", note = "use `ValidationError::with_code`")]
+    pub fn new(code: impl Into<Cow<'static, str>>) -> Self {
+        Self::with_code(code)
+    }
+
+    /// Attaches the underlying cause of the error, available later through
+    /// [std::error::Error::source]. The source is not displayed nor
+    /// serialized.
+    /// ```
+    /// # use not_so_fast::*;
+    /// use std::error::Error;
+    ///
+    /// let parse_error = "abc".parse::<u32>().unwrap_err();
+    /// let error = ValidationError::with_code("number").and_source(parse_error);
+    /// assert_eq!("invalid digit found in string", error.source().unwrap().to_string());
+    /// ```
+    pub fn and_source(mut self, source: impl core::error::Error + Send + Sync + 'static) -> Self {
+        self.details_mut().source = Some(Arc::new(source));
+        self
+    }
+
+    /// Adds a tag to the error. Tags let you group errors into categories,
+    /// e.g. to route them to different logging sinks.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let error = ValidationError::with_code("weak_password").and_tag("security").and_tag("auth");
+    /// assert!(error.has_tag("security"));
+    /// assert_eq!(vec!["auth", "security"], error.tags().collect::<Vec<_>>());
+    /// ```
+    pub fn and_tag(mut self, tag: impl Into<Cow<'static, str>>) -> Self {
+        self.details_mut().tags.insert(tag.into());
+        self
+    }
+
+    /// Returns an iterator over tags of the error, sorted alphabetically.
+    pub fn tags(&self) -> impl Iterator<Item = &str> + '_ {
+        self.tag_set().iter().map(AsRef::as_ref)
+    }
+
+    /// Checks if the error has tag `tag`.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tag_set().contains(tag)
+    }
+
+    /// Sets severity of the error. Errors have [Severity::Error] by default.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let error = ValidationError::with_code("deprecated").and_severity(Severity::Warning);
+    /// assert_eq!(Severity::Warning, error.severity());
+    /// ```
+    pub fn and_severity(mut self, severity: Severity) -> Self {
+        self.details_mut().severity = severity;
+        self
+    }
+
+    /// Returns severity of the error.
+    pub fn severity(&self) -> Severity {
+        self.details
+            .as_ref()
+            .map_or(Severity::Error, |details| details.severity)
+    }
+
     /// Adds a message to the error. If called multiple times, the last message
     /// will be preserved.
     /// ```
@@ -91,7 +503,30 @@ impl ValidationError {
     /// let error = ValidationError::with_code("length").and_message("String too long");
     /// ```
     pub fn and_message(mut self, message: impl Into<Cow<'static, str>>) -> Self {
-        self.message = Some(message.into());
+        self.message = Some(Message::Text(message.into()));
+        self
+    }
+
+    /// Adds a message formatted by `f` when it's first needed, e.g. when the
+    /// error is displayed. Errors that are only checked with `is_ok` never
+    /// format their messages. Without the `std` feature, the message is
+    /// formatted immediately.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let max = 100;
+    /// let error = ValidationError::with_code("length")
+    ///     .and_message_with(move || format!("String longer than {max} characters"));
+    /// assert_eq!(Some("String longer than 100 characters"), error.message());
+    /// ```
+    pub fn and_message_with(mut self, f: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        #[cfg(feature = "std")]
+        let message = Message::Lazy(Arc::new(LazyMessage {
+            format: Box::new(f),
+            text: std::sync::OnceLock::new(),
+        }));
+        #[cfg(not(feature = "std"))]
+        let message = Message::Text(f().into());
+        self.message = Some(message);
         self
     }
 
@@ -106,13 +541,93 @@ impl ValidationError {
         key: impl Into<Cow<'static, str>>,
         value: impl Into<ParamValue>,
     ) -> Self {
-        self.params.insert(key.into(), value.into());
+        self.details_mut().params.insert(key.into(), value.into());
         self
     }
+
+    /// Returns code of the error.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let error = ValidationError::with_code("length");
+    /// assert_eq!("length", error.code());
+    /// ```
+    pub fn code(&self) -> &str {
+        self.code.as_ref()
+    }
+
+    /// Returns message of the error, if present.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let error = ValidationError::with_code("length").and_message("String too long");
+    /// assert_eq!(Some("String too long"), error.message());
+    /// ```
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_ref().map(Message::as_str)
+    }
+
+    /// Returns an iterator over parameters of the error, sorted by key.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let error = ValidationError::with_code("length").and_param("max", 100);
+    /// let params: Vec<_> = error.params().map(|(key, value)| (key, value.to_string())).collect();
+    /// assert_eq!(vec![("max", "100".to_string())], params);
+    /// ```
+    pub fn params(&self) -> impl Iterator<Item = (&str, &ParamValue)> + '_ {
+        self.param_map()
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value))
+    }
+
+    /// Returns value of parameter `key`, if present.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let error = ValidationError::with_code("length").and_param("max", 100);
+    /// assert_eq!("100", error.param("max").unwrap().to_string());
+    /// assert!(error.param("min").is_none());
+    /// ```
+    pub fn param(&self, key: &str) -> Option<&ParamValue> {
+        self.param_map().get(key)
+    }
+
+    /// Replaces message of the error in place. Unlike
+    /// [and_message](ValidationError::and_message), it works with borrowed
+    /// errors.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut error = ValidationError::with_code("length").and_message("String too long");
+    /// error.set_message("Text too long");
+    /// assert_eq!(Some("Text too long"), error.message());
+    /// ```
+    pub fn set_message(&mut self, message: impl Into<Cow<'static, str>>) {
+        self.message = Some(Message::Text(message.into()));
+    }
+}
+
+impl core::fmt::Display for ValidationError {
+    /// Prints code, message and params of the error, the same way as
+    /// [ValidationNode]'s `Display` does.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let error = ValidationError::with_code("length").and_message("Invalid length").and_param("max", 10);
+    /// assert_eq!("length: Invalid length: max=10", error.to_string());
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_error(self, f)
+    }
+}
+
+impl core::error::Error for ValidationError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        self.details
+            .as_ref()?
+            .source
+            .as_deref()
+            .map(|source| source as &(dyn core::error::Error + 'static))
+    }
 }
 
 /// Parameter value stored in [ValidationError].
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ParamValue {
     Bool(bool),
     I8(i8),
@@ -131,10 +646,29 @@ pub enum ParamValue {
     Char(char),
     String(Cow<'static, str>),
     Raw(Cow<'static, str>),
+    Bytes(Cow<'static, [u8]>),
+    List(Vec<ParamValue>),
+    Map(BTreeMap<Cow<'static, str>, ParamValue>),
+    Duration(core::time::Duration),
+    /// Arbitrary structured value, e.g. the offending sub-document.
+    /// Serialized as JSON, not as a string.
+    #[cfg(feature = "serde")]
+    Json(serde_json::Value),
+    /// UUID, displayed and serialized in the hyphenated format.
+    #[cfg(feature = "uuid")]
+    Uuid(uuid::Uuid),
+    /// Decimal number, displayed in full precision and serialized as a
+    /// string.
+    #[cfg(feature = "rust_decimal")]
+    Decimal(rust_decimal::Decimal),
+    /// Arbitrary precision decimal number, displayed in full precision and
+    /// serialized as a string.
+    #[cfg(feature = "bigdecimal")]
+    BigDecimal(bigdecimal::BigDecimal),
 }
 
-impl std::fmt::Display for ParamValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         use ParamValue::*;
         match self {
             Bool(value) => write!(f, "{}", value),
@@ -154,6 +688,42 @@ impl std::fmt::Display for ParamValue {
             Char(value) => write!(f, "'{}'", value.escape_default()),
             String(value) => write!(f, "\"{}\"", value.escape_default()),
             Raw(value) => write!(f, "{}", value),
+            Bytes(value) => {
+                f.write_str("b\"")?;
+                for byte in value.iter() {
+                    write!(f, "{}", core::ascii::escape_default(*byte))?;
+                }
+                f.write_char('"')
+            }
+            List(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_char(']')
+            }
+            Map(entries) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "\"{}\": {}", key.escape_default(), value)?;
+                }
+                f.write_char('}')
+            }
+            Duration(value) => write!(f, "{:?}", value),
+            #[cfg(feature = "serde")]
+            Json(value) => write!(f, "{}", value),
+            #[cfg(feature = "uuid")]
+            Uuid(value) => write!(f, "{}", value),
+            #[cfg(feature = "rust_decimal")]
+            Decimal(value) => write!(f, "{}", value),
+            #[cfg(feature = "bigdecimal")]
+            BigDecimal(value) => write!(f, "{}", value),
         }
     }
 }
@@ -196,534 +766,4152 @@ impl From<String> for ParamValue {
     }
 }
 
-/// Container for [ValidationError]s associated with some value. If the value
-/// is an object or a list, field or item ValidationNodes can be attached to
-/// the root node, effectively forming an error tree.
-#[derive(Debug)]
-pub struct ValidationNode {
-    /// Errors of the validated value.
-    errors: Vec<ValidationError>,
-    /// Errors of fields of the validated object.
-    fields: BTreeMap<Cow<'static, str>, ValidationNode>,
-    /// Errors of items of the validate list.
-    items: BTreeMap<usize, ValidationNode>,
+impl From<&'static [u8]> for ParamValue {
+    fn from(value: &'static [u8]) -> Self {
+        Self::Bytes(Cow::Borrowed(value))
+    }
 }
 
-impl ValidationNode {
-    /// Creates `ValidationNode` with no value errors, no field errors and no
-    /// item errors. You'll be able to add errors to the returned value later.
+#[cfg(feature = "serde")]
+impl From<serde_json::Value> for ParamValue {
     /// ```
     /// # use not_so_fast::*;
-    /// let errors = ValidationNode::ok();
-    /// assert!(errors.is_ok());
-    /// assert_eq!("", errors.to_string());
+    /// let error = ValidationError::with_code("conflict").and_param("document", serde_json::json!({"id": 1}));
+    /// assert_eq!(r#"conflict: document={"id":1}"#, error.to_string());
     /// ```
-    pub fn ok() -> Self {
-        Self {
-            errors: Default::default(),
-            fields: Default::default(),
-            items: Default::default(),
-        }
+    fn from(value: serde_json::Value) -> Self {
+        Self::Json(value)
     }
+}
 
-    /// Converts `ValidationNode` into `Result<(), ValidationNode>`. It's
-    /// useful when you want to propagate errors with `?` operator or transform
-    /// the error using `Result`'s methods.
-    /// Returns `Ok(())` if `self` has no value errors, no field errors and no
-    /// item errors. Otherwise, returns `Err(self)`.
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for ParamValue {
     /// ```
     /// # use not_so_fast::*;
-    /// let errors_ok = ValidationNode::ok();
-    /// assert!(matches!(errors_ok.result(), Ok(_)));
-    ///
-    /// let errors_bad = ValidationNode::error(ValidationError::with_code("abc"));
-    /// assert!(matches!(errors_bad.result(), Err(_)));
+    /// let id = uuid::Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8);
+    /// let error = ValidationError::with_code("not_found").and_param("id", id);
+    /// assert_eq!("not_found: id=67e55044-10b1-426f-9247-bb680e5fe0c8", error.to_string());
     /// ```
-    pub fn result(self) -> Result<(), Self> {
-        if self.is_ok() {
-            Ok(())
-        } else {
-            Err(self)
-        }
+    fn from(value: uuid::Uuid) -> Self {
+        Self::Uuid(value)
     }
+}
 
-    /// Checks if `ValidationNode` has no value errors, no field errors, and
-    /// no item errors.
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for ParamValue {
     /// ```
     /// # use not_so_fast::*;
-    /// let errors_ok = ValidationNode::ok();
-    /// assert!(errors_ok.is_ok());
-    ///
-    /// let errors_bad = ValidationNode::error(ValidationError::with_code("abc"));
-    /// assert!(!errors_bad.is_ok());
+    /// let price = rust_decimal::Decimal::new(1050, 2);
+    /// let error = ValidationError::with_code("range").and_param("value", price);
+    /// assert_eq!("range: value=10.50", error.to_string());
     /// ```
-    pub fn is_ok(&self) -> bool {
-        self.errors.is_empty() && self.fields.is_empty() && self.items.is_empty()
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Self::Decimal(value)
     }
+}
 
-    /// Checks if `ValidationNode` has at least one value error, field error, or
-    /// item error.
+#[cfg(feature = "bigdecimal")]
+impl From<bigdecimal::BigDecimal> for ParamValue {
     /// ```
     /// # use not_so_fast::*;
-    /// let errors_bad = ValidationNode::error(ValidationError::with_code("abc"));
-    /// assert!(errors_bad.is_err());
-    ///
-    /// let errors_ok = ValidationNode::ok();
-    /// assert!(!errors_ok.is_err());
+    /// let price: bigdecimal::BigDecimal = "10.50".parse().unwrap();
+    /// let error = ValidationError::with_code("range").and_param("value", price);
+    /// assert_eq!("range: value=10.50", error.to_string());
     /// ```
-    pub fn is_err(&self) -> bool {
-        !self.is_ok()
+    fn from(value: bigdecimal::BigDecimal) -> Self {
+        Self::BigDecimal(value)
     }
+}
 
-    /// Recursively adds errors from `other` to `self`.
-    /// ```
-    /// # use not_so_fast::*;
-    /// let errors_a = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("123")));
-    /// let errors_b = ValidationNode::field("b", ValidationNode::error(ValidationError::with_code("456")));
-    /// let errors_c = errors_a.merge(errors_b);
-    /// assert!(errors_c.is_err());
-    /// assert_eq!(".a: 123\n.b: 456", errors_c.to_string());
-    /// ```
-    pub fn merge(mut self, other: Self) -> Self {
-        self.merge_in_place(other);
-        self
+impl From<core::time::Duration> for ParamValue {
+    fn from(value: core::time::Duration) -> Self {
+        Self::Duration(value)
     }
+}
 
-    /// Merges `other` info `self` in-place (through `&mut`).
-    fn merge_in_place(&mut self, other: ValidationNode) {
-        self.errors.extend(other.errors);
-        for (key, value) in other.fields {
-            match self.fields.entry(key) {
-                Entry::Vacant(entry) => {
-                    entry.insert(value);
-                }
-                Entry::Occupied(mut entry) => {
-                    entry.get_mut().merge_in_place(value);
-                }
-            }
-        }
-        for (key, value) in other.items {
-            match self.items.entry(key) {
-                Entry::Vacant(entry) => {
-                    entry.insert(value);
-                }
-                Entry::Occupied(mut entry) => {
-                    entry.get_mut().merge_in_place(value);
-                }
-            }
-        }
+/// Converts a list of values to [ParamValue::List]. Use [ParamValue::Bytes]
+/// explicitly for byte buffers.
+/// ```
+/// # use not_so_fast::*;
+/// let error = ValidationError::with_code("one_of").and_param("allowed", vec!["a", "b"]);
+/// assert_eq!(r#"one_of: allowed=["a", "b"]"#, error.to_string());
+/// ```
+impl<T: Into<ParamValue>> From<Vec<T>> for ParamValue {
+    fn from(values: Vec<T>) -> Self {
+        Self::List(values.into_iter().map(Into::into).collect())
     }
+}
 
-    /// Constructs `ValidationError` with one value error.
-    /// ```
-    /// # use not_so_fast::*;
-    /// let errors = ValidationNode::error(ValidationError::with_code("abc"));
-    /// assert!(errors.is_err());
-    /// assert_eq!(".: abc", errors.to_string());
-    /// ```
-    pub fn error(error: ValidationError) -> Self {
-        Self {
-            errors: vec![error],
-            fields: Default::default(),
-            items: Default::default(),
-        }
+impl<T: Into<ParamValue>, const N: usize> From<[T; N]> for ParamValue {
+    fn from(values: [T; N]) -> Self {
+        Self::List(values.into_iter().map(Into::into).collect())
     }
+}
 
-    /// Adds one value error to `self`.
-    /// ```
-    /// # use not_so_fast::*;
-    /// let errors = ValidationNode::ok().and_error(ValidationError::with_code("abc"));
+/// Converts a map to [ParamValue::Map].
+/// ```
+/// # use not_so_fast::*;
+/// use std::collections::BTreeMap;
+///
+/// let missing = BTreeMap::from([("a", 1), ("b", 2)]);
+/// let error = ValidationError::with_code("required_keys").and_param("missing", missing);
+/// assert_eq!(r#"required_keys: missing={"a": 1, "b": 2}"#, error.to_string());
+/// ```
+impl<K, V> From<BTreeMap<K, V>> for ParamValue
+where
+    K: Into<Cow<'static, str>>,
+    V: Into<ParamValue>,
+{
+    fn from(entries: BTreeMap<K, V>) -> Self {
+        Self::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Container for [ValidationError]s associated with some value. If the value
+/// is an object or a list, field or item ValidationNodes can be attached to
+/// the root node, effectively forming an error tree.
+///
+/// Nodes are equal if they have equal errors, fields and items. Order of
+/// fields set by [ValidationNode::with_order] and
+/// [ValidationNode::with_insertion_order] affects only display, so it's not
+/// compared. Nodes don't implement `Eq`, because error params can be floats.
+#[derive(Debug, Clone)]
+pub struct ValidationNode {
+    /// Errors of the validated value.
+    errors: ErrorList,
+    /// Errors of fields of the validated object.
+    fields: SortedMap<Cow<'static, str>, ValidationNode>,
+    /// Errors of items of the validate list.
+    items: SortedMap<ItemKey, ValidationNode>,
+    /// Position of the node among fields of the parent node. Fields with
+    /// order go first, the rest is sorted by name.
+    order: Option<u32>,
+    /// Assign order to added fields, so that they keep insertion order.
+    insertion_order: bool,
+}
+
+/// Identifies an item of a list. Items are either addressed by their index,
+/// or by a key derived from the item, e.g. its id.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum ItemKey {
+    Index(usize),
+    Key(Cow<'static, str>),
+}
+
+impl ItemKey {
+    /// Compares `self` with `ItemKey::Index(index)`, without constructing it.
+    fn cmp_index(&self, index: usize) -> core::cmp::Ordering {
+        match self {
+            Self::Index(this) => this.cmp(&index),
+            Self::Key(_) => core::cmp::Ordering::Greater,
+        }
+    }
+
+    /// Compares `self` with `ItemKey::Key(key)`, without allocating the key.
+    fn cmp_key(&self, key: &str) -> core::cmp::Ordering {
+        match self {
+            Self::Index(_) => core::cmp::Ordering::Less,
+            Self::Key(this) => this.as_ref().cmp(key),
+        }
+    }
+
+    fn to_path_segment(&self) -> PathSegment {
+        match self {
+            Self::Index(index) => PathSegment::Index(*index),
+            Self::Key(key) => PathSegment::Key(key.clone()),
+        }
+    }
+
+    fn into_path_segment(self) -> PathSegment {
+        match self {
+            Self::Index(index) => PathSegment::Index(index),
+            Self::Key(key) => PathSegment::Key(key),
+        }
+    }
+}
+
+/// Location of an error in a [ValidationNode] tree. Displays as a `jq`-like
+/// path, the same way as in [ValidationNode]'s `Display` output.
+/// ```
+/// # use not_so_fast::*;
+/// let errors = ValidationNode::field("a", ValidationNode::item(3, ValidationNode::error(ValidationError::with_code("abc"))));
+/// let (path, _) = errors.iter().next().unwrap();
+/// assert_eq!(
+///     &[PathSegment::Field("a".into()), PathSegment::Index(3)],
+///     path.segments(),
+/// );
+/// assert_eq!(".a[3]", path.to_string());
+/// ```
+///
+/// Paths can be built segment by segment, or parsed from their textual form.
+/// ```
+/// # use not_so_fast::*;
+/// let path = Path::root().and_field("users").and_key("abc123").and_field("first name");
+/// assert_eq!(r#".users["abc123"]."first name""#, path.to_string());
+/// assert_eq!(path, r#".users["abc123"]."first name""#.parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+/// One step of a [Path].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PathSegment {
+    /// Field of an object.
+    Field(Cow<'static, str>),
+    /// Item of a list identified by index.
+    Index(usize),
+    /// Item of a list identified by key.
+    Key(Cow<'static, str>),
+}
+
+impl Path {
+    /// Creates path pointing at the root node.
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Returns segments of the path, starting at the root node.
+    pub fn segments(&self) -> &[PathSegment] {
+        self.segments.as_slice()
+    }
+
+    /// Checks if the path points at the root node.
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Appends a segment to the path.
+    pub fn push(&mut self, segment: PathSegment) {
+        self.segments.push(segment);
+    }
+
+    /// Removes the last segment of the path and returns it.
+    pub fn pop(&mut self) -> Option<PathSegment> {
+        self.segments.pop()
+    }
+
+    /// Returns the path extended with field `name`.
+    pub fn and_field(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.push(PathSegment::Field(name.into()));
+        self
+    }
+
+    /// Returns the path extended with item `index`.
+    pub fn and_index(mut self, index: usize) -> Self {
+        self.push(PathSegment::Index(index));
+        self
+    }
+
+    /// Returns the path extended with item identified by `key`.
+    pub fn and_key(mut self, key: impl Into<Cow<'static, str>>) -> Self {
+        self.push(PathSegment::Key(key.into()));
+        self
+    }
+
+    /// Checks if `self` points at `other` or at one of its descendants.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let path: Path = ".a.b[1]".parse().unwrap();
+    /// assert!(path.starts_with(&".a".parse().unwrap()));
+    /// assert!(path.starts_with(&Path::root()));
+    /// assert!(!path.starts_with(&".b".parse().unwrap()));
+    /// ```
+    pub fn starts_with(&self, other: &Path) -> bool {
+        self.segments.starts_with(other.segments())
+    }
+}
+
+impl From<Vec<PathSegment>> for Path {
+    fn from(segments: Vec<PathSegment>) -> Self {
+        Self { segments }
+    }
+}
+
+impl FromIterator<PathSegment> for Path {
+    fn from_iter<T: IntoIterator<Item = PathSegment>>(iter: T) -> Self {
+        Self {
+            segments: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl core::str::FromStr for Path {
+    type Err = ParsePathError;
+
+    /// Parses path in the format produced by `Display`, e.g. `.`, `.a.b`,
+    /// `.[2]`, `.a[2]["key"]."quoted name"`.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let path: Path = r#".a[2]["x"]."b c""#.parse().unwrap();
+    /// assert_eq!(
+    ///     &[
+    ///         PathSegment::Field("a".into()),
+    ///         PathSegment::Index(2),
+    ///         PathSegment::Key("x".into()),
+    ///         PathSegment::Field("b c".into()),
+    ///     ],
+    ///     path.segments(),
+    /// );
+    /// assert!(".".parse::<Path>().unwrap().is_root());
+    /// assert!("a.b".parse::<Path>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = PathParser {
+            input: s,
+            position: 0,
+        };
+        let mut path = Path::root();
+
+        parser.expect('.')?;
+        if parser.is_at_end() {
+            return Ok(path);
+        }
+        if parser.peek() == Some('[') {
+            path.push(parser.parse_item()?);
+        } else {
+            path.push(PathSegment::Field(parser.parse_name()?.into()));
+        }
+        while let Some(c) = parser.peek() {
+            match c {
+                '.' => {
+                    parser.expect('.')?;
+                    path.push(PathSegment::Field(parser.parse_name()?.into()));
+                }
+                '[' => path.push(parser.parse_item()?),
+                _ => return Err(parser.error("'.' or '['")),
+            }
+        }
+        Ok(path)
+    }
+}
+
+impl TryFrom<&str> for Path {
+    type Error = ParsePathError;
+
+    /// Parses path like [FromStr](std::str::FromStr) does.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Error returned when parsing [Path] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePathError {
+    position: usize,
+    expected: &'static str,
+}
+
+impl ParsePathError {
+    /// Returns byte offset in the parsed string at which parsing failed.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl core::fmt::Display for ParsePathError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "invalid path: expected {} at position {}",
+            self.expected, self.position
+        )
+    }
+}
+
+impl core::error::Error for ParsePathError {}
+
+struct PathParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl PathParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.position == self.input.len()
+    }
+
+    fn error(&self, expected: &'static str) -> ParsePathError {
+        ParsePathError {
+            position: self.position,
+            expected,
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParsePathError> {
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(match expected {
+                '.' => "'.'",
+                '[' => "'['",
+                ']' => "']'",
+                _ => "'\"'",
+            }))
+        }
+    }
+
+    /// Parses `[123]` or `["key"]`.
+    fn parse_item(&mut self) -> Result<PathSegment, ParsePathError> {
+        self.expect('[')?;
+        let segment = if self.peek() == Some('"') {
+            PathSegment::Key(self.parse_quoted()?.into())
+        } else {
+            let digits = self.take_while(|c| c.is_ascii_digit());
+            PathSegment::Index(digits.parse().map_err(|_| self.error("index"))?)
+        };
+        self.expect(']')?;
+        Ok(segment)
+    }
+
+    /// Parses `name` or `"quoted name"`.
+    fn parse_name(&mut self) -> Result<String, ParsePathError> {
+        if self.peek() == Some('"') {
+            return self.parse_quoted();
+        }
+        let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        if name.is_empty() {
+            return Err(self.error("field name"));
+        }
+        Ok(name.to_owned())
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, ParsePathError> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.position += 1;
+                    return Ok(value);
+                }
+                Some('\\') => {
+                    self.position += 1;
+                    match self.peek() {
+                        Some(c @ ('"' | '\\')) => {
+                            self.position += 1;
+                            value.push(c);
+                        }
+                        _ => return Err(self.error("'\"' or '\\' after '\\'")),
+                    }
+                }
+                Some(c) => {
+                    self.position += c.len_utf8();
+                    value.push(c);
+                }
+                None => return Err(self.error("'\"'")),
+            }
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &str {
+        let start = self.position;
+        while let Some(c) = self.peek().filter(|c| predicate(*c)) {
+            self.position += c.len_utf8();
+        }
+        &self.input[start..self.position]
+    }
+}
+
+impl core::fmt::Display for Path {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let path: Vec<_> = self.segments.iter().map(PathElement::from).collect();
+        fmt_path(path.as_slice(), f)
+    }
+}
+
+impl ValidationNode {
+    /// Creates `ValidationNode` with no value errors, no field errors and no
+    /// item errors. You'll be able to add errors to the returned value later.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok();
+    /// assert!(errors.is_ok());
+    /// assert_eq!("", errors.to_string());
+    /// ```
+    pub fn ok() -> Self {
+        Self {
+            errors: Default::default(),
+            fields: Default::default(),
+            items: Default::default(),
+            order: None,
+            insertion_order: false,
+        }
+    }
+
+    /// Former name of [ValidationNode::ok].
+    #[deprecated(since = "0.3.0", note = "use `ValidationNode::ok`")]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self::ok()
+    }
+
+    /// Converts `ValidationNode` into `Result<(), ValidationNode>`. It's
+    /// useful when you want to propagate errors with `?` operator or transform
+    /// the error using `Result`'s methods.
+    /// Returns `Ok(())` if `self` has no value errors, no field errors and no
+    /// item errors. Otherwise, returns `Err(self)`.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors_ok = ValidationNode::ok();
+    /// assert!(matches!(errors_ok.result(), Ok(_)));
+    ///
+    /// let errors_bad = ValidationNode::error(ValidationError::with_code("abc"));
+    /// assert!(matches!(errors_bad.result(), Err(_)));
+    /// ```
+    pub fn result(self) -> Result<(), Self> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Checks if `ValidationNode` has no value errors, no field errors, and
+    /// no item errors.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors_ok = ValidationNode::ok();
+    /// assert!(errors_ok.is_ok());
+    ///
+    /// let errors_bad = ValidationNode::error(ValidationError::with_code("abc"));
+    /// assert!(!errors_bad.is_ok());
+    /// ```
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty() && self.fields.is_empty() && self.items.is_empty()
+    }
+
+    /// Checks if the tree contains at least one error with
+    /// [Severity::Error]. Unlike [is_err](ValidationNode::is_err), it
+    /// ignores warnings and infos.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("abc").and_severity(Severity::Warning)));
     /// assert!(errors.is_err());
+    /// assert!(!errors.has_errors());
+    /// assert!(errors.and_error(ValidationError::with_code("def")).has_errors());
+    /// ```
+    pub fn has_errors(&self) -> bool {
+        self.has_severity(Severity::Error)
+    }
+
+    /// Checks if the tree contains at least one error with
+    /// [Severity::Warning].
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("abc").and_severity(Severity::Warning)));
+    /// assert!(errors.has_warnings());
+    /// assert!(!ValidationNode::error(ValidationError::with_code("def")).has_warnings());
+    /// ```
+    pub fn has_warnings(&self) -> bool {
+        self.has_severity(Severity::Warning)
+    }
+
+    fn has_severity(&self, severity: Severity) -> bool {
+        self.errors.iter().any(|error| error.severity() == severity)
+            || self
+                .fields
+                .values()
+                .chain(self.items.values())
+                .any(|child| child.has_severity(severity))
+    }
+
+    /// Checks if the tree contains at least one error with tag `tag`.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("abc").and_tag("pii")));
+    /// assert!(errors.has_tag("pii"));
+    /// assert!(!errors.has_tag("security"));
+    /// ```
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.errors.iter().any(|error| error.has_tag(tag))
+            || self
+                .fields
+                .values()
+                .chain(self.items.values())
+                .any(|child| child.has_tag(tag))
+    }
+
+    /// Keeps only errors with tag `tag`. Nodes left without errors are
+    /// removed.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut errors = ValidationNode::error(ValidationError::with_code("abc").and_tag("security"))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("def")));
+    ///
+    /// errors.retain_tag("security");
     /// assert_eq!(".: abc", errors.to_string());
     /// ```
-    pub fn and_error(mut self, error: ValidationError) -> Self {
-        self.errors.push(error);
+    pub fn retain_tag(&mut self, tag: &str) {
+        self.retain(|_path, error| error.has_tag(tag));
+    }
+
+    /// Keeps only errors with severity equal to or greater than `severity`.
+    /// Nodes left without errors are removed.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut errors = ValidationNode::error(ValidationError::with_code("abc").and_severity(Severity::Info))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("def").and_severity(Severity::Warning)));
+    ///
+    /// errors.retain_severity(Severity::Warning);
+    /// assert_eq!(".x: def", errors.to_string());
+    /// ```
+    pub fn retain_severity(&mut self, severity: Severity) {
+        self.retain(|_path, error| error.severity() >= severity);
+    }
+
+    /// Checks if `ValidationNode` has at least one value error, field error, or
+    /// item error.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors_bad = ValidationNode::error(ValidationError::with_code("abc"));
+    /// assert!(errors_bad.is_err());
+    ///
+    /// let errors_ok = ValidationNode::ok();
+    /// assert!(!errors_ok.is_err());
+    /// ```
+    pub fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+
+    /// Recursively adds errors from `other` to `self`.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors_a = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("123")));
+    /// let errors_b = ValidationNode::field("b", ValidationNode::error(ValidationError::with_code("456")));
+    /// let errors_c = errors_a.merge(errors_b);
+    /// assert!(errors_c.is_err());
+    /// assert_eq!(".a: 123\n.b: 456", errors_c.to_string());
+    /// ```
+    pub fn merge(mut self, other: Self) -> Self {
+        self.merge_in_place(other);
         self
     }
 
-    /// Constructs `ValidationNode` with the value error returned by function
-    /// `f` if `condition` is `true`. Otherwise, returns
-    /// `ValidationNode::ok()`. Function `f` will be called at most once.
+    /// Merges all `nodes`, like chained [merge](ValidationNode::merge)
+    /// calls do. Returns `ValidationNode::ok()` if there are no nodes.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let value = 15;
+    /// let errors = ValidationNode::all([
+    ///     ValidationNode::error_if(value % 2 == 1, || ValidationError::with_code("odd")),
+    ///     ValidationNode::error_if(value > 10, || ValidationError::with_code("too_big")),
+    ///     ValidationNode::error_if(value < 0, || ValidationError::with_code("negative")),
+    /// ]);
+    /// assert_eq!(".: odd\n.: too_big", errors.to_string());
+    /// assert!(ValidationNode::all([]).is_ok());
+    /// ```
+    pub fn all(nodes: impl IntoIterator<Item = ValidationNode>) -> Self {
+        let mut node = Self::ok();
+        for other in nodes {
+            node.merge_in_place(other);
+        }
+        node
+    }
+
+    /// Returns `ValidationNode::ok()` if at least one of `alternatives` is
+    /// ok. Otherwise, returns node with a single `any` error, which lists
+    /// errors of every alternative in param `alternatives`. Errors not
+    /// reported at the root of an alternative are prefixed with their path.
+    /// Alternatives are evaluated eagerly; an empty iterator yields the
+    /// `any` error.
+    /// ```
+    /// # use not_so_fast::*;
+    /// fn email(value: &str) -> ValidationNode {
+    ///     ValidationNode::error_if(!value.contains('@'), || ValidationError::with_code("email"))
+    /// }
+    ///
+    /// fn phone(value: &str) -> ValidationNode {
+    ///     ValidationNode::error_if(!value.starts_with('+'), || ValidationError::with_code("phone"))
+    /// }
+    ///
+    /// assert!(ValidationNode::any([email("a@b.c"), phone("a@b.c")]).is_ok());
+    ///
+    /// let errors = ValidationNode::any([email("abc"), phone("abc")]);
+    /// assert_eq!(
+    ///     r#".: any: None of the alternatives is valid: alternatives=[["email"], ["phone"]]"#,
+    ///     errors.to_string(),
+    /// );
+    /// ```
+    pub fn any(alternatives: impl IntoIterator<Item = ValidationNode>) -> Self {
+        let mut errors = Vec::new();
+        for alternative in alternatives {
+            if alternative.is_ok() {
+                return Self::ok();
+            }
+            let alternative_errors: Vec<_> = alternative
+                .iter()
+                .map(|(path, error)| match path.is_root() {
+                    true => ParamValue::String(error.to_string().into()),
+                    false => ParamValue::String(format!("{}: {}", path, error).into()),
+                })
+                .collect();
+            errors.push(ParamValue::List(alternative_errors));
+        }
+        Self::error(
+            ValidationError::with_code(codes::ANY)
+                .and_message("None of the alternatives is valid")
+                .and_param("alternatives", ParamValue::List(errors)),
+        )
+    }
+
+    /// Inverts `node`: returns node with the error returned by `f` if `node`
+    /// is ok, and `ValidationNode::ok()` otherwise. Errors of `node` are
+    /// discarded. Function `f` will be called at most once.
+    /// ```
+    /// # use not_so_fast::*;
+    /// fn digits(value: &str) -> ValidationNode {
+    ///     ValidationNode::error_if(!value.chars().all(|c| c.is_ascii_digit()), || ValidationError::with_code("digits"))
+    /// }
+    ///
+    /// let errors = ValidationNode::not(digits("123"), || ValidationError::with_code("not_digits"));
+    /// assert_eq!(".: not_digits", errors.to_string());
+    ///
+    /// let errors = ValidationNode::not(digits("abc"), || ValidationError::with_code("not_digits"));
+    /// assert!(errors.is_ok());
+    /// ```
+    pub fn not(node: ValidationNode, f: impl FnOnce() -> ValidationError) -> Self {
+        Self::error_if(node.is_ok(), f)
+    }
+
+    /// Merges `other` info `self` in-place (through `&mut`).
+    fn merge_in_place(&mut self, other: ValidationNode) {
+        self.order = match (self.order, other.order) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.errors.extend(other.errors);
+        for (key, value) in Self::into_ordered_fields(other.fields) {
+            self.insert_field(key, value);
+        }
+        for (key, value) in other.items {
+            match self.items.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+                Entry::Occupied(mut entry) => {
+                    entry.get_mut().merge_in_place(value);
+                }
+            }
+        }
+    }
+
+    /// Sets position of `self` among fields of the parent node. By default,
+    /// fields are sorted by name. Fields with order go before fields without
+    /// it, in ascending order. If two merged nodes have order, the lower one
+    /// is preserved.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok()
+    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("1")))
+    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("2")).with_order(1))
+    ///     .and_field("c", ValidationNode::error(ValidationError::with_code("3")).with_order(0));
+    /// assert_eq!(".c: 3\n.b: 2\n.a: 1", errors.to_string());
+    /// ```
+    pub fn with_order(mut self, order: u32) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Makes fields added to `self` keep insertion order instead of being
+    /// sorted by name, by setting their order. Existing fields without order
+    /// keep their current position. Only fields of `self` are affected,
+    /// not fields of nested nodes. Use `declaration_order` attribute of the
+    /// derive macro to order fields by declaration.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok()
+    ///     .with_insertion_order()
+    ///     .and_field("c", ValidationNode::error(ValidationError::with_code("1")))
+    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("2")))
+    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("3")))
+    ///     .and_field("c", ValidationNode::error(ValidationError::with_code("4")));
+    /// assert_eq!(".c: 1\n.c: 4\n.a: 2\n.b: 3", errors.to_string());
+    /// ```
+    pub fn with_insertion_order(mut self) -> Self {
+        if !self.insertion_order {
+            self.insertion_order = true;
+            // Fields without order are displayed after the ordered ones,
+            // sorted by name, like they are stored.
+            let mut next_order = self.next_field_order();
+            for field in self.fields.values_mut() {
+                if field.order.is_none() {
+                    field.order = Some(next_order);
+                    next_order += 1;
+                }
+            }
+        }
+        self
+    }
+
+    /// Returns field nodes in display order, consuming the fields.
+    fn into_ordered_fields(
+        fields: SortedMap<Cow<'static, str>, ValidationNode>,
+    ) -> Vec<(Cow<'static, str>, ValidationNode)> {
+        let mut fields: Vec<_> = fields.into_iter().collect();
+        // Same ordering as in `ordered_fields`.
+        fields.sort_by_key(|(_, field)| (field.order.is_none(), field.order));
+        fields
+    }
+
+    /// Returns field nodes in display order.
+    fn ordered_fields(&self) -> OrderedFields<'_> {
+        if self.fields.values().all(|field| field.order.is_none()) {
+            OrderedFields::ByName(self.fields.iter())
+        } else {
+            let mut fields: Vec<_> = self.fields.iter().collect();
+            // Stable sort keeps fields with equal order sorted by name.
+            fields.sort_by_key(|(_, field)| (field.order.is_none(), field.order));
+            OrderedFields::ByOrder(fields.into_iter())
+        }
+    }
+
+    /// Constructs `ValidationError` with one value error.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("abc"));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".: abc", errors.to_string());
+    /// ```
+    pub fn error(error: ValidationError) -> Self {
+        Self {
+            errors: ErrorList::one(error),
+            fields: Default::default(),
+            items: Default::default(),
+            order: None,
+            insertion_order: false,
+        }
+    }
+
+    /// Adds one value error to `self`.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok().and_error(ValidationError::with_code("abc"));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".: abc", errors.to_string());
+    /// ```
+    pub fn and_error(mut self, error: ValidationError) -> Self {
+        self.errors.push(error);
+        self
+    }
+
+    /// Constructs `ValidationNode` with the value error returned by function
+    /// `f` if `condition` is `true`. Otherwise, returns
+    /// `ValidationNode::ok()`. Function `f` will be called at most once.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let value = 10;
+    /// let errors = ValidationNode::error_if(value >= 20, || ValidationError::with_code("abc"));
+    /// assert!(errors.is_ok());
+    ///
+    /// let errors = ValidationNode::error_if(value >= 10, || ValidationError::with_code("def"));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".: def", errors.to_string());
+    /// ```
+    pub fn error_if(condition: bool, f: impl FnOnce() -> ValidationError) -> Self {
+        Self {
+            errors: if condition {
+                ErrorList::one(f())
+            } else {
+                Default::default()
+            },
+            fields: Default::default(),
+            items: Default::default(),
+            order: None,
+            insertion_order: false,
+        }
+    }
+
+    /// Adds value error returned by function `f` to `ValidationNode` if
+    /// `condition` is `true`. Otherwise, returns unchanged `self`. Function
+    /// `f` will be called at most once.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let value = 10;
+    /// let errors = ValidationNode::ok().and_error_if(value >= 20, || ValidationError::with_code("abc"));
+    /// assert!(errors.is_ok());
+    ///
+    /// let errors = ValidationNode::ok().and_error_if(value >= 10, || ValidationError::with_code("def"));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".: def", errors.to_string());
+    /// ```
+    pub fn and_error_if(mut self, condition: bool, f: impl FnOnce() -> ValidationError) -> Self {
+        if condition {
+            self.errors.push(f());
+        }
+        self
+    }
+
+    /// Constructs `ValidationNode` from the value error iterator.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let value = 9;
+    ///
+    /// // error if value is divisible by 3, 5, or 15
+    /// let errors_iter = [3, 5, 15]
+    ///     .into_iter()
+    ///     .filter_map(|divisor| (value % divisor == 0).then(|| {
+    ///         ValidationError::with_code("divisible").and_param("by", divisor)
+    ///     }));
+    ///
+    /// let errors = ValidationNode::errors(errors_iter);
+    /// assert!(errors.is_err());
+    /// assert_eq!(".: divisible: by=3", errors.to_string());
+    /// ```
+    pub fn errors(errors: impl Iterator<Item = ValidationError>) -> ValidationNode {
+        Self {
+            errors: errors.collect(),
+            fields: Default::default(),
+            items: Default::default(),
+            order: None,
+            insertion_order: false,
+        }
+    }
+
+    /// Adds value errors from `errors` iterator to `self`.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let value = 9;
+    ///
+    /// // error if value is divisible by 3, 5, or 15
+    /// let errors_iter = [3, 5, 15]
+    ///     .into_iter()
+    ///     .filter_map(|divisor| (value % divisor == 0).then(|| {
+    ///         ValidationError::with_code("divisible").and_param("by", divisor)
+    ///     }));
+    ///
+    /// let errors = ValidationNode::ok().and_errors(errors_iter);
+    /// assert!(errors.is_err());
+    /// assert_eq!(".: divisible: by=3", errors.to_string());
+    /// ```
+    pub fn and_errors(mut self, errors: impl Iterator<Item = ValidationError>) -> ValidationNode {
+        self.errors.extend(errors);
+        self
+    }
+
+    /// Constructs `ValidationNode` with errors of one field. If
+    /// `validation_errors` is ok, the function also returns an ok node.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("a", ValidationNode::ok());
+    /// assert!(errors.is_ok());
+    ///
+    /// let errors = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("abc")));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".a: abc", errors.to_string());
+    /// ```
+    pub fn field(name: impl Into<Cow<'static, str>>, validation_errors: ValidationNode) -> Self {
+        Self {
+            errors: Default::default(),
+            fields: if !validation_errors.is_ok() {
+                count_added_errors(validation_errors.errors.len());
+                let mut fields = SortedMap::new();
+                fields.insert(name.into(), validation_errors);
+                fields
+            } else {
+                Default::default()
+            },
+            items: Default::default(),
+            order: None,
+            insertion_order: false,
+        }
+    }
+
+    /// Adds errors of one field to self. If self already contains errors for
+    /// that field, the errors will be merged. If `validation_errors` is ok,
+    /// the function will return self unchanged.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok().and_field("a", ValidationNode::ok());
+    /// assert!(errors.is_ok());
+    ///
+    /// let errors = ValidationNode::ok().and_field("a", ValidationNode::error(ValidationError::with_code("abc")));
+    /// assert!(errors.is_err());
+    ///
+    /// let errors = ValidationNode::ok()
+    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("def")))
+    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("ghi")));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".a: abc\n.a: def\n.b: ghi", errors.to_string());
+    /// ```
+    pub fn and_field(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        validation_errors: ValidationNode,
+    ) -> Self {
+        if !validation_errors.is_ok() {
+            count_added_errors(validation_errors.errors.len());
+            self.insert_field(name.into(), validation_errors);
+        }
+        self
+    }
+
+    /// Adds `node` as field `name`, merging it with the existing field.
+    fn insert_field(&mut self, name: Cow<'static, str>, mut node: ValidationNode) {
+        let next_order = self.insertion_order.then(|| self.next_field_order());
+        match self.fields.entry(name) {
+            Entry::Vacant(entry) => {
+                node.order = node.order.or(next_order);
+                entry.insert(node);
+            }
+            Entry::Occupied(mut entry) => entry.get_mut().merge_in_place(node),
+        }
+    }
+
+    /// Returns order following orders of all fields.
+    fn next_field_order(&self) -> u32 {
+        self.fields
+            .values()
+            .filter_map(|field| field.order)
+            .max()
+            .map_or(0, |order| order + 1)
+    }
+
+    /// Collects field errors from an iterator to (key, value) pairs and a
+    /// function transforming key and value references into validation errors.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let map: std::collections::HashMap<String, u32> = [
+    ///     ("one".into(), 1),
+    ///     ("two".into(), 2),
+    ///     ("three".into(), 3),
+    /// ].into_iter().collect();
+    /// let errors = ValidationNode::fields(map.iter(), |_key, value| {
+    ///     ValidationNode::error_if(*value > 2, || ValidationError::with_code("abc"))
+    /// });
+    /// assert!(errors.is_err());
+    /// assert_eq!(".three: abc", errors.to_string());
+    /// ```
+    pub fn fields<'a, K, V: 'a>(
+        iterator: impl Iterator<Item = (&'a K, &'a V)>,
+        f: impl FnMut(&'a K, &'a V) -> ValidationNode,
+    ) -> Self
+    where
+        // The requirement for K here is not `impl Into<Cow<_, str>>` like in
+        // `field` or `and_field`. That's because this function is meant to be
+        // used with dynamic objects, like `HashMap`, whose keys might not
+        // implement `Into<Cow<_, str>>` (think i32, uuid::Uuid, etc.).
+        K: ToString + 'a,
+    {
+        Self::fields_with_key_display(iterator, K::to_string, f)
+    }
+
+    /// Collects field errors like [fields](ValidationNode::fields), but
+    /// builds field names with `key_display` function instead of `ToString`.
+    /// It's useful when keys should not appear in the error output verbatim,
+    /// e.g. because they contain secrets.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let map: std::collections::HashMap<String, u32> = [
+    ///     ("secret-token-123".into(), 3),
+    /// ].into_iter().collect();
+    /// let errors = ValidationNode::fields_with_key_display(
+    ///     map.iter(),
+    ///     |key| format!("{}***", &key[..6]),
+    ///     |_key, value| {
+    ///         ValidationNode::error_if(*value > 2, || ValidationError::with_code("abc"))
+    ///     },
+    /// );
+    /// assert!(errors.is_err());
+    /// assert_eq!(".\"secret***\": abc", errors.to_string());
+    /// ```
+    pub fn fields_with_key_display<'a, K: 'a, V: 'a>(
+        iterator: impl Iterator<Item = (&'a K, &'a V)>,
+        mut key_display: impl FnMut(&'a K) -> String,
+        mut f: impl FnMut(&'a K, &'a V) -> ValidationNode,
+    ) -> Self {
+        iterator.fold(ValidationNode::ok(), |acc, (key, value)| {
+            let validation_errors = f(key, value);
+
+            // Generate key string only if value has errors.
+            if !validation_errors.is_ok() {
+                let key_owned = Cow::Owned(key_display(key));
+                acc.and_field(key_owned, validation_errors)
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Adds field errors collected the same way as in
+    /// [fields](ValidationNode::fields) method to self.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let map: std::collections::HashMap<String, u32> = [
+    ///     ("one".into(), 1),
+    ///     ("two".into(), 2),
+    ///     ("three".into(), 3),
+    /// ].into_iter().collect();
+    /// let errors = ValidationNode::ok().and_fields(map.iter(), |_key, value| {
+    ///     ValidationNode::error_if(*value > 2, || ValidationError::with_code("abc"))
+    /// });
+    /// assert!(errors.is_err());
+    /// assert_eq!(".three: abc", errors.to_string());
+    /// ```
+    pub fn and_fields<'a, K, V: 'a>(
+        self,
+        iterator: impl Iterator<Item = (&'a K, &'a V)>,
+        f: impl FnMut(&'a K, &'a V) -> ValidationNode,
+    ) -> Self
+    where
+        K: ToString + 'a,
+    {
+        self.merge(Self::fields(iterator, f))
+    }
+
+    /// Adds field errors collected the same way as in
+    /// [fields_with_key_display](ValidationNode::fields_with_key_display)
+    /// method to self.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let map: std::collections::HashMap<String, u32> = [
+    ///     ("secret-token-123".into(), 3),
+    /// ].into_iter().collect();
+    /// let errors = ValidationNode::ok().and_fields_with_key_display(
+    ///     map.iter(),
+    ///     |_key| "<redacted>".into(),
+    ///     |_key, value| {
+    ///         ValidationNode::error_if(*value > 2, || ValidationError::with_code("abc"))
+    ///     },
+    /// );
+    /// assert!(errors.is_err());
+    /// assert_eq!(".\"<redacted>\": abc", errors.to_string());
+    /// ```
+    pub fn and_fields_with_key_display<'a, K: 'a, V: 'a>(
+        self,
+        iterator: impl Iterator<Item = (&'a K, &'a V)>,
+        key_display: impl FnMut(&'a K) -> String,
+        f: impl FnMut(&'a K, &'a V) -> ValidationNode,
+    ) -> Self {
+        self.merge(Self::fields_with_key_display(iterator, key_display, f))
+    }
+
+    /// Constructs `ValidationNode` with errors of one item. If
+    /// `validation_errors` is ok, the function also returns an ok node.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::item(5, ValidationNode::ok());
+    /// assert!(errors.is_ok());
+    ///
+    /// let errors = ValidationNode::item(5, ValidationNode::error(ValidationError::with_code("abc")));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".[5]: abc", errors.to_string());
+    /// ```
+    pub fn item(index: usize, validation_errors: ValidationNode) -> Self {
+        Self::ok().and_item_key(ItemKey::Index(index), validation_errors)
+    }
+
+    /// Adds errors of one item to self. If self already contains errors for
+    /// that item, the errors will be merged. If `validation_errors` is ok,
+    /// the function will return self unchanged.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok().and_item(5, ValidationNode::ok());
+    /// assert!(errors.is_ok());
+    ///
+    /// let errors = ValidationNode::ok().and_item(5, ValidationNode::error(ValidationError::with_code("abc")));
+    /// assert!(errors.is_err());
+    ///
+    /// let errors = ValidationNode::ok()
+    ///     .and_item(5, ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_item(5, ValidationNode::error(ValidationError::with_code("def")))
+    ///     .and_item(8, ValidationNode::error(ValidationError::with_code("ghi")));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".[5]: abc\n.[5]: def\n.[8]: ghi", errors.to_string());
+    /// ```
+    pub fn and_item(self, index: usize, validation_errors: ValidationNode) -> Self {
+        self.and_item_key(ItemKey::Index(index), validation_errors)
+    }
+
+    /// Constructs `ValidationNode` with errors of one item identified by
+    /// `key` instead of index. If `validation_errors` is ok, the function
+    /// also returns an ok node.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::keyed_item("abc123", ValidationNode::ok());
+    /// assert!(errors.is_ok());
+    ///
+    /// let errors = ValidationNode::keyed_item("abc123", ValidationNode::error(ValidationError::with_code("abc")));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".[\"abc123\"]: abc", errors.to_string());
+    /// ```
+    pub fn keyed_item(
+        key: impl Into<Cow<'static, str>>,
+        validation_errors: ValidationNode,
+    ) -> Self {
+        Self::ok().and_item_key(ItemKey::Key(key.into()), validation_errors)
+    }
+
+    /// Adds errors of one item identified by `key` to self. If self already
+    /// contains errors for that item, the errors will be merged. If
+    /// `validation_errors` is ok, the function will return self unchanged.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok()
+    ///     .and_keyed_item("b", ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_keyed_item("a", ValidationNode::error(ValidationError::with_code("def")))
+    ///     .and_item(8, ValidationNode::error(ValidationError::with_code("ghi")));
+    /// assert!(errors.is_err());
+    /// assert_eq!(".[8]: ghi\n.[\"a\"]: def\n.[\"b\"]: abc", errors.to_string());
+    /// ```
+    pub fn and_keyed_item(
+        self,
+        key: impl Into<Cow<'static, str>>,
+        validation_errors: ValidationNode,
+    ) -> Self {
+        self.and_item_key(ItemKey::Key(key.into()), validation_errors)
+    }
+
+    fn and_item_key(mut self, key: ItemKey, validation_errors: ValidationNode) -> Self {
+        if !validation_errors.is_ok() {
+            count_added_errors(validation_errors.errors.len());
+            match self.items.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(validation_errors);
+                }
+                Entry::Occupied(mut entry) => entry.get_mut().merge_in_place(validation_errors),
+            }
+        }
+        self
+    }
+
+    /// Collects item errors from an iterator to (index, value) pairs and a
+    /// function transforming index and value references into validation
+    /// errors.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let list: Vec<u32> = vec![10, 20, 30];
+    ///
+    /// let errors = ValidationNode::items(list.iter(), |_index, value| {
+    ///     ValidationNode::error_if(*value > 25, || ValidationError::with_code("abc"))
+    /// });
+    /// assert!(errors.is_err());
+    /// assert_eq!(".[2]: abc", errors.to_string());
+    /// ```
+    pub fn items<'a, T: 'a>(
+        items: impl Iterator<Item = &'a T>,
+        mut f: impl FnMut(usize, &'a T) -> ValidationNode,
+    ) -> Self {
+        items
+            .enumerate()
+            .fold(ValidationNode::ok(), |acc, (index, item)| {
+                acc.and_item(index, f(index, item))
+            })
+    }
+
+    /// Adds item errors collected the same way as in
+    /// [items](ValidationNode::items) method to self.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let list = vec![10, 20, 30];
+    ///
+    /// let errors = ValidationNode::ok().and_items(list.iter(), |_index, value| {
+    ///     ValidationNode::error_if(*value > 25, || ValidationError::with_code("abc"))
+    /// });
+    /// assert!(errors.is_err());
+    /// assert_eq!(".[2]: abc", errors.to_string());
+    /// ```
+    pub fn and_items<'a, T: 'a>(
+        self,
+        items: impl Iterator<Item = &'a T>,
+        f: impl FnMut(usize, &'a T) -> ValidationNode,
+    ) -> Self {
+        self.merge(Self::items(items, f))
+    }
+
+    /// Collects item errors like [items](ValidationNode::items), but
+    /// validates items of an indexed parallel iterator on the rayon thread
+    /// pool. Item nodes are merged in index order, so the result is the same
+    /// as in sequential validation. Inside [ValidationLimits::run], whose
+    /// limits are tracked per thread, items are validated on the current
+    /// thread. Requires the `rayon` feature.
+    /// ```
+    /// # use not_so_fast::*;
+    /// use rayon::prelude::*;
+    ///
+    /// let list: Vec<u32> = (0..1000).collect();
+    ///
+    /// let errors = ValidationNode::par_items(list.par_iter(), |_index, value| {
+    ///     ValidationNode::error_if(*value % 400 == 399, || ValidationError::with_code("abc"))
+    /// });
+    /// assert_eq!(".[399]: abc\n.[799]: abc", errors.to_string());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_items<'a, T: Sync + 'a>(
+        items: impl rayon::iter::IndexedParallelIterator<Item = &'a T>,
+        f: impl Fn(usize, &'a T) -> ValidationNode + Sync + Send,
+    ) -> Self {
+        use rayon::iter::ParallelIterator;
+
+        if LIMITS.with(|limits| limits.borrow().is_some()) {
+            let items: Vec<&'a T> = items.collect();
+            return Self::items(items.into_iter(), f);
+        }
+
+        let nodes: Vec<(usize, ValidationNode)> = items
+            .enumerate()
+            .map(|(index, item)| (index, f(index, item)))
+            .filter(|(_, node)| !node.is_ok())
+            .collect();
+        nodes
+            .into_iter()
+            .fold(ValidationNode::ok(), |acc, (index, node)| {
+                acc.and_item(index, node)
+            })
+    }
+
+    /// Collects item errors like [items](ValidationNode::items), but
+    /// identifies items with keys returned by function `key` instead of
+    /// indexes. Keys are generated only for items with errors.
+    /// ```
+    /// # use not_so_fast::*;
+    /// struct User {
+    ///     id: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let users = vec![
+    ///     User { id: "abc".into(), age: 20 },
+    ///     User { id: "def".into(), age: 200 },
+    /// ];
+    ///
+    /// let errors = ValidationNode::keyed_items(users.iter(), |user| &user.id, |_index, user| {
+    ///     ValidationNode::error_if(user.age > 150, || ValidationError::with_code("age"))
+    /// });
+    /// assert!(errors.is_err());
+    /// assert_eq!(".[\"def\"]: age", errors.to_string());
+    /// ```
+    pub fn keyed_items<'a, T: 'a, K>(
+        items: impl Iterator<Item = &'a T>,
+        mut key: impl FnMut(&'a T) -> K,
+        mut f: impl FnMut(usize, &'a T) -> ValidationNode,
+    ) -> Self
+    where
+        K: ToString,
+    {
+        items
+            .enumerate()
+            .fold(ValidationNode::ok(), |acc, (index, item)| {
+                let validation_errors = f(index, item);
+
+                // Generate key string only if item has errors.
+                if !validation_errors.is_ok() {
+                    acc.and_keyed_item(key(item).to_string(), validation_errors)
+                } else {
+                    acc
+                }
+            })
+    }
+
+    /// Adds item errors collected the same way as in
+    /// [keyed_items](ValidationNode::keyed_items) method to self.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let list = vec![10, 20, 30];
+    ///
+    /// let errors = ValidationNode::ok().and_keyed_items(list.iter(), |value| *value, |_index, value| {
+    ///     ValidationNode::error_if(*value > 25, || ValidationError::with_code("abc"))
+    /// });
+    /// assert!(errors.is_err());
+    /// assert_eq!(".[\"30\"]: abc", errors.to_string());
+    /// ```
+    pub fn and_keyed_items<'a, T: 'a, K>(
+        self,
+        items: impl Iterator<Item = &'a T>,
+        key: impl FnMut(&'a T) -> K,
+        f: impl FnMut(usize, &'a T) -> ValidationNode,
+    ) -> Self
+    where
+        K: ToString,
+    {
+        self.merge(Self::keyed_items(items, key, f))
+    }
+
+    /// Returns [ValidationNode] with only the first error in display order,
+    /// or an ok node it there are no errors.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok()
+    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("1")))
+    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("2")))
+    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("3")));
+    /// assert_eq!(".a: 1\n.a: 2\n.b: 3", errors.to_string());
+    ///
+    /// let first = errors.first();
+    /// assert_eq!(".a: 1", first.to_string());
+    /// ```
+    pub fn first(mut self) -> Self {
+        if !self.errors.is_empty() {
+            Self {
+                errors: ErrorList::one(self.errors.remove(0)),
+                fields: Default::default(),
+                items: Default::default(),
+                order: self.order,
+                insertion_order: self.insertion_order,
+            }
+        } else if !self.fields.is_empty() {
+            Self {
+                errors: Default::default(),
+                fields: Self::into_ordered_fields(self.fields)
+                    .into_iter()
+                    .take(1)
+                    .map(|(key, errors)| (key, errors.first()))
+                    .collect(),
+                items: Default::default(),
+                order: self.order,
+                insertion_order: self.insertion_order,
+            }
+        } else if !self.items.is_empty() {
+            Self {
+                errors: Default::default(),
+                fields: Default::default(),
+                items: self
+                    .items
+                    .into_iter()
+                    .take(1)
+                    .map(|(index, errors)| (index, errors.first()))
+                    .collect(),
+                order: self.order,
+                insertion_order: self.insertion_order,
+            }
+        } else {
+            Self::ok()
+        }
+    }
+
+    /// Returns the first error of the tree, in `Display` order, and its
+    /// path. Unlike [first](ValidationNode::first), it doesn't consume the
+    /// node.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok()
+    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("1")))
+    ///     .and_field("a", ValidationNode::item(3, ValidationNode::error(ValidationError::with_code("2"))));
+    ///
+    /// let (path, error) = errors.first_error().unwrap();
+    /// assert_eq!(".a[3]", path.to_string());
+    /// assert_eq!("2", error.code());
+    /// assert!(ValidationNode::ok().first_error().is_none());
+    /// ```
+    pub fn first_error(&self) -> Option<(Path, &ValidationError)> {
+        let mut path = Path::root();
+        let error = self.first_error_in(&mut path)?;
+        Some((path, error))
+    }
+
+    fn first_error_in(&self, path: &mut Path) -> Option<&ValidationError> {
+        if let Some(error) = self.errors.first() {
+            return Some(error);
+        }
+        if let Some((name, field)) = self.ordered_fields().next() {
+            path.push(PathSegment::Field(name.clone()));
+            return field.first_error_in(path);
+        }
+        if let Some((key, item)) = self.items.iter().next() {
+            path.push(key.to_path_segment());
+            return item.first_error_in(path);
+        }
+        None
+    }
+
+    /// Returns errors attached directly to this node, without errors of
+    /// fields and items. Named differently than the
+    /// [errors](ValidationNode::errors) constructor.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("abc"))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("def")));
+    /// assert_eq!(1, errors.direct_errors().len());
+    /// assert_eq!("abc", errors.direct_errors()[0].code());
+    /// ```
+    pub fn direct_errors(&self) -> &[ValidationError] {
+        &self.errors
+    }
+
+    /// Returns node of field `name`, if the field has errors.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("abc")));
+    /// assert!(errors.get_field("x").is_some());
+    /// assert!(errors.get_field("y").is_none());
+    /// ```
+    pub fn get_field(&self, name: &str) -> Option<&ValidationNode> {
+        self.fields.get(name)
+    }
+
+    /// Returns node of item at `index`, if the item has errors.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::item(3, ValidationNode::error(ValidationError::with_code("abc")));
+    /// assert!(errors.get_item(3).is_some());
+    /// assert!(errors.get_item(0).is_none());
+    /// ```
+    pub fn get_item(&self, index: usize) -> Option<&ValidationNode> {
+        self.items.get(&ItemKey::Index(index))
+    }
+
+    /// Returns node of item identified by `key`, if the item has errors.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::keyed_item("abc123", ValidationNode::error(ValidationError::with_code("abc")));
+    /// assert!(errors.get_keyed_item("abc123").is_some());
+    /// assert!(errors.get_keyed_item("def456").is_none());
+    /// ```
+    pub fn get_keyed_item(&self, key: &str) -> Option<&ValidationNode> {
+        self.items.get_by(|item_key| item_key.cmp_key(key))
+    }
+
+    /// Returns an iterator over fields with errors and their nodes, in the
+    /// same order as in the `Display` output.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok()
+    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("def")));
+    /// let names: Vec<_> = errors.field_nodes().map(|(name, _)| name).collect();
+    /// assert_eq!(vec!["a", "b"], names);
+    /// ```
+    pub fn field_nodes(&self) -> impl Iterator<Item = (&str, &ValidationNode)> + '_ {
+        self.ordered_fields()
+            .map(|(name, node)| (name.as_ref(), node))
+    }
+
+    /// Returns an iterator over items with errors and their nodes. Items
+    /// are identified by [PathSegment::Index] or [PathSegment::Key].
+    /// Indexed items go first.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok()
+    ///     .and_keyed_item("abc123", ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_item(4, ValidationNode::error(ValidationError::with_code("def")));
+    /// let items: Vec<_> = errors.item_nodes().map(|(segment, _)| segment).collect();
+    /// assert_eq!(vec![PathSegment::Index(4), PathSegment::Key("abc123".into())], items);
+    /// ```
+    pub fn item_nodes(&self) -> impl Iterator<Item = (PathSegment, &ValidationNode)> + '_ {
+        self.items
+            .iter()
+            .map(|(key, node)| (key.to_path_segment(), node))
+    }
+
+    /// Counts all errors in the tree.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("abc"))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("def")).and_error(ValidationError::with_code("ghi")))
+    ///     .and_item(1, ValidationNode::error(ValidationError::with_code("jkl")));
+    /// assert_eq!(4, errors.error_count());
+    /// assert_eq!(0, ValidationNode::ok().error_count());
+    /// ```
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+            + self.fields.values().map(Self::error_count).sum::<usize>()
+            + self.items.values().map(Self::error_count).sum::<usize>()
+    }
+
+    /// Counts leaf nodes of the tree, that is nodes with errors, but without
+    /// fields or items. An ok node has no leaves.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("abc"))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("def")).and_error(ValidationError::with_code("ghi")))
+    ///     .and_item(1, ValidationNode::error(ValidationError::with_code("jkl")));
+    /// assert_eq!(2, errors.leaf_count());
+    /// assert_eq!(0, ValidationNode::ok().leaf_count());
+    /// ```
+    pub fn leaf_count(&self) -> usize {
+        if self.fields.is_empty() && self.items.is_empty() {
+            usize::from(!self.errors.is_empty())
+        } else {
+            self.fields.values().map(Self::leaf_count).sum::<usize>()
+                + self.items.values().map(Self::leaf_count).sum::<usize>()
+        }
+    }
+
+    /// Returns length of the longest path in the tree. Nodes with errors only
+    /// at the root, as well as ok nodes, have depth 0.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("abc"))
+    ///     .and_field("x", ValidationNode::item(2, ValidationNode::error(ValidationError::with_code("def"))));
+    /// assert_eq!(2, errors.depth());
+    /// assert_eq!(0, ValidationNode::error(ValidationError::with_code("abc")).depth());
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.fields
+            .values()
+            .chain(self.items.values())
+            .map(|child| child.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns an iterator over all errors in the tree, paired with their
+    /// paths. Errors are visited in the same order as in the `Display`
+    /// output.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("abc"))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("def")))
+    ///     .and_item(1, ValidationNode::error(ValidationError::with_code("ghi")));
+    ///
+    /// let pairs: Vec<_> = errors
+    ///     .iter()
+    ///     .map(|(path, error)| (path.to_string(), error.code()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     vec![
+    ///         (".".to_string(), "abc"),
+    ///         (".x".to_string(), "def"),
+    ///         (".[1]".to_string(), "ghi"),
+    ///     ],
+    ///     pairs,
+    /// );
+    /// ```
+    pub fn iter(&self) -> Iter<'_> {
+        let mut errors = Vec::new();
+        collect_errors(self, &mut Path::root(), &mut errors);
+        Iter(errors.into_iter())
+    }
+
+    /// Groups errors of the tree by path, consuming the tree. Paths are
+    /// rendered like in the `Display` output, but without the leading dot,
+    /// e.g. `address.city` or `cars[2]`. Errors of the root node are stored
+    /// under an empty string.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("abc"))
+    ///     .and_field("x", ValidationNode::item(2, ValidationNode::error(ValidationError::with_code("def")).and_error(ValidationError::with_code("ghi"))));
+    ///
+    /// let map = errors.flatten();
+    /// assert_eq!(vec!["", "x[2]"], map.keys().collect::<Vec<_>>());
+    /// assert_eq!(2, map["x[2]"].len());
+    /// ```
+    pub fn flatten(self) -> BTreeMap<String, Vec<ValidationError>> {
+        let mut map = BTreeMap::<String, Vec<ValidationError>>::new();
+        for (path, error) in self {
+            map.entry(flat_path(&path)).or_default().push(error);
+        }
+        map
+    }
+
+    /// Groups references to errors of the tree by path. Paths are formatted
+    /// the same way as in [flatten](ValidationNode::flatten).
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("x", ValidationNode::field("y z", ValidationNode::error(ValidationError::with_code("abc"))));
+    ///
+    /// let map = errors.flatten_ref();
+    /// assert_eq!("abc", map["x.\"y z\""][0].code());
+    /// ```
+    pub fn flatten_ref(&self) -> BTreeMap<String, Vec<&ValidationError>> {
+        let mut map = BTreeMap::<String, Vec<&ValidationError>>::new();
+        for (path, error) in self {
+            map.entry(flat_path(&path)).or_default().push(error);
+        }
+        map
+    }
+}
+
+impl ValidationNode {
+    /// Replaces every error in the tree with the result of function `f`,
+    /// which receives the error's path and the error itself.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("abc"))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("def")));
+    ///
+    /// let errors = errors.map_errors(|path, error| {
+    ///     if path.is_root() {
+    ///         error.and_message("Root error")
+    ///     } else {
+    ///         error
+    ///     }
+    /// });
+    /// assert_eq!(".: abc: Root error\n.x: def", errors.to_string());
+    /// ```
+    pub fn map_errors(
+        mut self,
+        mut f: impl FnMut(&Path, ValidationError) -> ValidationError,
+    ) -> Self {
+        self.map_errors_in_place(&mut Path::root(), &mut f);
+        self
+    }
+
+    fn map_errors_in_place(
+        &mut self,
+        path: &mut Path,
+        f: &mut impl FnMut(&Path, ValidationError) -> ValidationError,
+    ) {
+        self.errors = core::mem::take(&mut self.errors)
+            .into_iter()
+            .map(|error| f(path, error))
+            .collect();
+        for (name, field) in self.fields.iter_mut() {
+            path.push(PathSegment::Field(name.clone()));
+            field.map_errors_in_place(path, f);
+            path.pop();
+        }
+        for (key, item) in self.items.iter_mut() {
+            path.push(key.to_path_segment());
+            item.map_errors_in_place(path, f);
+            path.pop();
+        }
+    }
+
+    /// Returns node at `path`, if the path has errors. An empty path points
+    /// at self.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("x", ValidationNode::item(2, ValidationNode::error(ValidationError::with_code("abc"))));
+    ///
+    /// assert!(errors.at(&".x[2]".parse().unwrap()).is_some());
+    /// assert!(errors.at(&".x[3]".parse().unwrap()).is_none());
+    /// assert_eq!(Some(&errors), errors.at(&Path::root()));
+    /// ```
+    pub fn at(&self, path: &Path) -> Option<&ValidationNode> {
+        path.segments()
+            .iter()
+            .try_fold(self, |node, segment| node.child(segment))
+    }
+
+    /// Removes node at `path` from the tree and returns it. Nodes left
+    /// without errors after the removal are removed too. An empty path
+    /// takes all errors of self.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut errors = ValidationNode::field("x", ValidationNode::item(2, ValidationNode::error(ValidationError::with_code("abc"))))
+    ///     .and_field("y", ValidationNode::error(ValidationError::with_code("def")));
+    ///
+    /// let taken = errors.take_at(&".x[2]".parse().unwrap()).unwrap();
+    /// assert_eq!(".: abc", taken.to_string());
+    /// assert_eq!(".y: def", errors.to_string());
+    /// ```
+    pub fn take_at(&mut self, path: &Path) -> Option<ValidationNode> {
+        match path.segments().split_first() {
+            None if self.is_err() => Some(core::mem::replace(self, ValidationNode::ok())),
+            None => None,
+            Some((segment, rest)) => self.take_at_segments(segment, rest),
+        }
+    }
+
+    /// Removes node at `path` from the tree, like
+    /// [take_at](ValidationNode::take_at) does, and returns whether
+    /// anything was removed.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_field("y", ValidationNode::error(ValidationError::with_code("def")));
+    ///
+    /// assert!(errors.remove_at(&".x".parse().unwrap()));
+    /// assert!(!errors.remove_at(&".x".parse().unwrap()));
+    /// assert_eq!(".y: def", errors.to_string());
+    /// ```
+    pub fn remove_at(&mut self, path: &Path) -> bool {
+        self.take_at(path).is_some()
+    }
+
+    fn take_at_segments(
+        &mut self,
+        segment: &PathSegment,
+        rest: &[PathSegment],
+    ) -> Option<ValidationNode> {
+        match rest.split_first() {
+            None => match segment {
+                PathSegment::Field(name) => self.fields.remove(name.as_ref()),
+                PathSegment::Index(index) => self.items.remove_by(|key| key.cmp_index(*index)),
+                PathSegment::Key(name) => self.items.remove_by(|key| key.cmp_key(name)),
+            },
+            Some((next, rest)) => {
+                let child = self.child_mut(segment)?;
+                let taken = child.take_at_segments(next, rest);
+                if child.is_ok() {
+                    self.take_at_segments(segment, &[]);
+                }
+                taken
+            }
+        }
+    }
+
+    /// Adds `error` to the node at `path`, creating intermediate field and
+    /// item nodes. Path can be a [Path] or a string in the format accepted
+    /// by [Path]'s `FromStr`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is a string that is not a valid path. Pass only path
+    /// literals as strings, and parse paths coming from input with
+    /// [try_and_at](ValidationNode::try_and_at).
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok()
+    ///     .and_at(".settings.notifications[2].email", ValidationError::with_code("email"))
+    ///     .and_at(Path::root().and_field("settings"), ValidationError::with_code("abc"))
+    ///     .and_at(".", ValidationError::with_code("def"));
+    ///
+    /// assert_eq!(
+    ///     ".: def\n.settings: abc\n.settings.notifications[2].email: email",
+    ///     errors.to_string(),
+    /// );
+    /// ```
+    pub fn and_at<P>(mut self, path: P, error: ValidationError) -> Self
+    where
+        P: TryInto<Path>,
+        P::Error: core::fmt::Debug,
+    {
+        let path = path.try_into().expect("invalid path");
+        self.push_at(path, error);
+        self
+    }
+
+    /// Adds `error` to the node at `path`, like
+    /// [and_at](ValidationNode::and_at) does, or returns an error if `path`
+    /// is not a valid path.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::ok()
+    ///     .try_and_at(".settings[2]", ValidationError::with_code("abc"))
+    ///     .unwrap();
+    /// assert_eq!(".settings[2]: abc", errors.to_string());
+    ///
+    /// let error = ValidationNode::ok()
+    ///     .try_and_at("settings", ValidationError::with_code("abc"))
+    ///     .unwrap_err();
+    /// assert_eq!(0, error.position());
+    /// ```
+    pub fn try_and_at(
+        mut self,
+        path: &str,
+        error: ValidationError,
+    ) -> Result<Self, ParsePathError> {
+        self.push_at(path.parse()?, error);
+        Ok(self)
+    }
+
+    /// Adds `error` at `path`, moving names out of the path instead of
+    /// cloning them.
+    fn push_at(&mut self, path: Path, error: ValidationError) {
+        count_added_errors(1);
+        path.segments
+            .into_iter()
+            .fold(self, |node, segment| node.child_or_insert(segment))
+            .errors
+            .push(error);
+    }
+
+    fn child_or_insert(&mut self, segment: PathSegment) -> &mut ValidationNode {
+        let key = match segment {
+            PathSegment::Field(name) => {
+                let next_order = self.insertion_order.then(|| self.next_field_order());
+                return self.fields.entry(name).or_insert_with(|| Self {
+                    order: next_order,
+                    ..Self::ok()
+                });
+            }
+            PathSegment::Index(index) => ItemKey::Index(index),
+            PathSegment::Key(key) => ItemKey::Key(key),
+        };
+        self.items.entry(key).or_insert_with(Self::ok)
+    }
+
+    fn child(&self, segment: &PathSegment) -> Option<&ValidationNode> {
+        match segment {
+            PathSegment::Field(name) => self.fields.get(name.as_ref()),
+            PathSegment::Index(index) => self.items.get_by(|key| key.cmp_index(*index)),
+            PathSegment::Key(name) => self.items.get_by(|key| key.cmp_key(name)),
+        }
+    }
+
+    fn child_mut(&mut self, segment: &PathSegment) -> Option<&mut ValidationNode> {
+        match segment {
+            PathSegment::Field(name) => self.fields.get_mut(name.as_ref()),
+            PathSegment::Index(index) => self.items.get_mut_by(|key| key.cmp_index(*index)),
+            PathSegment::Key(name) => self.items.get_mut_by(|key| key.cmp_key(name)),
+        }
+    }
+
+    /// Keeps only errors for which predicate `f` returns true. Fields and
+    /// items left without errors are removed from the tree.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut errors = ValidationNode::error(ValidationError::with_code("abc"))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("internal_def")))
+    ///     .and_item(1, ValidationNode::error(ValidationError::with_code("ghi")));
+    ///
+    /// errors.retain(|_path, error| !error.code().starts_with("internal_"));
+    /// assert_eq!(".: abc\n.[1]: ghi", errors.to_string());
+    ///
+    /// errors.retain(|path, _error| !path.is_root());
+    /// assert_eq!(".[1]: ghi", errors.to_string());
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(&Path, &ValidationError) -> bool) {
+        self.retain_in_place(&mut Path::root(), &mut f);
+    }
+
+    fn retain_in_place(
+        &mut self,
+        path: &mut Path,
+        f: &mut impl FnMut(&Path, &ValidationError) -> bool,
+    ) {
+        self.errors.retain(|error| f(path, error));
+        self.fields.retain(|name, field| {
+            path.push(PathSegment::Field(name.clone()));
+            field.retain_in_place(path, f);
+            path.pop();
+            field.is_err()
+        });
+        self.items.retain(|key, item| {
+            path.push(key.to_path_segment());
+            item.retain_in_place(path, f);
+            path.pop();
+            item.is_err()
+        });
+    }
+
+    /// Keeps at most `max_errors` errors of the tree, in `Display` order, and
+    /// removes the rest. If any errors were removed, adds error with code
+    /// `truncated` and param `omitted` (number of removed errors) to the root
+    /// node.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut errors = ValidationNode::items([10, 20, 30, 40].iter(), |_index, value| {
+    ///     ValidationNode::error(ValidationError::with_code("abc"))
+    /// });
+    ///
+    /// errors.truncate(2);
+    /// assert_eq!(".: truncated: Too many errors: omitted=2\n.[0]: abc\n.[1]: abc", errors.to_string());
+    /// ```
+    pub fn truncate(&mut self, max_errors: usize) {
+        let mut budget = max_errors;
+        let omitted = self.truncate_in_place(&mut budget);
+        if omitted > 0 {
+            self.errors.push(
+                ValidationError::with_code(codes::TRUNCATED)
+                    .and_message("Too many errors")
+                    .and_param("omitted", omitted),
+            );
+        }
+    }
+
+    /// Keeps at most `budget` errors, decreasing the budget by the number of
+    /// kept errors. Returns the number of removed errors.
+    fn truncate_in_place(&mut self, budget: &mut usize) -> usize {
+        let mut omitted = self.errors.len().saturating_sub(*budget);
+        self.errors.truncate(*budget);
+        *budget -= self.errors.len();
+
+        let mut fields: Vec<_> = self.fields.values_mut().collect();
+        // Same ordering as in `ordered_fields`.
+        fields.sort_by_key(|field| (field.order.is_none(), field.order));
+        for field in fields {
+            omitted += field.truncate_in_place(budget);
+        }
+        self.fields.retain(|_, field| !field.is_ok());
+        self.items.retain(|_, item| {
+            omitted += item.truncate_in_place(budget);
+            item.is_err()
+        });
+        omitted
+    }
+
+    /// Removes errors equal to another error at the same path, keeping the
+    /// first occurrence. Useful when composed validators report the same
+    /// problem more than once.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("abc").and_param("max", 10)))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("abc").and_param("max", 10)))
+    ///     .and_field("x", ValidationNode::error(ValidationError::with_code("abc").and_param("max", 20)));
+    ///
+    /// errors.dedupe();
+    /// assert_eq!(".x: abc: max=10\n.x: abc: max=20", errors.to_string());
+    /// ```
+    pub fn dedupe(&mut self) {
+        let mut unique = ErrorList::new();
+        for error in core::mem::take(&mut self.errors) {
+            if !unique.contains(&error) {
+                unique.push(error);
+            }
+        }
+        self.errors = unique;
+        for child in self.fields.values_mut().chain(self.items.values_mut()) {
+            child.dedupe();
+        }
+    }
+
+    /// Calls `f` for every error in the tree.
+    fn for_each_error_mut(&mut self, f: &mut impl FnMut(&mut ValidationError)) {
+        self.errors.iter_mut().for_each(&mut *f);
+        for field in self.fields.values_mut() {
+            field.for_each_error_mut(f);
+        }
+        for item in self.items.values_mut() {
+            item.for_each_error_mut(f);
+        }
+    }
+
+    /// Replaces codes of all errors in the tree with the result of function
+    /// `f`.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("char_length")));
+    ///
+    /// let errors = errors.map_codes(|code| if code == "char_length" { "too_long".into() } else { code });
+    /// assert_eq!(".x: too_long", errors.to_string());
+    /// ```
+    pub fn map_codes(mut self, mut f: impl FnMut(Cow<'static, str>) -> Cow<'static, str>) -> Self {
+        self.for_each_error_mut(&mut |error| {
+            error.code = f(core::mem::take(&mut error.code));
+        });
+        self
+    }
+
+    /// Replaces messages of all errors in the tree with the result of
+    /// function `f`, e.g. a localized message. Returning `None` removes the
+    /// message.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("range").and_param("max", 10)));
+    ///
+    /// let errors = errors.map_messages(|error| match error.code() {
+    ///     "range" => Some(format!("Liczba większa niż {}", error.param("max")?).into()),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(".x: range: Liczba większa niż 10: max=10", errors.to_string());
+    /// ```
+    pub fn map_messages(
+        mut self,
+        mut f: impl FnMut(&ValidationError) -> Option<Cow<'static, str>>,
+    ) -> Self {
+        self.for_each_error_mut(&mut |error| {
+            error.message = f(error).map(Message::Text);
+        });
+        self
+    }
+
+    /// Replaces parameters of all errors in the tree with the result of
+    /// function `f`, which receives parameter key and value.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("abc").and_param("secret", "xyz"));
+    ///
+    /// let errors = errors.map_params(|key, value| if key == "secret" { ParamValue::Raw("***".into()) } else { value });
+    /// assert_eq!(".: abc: secret=***", errors.to_string());
+    /// ```
+    pub fn map_params(mut self, mut f: impl FnMut(&str, ParamValue) -> ParamValue) -> Self {
+        self.for_each_error_mut(&mut |error| {
+            if let Some(details) = &mut error.details {
+                details.params = core::mem::take(&mut details.params)
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let value = f(&key, value);
+                        (key, value)
+                    })
+                    .collect();
+            }
+        });
+        self
+    }
+}
+
+fn flat_path(path: &Path) -> String {
+    let mut path = path.to_string();
+    path.remove(0);
+    path
+}
+
+impl<'a> IntoIterator for &'a ValidationNode {
+    type Item = (Path, &'a ValidationError);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PartialEq for ValidationNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.errors == other.errors && self.fields == other.fields && self.items == other.items
+    }
+}
+
+impl IntoIterator for ValidationNode {
+    type Item = (Path, ValidationError);
+    type IntoIter = IntoIter;
+
+    /// Consumes the tree, returning owned errors paired with their paths in
+    /// the same order as [iter](ValidationNode::iter).
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("abc")));
+    ///
+    /// let (path, error) = errors.into_iter().next().unwrap();
+    /// assert_eq!(".x", path.to_string());
+    /// assert_eq!("abc", error.code());
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        let mut errors = Vec::new();
+        collect_owned_errors(self, &mut Path::root(), &mut errors);
+        IntoIter(errors.into_iter())
+    }
+}
+
+fn collect_errors<'a>(
+    node: &'a ValidationNode,
+    path: &mut Path,
+    errors: &mut Vec<(Path, &'a ValidationError)>,
+) {
+    for error in node.errors.iter() {
+        errors.push((path.clone(), error));
+    }
+    for (name, field) in node.ordered_fields() {
+        path.segments.push(PathSegment::Field(name.clone()));
+        collect_errors(field, path, errors);
+        path.segments.pop();
+    }
+    for (key, item) in node.items.iter() {
+        path.segments.push(key.to_path_segment());
+        collect_errors(item, path, errors);
+        path.segments.pop();
+    }
+}
+
+fn collect_owned_errors(
+    node: ValidationNode,
+    path: &mut Path,
+    errors: &mut Vec<(Path, ValidationError)>,
+) {
+    for error in node.errors {
+        errors.push((path.clone(), error));
+    }
+    let mut fields: Vec<_> = node.fields.into_iter().collect();
+    // Same ordering as in `ordered_fields`.
+    fields.sort_by_key(|(_, field)| (field.order.is_none(), field.order));
+    for (name, field) in fields {
+        path.segments.push(PathSegment::Field(name));
+        collect_owned_errors(field, path, errors);
+        path.segments.pop();
+    }
+    for (key, item) in node.items {
+        path.segments.push(key.into_path_segment());
+        collect_owned_errors(item, path, errors);
+        path.segments.pop();
+    }
+}
+
+/// Iterator over errors of a [ValidationNode] and their paths, created by
+/// [iter](ValidationNode::iter).
+#[derive(Debug)]
+pub struct Iter<'a>(alloc::vec::IntoIter<(Path, &'a ValidationError)>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (Path, &'a ValidationError);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+/// Owning iterator over errors of a [ValidationNode] and their paths,
+/// created by `into_iter`.
+#[derive(Debug)]
+pub struct IntoIter(alloc::vec::IntoIter<(Path, ValidationError)>);
+
+impl Iterator for IntoIter {
+    type Item = (Path, ValidationError);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+/// Iterator over fields of [ValidationNode] in display order.
+enum OrderedFields<'a> {
+    ByName(sorted_map::Iter<'a, Cow<'static, str>, ValidationNode>),
+    ByOrder(alloc::vec::IntoIter<(&'a Cow<'static, str>, &'a ValidationNode)>),
+}
+
+impl<'a> Iterator for OrderedFields<'a> {
+    type Item = (&'a Cow<'static, str>, &'a ValidationNode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::ByName(iter) => iter.next(),
+            Self::ByOrder(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::ByName(iter) => iter.size_hint(),
+            Self::ByOrder(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for OrderedFields<'_> {}
+
+/// Limits protecting validation of untrusted, deeply nested data. Limits
+/// apply to validations run by [ValidationLimits::run] on the current thread
+/// and are enforced by [ValidationNode::nested], which the derive macro uses
+/// for `nested` validators.
+///
+/// If validation of a nested value would exceed the maximum depth, the value
+/// is not validated and gets `depth_exceeded` error instead. Once the
+/// maximum number of errors is reached, remaining nested values are not
+/// validated, and the result is truncated like
+/// [ValidationNode::truncate] does. Param `skipped` of the `truncated`
+/// error tells how many nested validations were skipped.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(Validate)]
+/// struct Comment {
+///     #[validate(char_length(max = 5))]
+///     text: String,
+///     #[validate(items(nested))]
+///     replies: Vec<Comment>,
+/// }
+///
+/// let comment = Comment {
+///     text: "Hello".into(),
+///     replies: vec![Comment {
+///         text: "Hi".into(),
+///         replies: vec![Comment { text: "Hey".into(), replies: vec![] }],
+///     }],
+/// };
+///
+/// let errors = ValidationLimits::new().with_max_depth(1).run(|| comment.validate());
+/// assert_eq!(".replies[0].replies[0]: depth_exceeded: Maximum depth exceeded: max=1", errors.to_string());
+/// assert!(ValidationLimits::new().with_max_depth(2).run(|| comment.validate()).is_ok());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationLimits {
+    max_depth: Option<usize>,
+    max_errors: Option<usize>,
+}
+
+/// Limits installed by [ValidationLimits::run] and progress of validation.
+#[cfg(feature = "std")]
+struct LimitsState {
+    limits: ValidationLimits,
+    depth: usize,
+    errors: usize,
+    skipped: usize,
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static LIMITS: core::cell::RefCell<Option<LimitsState>> = const { core::cell::RefCell::new(None) };
+}
+
+/// Restores limits replaced by [ValidationLimits::run], also on panic.
+#[cfg(feature = "std")]
+struct LimitsGuard(Option<Option<LimitsState>>);
+
+#[cfg(feature = "std")]
+impl Drop for LimitsGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            LIMITS.with(|limits| *limits.borrow_mut() = previous);
+        }
+    }
+}
+
+/// Leaves a level of [ValidationNode::nested], also on panic. Errors counted
+/// by the panicking validation are discarded with its result.
+#[cfg(feature = "std")]
+struct NestedGuard {
+    errors_before: usize,
+}
+
+#[cfg(feature = "std")]
+impl Drop for NestedGuard {
+    fn drop(&mut self) {
+        LIMITS.with(|limits| {
+            if let Some(state) = limits.borrow_mut().as_mut() {
+                state.depth -= 1;
+                if std::thread::panicking() {
+                    state.errors = self.errors_before;
+                }
+            }
+        });
+    }
+}
+
+/// Counts `count` errors added to a tree towards the maximum number of
+/// errors of [ValidationLimits] of the current thread. Nodes added as
+/// children count only their own errors, because errors of their children
+/// were counted when the children were added.
+fn count_added_errors(count: usize) {
+    #[cfg(feature = "std")]
+    if count > 0 {
+        LIMITS.with(|limits| {
+            if let Some(state) = limits.borrow_mut().as_mut() {
+                state.errors += count;
+            }
+        });
+    }
+    #[cfg(not(feature = "std"))]
+    let _ = count;
+}
+
+#[cfg(feature = "std")]
+impl ValidationLimits {
+    /// Creates limits that don't limit anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets maximum number of nested values on the path from the validated
+    /// value to any validated value.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets maximum number of errors of the validation. Errors count towards
+    /// the maximum when they are added to the tree, so errors discarded
+    /// later, e.g. by [ValidationNode::any], count too.
+    pub fn with_max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    /// Runs validation `f` with the limits.
+    pub fn run(self, f: impl FnOnce() -> ValidationNode) -> ValidationNode {
+        let state = LimitsState {
+            limits: self,
+            depth: 0,
+            errors: 0,
+            skipped: 0,
+        };
+        let mut guard = LimitsGuard(Some(LIMITS.with(|limits| limits.replace(Some(state)))));
+        let mut node = f();
+        let previous = guard.0.take().unwrap();
+        let state = LIMITS.with(|limits| limits.replace(previous)).unwrap();
+
+        if let Some(mut budget) = self.max_errors {
+            let omitted = node.truncate_in_place(&mut budget);
+            if omitted > 0 || state.skipped > 0 {
+                let mut error = ValidationError::with_code(codes::TRUNCATED)
+                    .and_message("Too many errors")
+                    .and_param("omitted", omitted);
+                if state.skipped > 0 {
+                    error = error.and_param("skipped", state.skipped);
+                }
+                node.errors.push(error);
+            }
+        }
+        node
+    }
+}
+
+impl ValidationNode {
+    /// Runs `f`, validation of a nested value, enforcing [ValidationLimits]
+    /// of the current thread. Without limits or the `std` feature, just
+    /// returns the result of `f`. Manual validators of recursive data should
+    /// validate nested values through this function.
+    /// ```
+    /// # use not_so_fast::*;
+    /// struct Tree(Vec<Tree>);
+    ///
+    /// fn validate_tree(tree: &Tree) -> ValidationNode {
+    ///     ValidationNode::items(tree.0.iter(), |_, child| {
+    ///         ValidationNode::nested(|| validate_tree(child))
+    ///     })
+    /// }
+    ///
+    /// let tree = Tree(vec![Tree(vec![Tree(vec![])])]);
+    /// assert!(validate_tree(&tree).is_ok());
+    /// assert_eq!(
+    ///     ".[0][0]: depth_exceeded: Maximum depth exceeded: max=1",
+    ///     ValidationLimits::new().with_max_depth(1).run(|| validate_tree(&tree)).to_string(),
+    /// );
+    /// ```
+    pub fn nested(f: impl FnOnce() -> ValidationNode) -> ValidationNode {
+        #[cfg(feature = "std")]
+        return Self::nested_limited(f);
+        #[cfg(not(feature = "std"))]
+        f()
+    }
+
+    #[cfg(feature = "std")]
+    fn nested_limited(f: impl FnOnce() -> ValidationNode) -> ValidationNode {
+        let entered = LIMITS.with(|limits| {
+            let mut limits = limits.borrow_mut();
+            let state = limits.as_mut()?;
+            if state
+                .limits
+                .max_errors
+                .is_some_and(|max| state.errors >= max)
+            {
+                state.skipped += 1;
+                return Some(Err(Self::ok()));
+            }
+            if state.limits.max_depth.is_some_and(|max| state.depth >= max) {
+                let max = state.depth;
+                return Some(Err(Self::error(
+                    ValidationError::with_code(codes::DEPTH_EXCEEDED)
+                        .and_message("Maximum depth exceeded")
+                        .and_param("max", max),
+                )));
+            }
+            state.depth += 1;
+            Some(Ok(state.errors))
+        });
+        let errors_before = match entered {
+            None => return f(),
+            Some(Err(node)) => return node,
+            Some(Ok(errors_before)) => errors_before,
+        };
+        // Errors of the nested value are counted when it's added to the
+        // parent node, and errors of its children were counted by `f`.
+        let _guard = NestedGuard { errors_before };
+        f()
+    }
+
+    /// Adds errors of field `name` returned by `f`, like
+    /// [ValidationNode::and_field] does. `f` is called only if validation
+    /// running with [ValidationLimits] hasn't reached maximum number of
+    /// errors, so expensive validation of fields can be delayed until it's
+    /// known to be needed. Skipped calls are counted in param `skipped` of
+    /// the `truncated` error.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let validate = || {
+    ///     ValidationNode::error(ValidationError::with_code("abc"))
+    ///         .and_field_with("x", || ValidationNode::error(ValidationError::with_code("def")))
+    /// };
+    /// assert_eq!(".: abc\n.x: def", validate().to_string());
+    /// assert_eq!(
+    ///     ".: abc\n.: truncated: Too many errors: omitted=0, skipped=1",
+    ///     ValidationLimits::new().with_max_errors(1).run(validate).to_string(),
+    /// );
+    /// ```
+    pub fn and_field_with(
+        self,
+        name: impl Into<Cow<'static, str>>,
+        f: impl FnOnce() -> ValidationNode,
+    ) -> Self {
+        if self.error_budget_exhausted() {
+            return self;
+        }
+        self.and_field(name, f())
+    }
+
+    /// Adds errors of item at `index` returned by `f`, like
+    /// [ValidationNode::and_item] does. `f` is called only if more errors are
+    /// needed, see [ValidationNode::and_field_with].
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationLimits::new().with_max_errors(1).run(|| {
+    ///     ValidationNode::ok()
+    ///         .and_item_with(0, || ValidationNode::error(ValidationError::with_code("abc")))
+    ///         .and_item_with(1, || unreachable!())
+    /// });
+    /// assert_eq!(".: truncated: Too many errors: omitted=0, skipped=1\n.[0]: abc", errors.to_string());
+    /// ```
+    pub fn and_item_with(self, index: usize, f: impl FnOnce() -> ValidationNode) -> Self {
+        if self.error_budget_exhausted() {
+            return self;
+        }
+        self.and_item(index, f())
+    }
+
+    /// Tells whether [ValidationLimits] of the current thread need no more
+    /// errors, counting the skipped validation if so.
+    fn error_budget_exhausted(&self) -> bool {
+        #[cfg(feature = "std")]
+        return LIMITS.with(|limits| {
+            let mut limits = limits.borrow_mut();
+            let Some(state) = limits.as_mut() else {
+                return false;
+            };
+            // Errors of children of `self` were counted when they were
+            // added, but errors of `self` are counted only when it's added
+            // to its parent.
+            let errors = state.errors + self.errors.len();
+            let exhausted = state.limits.max_errors.is_some_and(|max| errors >= max);
+            if exhausted {
+                state.skipped += 1;
+            }
+            exhausted
+        });
+        #[cfg(not(feature = "std"))]
+        false
+    }
+}
+
+/// [ValidationNode] with parts whose validation is deferred until the node
+/// is evaluated. If the node is dropped without evaluation, deferred
+/// validations never run. Created by [ValidationNode::lazy],
+/// [ValidationNode::and_field_lazy] or from [ValidationNode] with `into`.
+/// ```
+/// # use not_so_fast::*;
+/// # use std::cell::Cell;
+/// let calls = Cell::new(0);
+/// let expensive = |code: &'static str| {
+///     calls.set(calls.get() + 1);
+///     ValidationNode::error(ValidationError::with_code(code))
+/// };
+///
+/// let lazy = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("abc")))
+///     .and_field_lazy("b", || expensive("def"))
+///     .and_lazy(|| expensive("ghi"));
+/// assert_eq!(0, calls.get());
+///
+/// let errors = lazy.evaluate();
+/// assert_eq!(2, calls.get());
+/// assert_eq!(".: ghi\n.a: abc\n.b: def", errors.to_string());
+/// ```
+pub struct LazyNode<'a> {
+    node: ValidationNode,
+    deferred: Vec<Deferred<'a>>,
+}
+
+/// Validation deferred by [LazyNode].
+enum Deferred<'a> {
+    Node(Box<dyn FnOnce() -> ValidationNode + 'a>),
+    Field(Cow<'static, str>, Box<dyn FnOnce() -> ValidationNode + 'a>),
+}
+
+impl<'a> LazyNode<'a> {
+    /// Adds errors of field `name`, like [ValidationNode::and_field] does.
+    pub fn and_field(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        validation_errors: ValidationNode,
+    ) -> Self {
+        self.node = self.node.and_field(name, validation_errors);
+        self
+    }
+
+    /// Defers validation of field `name` until the node is evaluated.
+    pub fn and_field_lazy(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        f: impl FnOnce() -> ValidationNode + 'a,
+    ) -> Self {
+        self.deferred
+            .push(Deferred::Field(name.into(), Box::new(f)));
+        self
+    }
+
+    /// Defers validation merged into the node until the node is evaluated.
+    pub fn and_lazy(mut self, f: impl FnOnce() -> ValidationNode + 'a) -> Self {
+        self.deferred.push(Deferred::Node(Box::new(f)));
+        self
+    }
+
+    /// Runs all deferred validations, in the order they were added, and
+    /// returns the complete node.
+    pub fn evaluate(self) -> ValidationNode {
+        self.deferred
+            .into_iter()
+            .fold(self.node, |node, deferred| deferred.apply(node))
+    }
+
+    /// Runs deferred validations, in the order they were added, until the
+    /// node has an error. The returned node is ok only if all validations
+    /// passed.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::lazy(|| ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_field_lazy("x", || unreachable!())
+    ///     .evaluate_fail_fast();
+    /// assert_eq!(".: abc", errors.to_string());
+    /// ```
+    pub fn evaluate_fail_fast(self) -> ValidationNode {
+        let mut node = self.node;
+        for deferred in self.deferred {
+            if node.is_err() {
+                break;
+            }
+            node = deferred.apply(node);
+        }
+        node
+    }
+}
+
+impl Deferred<'_> {
+    fn apply(self, node: ValidationNode) -> ValidationNode {
+        match self {
+            Deferred::Node(f) => node.merge(f()),
+            Deferred::Field(name, f) => node.and_field(name, f()),
+        }
+    }
+}
+
+impl From<ValidationNode> for LazyNode<'_> {
+    fn from(node: ValidationNode) -> Self {
+        Self {
+            node,
+            deferred: Vec::new(),
+        }
+    }
+}
+
+impl core::fmt::Debug for LazyNode<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LazyNode")
+            .field("node", &self.node)
+            .field("deferred", &self.deferred.len())
+            .finish()
+    }
+}
+
+impl ValidationNode {
+    /// Creates [LazyNode] whose errors are returned by `f` when the node is
+    /// evaluated.
+    pub fn lazy<'a>(f: impl FnOnce() -> ValidationNode + 'a) -> LazyNode<'a> {
+        LazyNode::from(Self::ok()).and_lazy(f)
+    }
+
+    /// Converts `self` into [LazyNode] with validation of field `name`
+    /// deferred until the node is evaluated.
+    pub fn and_field_lazy<'a>(
+        self,
+        name: impl Into<Cow<'static, str>>,
+        f: impl FnOnce() -> ValidationNode + 'a,
+    ) -> LazyNode<'a> {
+        LazyNode::from(self).and_field_lazy(name, f)
+    }
+}
+
+/// Errors added and removed between two validation trees. Created by
+/// [ValidationNode::diff].
+///
+/// `Display` prints added errors prefixed with `+` and removed errors
+/// prefixed with `-`, one per line.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidationDiff<'a> {
+    added: Vec<(Path, &'a ValidationError)>,
+    removed: Vec<(Path, &'a ValidationError)>,
+}
+
+impl<'a> ValidationDiff<'a> {
+    /// Returns errors present only in the new tree, in its `Display` order.
+    pub fn added(&self) -> &[(Path, &'a ValidationError)] {
+        &self.added
+    }
+
+    /// Returns errors present only in the old tree, in its `Display` order.
+    pub fn removed(&self) -> &[(Path, &'a ValidationError)] {
+        &self.removed
+    }
+
+    /// Checks if both trees have the same errors.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl core::fmt::Display for ValidationDiff<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let added = self.added.iter().map(|entry| ('+', entry));
+        let removed = self.removed.iter().map(|entry| ('-', entry));
+        for (i, (sign, (path, error))) in added.chain(removed).enumerate() {
+            if i != 0 {
+                f.write_char('\n')?;
+            }
+            write!(f, "{} {}: {}", sign, path, error)?;
+        }
+        Ok(())
+    }
+}
+
+impl ValidationNode {
+    /// Compares errors of `self` (the old tree) and `other` (the new tree).
+    /// Errors are matched by path and equality. If an error repeats at a
+    /// path, the difference in the number of its occurrences is reported.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let old = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("def")));
+    /// let new = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("def").and_param("max", 3)))
+    ///     .and_field("c", ValidationNode::error(ValidationError::with_code("ghi")));
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(2, diff.added().len());
+    /// assert_eq!(1, diff.removed().len());
+    /// assert_eq!("+ .b: def: max=3\n+ .c: ghi\n- .b: def", diff.to_string());
+    /// assert!(new.diff(&new).is_empty());
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a ValidationNode) -> ValidationDiff<'a> {
+        // Errors of `self` not matched with errors of `other` yet.
+        let mut unmatched = BTreeMap::<Path, Vec<&ValidationError>>::new();
+        for (path, error) in self {
+            unmatched.entry(path).or_default().push(error);
+        }
+        let mut take = |path: &Path, error: &ValidationError| {
+            let errors = match unmatched.get_mut(path) {
+                Some(errors) => errors,
+                None => return false,
+            };
+            match errors.iter().position(|unmatched| *unmatched == error) {
+                Some(position) => {
+                    errors.remove(position);
+                    true
+                }
+                None => false,
+            }
+        };
+        let added = other
+            .iter()
+            .filter(|(path, error)| !take(path, error))
+            .collect();
+        // What is left unmatched was removed.
+        let removed = self
+            .iter()
+            .filter(|(path, error)| take(path, error))
+            .collect();
+        ValidationDiff { added, removed }
+    }
+}
+
+/// Visitor of [ValidationNode] trees, driven by [ValidationNode::walk].
+/// Nodes are visited in `Display` order: errors of a node first, then its
+/// fields, then its items. All methods do nothing by default.
+/// ```
+/// # use not_so_fast::*;
+/// /// Counts errors per depth.
+/// #[derive(Default)]
+/// struct DepthCounter {
+///     depth: usize,
+///     counts: Vec<usize>,
+/// }
+///
+/// impl Visit for DepthCounter {
+///     fn enter_field(&mut self, _name: &str) {
+///         self.depth += 1;
+///     }
+///     fn leave_field(&mut self, _name: &str) {
+///         self.depth -= 1;
+///     }
+///     fn enter_item(&mut self, _item: &PathSegment) {
+///         self.depth += 1;
+///     }
+///     fn leave_item(&mut self, _item: &PathSegment) {
+///         self.depth -= 1;
+///     }
+///     fn visit_error(&mut self, _error: &ValidationError) {
+///         self.counts.resize(self.counts.len().max(self.depth + 1), 0);
+///         self.counts[self.depth] += 1;
+///     }
+/// }
+///
+/// let errors = ValidationNode::error(ValidationError::with_code("abc"))
+///     .and_field("x", ValidationNode::item(1, ValidationNode::error(ValidationError::with_code("def"))))
+///     .and_field("y", ValidationNode::item(2, ValidationNode::error(ValidationError::with_code("ghi"))));
+///
+/// let mut counter = DepthCounter::default();
+/// errors.walk(&mut counter);
+/// assert_eq!(vec![1, 0, 2], counter.counts);
+/// ```
+pub trait Visit {
+    /// Called before visiting node of field `name`.
+    fn enter_field(&mut self, name: &str) {
+        let _ = name;
+    }
+
+    /// Called after visiting node of field `name`.
+    fn leave_field(&mut self, name: &str) {
+        let _ = name;
+    }
+
+    /// Called before visiting node of an item, identified by
+    /// [PathSegment::Index] or [PathSegment::Key].
+    fn enter_item(&mut self, item: &PathSegment) {
+        let _ = item;
+    }
+
+    /// Called after visiting node of an item.
+    fn leave_item(&mut self, item: &PathSegment) {
+        let _ = item;
+    }
+
+    /// Called for each error of the visited node.
+    fn visit_error(&mut self, error: &ValidationError) {
+        let _ = error;
+    }
+}
+
+impl ValidationNode {
+    /// Walks the tree with `visitor`. See [Visit] for details.
+    pub fn walk(&self, visitor: &mut (impl Visit + ?Sized)) {
+        for error in self.errors.iter() {
+            visitor.visit_error(error);
+        }
+        for (name, field) in self.ordered_fields() {
+            visitor.enter_field(name);
+            field.walk(visitor);
+            visitor.leave_field(name);
+        }
+        for (key, item) in self.items.iter() {
+            let segment = key.to_path_segment();
+            visitor.enter_item(&segment);
+            item.walk(visitor);
+            visitor.leave_item(&segment);
+        }
+    }
+}
+
+/// Trait describing types that can be validated without arguments. It is
+/// automatically implemented for all types that implement `ValidateArgs<Args=()>`.
+pub trait Validate {
+    fn validate(&self) -> ValidationNode;
+
+    /// Validates `self` and passes errors to `sink`, in `Display` order,
+    /// until the sink is done. Types validated with [ValidateArgs] forward
+    /// to [ValidateArgs::validate_args_into], so derived types stop
+    /// validating fields once the sink is done.
+    /// ```
+    /// # use not_so_fast::*;
+    /// #[derive(Validate)]
+    /// struct User {
+    ///     #[validate(char_length(max = 3))]
+    ///     name: String,
+    ///     #[validate(range(max = 100))]
+    ///     age: u32,
+    /// }
+    ///
+    /// let user = User { name: "Alice".into(), age: 200 };
+    /// let mut first = FirstError::default();
+    /// user.validate_into(&mut first);
+    /// assert_eq!(".age", first.path().unwrap().to_string());
+    /// ```
+    fn validate_into(&self, sink: &mut dyn ErrorSink) {
+        self.validate().drain_into(sink);
+    }
+
+    /// Validates `self` as the result of a partial update, e.g. of a `PATCH`
+    /// request, keeping errors of fields present in the update and errors
+    /// of their parents, like errors of type-level custom validators
+    /// checking cross-field invariants. See [FieldMask].
+    /// ```
+    /// # use not_so_fast::*;
+    /// #[derive(Validate)]
+    /// #[validate(custom = check_dates)]
+    /// struct Event {
+    ///     #[validate(char_length(max = 10))]
+    ///     title: String,
+    ///     #[validate(range(max = 100))]
+    ///     capacity: u32,
+    ///     start: u32,
+    ///     end: u32,
+    /// }
+    ///
+    /// fn check_dates(event: &Event) -> ValidationNode {
+    ///     ValidationNode::error_if(event.start > event.end, || ValidationError::with_code("dates"))
+    /// }
+    ///
+    /// // Stored event with an invalid capacity, patched with `{ "title": ..., "end": 1 }`.
+    /// let event = Event { title: "Birthday party".into(), capacity: 500, start: 5, end: 1 };
+    /// let present = FieldMask::new().with_field("title").with_field("end");
+    /// assert_eq!(
+    ///     ".: dates\n.title: char_length: Invalid character length: max=10, value=14",
+    ///     event.validate_partial(&present).to_string(),
+    /// );
+    /// ```
+    fn validate_partial(&self, present: &FieldMask) -> ValidationNode {
+        let mut node = self.validate();
+        node.retain_present(present);
+        node
+    }
+
+    /// Validates `self`, reusing results cached in `cache` for nested fields
+    /// validated with `nested(cached)`, by [ValidationKey] of the fields, and
+    /// caching results of the fields validated for the first time. Large
+    /// unchanged sub-documents, shared by many validated values, are
+    /// validated once. Without the `std` feature, nested fields are
+    /// validated every time. See also [ValidationCache::validate].
+    /// ```
+    /// # use not_so_fast::*;
+    /// #[derive(Clone, Validate)]
+    /// struct Catalog {
+    ///     version: u64,
+    ///     #[validate(items(char_length(max = 10)))]
+    ///     products: Vec<String>,
+    /// }
+    ///
+    /// impl ValidationKey for Catalog {
+    ///     fn validation_key(&self) -> u64 {
+    ///         self.version
+    ///     }
+    /// }
+    ///
+    /// #[derive(Validate)]
+    /// struct Order {
+    ///     #[validate(range(min = 1))]
+    ///     quantity: u32,
+    ///     #[validate(nested(cached))]
+    ///     catalog: Catalog,
+    /// }
+    ///
+    /// let catalog = Catalog { version: 7, products: vec!["Chocolate cookies".into()] };
+    /// let mut cache = ValidationCache::new();
+    /// for quantity in [0, 1] {
+    ///     let order = Order { quantity, catalog: catalog.clone() };
+    ///     assert!(order.validate_cached(&mut cache).is_err());
+    /// }
+    /// assert_eq!(1, cache.len());
+    /// ```
+    fn validate_cached(&self, cache: &mut ValidationCache) -> ValidationNode {
+        crate::cache::with_current_cache(cache, || self.validate())
+    }
+
+    /// Validates `self` and reports the outcome to the observer registered
+    /// with [set_observer], if any.
     /// ```
     /// # use not_so_fast::*;
-    /// let value = 10;
-    /// let errors = ValidationNode::error_if(value >= 20, || ValidationError::with_code("abc"));
-    /// assert!(errors.is_ok());
+    /// #[derive(Validate)]
+    /// struct Order {
+    ///     #[validate(range(min = 1))]
+    ///     quantity: u32,
+    /// }
     ///
-    /// let errors = ValidationNode::error_if(value >= 10, || ValidationError::with_code("def"));
-    /// assert!(errors.is_err());
-    /// assert_eq!(".: def", errors.to_string());
+    /// assert!(Order { quantity: 0 }.validate_observed().is_err());
     /// ```
-    pub fn error_if(condition: bool, f: impl FnOnce() -> ValidationError) -> Self {
-        Self {
-            errors: if condition {
-                vec![f()]
-            } else {
-                Default::default()
-            },
-            fields: Default::default(),
-            items: Default::default(),
+    #[cfg(feature = "std")]
+    fn validate_observed(&self) -> ValidationNode {
+        match crate::observer::global_observer() {
+            Some(observer) => self.validate_observed_by(observer),
+            None => self.validate(),
         }
     }
 
-    /// Adds value error returned by function `f` to `ValidationNode` if
-    /// `condition` is `true`. Otherwise, returns unchanged `self`. Function
-    /// `f` will be called at most once.
+    /// Validates `self` and reports the outcome to `observer`, with the type
+    /// name of `Self`. See [ValidationObserver].
+    #[cfg(feature = "std")]
+    fn validate_observed_by(&self, observer: &dyn ValidationObserver) -> ValidationNode {
+        crate::observer::observe(core::any::type_name::<Self>(), observer, || self.validate())
+    }
+}
+
+/// Trait describing types that can be validated with arguments.
+/// Implemented for references and smart pointers to types implementing it,
+/// which validate the pointed-to value, and for tuples of up to 12 elements
+/// validated without arguments, which report errors of elements as items.
+/// ```
+/// # use not_so_fast::*;
+/// # use std::borrow::Cow;
+/// # use std::rc::Rc;
+/// #[derive(Clone, Validate)]
+/// struct User {
+///     #[validate(range(max = 150))]
+///     age: u32,
+/// }
+///
+/// fn is_valid(value: impl Validate) -> bool {
+///     value.validate().is_ok()
+/// }
+///
+/// let user = User { age: 200 };
+/// assert!(!is_valid(&user));
+/// assert!(!is_valid(Box::new(user.clone())));
+/// assert!(!is_valid(Rc::new(user.clone())));
+/// assert!(!is_valid(Cow::Borrowed(&user)));
+/// assert_eq!(
+///     ".[1].age: range: Number not in range: max=150, value=200",
+///     (User { age: 30 }, user).validate().to_string(),
+/// );
+/// ```
+pub trait ValidateArgs<'arg> {
+    type Args;
+    fn validate_args(&self, args: Self::Args) -> ValidationNode;
+
+    /// Validates `self` with `args` and passes errors to `sink` until the
+    /// sink is done. The default implementation drains the result of
+    /// `validate_args` in `Display` order. Derived implementations validate
+    /// fields one by one, pushing errors of type-level validators first and
+    /// then errors of fields in `Display` order of the fields, and skip
+    /// fields left after the sink is done.
     /// ```
     /// # use not_so_fast::*;
-    /// let value = 10;
-    /// let errors = ValidationNode::ok().and_error_if(value >= 20, || ValidationError::with_code("abc"));
-    /// assert!(errors.is_ok());
+    /// #[derive(Validate)]
+    /// #[validate(args(max_age: u32))]
+    /// struct User {
+    ///     #[validate(range(max = max_age))]
+    ///     age: u32,
+    /// }
     ///
-    /// let errors = ValidationNode::ok().and_error_if(value >= 10, || ValidationError::with_code("def"));
-    /// assert!(errors.is_err());
-    /// assert_eq!(".: def", errors.to_string());
+    /// let mut errors = Vec::new();
+    /// User { age: 200 }.validate_args_into((150,), &mut errors);
+    /// assert_eq!(".age", errors[0].0.to_string());
     /// ```
-    pub fn and_error_if(mut self, condition: bool, f: impl FnOnce() -> ValidationError) -> Self {
-        if condition {
-            self.errors.push(f());
+    fn validate_args_into(&self, args: Self::Args, sink: &mut dyn ErrorSink) {
+        self.validate_args(args).drain_into(sink);
+    }
+}
+
+impl<'arg, T: ValidateArgs<'arg> + ?Sized> ValidateArgs<'arg> for &T {
+    type Args = T::Args;
+
+    fn validate_args(&self, args: Self::Args) -> ValidationNode {
+        (**self).validate_args(args)
+    }
+
+    fn validate_args_into(&self, args: Self::Args, sink: &mut dyn ErrorSink) {
+        (**self).validate_args_into(args, sink);
+    }
+}
+
+impl<'arg, T: ValidateArgs<'arg> + ?Sized> ValidateArgs<'arg> for &mut T {
+    type Args = T::Args;
+
+    fn validate_args(&self, args: Self::Args) -> ValidationNode {
+        (**self).validate_args(args)
+    }
+
+    fn validate_args_into(&self, args: Self::Args, sink: &mut dyn ErrorSink) {
+        (**self).validate_args_into(args, sink);
+    }
+}
+
+impl<'arg, T: ValidateArgs<'arg> + ?Sized> ValidateArgs<'arg> for Box<T> {
+    type Args = T::Args;
+
+    fn validate_args(&self, args: Self::Args) -> ValidationNode {
+        (**self).validate_args(args)
+    }
+
+    fn validate_args_into(&self, args: Self::Args, sink: &mut dyn ErrorSink) {
+        (**self).validate_args_into(args, sink);
+    }
+}
+
+impl<'arg, T: ValidateArgs<'arg> + ?Sized> ValidateArgs<'arg> for alloc::rc::Rc<T> {
+    type Args = T::Args;
+
+    fn validate_args(&self, args: Self::Args) -> ValidationNode {
+        (**self).validate_args(args)
+    }
+
+    fn validate_args_into(&self, args: Self::Args, sink: &mut dyn ErrorSink) {
+        (**self).validate_args_into(args, sink);
+    }
+}
+
+impl<'arg, T: ValidateArgs<'arg> + ?Sized> ValidateArgs<'arg> for Arc<T> {
+    type Args = T::Args;
+
+    fn validate_args(&self, args: Self::Args) -> ValidationNode {
+        (**self).validate_args(args)
+    }
+
+    fn validate_args_into(&self, args: Self::Args, sink: &mut dyn ErrorSink) {
+        (**self).validate_args_into(args, sink);
+    }
+}
+
+impl<'arg, T: ValidateArgs<'arg> + ToOwned + ?Sized> ValidateArgs<'arg> for Cow<'_, T> {
+    type Args = T::Args;
+
+    fn validate_args(&self, args: Self::Args) -> ValidationNode {
+        (**self).validate_args(args)
+    }
+
+    fn validate_args_into(&self, args: Self::Args, sink: &mut dyn ErrorSink) {
+        (**self).validate_args_into(args, sink);
+    }
+}
+
+/// Implements [ValidateArgs] without arguments for tuples of elements
+/// validated without arguments, reporting errors of elements as items.
+macro_rules! impl_validate_args_tuple {
+    ($($element:ident $index:tt),+) => {
+        impl<'arg, $($element),+> ValidateArgs<'arg> for ($($element,)+)
+        where
+            $($element: ValidateArgs<'arg, Args = ()>,)+
+        {
+            type Args = ();
+
+            fn validate_args(&self, _args: Self::Args) -> ValidationNode {
+                ValidationNode::ok()
+                    $(.and_item($index, self.$index.validate_args(())))+
+            }
+
+            fn validate_args_into(&self, _args: Self::Args, sink: &mut dyn ErrorSink) {
+                $(
+                    self.$index.validate_args_into((), &mut sink::PrefixedSink::index($index, sink));
+                    if sink.is_done() {
+                        return;
+                    }
+                )+
+            }
         }
-        self
+    };
+}
+
+impl_validate_args_tuple!(A 0);
+impl_validate_args_tuple!(A 0, B 1);
+impl_validate_args_tuple!(A 0, B 1, C 2);
+impl_validate_args_tuple!(A 0, B 1, C 2, D 3);
+impl_validate_args_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_validate_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_validate_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_validate_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_validate_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_validate_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_validate_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_validate_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+impl<'a, T> Validate for T
+where
+    T: ValidateArgs<'a, Args = ()>,
+{
+    fn validate(&self) -> ValidationNode {
+        self.validate_args(())
     }
 
-    /// Constructs `ValidationNode` from the value error iterator.
+    fn validate_into(&self, sink: &mut dyn ErrorSink) {
+        self.validate_args_into((), sink);
+    }
+}
+
+/// Extension of [Validate] returning validation results as `Result`s, for
+/// use with the `?` operator. Implemented for all types implementing
+/// [Validate].
+pub trait ValidateExt: Validate {
+    /// Validates `self`, returning `self` if it's valid.
     /// ```
     /// # use not_so_fast::*;
-    /// let value = 9;
+    /// #[derive(Validate)]
+    /// struct Order {
+    ///     #[validate(range(min = 1))]
+    ///     quantity: u32,
+    /// }
     ///
-    /// // error if value is divisible by 3, 5, or 15
-    /// let errors_iter = [3, 5, 15]
-    ///     .into_iter()
-    ///     .filter_map(|divisor| (value % divisor == 0).then(|| {
-    ///         ValidationError::with_code("divisible").and_param("by", divisor)
-    ///     }));
+    /// fn total(order: &Order, price: u32) -> Result<u32, ValidationNode> {
+    ///     Ok(order.validate_or()?.quantity * price)
+    /// }
     ///
-    /// let errors = ValidationNode::errors(errors_iter);
-    /// assert!(errors.is_err());
-    /// assert_eq!(".: divisible: by=3", errors.to_string());
+    /// assert_eq!(Ok(6), total(&Order { quantity: 2 }, 3));
+    /// assert!(total(&Order { quantity: 0 }, 3).is_err());
     /// ```
-    pub fn errors(errors: impl Iterator<Item = ValidationError>) -> ValidationNode {
-        Self {
-            errors: errors.collect(),
-            fields: Default::default(),
-            items: Default::default(),
-        }
+    fn validate_or(&self) -> Result<&Self, ValidationNode> {
+        self.validate().result().map(|()| self)
     }
 
-    /// Adds value errors from `errors` iterator to `self`.
+    /// Validates `self`, returning `self` if it's valid, or `self` with
+    /// its errors otherwise, so that invalid values can still be used.
     /// ```
     /// # use not_so_fast::*;
-    /// let value = 9;
+    /// #[derive(Debug, Validate)]
+    /// struct Order {
+    ///     #[validate(range(min = 1))]
+    ///     quantity: u32,
+    /// }
     ///
-    /// // error if value is divisible by 3, 5, or 15
-    /// let errors_iter = [3, 5, 15]
-    ///     .into_iter()
-    ///     .filter_map(|divisor| (value % divisor == 0).then(|| {
-    ///         ValidationError::with_code("divisible").and_param("by", divisor)
-    ///     }));
+    /// let order = Order { quantity: 2 }.validate_owned().unwrap();
+    /// assert_eq!(2, order.quantity);
     ///
-    /// let errors = ValidationNode::ok().and_errors(errors_iter);
-    /// assert!(errors.is_err());
-    /// assert_eq!(".: divisible: by=3", errors.to_string());
+    /// let (order, errors) = Order { quantity: 0 }.validate_owned().unwrap_err();
+    /// assert_eq!(0, order.quantity);
+    /// assert_eq!(".quantity: range: Number not in range: min=1, value=0", errors.to_string());
     /// ```
-    pub fn and_errors(mut self, errors: impl Iterator<Item = ValidationError>) -> ValidationNode {
-        self.errors.extend(errors);
-        self
+    fn validate_owned(self) -> Result<Self, (Self, ValidationNode)>
+    where
+        Self: Sized,
+    {
+        match self.validate().result() {
+            Ok(()) => Ok(self),
+            Err(node) => Err((self, node)),
+        }
     }
 
-    /// Constructs `ValidationNode` with errors of one field. If
-    /// `validation_errors` is ok, the function also returns an ok node.
+    /// Validates `self`, returning errors if it's invalid.
     /// ```
     /// # use not_so_fast::*;
-    /// let errors = ValidationNode::field("a", ValidationNode::ok());
-    /// assert!(errors.is_ok());
+    /// #[derive(Validate)]
+    /// struct Order {
+    ///     #[validate(range(min = 1))]
+    ///     quantity: u32,
+    /// }
     ///
-    /// let errors = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("abc")));
-    /// assert!(errors.is_err());
-    /// assert_eq!(".a: abc", errors.to_string());
+    /// fn submit(order: &Order) -> Result<(), ValidationNode> {
+    ///     order.ensure_valid()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// assert!(submit(&Order { quantity: 0 }).is_err());
     /// ```
-    pub fn field(name: impl Into<Cow<'static, str>>, validation_errors: ValidationNode) -> Self {
-        Self {
-            errors: Default::default(),
-            fields: if !validation_errors.is_ok() {
-                let mut fields = BTreeMap::default();
-                fields.insert(name.into(), validation_errors);
-                fields
-            } else {
-                Default::default()
-            },
-            items: Default::default(),
+    fn ensure_valid(&self) -> Result<(), ValidationNode> {
+        self.validate().result()
+    }
+}
+
+impl<T: Validate + ?Sized> ValidateExt for T {}
+
+/// Object-safe counterpart of [Validate], for validating values of
+/// different types through trait objects, e.g. `Vec<Box<dyn ValidateDyn>>`.
+/// Implemented for all types implementing [Validate]. Trait objects
+/// implement [ValidateArgs] without arguments, so boxed and referenced
+/// trait objects implement [Validate] and can be validated as nested values.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(Validate)]
+/// struct Email {
+///     #[validate(char_length(max = 10))]
+///     address: String,
+/// }
+///
+/// #[derive(Validate)]
+/// struct Phone {
+///     #[validate(char_length(min = 7))]
+///     number: String,
+/// }
+///
+/// #[derive(Validate)]
+/// struct Contacts {
+///     #[validate(items(nested))]
+///     list: Vec<Box<dyn ValidateDyn>>,
+/// }
+///
+/// let contacts = Contacts {
+///     list: vec![
+///         Box::new(Email { address: "alice@example.com".into() }),
+///         Box::new(Phone { number: "12345".into() }),
+///     ],
+/// };
+/// assert_eq!(
+///     ".list[0].address: char_length: Invalid character length: max=10, value=17\n\
+///      .list[1].number: char_length: Invalid character length: min=7, value=5",
+///     contacts.validate().to_string(),
+/// );
+/// assert!(contacts.list[1].validate_dyn().is_err());
+/// ```
+pub trait ValidateDyn {
+    /// Validates `self`, like [Validate::validate] does.
+    fn validate_dyn(&self) -> ValidationNode;
+}
+
+impl<T: Validate> ValidateDyn for T {
+    fn validate_dyn(&self) -> ValidationNode {
+        self.validate()
+    }
+}
+
+macro_rules! impl_validate_args_dyn {
+    ($($bounds:tt)*) => {
+        impl<'arg> ValidateArgs<'arg> for dyn ValidateDyn $($bounds)* + '_ {
+            type Args = ();
+
+            fn validate_args(&self, (): ()) -> ValidationNode {
+                self.validate_dyn()
+            }
         }
+    };
+}
+
+impl_validate_args_dyn!();
+impl_validate_args_dyn!(+ Send);
+impl_validate_args_dyn!(+ Send + Sync);
+
+/// Validates every element of `values`, reporting errors of elements as
+/// items at their indices, e.g. for batch endpoints.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(Validate)]
+/// struct User {
+///     #[validate(range(max = 150))]
+///     age: u32,
+/// }
+///
+/// let users = vec![User { age: 30 }, User { age: 200 }];
+/// assert_eq!(
+///     ".[1].age: range: Number not in range: max=150, value=200",
+///     validate_all(&users).to_string(),
+/// );
+/// ```
+pub fn validate_all<I>(values: I) -> ValidationNode
+where
+    I: IntoIterator,
+    I::Item: Validate,
+{
+    values
+        .into_iter()
+        .enumerate()
+        .fold(ValidationNode::ok(), |node, (index, value)| {
+            node.and_item(index, value.validate())
+        })
+}
+
+/// Validates every element of `values` with `args`, reporting errors of
+/// elements as items at their indices, like [validate_all] does.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(Validate)]
+/// #[validate(args(max_age: u32))]
+/// struct User {
+///     #[validate(range(max = max_age))]
+///     age: u32,
+/// }
+///
+/// let users = [User { age: 30 }, User { age: 70 }];
+/// assert!(validate_all_args(&users, (100,)).is_ok());
+/// assert_eq!(
+///     ".[1].age: range: Number not in range: max=65, value=70",
+///     validate_all_args(&users, (65,)).to_string(),
+/// );
+/// ```
+pub fn validate_all_args<'arg, I>(
+    values: I,
+    args: <I::Item as ValidateArgs<'arg>>::Args,
+) -> ValidationNode
+where
+    I: IntoIterator,
+    I::Item: ValidateArgs<'arg>,
+    <I::Item as ValidateArgs<'arg>>::Args: Clone,
+{
+    values
+        .into_iter()
+        .enumerate()
+        .fold(ValidationNode::ok(), |node, (index, value)| {
+            node.and_item(index, value.validate_args(args.clone()))
+        })
+}
+
+/// Receiver of errors, an alternative to building [ValidationNode] trees
+/// for consumers that need only a part of the result. See
+/// [Validate::validate_into] and [ValidationNode::drain_into].
+///
+/// Implemented for [ValidationNode] (collects all errors into a tree),
+/// `Vec<(Path, ValidationError)>` (collects all errors into a list),
+/// [FirstError] and [ErrorCount].
+pub trait ErrorSink {
+    /// Receives `error` reported at `path`.
+    fn push(&mut self, path: Path, error: ValidationError);
+
+    /// Checks if the sink doesn't need more errors. Returns `false` by
+    /// default.
+    fn is_done(&self) -> bool {
+        false
+    }
+}
+
+impl ErrorSink for ValidationNode {
+    fn push(&mut self, path: Path, error: ValidationError) {
+        self.push_at(path, error);
     }
+}
 
-    /// Adds errors of one field to self. If self already contains errors for
-    /// that field, the errors will be merged. If `validation_errors` is ok,
-    /// the function will return self unchanged.
+impl ErrorSink for Vec<(Path, ValidationError)> {
+    fn push(&mut self, path: Path, error: ValidationError) {
+        Vec::push(self, (path, error));
+    }
+}
+
+/// [ErrorSink] keeping only the first error.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FirstError {
+    first: Option<(Path, ValidationError)>,
+}
+
+impl FirstError {
+    /// Returns path of the first error.
+    pub fn path(&self) -> Option<&Path> {
+        self.first.as_ref().map(|(path, _)| path)
+    }
+
+    /// Returns the first error.
+    pub fn error(&self) -> Option<&ValidationError> {
+        self.first.as_ref().map(|(_, error)| error)
+    }
+
+    /// Returns the first error and its path.
+    pub fn into_inner(self) -> Option<(Path, ValidationError)> {
+        self.first
+    }
+}
+
+impl ErrorSink for FirstError {
+    fn push(&mut self, path: Path, error: ValidationError) {
+        if self.first.is_none() {
+            self.first = Some((path, error));
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.first.is_some()
+    }
+}
+
+/// [ErrorSink] counting errors.
+/// ```
+/// # use not_so_fast::*;
+/// let errors = ValidationNode::error(ValidationError::with_code("abc"))
+///     .and_field("x", ValidationNode::error(ValidationError::with_code("def")));
+///
+/// let mut count = ErrorCount::default();
+/// errors.drain_into(&mut count);
+/// assert_eq!(2, count.count());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErrorCount {
+    count: usize,
+}
+
+impl ErrorCount {
+    /// Returns the number of received errors.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl ErrorSink for ErrorCount {
+    fn push(&mut self, _path: Path, _error: ValidationError) {
+        self.count += 1;
+    }
+}
+
+impl ValidationNode {
+    /// Passes errors of the tree to `sink`, in `Display` order, until the
+    /// sink is done.
     /// ```
     /// # use not_so_fast::*;
-    /// let errors = ValidationNode::ok().and_field("a", ValidationNode::ok());
-    /// assert!(errors.is_ok());
+    /// let errors = ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("abc")))
+    ///     .and_field("y", ValidationNode::error(ValidationError::with_code("def")));
     ///
-    /// let errors = ValidationNode::ok().and_field("a", ValidationNode::error(ValidationError::with_code("abc")));
-    /// assert!(errors.is_err());
+    /// let mut list = Vec::new();
+    /// errors.clone().drain_into(&mut list);
+    /// assert_eq!(2, list.len());
     ///
-    /// let errors = ValidationNode::ok()
-    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("abc")))
-    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("def")))
-    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("ghi")));
-    /// assert!(errors.is_err());
-    /// assert_eq!(".a: abc\n.a: def\n.b: ghi", errors.to_string());
+    /// let mut tree = ValidationNode::ok();
+    /// errors.clone().drain_into(&mut tree);
+    /// assert_eq!(errors, tree);
     /// ```
-    pub fn and_field(
+    pub fn drain_into(self, sink: &mut (impl ErrorSink + ?Sized)) {
+        for (path, error) in self {
+            if sink.is_done() {
+                return;
+            }
+            sink.push(path, error);
+        }
+    }
+}
+
+/// Codes of errors reported by built-in validators of the derive macro and
+/// by methods of [ValidationNode].
+pub mod codes {
+    /// Reported by `length` validator when the length of a collection is out
+    /// of bounds.
+    pub const LENGTH: &str = "length";
+    /// Reported by `char_length` validator when the number of characters of
+    /// a string is out of bounds.
+    pub const CHAR_LENGTH: &str = "char_length";
+    /// Reported by `range` validator when a number is out of range, and by
+    /// `ord_range` validator when a value is out of range.
+    pub const RANGE: &str = "range";
+    /// Reported by `before` validator when a date or time is not before the
+    /// limit.
+    pub const BEFORE: &str = "before";
+    /// Reported by `after` validator when a date or time is not after the
+    /// limit.
+    pub const AFTER: &str = "after";
+    /// Reported by `within_last` validator when a date or time is not within
+    /// the period before now.
+    pub const WITHIN_LAST: &str = "within_last";
+    /// Reported by `uuid_version` validator when a UUID has a different
+    /// version.
+    pub const UUID_VERSION: &str = "uuid_version";
+    /// Reported by `scheme_in` validator when a URL has a scheme not on the
+    /// list.
+    pub const SCHEME_IN: &str = "scheme_in";
+    /// Reported by `host_in` validator when a URL has a host not on the list
+    /// or no host.
+    pub const HOST_IN: &str = "host_in";
+    /// Reported by `port_in` validator when a URL has a port not on the list
+    /// or no port.
+    pub const PORT_IN: &str = "port_in";
+    /// Reported by `no_credentials` validator when a URL has a username or
+    /// a password.
+    pub const NO_CREDENTIALS: &str = "no_credentials";
+    /// Reported by `cidr` validator when a string is not an IP network in
+    /// CIDR notation.
+    pub const CIDR: &str = "cidr";
+    /// Reported by `prefix_len` validator when the prefix length of an IP
+    /// network is out of bounds.
+    pub const PREFIX_LEN: &str = "prefix_len";
+    /// Reported by `language_tag` validator when a string is not a BCP 47
+    /// language tag.
+    pub const LANGUAGE_TAG: &str = "language_tag";
+    /// Reported by `country_code` validator when a string is not an ISO
+    /// 3166-1 country code.
+    pub const COUNTRY_CODE: &str = "country_code";
+    /// Reported by `currency_code` validator when a string is not an ISO 4217
+    /// currency code.
+    pub const CURRENCY_CODE: &str = "currency_code";
+    /// Reported by `required` rule of [dynamic](crate::dynamic) validators
+    /// when a value is missing or null.
+    pub const REQUIRED: &str = "required";
+    /// Reported by `type` rule of [dynamic](crate::dynamic) validators when
+    /// a value has another JSON type.
+    pub const TYPE: &str = "type";
+    /// Reported by `one_of` rule of [dynamic](crate::dynamic) validators
+    /// when a value is not one of the allowed values.
+    pub const ONE_OF: &str = "one_of";
+    /// Reported by `ascii` rule of [StringRules](crate::rules::StringRules)
+    /// when a string contains non-ASCII characters.
+    pub const ASCII: &str = "ascii";
+    /// Reported by [ValidatorRegistry](crate::ValidatorRegistry) when no
+    /// validator of the value type is registered under the name.
+    pub const UNKNOWN_VALIDATOR: &str = "unknown_validator";
+    /// Reported by [ValidationNode::truncate](crate::ValidationNode::truncate)
+    /// when errors are removed.
+    pub const TRUNCATED: &str = "truncated";
+    /// Reported by [ValidationNode::any](crate::ValidationNode::any) when
+    /// none of the alternatives is valid.
+    pub const ANY: &str = "any";
+    /// Reported by [ValidationNode::nested](crate::ValidationNode::nested)
+    /// when [ValidationLimits](crate::ValidationLimits) maximum depth is
+    /// exceeded.
+    pub const DEPTH_EXCEEDED: &str = "depth_exceeded";
+    /// Reported for deserialization errors converted from
+    /// `serde_path_to_error::Error`.
+    pub const DESERIALIZE: &str = "deserialize";
+    /// Reported for errors converted from `garde::Report`, which have
+    /// messages but no codes.
+    pub const GARDE: &str = "garde";
+
+    /// All codes listed above.
+    pub const ALL: &[&str] = &[
+        LENGTH,
+        CHAR_LENGTH,
+        RANGE,
+        BEFORE,
+        AFTER,
+        WITHIN_LAST,
+        UUID_VERSION,
+        SCHEME_IN,
+        HOST_IN,
+        PORT_IN,
+        NO_CREDENTIALS,
+        CIDR,
+        PREFIX_LEN,
+        LANGUAGE_TAG,
+        COUNTRY_CODE,
+        CURRENCY_CODE,
+        REQUIRED,
+        TYPE,
+        ONE_OF,
+        ASCII,
+        UNKNOWN_VALIDATOR,
+        TRUNCATED,
+        ANY,
+        DEPTH_EXCEEDED,
+        DESERIALIZE,
+        GARDE,
+    ];
+}
+
+/// Set of error codes, e.g. codes a type can report. Registries of types
+/// implementing [ErrorCodes] are created with [CodeRegistry::of].
+/// ```
+/// # use not_so_fast::*;
+/// let registry = CodeRegistry::new()
+///     .with_code(codes::LENGTH)
+///     .with_code("alpha_only");
+///
+/// assert!(registry.contains("alpha_only"));
+/// assert!(!registry.contains(codes::RANGE));
+/// assert_eq!(vec!["alpha_only", "length"], registry.iter().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeRegistry {
+    codes: BTreeSet<Cow<'static, str>>,
+}
+
+impl CodeRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with codes of type `T`.
+    pub fn of<T: ErrorCodes + ?Sized>() -> Self {
+        let mut registry = Self::new();
+        T::register_codes(&mut registry);
+        registry
+    }
+
+    /// Adds `code` to the registry.
+    pub fn with_code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+        self.insert(code);
+        self
+    }
+
+    /// Adds `code` to the registry. Returns whether the code was not present.
+    pub fn insert(&mut self, code: impl Into<Cow<'static, str>>) -> bool {
+        self.codes.insert(code.into())
+    }
+
+    /// Checks if the registry contains `code`.
+    pub fn contains(&self, code: &str) -> bool {
+        self.codes.contains(code)
+    }
+
+    /// Returns iterator over codes in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.codes.iter().map(|code| code.as_ref())
+    }
+
+    /// Returns the number of codes.
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Checks if the registry has no codes.
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+}
+
+impl<C: Into<Cow<'static, str>>> Extend<C> for CodeRegistry {
+    fn extend<T: IntoIterator<Item = C>>(&mut self, iter: T) {
+        self.codes.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+impl<C: Into<Cow<'static, str>>> FromIterator<C> for CodeRegistry {
+    fn from_iter<T: IntoIterator<Item = C>>(iter: T) -> Self {
+        let mut registry = Self::new();
+        registry.extend(iter);
+        registry
+    }
+}
+
+/// Trait describing types that know codes of errors their validation can
+/// report. The derive macro implements it, registering codes of built-in
+/// validators applied to the type's fields. Codes reported by custom
+/// validators and by nested types are not known to the macro; add them to
+/// the registry manually if needed.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(Validate)]
+/// struct User {
+///     #[validate(char_length(max = 30))]
+///     nick: String,
+///     #[validate(items(range(max = 100)))]
+///     scores: Vec<u32>,
+/// }
+///
+/// let registry = CodeRegistry::of::<User>();
+/// assert_eq!(vec![codes::CHAR_LENGTH, codes::RANGE], registry.iter().collect::<Vec<_>>());
+///
+/// let registry = CodeRegistry::of::<User>().with_code("alpha_only");
+/// assert!(registry.contains("alpha_only"));
+/// ```
+pub trait ErrorCodes {
+    /// Adds codes of errors the type can report to `registry`.
+    fn register_codes(registry: &mut CodeRegistry);
+}
+
+/// Maps error codes and params to messages at render time. Lets applications
+/// localize messages, which validators (including the derive macro) set in
+/// English, or set messages where validators set none.
+///
+/// The trait is implemented for closures.
+/// ```
+/// # use not_so_fast::*;
+/// # use std::borrow::Cow;
+/// # use std::collections::BTreeMap;
+/// struct Polish;
+///
+/// impl MessageResolver for Polish {
+///     fn resolve(&self, code: &str, params: &BTreeMap<Cow<'static, str>, ParamValue>) -> Option<String> {
+///         match code {
+///             "length" => Some(format!("nieprawidłowa długość: {}", params.get("value")?)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let errors = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("length").and_message("Invalid length").and_param("value", 3)))
+///     .and_field("b", ValidationNode::error(ValidationError::with_code("abc").and_message("Abc")));
+///
+/// assert_eq!(
+///     ".a: length: nieprawidłowa długość: 3: value=3\n.b: abc: Abc",
+///     errors.display_resolved(&Polish).to_string(),
+/// );
+///
+/// let upper = |code: &str, _: &BTreeMap<Cow<'static, str>, ParamValue>| Some(code.to_uppercase());
+/// let options = DisplayOptions::new().with_params(false).with_separator("; ");
+/// assert_eq!(".a: length: LENGTH; .b: abc: ABC", errors.display_with(options).with_resolver(&upper).to_string());
+/// ```
+pub trait MessageResolver {
+    /// Returns message for an error with `code` and `params`, or `None` if
+    /// the error's own message should be used.
+    fn resolve(
+        &self,
+        code: &str,
+        params: &BTreeMap<Cow<'static, str>, ParamValue>,
+    ) -> Option<String>;
+}
+
+impl<F> MessageResolver for F
+where
+    F: Fn(&str, &BTreeMap<Cow<'static, str>, ParamValue>) -> Option<String>,
+{
+    fn resolve(
+        &self,
+        code: &str,
+        params: &BTreeMap<Cow<'static, str>, ParamValue>,
+    ) -> Option<String> {
+        self(code, params)
+    }
+}
+
+/// Maps error codes to message templates. Templates can reference error
+/// params by name, e.g. `"must be between {min} and {max}"`. String and char
+/// params are inserted without quotes. `{{` and `}}` print literal braces.
+/// References to missing params are printed as they are.
+///
+/// Catalog is a [MessageResolver], so it can be applied with
+/// [ValidationNode::render_with] or [ValidationNode::display_resolved].
+/// ```
+/// # use not_so_fast::*;
+/// let catalog = MessageCatalog::new()
+///     .with_template("range", "must be between {min} and {max}")
+///     .with_template("pattern", "must match {pattern} {{regex}}");
+///
+/// let errors = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("range").and_param("min", 1).and_param("max", 5)))
+///     .and_field("b", ValidationNode::error(ValidationError::with_code("pattern").and_param("pattern", "^a+$")))
+///     .and_field("c", ValidationNode::error(ValidationError::with_code("other").and_message("Other")));
+///
+/// assert_eq!(
+///     ".a: range: must be between 1 and 5: max=5, min=1\n.b: pattern: must match ^a+$ {regex}: pattern=\"^a+$\"\n.c: other: Other",
+///     errors.render_with(&catalog),
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MessageCatalog {
+    templates: BTreeMap<Cow<'static, str>, Cow<'static, str>>,
+}
+
+impl MessageCatalog {
+    /// Creates an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds template for errors with `code`, replacing the previous one.
+    pub fn with_template(
         mut self,
-        name: impl Into<Cow<'static, str>>,
-        validation_errors: ValidationNode,
+        code: impl Into<Cow<'static, str>>,
+        template: impl Into<Cow<'static, str>>,
     ) -> Self {
-        if !validation_errors.is_ok() {
-            match self.fields.entry(name.into()) {
-                Entry::Vacant(entry) => {
-                    entry.insert(validation_errors);
-                }
-                Entry::Occupied(mut entry) => entry.get_mut().merge_in_place(validation_errors),
-            }
-        }
+        self.insert(code, template);
         self
     }
 
-    /// Collects field errors from an iterator to (key, value) pairs and a
-    /// function transforming key and value references into validation errors.
+    /// Adds template for errors with `code`, returning the previous one.
     /// ```
     /// # use not_so_fast::*;
-    /// let map: std::collections::HashMap<String, u32> = [
-    ///     ("one".into(), 1),
-    ///     ("two".into(), 2),
-    ///     ("three".into(), 3),
-    /// ].into_iter().collect();
-    /// let errors = ValidationNode::fields(map.iter(), |_key, value| {
-    ///     ValidationNode::error_if(*value > 2, || ValidationError::with_code("abc"))
-    /// });
-    /// assert!(errors.is_err());
-    /// assert_eq!(".three: abc", errors.to_string());
+    /// let mut catalog = MessageCatalog::new();
+    /// assert_eq!(None, catalog.insert("length", "invalid length"));
+    /// assert_eq!(Some("invalid length".into()), catalog.insert("length", "must have {min} to {max} items"));
+    /// assert_eq!(Some("must have {min} to {max} items"), catalog.template("length"));
     /// ```
-    pub fn fields<'a, K: 'a, V: 'a>(
-        iterator: impl Iterator<Item = (&'a K, &'a V)>,
-        mut f: impl FnMut(&'a K, &'a V) -> ValidationNode,
-    ) -> Self
-    where
-        // The requirement for K here is not `impl Into<Cow<_, str>>` like in
-        // `field` or `and_field`. That's because this function is meant to be
-        // used with dynamic objects, like `HashMap`, whose keys might not
-        // implement `Into<Cow<_, str>>` (think i32, uuid::Uuid, etc.).
-        K: ToString,
-    {
-        iterator.fold(ValidationNode::ok(), |acc, (key, value)| {
-            let validation_errors = f(key, value);
+    pub fn insert(
+        &mut self,
+        code: impl Into<Cow<'static, str>>,
+        template: impl Into<Cow<'static, str>>,
+    ) -> Option<Cow<'static, str>> {
+        self.templates.insert(code.into(), template.into())
+    }
 
-            // Generate key string only if value has errors.
-            if !validation_errors.is_ok() {
-                let key_owned = Cow::Owned(key.to_string());
-                acc.and_field(key_owned, validation_errors)
-            } else {
-                acc
-            }
-        })
+    /// Returns template for errors with `code`.
+    pub fn template(&self, code: &str) -> Option<&str> {
+        self.templates.get(code).map(|template| template.as_ref())
     }
+}
 
-    /// Adds field errors collected the same way as in
-    /// [fields](ValidationNode::fields) method to self.
-    /// ```
-    /// # use not_so_fast::*;
-    /// let map: std::collections::HashMap<String, u32> = [
-    ///     ("one".into(), 1),
-    ///     ("two".into(), 2),
-    ///     ("three".into(), 3),
-    /// ].into_iter().collect();
-    /// let errors = ValidationNode::ok().and_fields(map.iter(), |_key, value| {
-    ///     ValidationNode::error_if(*value > 2, || ValidationError::with_code("abc"))
-    /// });
-    /// assert!(errors.is_err());
-    /// assert_eq!(".three: abc", errors.to_string());
-    /// ```
-    pub fn and_fields<'a, K: 'a, V: 'a>(
-        self,
-        iterator: impl Iterator<Item = (&'a K, &'a V)>,
-        f: impl FnMut(&'a K, &'a V) -> ValidationNode,
-    ) -> Self
-    where
-        K: ToString,
-    {
-        self.merge(Self::fields(iterator, f))
+impl MessageResolver for MessageCatalog {
+    fn resolve(
+        &self,
+        code: &str,
+        params: &BTreeMap<Cow<'static, str>, ParamValue>,
+    ) -> Option<String> {
+        let template = self.templates.get(code)?;
+        let mut message = String::with_capacity(template.len());
+        let mut rest = template.as_ref();
+        while let Some(start) = rest.find(['{', '}']) {
+            message.push_str(&rest[..start]);
+            let brace = &rest[start..start + 1];
+            rest = &rest[start + 1..];
+            if rest.starts_with(brace) {
+                message.push_str(brace);
+                rest = &rest[1..];
+                continue;
+            }
+            let param = match brace {
+                "{" => rest
+                    .find('}')
+                    .and_then(|end| Some((end, params.get(&rest[..end])?))),
+                _ => None,
+            };
+            match param {
+                Some((end, ParamValue::String(value))) => {
+                    message.push_str(value);
+                    rest = &rest[end + 1..];
+                }
+                Some((end, ParamValue::Char(value))) => {
+                    message.push(*value);
+                    rest = &rest[end + 1..];
+                }
+                Some((end, value)) => {
+                    write!(message, "{}", value).unwrap();
+                    rest = &rest[end + 1..];
+                }
+                None => message.push_str(brace),
+            }
+        }
+        message.push_str(rest);
+        Some(message)
     }
+}
 
-    /// Constructs `ValidationNode` with errors of one item. If
-    /// `validation_errors` is ok, the function also returns an ok node.
-    /// ```
-    /// # use not_so_fast::*;
-    /// let errors = ValidationNode::item(5, ValidationNode::ok());
-    /// assert!(errors.is_ok());
-    ///
-    /// let errors = ValidationNode::item(5, ValidationNode::error(ValidationError::with_code("abc")));
-    /// assert!(errors.is_err());
-    /// assert_eq!(".[5]: abc", errors.to_string());
-    /// ```
-    pub fn item(index: usize, validation_errors: ValidationNode) -> Self {
+/// Controls how [ValidationNode::display_with] renders errors. Default
+/// options produce the same output as [ValidationNode]'s `Display`.
+/// ```
+/// # use not_so_fast::*;
+/// let errors = ValidationNode::field("a", ValidationNode::item(3, ValidationNode::error(ValidationError::with_code("abc").and_message("Abc"))))
+///     .and_field("b", ValidationNode::error(ValidationError::with_code("def").and_param("max", 3)));
+///
+/// let options = DisplayOptions::new()
+///     .with_path_style(PathStyle::JsonPointer)
+///     .with_separator("; ")
+///     .with_messages(false)
+///     .with_params(false);
+/// assert_eq!("/a/3: abc; /b: def", errors.display_with(options).to_string());
+///
+/// let options = DisplayOptions::new().with_path_style(PathStyle::Dotted).with_max_errors(1);
+/// assert_eq!("a.3: abc: Abc\n... 1 more", errors.display_with(options).to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DisplayOptions {
+    path_style: PathStyle,
+    separator: Cow<'static, str>,
+    messages: bool,
+    params: bool,
+    max_errors: Option<usize>,
+    colors: bool,
+}
+
+/// Style of paths printed by [ValidationNode::display_with].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PathStyle {
+    /// `jq`-like paths, e.g. `.users[2].name`, `.`.
+    #[default]
+    Jq,
+    /// Dot-separated paths, e.g. `users.2.name`. Errors of the root node are
+    /// printed without path.
+    Dotted,
+    /// JSON pointers (RFC 6901), e.g. `/users/2/name`. Errors of the root
+    /// node are printed without path.
+    JsonPointer,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
         Self {
-            errors: Default::default(),
-            fields: Default::default(),
-            items: if !validation_errors.is_ok() {
-                let mut items = BTreeMap::default();
-                items.insert(index, validation_errors);
-                items
-            } else {
-                Default::default()
-            },
+            path_style: PathStyle::Jq,
+            separator: Cow::Borrowed("\n"),
+            messages: true,
+            params: true,
+            max_errors: None,
+            colors: false,
         }
     }
+}
 
-    /// Adds errors of one item to self. If self already contains errors for
-    /// that item, the errors will be merged. If `validation_errors` is ok,
-    /// the function will return self unchanged.
+impl DisplayOptions {
+    /// Creates default options: `jq`-like paths, one error per line, with
+    /// messages and params.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets style of paths.
+    pub fn with_path_style(mut self, path_style: PathStyle) -> Self {
+        self.path_style = path_style;
+        self
+    }
+
+    /// Sets string printed between errors. Defaults to a new line.
+    pub fn with_separator(mut self, separator: impl Into<Cow<'static, str>>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Sets whether error messages are printed.
+    pub fn with_messages(mut self, messages: bool) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Sets whether error params are printed.
+    pub fn with_params(mut self, params: bool) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Limits the number of printed errors. If some errors are skipped,
+    /// `... N more` is printed at the end.
+    pub fn with_max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    /// Sets whether errors are colored with ANSI escape codes: paths in
+    /// cyan, codes in red and params dimmed. Colors are disabled by default.
+    /// Requires the `cli` feature.
+    #[cfg(feature = "cli")]
+    pub fn with_colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+}
+
+/// Displays [ValidationNode] according to [DisplayOptions]. Created by
+/// [ValidationNode::display_with].
+#[derive(Clone)]
+pub struct DisplayWith<'a> {
+    node: &'a ValidationNode,
+    options: DisplayOptions,
+    resolver: Option<&'a dyn MessageResolver>,
+}
+
+impl<'a> DisplayWith<'a> {
+    /// Replaces error messages with messages returned by `resolver`. Errors
+    /// for which the resolver returns `None` keep their own message.
+    pub fn with_resolver(mut self, resolver: &'a dyn MessageResolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+}
+
+impl core::fmt::Debug for DisplayWith<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DisplayWith")
+            .field("node", self.node)
+            .field("options", &self.options)
+            .field("resolver", &self.resolver.map(|_| ".."))
+            .finish()
+    }
+}
+
+impl core::fmt::Display for DisplayWith<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        render_node(self.node, &self.options, self.resolver, f)
+    }
+}
+
+impl ValidationNode {
+    /// Returns a value displaying errors according to `options`. See
+    /// [DisplayOptions] for details.
+    pub fn display_with(&self, options: DisplayOptions) -> DisplayWith<'_> {
+        DisplayWith {
+            node: self,
+            options,
+            resolver: None,
+        }
+    }
+
+    /// Returns a value displaying errors like `Display` does, but with
+    /// messages resolved by `resolver`, e.g. translated to user's language.
+    /// See [MessageResolver] for details.
+    pub fn display_resolved<'a>(&'a self, resolver: &'a dyn MessageResolver) -> DisplayWith<'a> {
+        self.display_with(DisplayOptions::default())
+            .with_resolver(resolver)
+    }
+
+    /// Renders errors like `to_string` does, with messages resolved by
+    /// `resolver`, e.g. a [MessageCatalog].
+    pub fn render_with(&self, resolver: &dyn MessageResolver) -> String {
+        self.display_resolved(resolver).to_string()
+    }
+
+    /// Returns a value displaying errors like `Display` does, colored with
+    /// ANSI escape codes for terminals, see [DisplayOptions::with_colors].
+    /// Checking whether the output is a terminal is left to the caller.
+    /// Requires the `cli` feature.
     /// ```
     /// # use not_so_fast::*;
-    /// let errors = ValidationNode::ok().and_item(5, ValidationNode::ok());
-    /// assert!(errors.is_ok());
+    /// use std::io::IsTerminal;
     ///
-    /// let errors = ValidationNode::ok().and_item(5, ValidationNode::error(ValidationError::with_code("abc")));
-    /// assert!(errors.is_err());
+    /// let errors = ValidationNode::field(
+    ///     "age",
+    ///     ValidationNode::error(ValidationError::with_code("range").and_message("Too old").and_param("max", 150)),
+    /// );
+    /// assert_eq!(
+    ///     "\x1b[36m.age\x1b[0m: \x1b[31mrange\x1b[0m: Too old: \x1b[2mmax=150\x1b[0m",
+    ///     errors.display_colored().to_string(),
+    /// );
     ///
-    /// let errors = ValidationNode::ok()
-    ///     .and_item(5, ValidationNode::error(ValidationError::with_code("abc")))
-    ///     .and_item(5, ValidationNode::error(ValidationError::with_code("def")))
-    ///     .and_item(8, ValidationNode::error(ValidationError::with_code("ghi")));
-    /// assert!(errors.is_err());
-    /// assert_eq!(".[5]: abc\n.[5]: def\n.[8]: ghi", errors.to_string());
+    /// if std::io::stderr().is_terminal() {
+    ///     eprintln!("{}", errors.display_colored());
+    /// } else {
+    ///     eprintln!("{errors}");
+    /// }
     /// ```
-    pub fn and_item(mut self, index: usize, validation_errors: ValidationNode) -> Self {
-        if !validation_errors.is_ok() {
-            match self.items.entry(index) {
-                Entry::Vacant(entry) => {
-                    entry.insert(validation_errors);
-                }
-                Entry::Occupied(mut entry) => entry.get_mut().merge_in_place(validation_errors),
-            }
-        }
-        self
+    #[cfg(feature = "cli")]
+    pub fn display_colored(&self) -> DisplayWith<'_> {
+        self.display_with(DisplayOptions::default().with_colors(true))
     }
 
-    /// Collects item errors from an iterator to (index, value) pairs and a
-    /// function transforming index and value references into validation
-    /// errors.
+    /// Writes errors to `writer` according to `options`, without building
+    /// an intermediate string. Requires the `std` feature.
     /// ```
     /// # use not_so_fast::*;
-    /// let list: Vec<u32> = vec![10, 20, 30];
+    /// let errors = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("abc")));
     ///
-    /// let errors = ValidationNode::items(list.iter(), |_index, value| {
-    ///     ValidationNode::error_if(*value > 25, || ValidationError::with_code("abc"))
-    /// });
-    /// assert!(errors.is_err());
-    /// assert_eq!(".[2]: abc", errors.to_string());
+    /// let mut buffer = Vec::new();
+    /// errors.write_to(&mut buffer, &DisplayOptions::new()).unwrap();
+    /// assert_eq!(b".a: abc", buffer.as_slice());
     /// ```
-    pub fn items<'a, T: 'a>(
-        items: impl Iterator<Item = &'a T>,
-        mut f: impl FnMut(usize, &'a T) -> ValidationNode,
-    ) -> Self {
-        items
-            .enumerate()
-            .fold(ValidationNode::ok(), |acc, (index, item)| {
-                acc.and_item(index, f(index, item))
-            })
+    #[cfg(feature = "std")]
+    pub fn write_to(
+        &self,
+        writer: &mut (impl std::io::Write + ?Sized),
+        options: &DisplayOptions,
+    ) -> std::io::Result<()> {
+        let mut adapter = IoWriteAdapter {
+            writer,
+            error: None,
+        };
+        match render_node(self, options, None, &mut adapter) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(adapter
+                .error
+                .unwrap_or_else(|| std::io::Error::other("formatting error"))),
+        }
     }
 
-    /// Adds item errors collected the same way as in
-    /// [items](ValidationNode::items) method to self.
+    /// Writes errors to `writer` according to `options`, like
+    /// [write_to](ValidationNode::write_to) does, but to a [std::fmt::Write]
+    /// implementation, e.g. a reused `String`.
     /// ```
     /// # use not_so_fast::*;
-    /// let list = vec![10, 20, 30];
+    /// let errors = ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("abc")));
     ///
-    /// let errors = ValidationNode::ok().and_items(list.iter(), |_index, value| {
-    ///     ValidationNode::error_if(*value > 25, || ValidationError::with_code("abc"))
-    /// });
-    /// assert!(errors.is_err());
-    /// assert_eq!(".[2]: abc", errors.to_string());
+    /// let mut buffer = String::new();
+    /// errors.write_to_fmt(&mut buffer, &DisplayOptions::new()).unwrap();
+    /// assert_eq!(".a: abc", buffer);
     /// ```
-    pub fn and_items<'a, T: 'a>(
-        self,
-        items: impl Iterator<Item = &'a T>,
-        f: impl FnMut(usize, &'a T) -> ValidationNode,
-    ) -> Self {
-        self.merge(Self::items(items, f))
+    pub fn write_to_fmt(
+        &self,
+        writer: &mut (impl Write + ?Sized),
+        options: &DisplayOptions,
+    ) -> core::fmt::Result {
+        render_node(self, options, None, writer)
     }
+}
 
-    /// Returns [ValidationNode] with only the first error, or an ok node
-    /// it there are no errors.
+impl ValidationNode {
+    /// Renders errors like `to_string` does, but keeps the result within
+    /// `max_bytes`. Errors that don't fit are replaced with `... N more`
+    /// marker. Errors are never cut in half. If not even the marker fits, an
+    /// empty string is returned.
     /// ```
     /// # use not_so_fast::*;
-    /// let errors = ValidationNode::ok()
-    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("1")))
-    ///     .and_field("a", ValidationNode::error(ValidationError::with_code("2")))
-    ///     .and_field("b", ValidationNode::error(ValidationError::with_code("3")));
-    /// assert_eq!(".a: 1\n.a: 2\n.b: 3", errors.to_string());
+    /// let errors = ValidationNode::items([1, 2, 3, 4].iter(), |_index, _value| {
+    ///     ValidationNode::error(ValidationError::with_code("abc"))
+    /// });
     ///
-    /// let first = errors.first();
-    /// assert_eq!(".a: 1", first.to_string());
+    /// assert_eq!(".[0]: abc\n.[1]: abc\n... 2 more", errors.to_string_budgeted(30));
+    /// assert_eq!(errors.to_string(), errors.to_string_budgeted(1000));
     /// ```
-    pub fn first(mut self) -> Self {
-        if !self.errors.is_empty() {
-            Self {
-                errors: vec![self.errors.remove(0)],
-                fields: Default::default(),
-                items: Default::default(),
+    pub fn to_string_budgeted(&self, max_bytes: usize) -> String {
+        let mut output = String::new();
+        // Offsets at which lines start, used to make room for the marker.
+        let mut line_starts = Vec::new();
+        let mut line = String::new();
+        let mut total = 0;
+
+        for (path, error) in self.iter() {
+            total += 1;
+            if line_starts.len() + 1 < total {
+                continue;
             }
-        } else if !self.fields.is_empty() {
-            Self {
-                errors: Default::default(),
-                fields: self
-                    .fields
-                    .into_iter()
-                    .map(|(key, errors)| (key, errors.first()))
-                    .take(1)
-                    .collect(),
-                items: Default::default(),
+            line.clear();
+            write!(line, "{}: {}", path, error).unwrap();
+            let separator = usize::from(!output.is_empty());
+            if output.len() + separator + line.len() <= max_bytes {
+                line_starts.push(output.len());
+                if separator > 0 {
+                    output.push('\n');
+                }
+                output.push_str(&line);
             }
-        } else if !self.items.is_empty() {
-            Self {
-                errors: Default::default(),
-                fields: Default::default(),
-                items: self
-                    .items
-                    .into_iter()
-                    .map(|(index, errors)| (index, errors.first()))
-                    .take(1)
-                    .collect(),
+        }
+
+        loop {
+            let omitted = total - line_starts.len();
+            if omitted == 0 {
+                return output;
+            }
+            let marker = format!("... {} more", omitted);
+            let separator = usize::from(!output.is_empty());
+            if output.len() + separator + marker.len() <= max_bytes {
+                if separator > 0 {
+                    output.push('\n');
+                }
+                output.push_str(&marker);
+                return output;
+            }
+            match line_starts.pop() {
+                Some(start) => output.truncate(start),
+                None => return String::new(),
             }
-        } else {
-            Self::ok()
         }
     }
 }
 
-/// Trait describing types that can be validated without arguments. It is
-/// automatically implemented for all types that implement `ValidateArgs<Args=()>`.
-pub trait Validate {
-    fn validate(&self) -> ValidationNode;
-}
-
-/// Trait describing types that can be validated with arguments.
-pub trait ValidateArgs<'arg> {
-    type Args;
-    fn validate_args(&self, args: Self::Args) -> ValidationNode;
-}
-
-impl<'a, T> Validate for T
-where
-    T: ValidateArgs<'a, Args = ()>,
-{
-    fn validate(&self) -> ValidationNode {
-        self.validate_args(())
+/// Forwards formatted output to [std::io::Write], keeping the first IO error.
+#[cfg(feature = "std")]
+struct IoWriteAdapter<'a, W: std::io::Write + ?Sized> {
+    writer: &'a mut W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> Write for IoWriteAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.writer.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            core::fmt::Error
+        })
     }
 }
 
-impl std::fmt::Display for ValidationNode {
+impl core::fmt::Display for ValidationNode {
     /// Prints validation errors, one per line with `jq`-like path and an error
     /// description.
     /// ```text
@@ -731,50 +4919,223 @@ impl std::fmt::Display for ValidationNode {
     /// .abc[4]: length: illegal string length: min=10, max=20, value=34
     /// .def.ghi: test
     /// ```
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut path = Vec::new();
-        display_fmt(self, &mut path, &mut false, f)
+    ///
+    /// The alternate flag (`{:#}`) prints errors as an indented tree instead.
+    /// ```text
+    /// - invariant_x: property x is not greater than property y
+    /// abc:
+    ///   [4]:
+    ///     - length: illegal string length: min=10, max=20, value=34
+    /// def:
+    ///   ghi:
+    ///     - test
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            display_tree_fmt(self, 0, &mut false, f)
+        } else {
+            render_node(self, &DisplayOptions::default(), None, f)
+        }
     }
 }
 
+impl core::error::Error for ValidationNode {}
+
 enum PathElement<'a> {
     Name(&'a str),
     Index(usize),
+    Key(&'a str),
+}
+
+impl<'a> From<&'a PathSegment> for PathElement<'a> {
+    fn from(segment: &'a PathSegment) -> Self {
+        match segment {
+            PathSegment::Field(name) => PathElement::Name(name),
+            PathSegment::Index(index) => PathElement::Index(*index),
+            PathSegment::Key(key) => PathElement::Key(key),
+        }
+    }
+}
+
+/// Number of printed and omitted errors.
+#[derive(Default)]
+struct RenderState {
+    printed: usize,
+    omitted: usize,
+}
+
+fn render_node(
+    node: &ValidationNode,
+    options: &DisplayOptions,
+    resolver: Option<&dyn MessageResolver>,
+    f: &mut (impl Write + ?Sized),
+) -> core::fmt::Result {
+    let mut state = RenderState::default();
+    render(node, &mut String::new(), &mut state, options, resolver, f)?;
+    if state.omitted > 0 {
+        if state.printed > 0 {
+            f.write_str(&options.separator)?;
+        }
+        write!(f, "... {} more", state.omitted)?;
+    }
+    Ok(())
 }
 
-fn display_fmt<'s, 'p>(
-    node: &'s ValidationNode,
-    path: &'p mut Vec<PathElement<'s>>,
-    first_printed: &'p mut bool,
-    f: &mut std::fmt::Formatter,
-) -> std::fmt::Result {
+/// Renders errors of `node` and its descendants. `path` holds the path of
+/// `node` already formatted in the requested style, extended in place for
+/// children, so that it's formatted once per node rather than per error.
+fn render(
+    node: &ValidationNode,
+    path: &mut String,
+    state: &mut RenderState,
+    options: &DisplayOptions,
+    resolver: Option<&dyn MessageResolver>,
+    f: &mut (impl Write + ?Sized),
+) -> core::fmt::Result {
     for direct in node.errors.iter() {
-        if *first_printed {
-            f.write_char('\n')?;
-            fmt_path(path.as_slice(), f)?;
+        if options.max_errors.is_some_and(|max| state.printed >= max) {
+            state.omitted += 1;
+            continue;
+        }
+        if state.printed > 0 {
+            f.write_str(&options.separator)?;
+        }
+        let path = match (path.is_empty(), options.path_style) {
+            (false, _) => Some(path.as_str()),
+            (true, PathStyle::Jq) => Some("."),
+            (true, _) => None,
+        };
+        if let Some(path) = path {
+            write_colored(f, path, ANSI_CYAN, options.colors)?;
             f.write_str(": ")?;
-            fmt_error(direct, f)?;
+        }
+        let resolved = resolver
+            .filter(|_| options.messages)
+            .and_then(|resolver| resolver.resolve(&direct.code, direct.param_map()));
+        let message = resolved
+            .as_deref()
+            .or(direct.message())
+            .filter(|_| options.messages);
+        if options.colors {
+            fmt_error_colored(direct, message, options.params, f)?;
         } else {
-            fmt_path(path.as_slice(), f)?;
-            f.write_str(": ")?;
-            fmt_error(direct, f)?;
-            *first_printed = true;
+            fmt_error_with(direct, message, options.params, f)?;
+        }
+        state.printed += 1;
+    }
+    let len = path.len();
+    for (name, field) in node.ordered_fields() {
+        push_path_element(path, &PathElement::Name(name), options.path_style)?;
+        render(field, path, state, options, resolver, f)?;
+        path.truncate(len);
+    }
+    for (key, item) in node.items.iter() {
+        let element = match key {
+            ItemKey::Index(index) => PathElement::Index(*index),
+            ItemKey::Key(key) => PathElement::Key(key),
+        };
+        push_path_element(path, &element, options.path_style)?;
+        render(item, path, state, options, resolver, f)?;
+        path.truncate(len);
+    }
+    Ok(())
+}
+
+/// Appends `element` to `path` formatted in `style`. Unlike the other
+/// styles, `jq`-like paths represent the root, as `.`, which is not written
+/// here.
+fn push_path_element(
+    path: &mut String,
+    element: &PathElement,
+    style: PathStyle,
+) -> core::fmt::Result {
+    match style {
+        PathStyle::Jq => {
+            if path.is_empty() || matches!(element, PathElement::Name(_)) {
+                path.push('.');
+            }
+            fmt_path_element(element, path)
+        }
+        PathStyle::Dotted => {
+            if !path.is_empty() {
+                path.push('.');
+            }
+            match element {
+                PathElement::Name(name) | PathElement::Key(name) => path.push_str(name),
+                PathElement::Index(index) => write!(path, "{}", index)?,
+            }
+            Ok(())
+        }
+        PathStyle::JsonPointer => {
+            path.push('/');
+            match element {
+                PathElement::Name(name) | PathElement::Key(name) => {
+                    let mut rest = *name;
+                    while let Some(index) = rest.find(['~', '/']) {
+                        path.push_str(&rest[..index]);
+                        path.push_str(if &rest[index..index + 1] == "~" {
+                            "~0"
+                        } else {
+                            "~1"
+                        });
+                        rest = &rest[index + 1..];
+                    }
+                    path.push_str(rest);
+                }
+                PathElement::Index(index) => write!(path, "{}", index)?,
+            }
+            Ok(())
+        }
+    }
+}
+
+fn display_tree_fmt(
+    node: &ValidationNode,
+    depth: usize,
+    first_printed: &mut bool,
+    f: &mut core::fmt::Formatter,
+) -> core::fmt::Result {
+    for direct in node.errors.iter() {
+        fmt_tree_line(depth, first_printed, f)?;
+        f.write_str("- ")?;
+        fmt_error(direct, f)?;
+    }
+    for (name, field) in node.ordered_fields() {
+        fmt_tree_line(depth, first_printed, f)?;
+        fmt_path_element(&PathElement::Name(name), f)?;
+        f.write_char(':')?;
+        display_tree_fmt(field, depth + 1, first_printed, f)?;
+    }
+    for (key, item) in node.items.iter() {
+        fmt_tree_line(depth, first_printed, f)?;
+        match key {
+            ItemKey::Index(index) => fmt_path_element(&PathElement::Index(*index), f)?,
+            ItemKey::Key(key) => fmt_path_element(&PathElement::Key(key), f)?,
         }
+        f.write_char(':')?;
+        display_tree_fmt(item, depth + 1, first_printed, f)?;
     }
-    for field in node.fields.iter() {
-        path.push(PathElement::Name(field.0));
-        display_fmt(field.1, path, first_printed, f)?;
-        path.pop();
+    Ok(())
+}
+
+/// Starts a new line of the tree output, indented to `depth`.
+fn fmt_tree_line(
+    depth: usize,
+    first_printed: &mut bool,
+    f: &mut core::fmt::Formatter,
+) -> core::fmt::Result {
+    if *first_printed {
+        f.write_char('\n')?;
+    } else {
+        *first_printed = true;
     }
-    for item in node.items.iter() {
-        path.push(PathElement::Index(*item.0));
-        display_fmt(item.1, path, first_printed, f)?;
-        path.pop();
+    for _ in 0..depth {
+        f.write_str("  ")?;
     }
     Ok(())
 }
 
-fn fmt_path(path: &[PathElement], f: &mut std::fmt::Formatter) -> std::fmt::Result {
+fn fmt_path(path: &[PathElement], f: &mut (impl Write + ?Sized)) -> core::fmt::Result {
     if path.is_empty() {
         return f.write_char('.');
     }
@@ -784,7 +5145,7 @@ fn fmt_path(path: &[PathElement], f: &mut std::fmt::Formatter) -> std::fmt::Resu
                 f.write_char('.')?;
                 fmt_path_element(element, f)?;
             }
-            PathElement::Index(_) => {
+            PathElement::Index(_) | PathElement::Key(_) => {
                 if i == 0 {
                     f.write_char('.')?;
                 }
@@ -795,37 +5156,59 @@ fn fmt_path(path: &[PathElement], f: &mut std::fmt::Formatter) -> std::fmt::Resu
     Ok(())
 }
 
-fn fmt_path_element(element: &PathElement, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+fn fmt_path_element(element: &PathElement, f: &mut (impl Write + ?Sized)) -> core::fmt::Result {
     match element {
         PathElement::Name(name) => {
-            if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            if !name.is_empty() && name.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_') {
                 f.write_str(name)?;
             } else {
-                f.write_char('"')?;
-                for c in name.chars() {
-                    if c == '"' {
-                        f.write_str("\\\"")?;
-                    } else {
-                        f.write_char(c)?;
-                    }
-                }
-                f.write_char('"')?;
+                fmt_quoted(name, f)?;
             }
         }
         PathElement::Index(index) => {
             write!(f, "[{}]", index)?;
         }
+        PathElement::Key(key) => {
+            f.write_char('[')?;
+            fmt_quoted(key, f)?;
+            f.write_char(']')?;
+        }
     }
     Ok(())
 }
 
-fn fmt_error(error: &ValidationError, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+fn fmt_quoted(value: &str, f: &mut (impl Write + ?Sized)) -> core::fmt::Result {
+    f.write_char('"')?;
+    let mut rest = value;
+    while let Some(index) = rest.find(['"', '\\']) {
+        f.write_str(&rest[..index])?;
+        f.write_char('\\')?;
+        f.write_str(&rest[index..index + 1])?;
+        rest = &rest[index + 1..];
+    }
+    f.write_str(rest)?;
+    f.write_char('"')
+}
+
+fn fmt_error(error: &ValidationError, f: &mut (impl Write + ?Sized)) -> core::fmt::Result {
+    fmt_error_with(error, error.message(), true, f)
+}
+
+fn fmt_error_with(
+    error: &ValidationError,
+    message: Option<&str>,
+    params: bool,
+    f: &mut (impl Write + ?Sized),
+) -> core::fmt::Result {
     f.write_str(error.code.as_ref())?;
-    if let Some(message) = &error.message {
+    if let Some(message) = message {
         f.write_str(": ")?;
-        f.write_str(message.as_ref())?;
+        f.write_str(message)?;
+    }
+    if !params {
+        return Ok(());
     }
-    for (i, param) in error.params.iter().enumerate() {
+    for (i, param) in error.param_map().iter().enumerate() {
         if i != 0 {
             f.write_str(", ")?;
         } else {
@@ -838,11 +5221,64 @@ fn fmt_error(error: &ValidationError, f: &mut std::fmt::Formatter) -> std::fmt::
     Ok(())
 }
 
+const ANSI_CYAN: &str = "\x1b[36m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Writes `text`, wrapped in ANSI escape codes of `color` if `colors` is set.
+fn write_colored(
+    f: &mut (impl Write + ?Sized),
+    text: &str,
+    color: &str,
+    colors: bool,
+) -> core::fmt::Result {
+    if colors {
+        f.write_str(color)?;
+        f.write_str(text)?;
+        f.write_str(ANSI_RESET)
+    } else {
+        f.write_str(text)
+    }
+}
+
+/// Writes error like [fmt_error_with] does, with the code in red and params
+/// dimmed.
+fn fmt_error_colored(
+    error: &ValidationError,
+    message: Option<&str>,
+    params: bool,
+    f: &mut (impl Write + ?Sized),
+) -> core::fmt::Result {
+    write_colored(f, error.code.as_ref(), ANSI_RED, true)?;
+    if let Some(message) = message {
+        f.write_str(": ")?;
+        f.write_str(message)?;
+    }
+    if !params || error.param_map().is_empty() {
+        return Ok(());
+    }
+    f.write_str(": ")?;
+    f.write_str(ANSI_DIM)?;
+    for (i, param) in error.param_map().iter().enumerate() {
+        if i != 0 {
+            f.write_str(", ")?;
+        }
+        f.write_str(param.0)?;
+        f.write_str("=")?;
+        write!(f, "{}", param.1)?;
+    }
+    f.write_str(ANSI_RESET)
+}
+
 #[cfg(feature = "serde")]
 mod serde {
-    use std::fmt::Write;
+    #[cfg(not(feature = "std"))]
+    use alloc::{borrow::ToOwned, string::String, vec::Vec};
 
-    use super::{ValidationError, ValidationNode};
+    use super::{
+        flat_path, ItemKey, Message, ParamValue, Severity, ValidationError, ValidationNode,
+    };
 
     impl serde::Serialize for ValidationNode {
         /// Serializes validation node into a tree reflecting the structure
@@ -867,23 +5303,17 @@ mod serde {
         /// }
         /// ```
         fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-            // Every value error has to be rendered to a nice message string.
-            // To reduce the number of allocations, we crete a buffer at the
-            // root for the entire tree and reuse it. Unfortunately, serde does
-            // not allow passing mutable data down to serializers, so we'll
-            // pass mutable pointer and cast it to mut reference with unsafe.
-            let mut buffer = String::new();
-            SerializableValidationNode(self, &mut buffer).serialize(serializer)
+            SerializableValidationNode(self).serialize(serializer)
         }
     }
 
-    struct SerializableValidationNode<'a>(&'a ValidationNode, *mut String);
+    struct SerializableValidationNode<'a>(&'a ValidationNode);
 
     impl<'a> serde::Serialize for SerializableValidationNode<'a> {
         fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
             use serde::ser::SerializeMap;
 
-            let (node, buffer) = (self.0, self.1);
+            let node = self.0;
 
             let entries =
                 usize::from(!node.errors.is_empty()) + node.fields.len() + node.items.len();
@@ -891,72 +5321,559 @@ mod serde {
             let mut map = serializer.serialize_map(Some(entries))?;
 
             if !node.errors.is_empty() {
-                map.serialize_entry(
-                    "errors",
-                    &SerializableValidationErrors(&node.errors, buffer),
-                )?;
+                map.serialize_entry("errors", &SerializableValidationErrors(&node.errors))?;
             }
-            for (name, field) in &node.fields {
-                map.serialize_entry(name, &SerializableValidationNode(field, buffer))?;
+            for (name, field) in node.ordered_fields() {
+                map.serialize_entry(name, &SerializableValidationNode(field))?;
             }
-            for (index, item) in &node.items {
-                map.serialize_entry(index, &SerializableValidationNode(item, buffer))?;
+            for (key, item) in &node.items {
+                match key {
+                    ItemKey::Index(index) => {
+                        map.serialize_entry(index, &SerializableValidationNode(item))?
+                    }
+                    ItemKey::Key(key) => {
+                        map.serialize_entry(key, &SerializableValidationNode(item))?
+                    }
+                }
             }
 
             map.end()
         }
     }
 
-    struct SerializableValidationErrors<'a>(&'a [ValidationError], *mut String);
+    struct SerializableValidationErrors<'a>(&'a [ValidationError]);
 
     impl<'a> serde::Serialize for SerializableValidationErrors<'a> {
         fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
             use serde::ser::SerializeSeq;
 
-            let (errors, buffer) = (self.0, self.1);
+            let errors = self.0;
 
             let mut seq = serializer.serialize_seq(Some(errors.len()))?;
 
             for error in errors {
-                seq.serialize_element(&SerializableValidationError(error, buffer))?;
+                seq.serialize_element(&SerializableValidationError(error))?;
             }
 
             seq.end()
         }
     }
 
-    struct SerializableValidationError<'a>(&'a ValidationError, *mut String);
+    struct SerializableValidationError<'a>(&'a ValidationError);
 
     impl<'a> serde::Serialize for SerializableValidationError<'a> {
         fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-            let (error, buffer) = (self.0, self.1);
+            // Error's `Display` output is streamed into the serializer, which
+            // for most formats avoids rendering the error into a `String`.
+            serializer.collect_str(self.0)
+        }
+    }
+
+    impl ValidationNode {
+        /// Returns a wrapper serializing the node into an alternative,
+        /// structured format. See [NodeAsTree] for details.
+        pub fn as_tree(&self) -> NodeAsTree<'_> {
+            NodeAsTree(self)
+        }
+
+        /// Returns a wrapper serializing the node into a flat list of errors.
+        /// See [NodeAsFlatList] for details.
+        pub fn as_flat_list(&self) -> NodeAsFlatList<'_> {
+            NodeAsFlatList(self)
+        }
+    }
+
+    /// Wrapper serializing [ValidationNode] into a nested object with
+    /// structured errors. Unlike the default format, field names can't clash
+    /// with the `errors` key and error parameters keep their types. Empty
+    /// entries are omitted.
+    ///
+    /// ```json
+    /// {
+    ///     "errors": [
+    ///         { "code": "invariant_x", "message": "property x is not greater than property y" }
+    ///     ],
+    ///     "fields": {
+    ///         "abc": {
+    ///             "items": {
+    ///                 "4": {
+    ///                     "errors": [
+    ///                         { "code": "length", "params": { "min": 10, "max": 20, "value": 34 } }
+    ///                     ]
+    ///                 }
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field(
+    ///     "abc",
+    ///     ValidationNode::item(4, ValidationNode::error(ValidationError::with_code("length").and_param("max", 20))),
+    /// );
+    /// assert_eq!(
+    ///     r#"{"fields":{"abc":{"items":{"4":{"errors":[{"code":"length","params":{"max":20}}]}}}}}"#,
+    ///     serde_json::to_string(&errors.as_tree()).unwrap(),
+    /// );
+    /// ```
+    #[derive(Debug, Clone, Copy)]
+    pub struct NodeAsTree<'a>(pub &'a ValidationNode);
+
+    impl<'a> serde::Serialize for NodeAsTree<'a> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeMap;
 
-            // This is a workaround for serde's serialization API giving us
-            // only immutable data. I can't think of any case where this could
-            // lead to undefined behavior.
-            let buffer = unsafe { buffer.as_mut().unwrap() };
+            let node = self.0;
+            let indexed = node
+                .items
+                .iter()
+                .filter(|(key, _)| matches!(key, ItemKey::Index(_)))
+                .count();
+            let keyed = node.items.len() - indexed;
 
-            buffer.write_str(&error.code).unwrap();
+            let entries = usize::from(!node.errors.is_empty())
+                + usize::from(!node.fields.is_empty())
+                + usize::from(indexed > 0)
+                + usize::from(keyed > 0)
+                + usize::from(node.order.is_some());
 
-            if let Some(message) = &error.message {
-                buffer.write_str(": ").unwrap();
-                buffer.write_str(message).unwrap();
+            let mut map = serializer.serialize_map(Some(entries))?;
+
+            if !node.errors.is_empty() {
+                map.serialize_entry("errors", &*node.errors)?;
+            }
+            if !node.fields.is_empty() {
+                map.serialize_entry("fields", &TreeFields(node))?;
+            }
+            if indexed > 0 {
+                map.serialize_entry("items", &TreeItems(node, false, indexed))?;
+            }
+            if keyed > 0 {
+                map.serialize_entry("keyed_items", &TreeItems(node, true, keyed))?;
+            }
+            if let Some(order) = node.order {
+                map.serialize_entry("order", &order)?;
             }
 
-            for (i, param) in error.params.iter().enumerate() {
-                if i == 0 {
-                    buffer.write_str(": ").unwrap();
-                } else {
-                    buffer.write_str(", ").unwrap();
+            map.end()
+        }
+    }
+
+    /// Wrapper serializing [ValidationNode] into a list of structured errors
+    /// in `Display` order. Each error gets a `path` member, formatted like
+    /// keys of [ValidationNode::flatten], next to members of the serialized
+    /// [ValidationError].
+    ///
+    /// ```json
+    /// [
+    ///     { "path": "", "code": "invariant_x" },
+    ///     { "path": "abc[4]", "code": "length", "params": { "max": 20, "value": 34 } }
+    /// ]
+    /// ```
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("invariant_x"))
+    ///     .and_field("abc", ValidationNode::item(4, ValidationNode::error(ValidationError::with_code("length").and_param("max", 20))));
+    /// assert_eq!(
+    ///     r#"[{"path":"","code":"invariant_x"},{"path":"abc[4]","code":"length","params":{"max":20}}]"#,
+    ///     serde_json::to_string(&errors.as_flat_list()).unwrap(),
+    /// );
+    /// ```
+    #[derive(Debug, Clone, Copy)]
+    pub struct NodeAsFlatList<'a>(pub &'a ValidationNode);
+
+    impl<'a> serde::Serialize for NodeAsFlatList<'a> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(
+                self.0
+                    .iter()
+                    .map(|(path, error)| FlatError(flat_path(&path), error)),
+            )
+        }
+    }
+
+    struct FlatError<'a>(String, &'a ValidationError);
+
+    impl<'a> serde::Serialize for FlatError<'a> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeMap;
+
+            let (path, error) = (&self.0, self.1);
+            let mut map = serializer.serialize_map(None)?;
+            map.serialize_entry("path", path)?;
+            map.serialize_entry("code", &error.code)?;
+            if let Some(message) = error.message() {
+                map.serialize_entry("message", message)?;
+            }
+            if !error.param_map().is_empty() {
+                map.serialize_entry("params", error.param_map())?;
+            }
+            if error.severity() != Severity::Error {
+                map.serialize_entry("severity", error.severity().as_str())?;
+            }
+            if !error.tag_set().is_empty() {
+                map.serialize_entry("tags", error.tag_set())?;
+            }
+            map.end()
+        }
+    }
+
+    struct TreeFields<'a>(&'a ValidationNode);
+
+    impl<'a> serde::Serialize for TreeFields<'a> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(
+                self.0
+                    .ordered_fields()
+                    .map(|(name, field)| (name, NodeAsTree(field))),
+            )
+        }
+    }
+
+    /// Items of a node, either indexed or keyed, with their count.
+    struct TreeItems<'a>(&'a ValidationNode, bool, usize);
+
+    impl<'a> serde::Serialize for TreeItems<'a> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeMap;
+
+            let (node, keyed, len) = (self.0, self.1, self.2);
+
+            let mut map = serializer.serialize_map(Some(len))?;
+
+            for (key, item) in &node.items {
+                match key {
+                    ItemKey::Index(index) if !keyed => {
+                        map.serialize_entry(index, &NodeAsTree(item))?
+                    }
+                    ItemKey::Key(key) if keyed => map.serialize_entry(key, &NodeAsTree(item))?,
+                    _ => {}
+                }
+            }
+
+            map.end()
+        }
+    }
+
+    impl serde::Serialize for ValidationError {
+        /// Serializes validation error into an object with code, optional
+        /// message and params.
+        ///
+        /// ```json
+        /// { "code": "length", "message": "Invalid length", "params": { "max": 20, "value": 34 } }
+        /// ```
+        ///
+        /// Severity other than `error` is serialized as `"severity": "warning"`
+        /// or `"severity": "info"`. Tags, if present, are serialized as
+        /// `"tags": ["pii", "security"]`.
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeStruct;
+
+            let len = 1
+                + usize::from(self.message.is_some())
+                + usize::from(!self.param_map().is_empty())
+                + usize::from(self.severity() != Severity::Error)
+                + usize::from(!self.tag_set().is_empty());
+            let mut error = serializer.serialize_struct("ValidationError", len)?;
+            error.serialize_field("code", &self.code)?;
+            if let Some(message) = self.message() {
+                error.serialize_field("message", message)?;
+            } else {
+                error.skip_field("message")?;
+            }
+            if !self.param_map().is_empty() {
+                error.serialize_field("params", self.param_map())?;
+            } else {
+                error.skip_field("params")?;
+            }
+            if self.severity() != Severity::Error {
+                error.serialize_field("severity", self.severity().as_str())?;
+            } else {
+                error.skip_field("severity")?;
+            }
+            if !self.tag_set().is_empty() {
+                error.serialize_field("tags", self.tag_set())?;
+            } else {
+                error.skip_field("tags")?;
+            }
+            error.end()
+        }
+    }
+
+    impl serde::Serialize for ParamValue {
+        /// Serializes parameter as a plain value, e.g. number or string. Lists
+        /// and maps are serialized as sequences and maps, durations as
+        /// numbers of seconds, decimals as strings keeping their precision.
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self {
+                ParamValue::Bool(value) => serializer.serialize_bool(*value),
+                ParamValue::I8(value) => serializer.serialize_i8(*value),
+                ParamValue::I16(value) => serializer.serialize_i16(*value),
+                ParamValue::I32(value) => serializer.serialize_i32(*value),
+                ParamValue::I64(value) => serializer.serialize_i64(*value),
+                ParamValue::I128(value) => serializer.serialize_i128(*value),
+                ParamValue::U8(value) => serializer.serialize_u8(*value),
+                ParamValue::U16(value) => serializer.serialize_u16(*value),
+                ParamValue::U32(value) => serializer.serialize_u32(*value),
+                ParamValue::U64(value) => serializer.serialize_u64(*value),
+                ParamValue::U128(value) => serializer.serialize_u128(*value),
+                ParamValue::Usize(value) => serializer.serialize_u64(*value as u64),
+                ParamValue::F32(value) => serializer.serialize_f32(*value),
+                ParamValue::F64(value) => serializer.serialize_f64(*value),
+                ParamValue::Char(value) => serializer.serialize_char(*value),
+                ParamValue::String(value) => serializer.serialize_str(value),
+                ParamValue::Raw(value) => serializer.serialize_str(value),
+                ParamValue::Bytes(value) => serializer.serialize_bytes(value),
+                ParamValue::List(values) => serializer.collect_seq(values),
+                ParamValue::Map(entries) => serializer.collect_map(entries),
+                ParamValue::Duration(value) => serializer.serialize_f64(value.as_secs_f64()),
+                ParamValue::Json(value) => serde::Serialize::serialize(value, serializer),
+                #[cfg(feature = "uuid")]
+                ParamValue::Uuid(value) => serializer.collect_str(value),
+                #[cfg(feature = "rust_decimal")]
+                ParamValue::Decimal(value) => serializer.collect_str(value),
+                #[cfg(feature = "bigdecimal")]
+                ParamValue::BigDecimal(value) => serializer.collect_str(value),
+            }
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for ValidationNode {
+        /// Deserializes validation node from the [NodeAsTree] format. The
+        /// default serialization format renders errors to strings, so it
+        /// can't be read back.
+        /// ```
+        /// # use not_so_fast::*;
+        /// let errors = ValidationNode::field("abc", ValidationNode::error(ValidationError::with_code("length").and_param("max", 20)));
+        ///
+        /// let json = serde_json::to_string(&errors.as_tree()).unwrap();
+        /// let deserialized: ValidationNode = serde_json::from_str(&json).unwrap();
+        /// assert_eq!(".abc: length: max=20", deserialized.to_string());
+        /// ```
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(NodeVisitor)
+        }
+    }
+
+    struct NodeVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for NodeVisitor {
+        type Value = ValidationNode;
+
+        fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            f.write_str("validation node")
+        }
+
+        fn visit_map<A: serde::de::MapAccess<'de>>(
+            self,
+            mut map: A,
+        ) -> Result<Self::Value, A::Error> {
+            use alloc::collections::BTreeMap;
+
+            let mut node = ValidationNode::ok();
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "errors" => {
+                        node =
+                            node.and_errors(map.next_value::<Vec<ValidationError>>()?.into_iter());
+                    }
+                    "fields" => {
+                        for (name, field) in map.next_value::<BTreeMap<String, ValidationNode>>()? {
+                            node = node.and_field(name, field);
+                        }
+                    }
+                    "items" => {
+                        for (index, item) in map.next_value::<BTreeMap<usize, ValidationNode>>()? {
+                            node = node.and_item(index, item);
+                        }
+                    }
+                    "keyed_items" => {
+                        for (key, item) in map.next_value::<BTreeMap<String, ValidationNode>>()? {
+                            node = node.and_keyed_item(key, item);
+                        }
+                    }
+                    "order" => {
+                        node.order = Some(map.next_value()?);
+                    }
+                    _ => {
+                        map.next_value::<serde::de::IgnoredAny>()?;
+                    }
+                }
+            }
+            Ok(node)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for ValidationError {
+        /// Deserializes validation error from an object with code, optional
+        /// message and params.
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(ErrorVisitor)
+        }
+    }
+
+    struct ErrorVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for ErrorVisitor {
+        type Value = ValidationError;
+
+        fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            f.write_str("validation error")
+        }
+
+        fn visit_map<A: serde::de::MapAccess<'de>>(
+            self,
+            mut map: A,
+        ) -> Result<Self::Value, A::Error> {
+            use alloc::collections::{BTreeMap, BTreeSet};
+
+            let mut code = None;
+            let mut message = None;
+            let mut params = BTreeMap::new();
+            let mut severity = Severity::Error;
+            let mut tags = BTreeSet::new();
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "code" => code = Some(map.next_value::<String>()?),
+                    "message" => message = map.next_value::<Option<String>>()?,
+                    "params" => params = map.next_value::<BTreeMap<String, ParamValue>>()?,
+                    "tags" => {
+                        tags = map
+                            .next_value::<Vec<String>>()?
+                            .into_iter()
+                            .map(Into::into)
+                            .collect()
+                    }
+                    "severity" => {
+                        severity = match map.next_value::<String>()?.as_str() {
+                            "info" => Severity::Info,
+                            "warning" => Severity::Warning,
+                            "error" => Severity::Error,
+                            other => {
+                                return Err(serde::de::Error::unknown_variant(
+                                    other,
+                                    &["info", "warning", "error"],
+                                ))
+                            }
+                        }
+                    }
+                    _ => {
+                        map.next_value::<serde::de::IgnoredAny>()?;
+                    }
                 }
-                buffer.write_str(param.0).unwrap();
-                buffer.write_char('=').unwrap();
-                write!(buffer, "{}", param.1).unwrap();
             }
+            let code = code.ok_or_else(|| serde::de::Error::missing_field("code"))?;
+            Ok(ValidationError::from_parts(
+                code.into(),
+                message.map(|message| Message::Text(message.into())),
+                params
+                    .into_iter()
+                    .map(|(key, value)| (key.into(), value))
+                    .collect(),
+                severity,
+                tags,
+            ))
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for ParamValue {
+        /// Deserializes parameter from a plain value. Integers become
+        /// `I64`/`U64` (or 128-bit variants if needed), floats become `F64`,
+        /// strings become `String`, sequences become `List`, maps become
+        /// `Map` and nulls become `Json(Value::Null)`.
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(ParamValueVisitor)
+        }
+    }
+
+    struct ParamValueVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for ParamValueVisitor {
+        type Value = ParamValue;
+
+        fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            f.write_str("boolean, number, string, null, sequence or map")
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(ParamValue::Json(serde_json::Value::Null))
+        }
+
+        fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(ParamValue::Json(serde_json::Value::Null))
+        }
+
+        fn visit_bool<E: serde::de::Error>(self, value: bool) -> Result<Self::Value, E> {
+            Ok(ParamValue::Bool(value))
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            Ok(ParamValue::I64(value))
+        }
+
+        fn visit_i128<E: serde::de::Error>(self, value: i128) -> Result<Self::Value, E> {
+            Ok(ParamValue::I128(value))
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            Ok(ParamValue::U64(value))
+        }
+
+        fn visit_u128<E: serde::de::Error>(self, value: u128) -> Result<Self::Value, E> {
+            Ok(ParamValue::U128(value))
+        }
+
+        fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Self::Value, E> {
+            Ok(ParamValue::F64(value))
+        }
+
+        fn visit_char<E: serde::de::Error>(self, value: char) -> Result<Self::Value, E> {
+            Ok(ParamValue::Char(value))
+        }
+
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            Ok(ParamValue::String(value.to_owned().into()))
+        }
+
+        fn visit_string<E: serde::de::Error>(self, value: String) -> Result<Self::Value, E> {
+            Ok(ParamValue::String(value.into()))
+        }
+
+        fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+            Ok(ParamValue::Bytes(value.to_owned().into()))
+        }
+
+        fn visit_byte_buf<E: serde::de::Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+            Ok(ParamValue::Bytes(value.into()))
+        }
+
+        fn visit_seq<A: serde::de::SeqAccess<'de>>(
+            self,
+            mut seq: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(value) = seq.next_element()? {
+                values.push(value);
+            }
+            Ok(ParamValue::List(values))
+        }
 
-            let result = serializer.serialize_str(buffer);
-            buffer.clear();
-            result
+        fn visit_map<A: serde::de::MapAccess<'de>>(
+            self,
+            mut map: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut entries = alloc::collections::BTreeMap::new();
+            while let Some((key, value)) = map.next_entry::<String, ParamValue>()? {
+                entries.insert(key.into(), value);
+            }
+            Ok(ParamValue::Map(entries))
         }
     }
 }
@@ -0,0 +1,157 @@
+//! Declarative macros building errors and error trees.
+
+use alloc::{borrow::Cow, string::String};
+
+use crate::ValidationNode;
+
+/// Builds a [ValidationError](crate::ValidationError) with a code and
+/// `key = value` params. Keys `message` and `severity` set the message and
+/// severity instead of adding params.
+/// ```
+/// # use not_so_fast::*;
+/// let error = validation_error!("range", message = "Number not in range", min = 1, max = 5);
+/// assert_eq!(
+///     ValidationError::with_code("range")
+///         .and_message("Number not in range")
+///         .and_param("min", 1)
+///         .and_param("max", 5),
+///     error,
+/// );
+///
+/// let warning = validation_error!("deprecated", severity = Severity::Warning);
+/// assert_eq!(Severity::Warning, warning.severity());
+/// ```
+#[macro_export]
+macro_rules! validation_error {
+    (@arg $error:ident, message, $value:expr) => {
+        $error.and_message($value)
+    };
+    (@arg $error:ident, severity, $value:expr) => {
+        $error.and_severity($value)
+    };
+    (@arg $error:ident, $key:ident, $value:expr) => {
+        $error.and_param(stringify!($key), $value)
+    };
+    ($code:expr $(, $key:ident = $value:expr)* $(,)?) => {{
+        let error = $crate::ValidationError::with_code($code);
+        $(let error = $crate::validation_error!(@arg error, $key, $value);)*
+        error
+    }};
+}
+
+/// Builds a [ValidationNode] from a comma-separated list of entries:
+/// - `error` adds a [ValidationError](crate::ValidationError) to the node,
+/// - `.name => { ... }` or `."name" => { ... }` adds a field with entries
+///   in braces,
+/// - `[index] => { ... }` adds an item with entries in braces, at an index
+///   of type `usize`, or at a string key,
+/// - `..node` merges another [ValidationNode] into the node.
+///
+/// Fields and items also accept an expression evaluating to a
+/// [ValidationNode] instead of braces, e.g. `.address => address.validate()`.
+/// ```
+/// # use not_so_fast::*;
+/// let errors = node! {
+///     validation_error!("conflict"),
+///     .name => { validation_error!("length", max = 5) },
+///     .pets => {
+///         [1] => { validation_error!("required") },
+///         ["rex"] => { validation_error!("duplicate") },
+///     },
+///     ."first-name" => ValidationNode::ok(),
+/// };
+/// assert_eq!(
+///     ".: conflict\n\
+///      .name: length: max=5\n\
+///      .pets[1]: required\n\
+///      .pets[\"rex\"]: duplicate",
+///     errors.to_string(),
+/// );
+/// ```
+#[macro_export]
+macro_rules! node {
+    ($($entries:tt)*) => {{
+        let node = $crate::ValidationNode::ok();
+        $crate::__node!(node; $($entries)*)
+    }};
+}
+
+/// Adds entries of [node] to a node, one at a time.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __node {
+    ($node:ident;) => {
+        $node
+    };
+    ($node:ident; .$name:ident => { $($child:tt)* } $(, $($rest:tt)*)?) => {{
+        let $node = $node.and_field(stringify!($name), $crate::node!($($child)*));
+        $crate::__node!($node; $($($rest)*)?)
+    }};
+    ($node:ident; .$name:literal => { $($child:tt)* } $(, $($rest:tt)*)?) => {{
+        let $node = $node.and_field($name, $crate::node!($($child)*));
+        $crate::__node!($node; $($($rest)*)?)
+    }};
+    ($node:ident; .$name:ident => $child:expr $(, $($rest:tt)*)?) => {{
+        let $node = $node.and_field(stringify!($name), $child);
+        $crate::__node!($node; $($($rest)*)?)
+    }};
+    ($node:ident; .$name:literal => $child:expr $(, $($rest:tt)*)?) => {{
+        let $node = $node.and_field($name, $child);
+        $crate::__node!($node; $($($rest)*)?)
+    }};
+    ($node:ident; [$key:expr] => { $($child:tt)* } $(, $($rest:tt)*)?) => {{
+        let $node = $crate::__private::node_item($node, $key, $crate::node!($($child)*));
+        $crate::__node!($node; $($($rest)*)?)
+    }};
+    ($node:ident; [$key:expr] => $child:expr $(, $($rest:tt)*)?) => {{
+        let $node = $crate::__private::node_item($node, $key, $child);
+        $crate::__node!($node; $($($rest)*)?)
+    }};
+    ($node:ident; ..$other:expr $(, $($rest:tt)*)?) => {{
+        let $node = $node.merge($other);
+        $crate::__node!($node; $($($rest)*)?)
+    }};
+    ($node:ident; $error:expr $(, $($rest:tt)*)?) => {{
+        let $node = $node.and_error($error);
+        $crate::__node!($node; $($($rest)*)?)
+    }};
+}
+
+/// Key of an item added by [node].
+#[doc(hidden)]
+pub trait NodeItemKey {
+    fn add_item(self, node: ValidationNode, item: ValidationNode) -> ValidationNode;
+}
+
+impl NodeItemKey for usize {
+    fn add_item(self, node: ValidationNode, item: ValidationNode) -> ValidationNode {
+        node.and_item(self, item)
+    }
+}
+
+impl NodeItemKey for &'static str {
+    fn add_item(self, node: ValidationNode, item: ValidationNode) -> ValidationNode {
+        node.and_keyed_item(self, item)
+    }
+}
+
+impl NodeItemKey for String {
+    fn add_item(self, node: ValidationNode, item: ValidationNode) -> ValidationNode {
+        node.and_keyed_item(self, item)
+    }
+}
+
+impl NodeItemKey for Cow<'static, str> {
+    fn add_item(self, node: ValidationNode, item: ValidationNode) -> ValidationNode {
+        node.and_keyed_item(self, item)
+    }
+}
+
+#[doc(hidden)]
+pub fn node_item(
+    node: ValidationNode,
+    key: impl NodeItemKey,
+    item: ValidationNode,
+) -> ValidationNode {
+    key.add_item(node, item)
+}
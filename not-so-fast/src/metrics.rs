@@ -0,0 +1,78 @@
+//! Adapter of [ValidationObserver] recording `metrics`.
+
+use std::time::Duration;
+
+use ::metrics::{counter, histogram};
+
+use crate::{Path, ValidationError, ValidationObserver};
+
+/// [ValidationObserver] recording validation outcomes with the `metrics`
+/// crate:
+///
+/// - counter `not_so_fast_validations_total` with labels `type` and
+///   `outcome`, `valid` or `invalid`,
+/// - histogram `not_so_fast_validation_duration_seconds` with label `type`,
+/// - counter `not_so_fast_validation_errors_total` with labels `type`, `code`
+///   and `severity`.
+///
+/// Metric names can be prefixed with [MetricsObserver::with_prefix].
+/// ```
+/// # use not_so_fast::*;
+/// set_observer(MetricsObserver::new());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MetricsObserver {
+    validations: String,
+    duration: String,
+    errors: String,
+}
+
+impl MetricsObserver {
+    /// Creates an observer recording metrics named `not_so_fast_*`.
+    pub fn new() -> Self {
+        Self::with_prefix("not_so_fast")
+    }
+
+    /// Creates an observer recording metrics named `{prefix}_*`, e.g.
+    /// `shop_validations_total`.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let observer = MetricsObserver::with_prefix("shop");
+    /// ```
+    pub fn with_prefix(prefix: &str) -> Self {
+        Self {
+            validations: format!("{prefix}_validations_total"),
+            duration: format!("{prefix}_validation_duration_seconds"),
+            errors: format!("{prefix}_validation_errors_total"),
+        }
+    }
+}
+
+impl Default for MetricsObserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValidationObserver for MetricsObserver {
+    fn on_validated(&self, type_name: &str, ok: bool, _error_count: usize, duration: Duration) {
+        let outcome = if ok { "valid" } else { "invalid" };
+        counter!(
+            self.validations.clone(),
+            "type" => type_name.to_owned(),
+            "outcome" => outcome,
+        )
+        .increment(1);
+        histogram!(self.duration.clone(), "type" => type_name.to_owned()).record(duration);
+    }
+
+    fn on_error(&self, type_name: &str, _path: &Path, error: &ValidationError) {
+        counter!(
+            self.errors.clone(),
+            "type" => type_name.to_owned(),
+            "code" => error.code().to_owned(),
+            "severity" => error.severity().as_str(),
+        )
+        .increment(1);
+    }
+}
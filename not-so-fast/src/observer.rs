@@ -0,0 +1,95 @@
+//! Hooks reporting validation outcomes, e.g. to metrics.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::{Path, ValidationError, ValidationNode};
+
+/// Receiver of validation outcomes of
+/// [Validate::validate_observed](crate::Validate::validate_observed) and
+/// [Validate::validate_observed_by](crate::Validate::validate_observed_by).
+/// ```
+/// # use not_so_fast::*;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::time::Duration;
+///
+/// #[derive(Default)]
+/// struct FailureCounter(AtomicUsize);
+///
+/// impl ValidationObserver for FailureCounter {
+///     fn on_validated(&self, _type_name: &str, ok: bool, _error_count: usize, _duration: Duration) {
+///         if !ok {
+///             self.0.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// #[derive(Validate)]
+/// struct Order {
+///     #[validate(range(min = 1))]
+///     quantity: u32,
+/// }
+///
+/// let counter = FailureCounter::default();
+/// Order { quantity: 0 }.validate_observed_by(&counter);
+/// Order { quantity: 1 }.validate_observed_by(&counter);
+/// assert_eq!(1, counter.0.load(Ordering::Relaxed));
+/// ```
+pub trait ValidationObserver: Send + Sync {
+    /// Called after a value of type `type_name` is validated. `ok` tells if
+    /// the value has no errors of [Severity::Error](crate::Severity::Error),
+    /// `error_count` counts errors of all severities, `duration` is the time
+    /// of validation.
+    fn on_validated(&self, type_name: &str, ok: bool, error_count: usize, duration: Duration);
+
+    /// Called for every error of a validated value, before
+    /// [on_validated](ValidationObserver::on_validated), e.g. to count failing
+    /// codes. Does nothing by default.
+    fn on_error(&self, type_name: &str, path: &Path, error: &ValidationError) {
+        let _ = (type_name, path, error);
+    }
+}
+
+static OBSERVER: OnceLock<Box<dyn ValidationObserver>> = OnceLock::new();
+
+/// Registers the global observer of
+/// [Validate::validate_observed](crate::Validate::validate_observed). Returns
+/// `false`, leaving the registered observer, if an observer is already
+/// registered.
+/// ```
+/// # use not_so_fast::*;
+/// struct Noop;
+///
+/// impl ValidationObserver for Noop {
+///     fn on_validated(&self, _: &str, _: bool, _: usize, _: std::time::Duration) {}
+/// }
+///
+/// assert!(set_observer(Noop));
+/// assert!(!set_observer(Noop));
+/// ```
+pub fn set_observer(observer: impl ValidationObserver + 'static) -> bool {
+    OBSERVER.set(Box::new(observer)).is_ok()
+}
+
+/// Returns the global observer, if registered.
+pub(crate) fn global_observer() -> Option<&'static dyn ValidationObserver> {
+    OBSERVER.get().map(|observer| observer.as_ref())
+}
+
+/// Runs `validate` and reports its outcome to `observer`.
+pub(crate) fn observe(
+    type_name: &str,
+    observer: &dyn ValidationObserver,
+    validate: impl FnOnce() -> ValidationNode,
+) -> ValidationNode {
+    let start = Instant::now();
+    let errors = validate();
+    let duration = start.elapsed();
+    let mut error_count = 0;
+    for (path, error) in &errors {
+        observer.on_error(type_name, &path, error);
+        error_count += 1;
+    }
+    observer.on_validated(type_name, !errors.has_errors(), error_count, duration);
+    errors
+}
@@ -0,0 +1,125 @@
+//! Validation of partial updates, keeping errors of fields present in the
+//! update.
+
+#[cfg(not(feature = "std"))]
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+
+use crate::{Path, ValidationNode};
+
+/// Set of fields present in a partial update, e.g. in the body of a `PATCH`
+/// request. See [Validate::validate_partial](crate::Validate::validate_partial).
+///
+/// Errors are relevant to the update if they are reported at a present
+/// field, inside it, or at one of its parents, where cross-field invariants
+/// of the parent types report errors.
+/// ```
+/// # use not_so_fast::*;
+/// let mask = FieldMask::new().with_field("name").with_path(".address.city".parse().unwrap());
+///
+/// assert!(mask.is_relevant(&".name".parse().unwrap()));
+/// assert!(mask.is_relevant(&".address.city".parse().unwrap()));
+/// assert!(mask.is_relevant(&".address".parse().unwrap()));
+/// assert!(mask.is_relevant(&Path::root()));
+/// assert!(!mask.is_relevant(&".address.street".parse().unwrap()));
+/// assert!(!mask.is_relevant(&".age".parse().unwrap()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldMask {
+    paths: BTreeSet<Path>,
+}
+
+impl FieldMask {
+    /// Creates a mask without present fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the mask with top-level field `name` present.
+    pub fn with_field(self, name: &str) -> Self {
+        self.with_path(Path::root().and_field(name.to_owned()))
+    }
+
+    /// Returns the mask with the value at `path` present.
+    pub fn with_path(mut self, path: Path) -> Self {
+        self.insert(path);
+        self
+    }
+
+    /// Marks the value at `path` present.
+    pub fn insert(&mut self, path: Path) {
+        self.paths.insert(path);
+    }
+
+    /// Returns paths of present values.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.paths.iter()
+    }
+
+    /// Checks if errors at `path` are relevant to the update, i.e. `path`
+    /// points at a present value, inside one, or at a parent of one.
+    pub fn is_relevant(&self, path: &Path) -> bool {
+        self.paths
+            .iter()
+            .any(|present| path.starts_with(present) || present.starts_with(path))
+    }
+
+    /// Creates a mask of fields present in a JSON document, e.g. in the body
+    /// of a `PATCH` request. Fields of nested objects are present
+    /// separately, other values, including arrays and nulls, as a whole.
+    /// ```
+    /// # use not_so_fast::*;
+    /// use serde_json::json;
+    ///
+    /// let mask = FieldMask::from_json(&json!({ "name": "Tom", "address": { "city": null }, "tags": [] }));
+    /// assert_eq!(
+    ///     vec![".address.city", ".name", ".tags"],
+    ///     mask.paths().map(|path| path.to_string()).collect::<Vec<_>>(),
+    /// );
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_json(value: &serde_json::Value) -> Self {
+        let mut mask = Self::new();
+        collect_json_paths(value, &mut Path::root(), &mut mask);
+        mask
+    }
+}
+
+impl FromIterator<Path> for FieldMask {
+    fn from_iter<T: IntoIterator<Item = Path>>(iter: T) -> Self {
+        Self {
+            paths: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn collect_json_paths(value: &serde_json::Value, path: &mut Path, mask: &mut FieldMask) {
+    match value {
+        // An empty object at the root has no present fields.
+        serde_json::Value::Object(object) if !object.is_empty() || path.is_root() => {
+            for (key, value) in object {
+                path.push(crate::PathSegment::Field(key.clone().into()));
+                collect_json_paths(value, path, mask);
+                path.pop();
+            }
+        }
+        _ => mask.insert(path.clone()),
+    }
+}
+
+impl ValidationNode {
+    /// Keeps errors relevant to a partial update with fields of `mask`. See
+    /// [FieldMask::is_relevant].
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut errors = ValidationNode::error(ValidationError::with_code("invariant"))
+    ///     .and_field("name", ValidationNode::error(ValidationError::with_code("length")))
+    ///     .and_field("age", ValidationNode::error(ValidationError::with_code("range")));
+    /// errors.retain_present(&FieldMask::new().with_field("name"));
+    /// assert_eq!(".: invariant\n.name: length", errors.to_string());
+    /// ```
+    pub fn retain_present(&mut self, mask: &FieldMask) {
+        self.retain(|path, _| mask.is_relevant(path));
+    }
+}
@@ -0,0 +1,117 @@
+//! Placement of deserialization errors tracked by `serde_path_to_error` in
+//! [ValidationNode], so that parse errors and validation errors share one
+//! structure.
+
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use core::fmt::Display;
+
+use serde::{Deserialize, Deserializer};
+use serde_path_to_error::Segment;
+
+use crate::{codes, Path, Validate, ValidationError, ValidationNode};
+
+/// Deserializes `T` with `deserializer` and validates it. Returns errors of
+/// the value, or `Err` with the deserialization error placed at its path.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(serde::Deserialize, Validate)]
+/// struct User {
+///     #[validate(range(max = 150))]
+///     age: u32,
+/// }
+///
+/// let deserializer = &mut serde_json::Deserializer::from_str(r#"{"age": 200}"#);
+/// let errors = deserialize_and_validate::<User, _>(deserializer).unwrap();
+/// assert_eq!(".age: range: Number not in range: max=150, value=200", errors.to_string());
+///
+/// let deserializer = &mut serde_json::Deserializer::from_str(r#"{"age": "old"}"#);
+/// let errors = deserialize_and_validate::<User, _>(deserializer).unwrap_err();
+/// assert_eq!(".age", errors.first_error().unwrap().0.to_string());
+/// ```
+pub fn deserialize_and_validate<'de, T, D>(
+    deserializer: D,
+) -> Result<ValidationNode, ValidationNode>
+where
+    T: Deserialize<'de> + Validate,
+    D: Deserializer<'de>,
+{
+    let value: T = serde_path_to_error::deserialize(deserializer)?;
+    Ok(value.validate())
+}
+
+/// Deserializes `T` from JSON text and validates it, like
+/// [deserialize_and_validate] does. Characters after the JSON value are
+/// reported as a deserialization error at the root. Requires the `serde`
+/// feature.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(serde::Deserialize, Validate)]
+/// struct Tag(#[validate(char_length(max = 3))] String);
+///
+/// assert!(validate_json_str::<Tag>(r#""abc""#).unwrap().is_ok());
+/// assert!(validate_json_str::<Tag>(r#""abcd""#).unwrap().is_err());
+/// assert_eq!(
+///     ".: deserialize: trailing characters at line 1 column 7",
+///     validate_json_str::<Tag>(r#""abc" 1"#).unwrap_err().to_string(),
+/// );
+/// ```
+#[cfg(feature = "serde")]
+pub fn validate_json_str<T>(json: &str) -> Result<ValidationNode, ValidationNode>
+where
+    T: serde::de::DeserializeOwned + Validate,
+{
+    let deserializer = &mut serde_json::Deserializer::from_str(json);
+    let value: T = serde_path_to_error::deserialize(&mut *deserializer)?;
+    deserializer.end().map_err(|error| {
+        ValidationNode::error(
+            ValidationError::with_code(codes::DESERIALIZE).and_message(error.to_string()),
+        )
+    })?;
+    Ok(value.validate())
+}
+
+impl From<&serde_path_to_error::Path> for Path {
+    /// Converts a path of `serde_path_to_error`. Map keys and enum variants
+    /// become fields, unknown segments are skipped.
+    /// ```
+    /// # use not_so_fast::*;
+    /// # use std::collections::BTreeMap;
+    /// let deserializer = &mut serde_json::Deserializer::from_str(r#"{"a": [1, "b"]}"#);
+    /// let result: Result<BTreeMap<String, Vec<u32>>, _> = serde_path_to_error::deserialize(deserializer);
+    ///
+    /// assert_eq!(".a[1]", Path::from(result.unwrap_err().path()).to_string());
+    /// ```
+    fn from(path: &serde_path_to_error::Path) -> Self {
+        path.iter()
+            .fold(Path::root(), |path, segment| match segment {
+                Segment::Seq { index } => path.and_index(*index),
+                Segment::Map { key } => path.and_field(key.clone()),
+                Segment::Enum { variant } => path.and_field(variant.clone()),
+                Segment::Unknown => path,
+            })
+    }
+}
+
+impl<E: Display> From<serde_path_to_error::Error<E>> for ValidationNode {
+    /// Places the deserialization error at its path, with code
+    /// [codes::DESERIALIZE] and the error's message.
+    /// ```
+    /// # use not_so_fast::*;
+    /// # use std::collections::BTreeMap;
+    /// let json = r#"[{"age": 20}, {"age": "old"}]"#;
+    /// let deserializer = &mut serde_json::Deserializer::from_str(json);
+    /// let result: Result<Vec<BTreeMap<String, u32>>, _> = serde_path_to_error::deserialize(deserializer);
+    ///
+    /// assert_eq!(
+    ///     r#".[1].age: deserialize: invalid type: string "old", expected u32 at line 1 column 27"#,
+    ///     ValidationNode::from(result.unwrap_err()).to_string(),
+    /// );
+    /// ```
+    fn from(error: serde_path_to_error::Error<E>) -> Self {
+        let path = Path::from(error.path());
+        let error = ValidationError::with_code(codes::DESERIALIZE)
+            .and_message(error.into_inner().to_string());
+        ValidationNode::ok().and_at(path, error)
+    }
+}
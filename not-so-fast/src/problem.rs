@@ -0,0 +1,208 @@
+//! Problem details (RFC 7807) describing validation errors, serialized as
+//! `application/problem+json` bodies.
+
+use alloc::borrow::Cow;
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+use serde::ser::{SerializeMap, Serializer};
+
+use crate::{push_path_element, PathElement, PathStyle, ValidationNode};
+
+/// Problem details object with an `errors` extension member listing errors of
+/// a [ValidationNode]. Paths of errors are JSON pointers, which are empty for
+/// errors of the root node.
+/// ```
+/// # use not_so_fast::*;
+/// let errors = ValidationNode::field(
+///     "name",
+///     ValidationNode::error(ValidationError::with_code("length").and_message("Name is too long")),
+/// );
+/// let problem = errors
+///     .to_problem("Invalid request", "https://example.com/problems/validation")
+///     .with_status(422);
+///
+/// assert_eq!(
+///     r#"{"type":"https://example.com/problems/validation","title":"Invalid request","status":422,"errors":[{"path":"/name","code":"length","message":"Name is too long"}]}"#,
+///     serde_json::to_string(&problem).unwrap(),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    type_uri: Cow<'static, str>,
+    title: Cow<'static, str>,
+    status: Option<u16>,
+    detail: Option<Cow<'static, str>>,
+    instance: Option<Cow<'static, str>>,
+    errors: Vec<ProblemError>,
+}
+
+/// Entry of the `errors` member of [Problem].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProblemError {
+    path: String,
+    code: String,
+    message: Option<String>,
+}
+
+impl Problem {
+    /// Media type of serialized problem details.
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+
+    /// Sets the HTTP status code of the problem.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Sets the explanation specific to this occurrence of the problem.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let problem = ValidationNode::ok()
+    ///     .to_problem("Invalid request", "about:blank")
+    ///     .with_detail("Request body is invalid");
+    /// assert_eq!(Some("Request body is invalid"), problem.detail());
+    /// ```
+    pub fn with_detail(mut self, detail: impl Into<Cow<'static, str>>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Sets the URI identifying this occurrence of the problem.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let problem = ValidationNode::ok()
+    ///     .to_problem("Invalid request", "about:blank")
+    ///     .with_instance("/users/12");
+    /// assert_eq!(Some("/users/12"), problem.instance());
+    /// ```
+    pub fn with_instance(mut self, instance: impl Into<Cow<'static, str>>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Returns the URI identifying the problem type.
+    pub fn type_uri(&self) -> &str {
+        &self.type_uri
+    }
+
+    /// Returns the summary of the problem type.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the HTTP status code of the problem.
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    /// Returns the explanation specific to this occurrence of the problem.
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    /// Returns the URI identifying this occurrence of the problem.
+    pub fn instance(&self) -> Option<&str> {
+        self.instance.as_deref()
+    }
+
+    /// Returns the validation errors.
+    pub fn errors(&self) -> &[ProblemError] {
+        &self.errors
+    }
+}
+
+impl ProblemError {
+    /// Returns JSON pointer to the invalid value.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns code of the error.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns message of the error.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl ValidationNode {
+    /// Describes errors of the tree as problem details with the given title
+    /// and type URI. Errors are listed in `Display` order.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::error(ValidationError::with_code("invariant"))
+    ///     .and_field("tags", ValidationNode::item(2, ValidationNode::error(ValidationError::with_code("empty"))));
+    /// let problem = errors.to_problem("Invalid request", "about:blank");
+    ///
+    /// let paths: Vec<_> = problem.errors().iter().map(|error| (error.path(), error.code())).collect();
+    /// assert_eq!(vec![("", "invariant"), ("/tags/2", "empty")], paths);
+    /// ```
+    pub fn to_problem(
+        &self,
+        title: impl Into<Cow<'static, str>>,
+        type_uri: impl Into<Cow<'static, str>>,
+    ) -> Problem {
+        let errors = self
+            .iter()
+            .map(|(path, error)| {
+                let mut pointer = String::new();
+                for segment in path.segments() {
+                    // Writing into a String doesn't fail.
+                    let _ = push_path_element(
+                        &mut pointer,
+                        &PathElement::from(segment),
+                        PathStyle::JsonPointer,
+                    );
+                }
+                ProblemError {
+                    path: pointer,
+                    code: error.code().to_owned(),
+                    message: error.message().map(ToOwned::to_owned),
+                }
+            })
+            .collect();
+        Problem {
+            type_uri: type_uri.into(),
+            title: title.into(),
+            status: None,
+            detail: None,
+            instance: None,
+            errors,
+        }
+    }
+}
+
+impl serde::Serialize for Problem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", &self.type_uri)?;
+        map.serialize_entry("title", &self.title)?;
+        if let Some(status) = self.status {
+            map.serialize_entry("status", &status)?;
+        }
+        if let Some(detail) = &self.detail {
+            map.serialize_entry("detail", detail)?;
+        }
+        if let Some(instance) = &self.instance {
+            map.serialize_entry("instance", instance)?;
+        }
+        map.serialize_entry("errors", &self.errors)?;
+        map.end()
+    }
+}
+
+impl serde::Serialize for ProblemError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("path", &self.path)?;
+        map.serialize_entry("code", &self.code)?;
+        if let Some(message) = &self.message {
+            map.serialize_entry("message", message)?;
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,310 @@
+//! `proptest` strategies generating valid and invalid values, used by the
+//! `proptest` flag of the derive macro.
+
+use alloc::collections::{BTreeSet, VecDeque};
+use core::fmt::Debug;
+use core::ops::RangeInclusive;
+
+use ::proptest::arbitrary::{any, Arbitrary};
+use ::proptest::collection::vec;
+use ::proptest::strategy::{BoxedStrategy, Just, Strategy, Union};
+
+/// Type with `proptest` strategies generating valid and invalid values,
+/// implemented by the derive macro for types with `#[validate(proptest)]`.
+pub trait ArbitraryValid: Sized + Debug {
+    /// Strategy generating values passing validation.
+    fn arbitrary_valid() -> BoxedStrategy<Self>;
+
+    /// Strategy generating values failing validation.
+    fn arbitrary_invalid() -> BoxedStrategy<Self>;
+}
+
+/// Longest generated length above the lower bound of unbounded lengths.
+const EXTRA_LENGTH: usize = 8;
+
+/// Number validated with `range`.
+#[doc(hidden)]
+pub trait RangeValue: Sized + Debug + 'static {
+    fn in_range(min: Option<Self>, max: Option<Self>) -> BoxedStrategy<Self>;
+    fn outside_range(min: Option<Self>, max: Option<Self>) -> Option<BoxedStrategy<Self>>;
+}
+
+macro_rules! impl_range_value_int {
+    ($($int:ty),*) => {
+        $(
+            impl RangeValue for $int {
+                fn in_range(min: Option<Self>, max: Option<Self>) -> BoxedStrategy<Self> {
+                    (min.unwrap_or(<$int>::MIN)..=max.unwrap_or(<$int>::MAX)).boxed()
+                }
+
+                fn outside_range(min: Option<Self>, max: Option<Self>) -> Option<BoxedStrategy<Self>> {
+                    let below = min
+                        .filter(|min| *min > <$int>::MIN)
+                        .map(|min| (<$int>::MIN..min).boxed());
+                    let above = max
+                        .filter(|max| *max < <$int>::MAX)
+                        .map(|max| (max + 1..=<$int>::MAX).boxed());
+                    union(below.into_iter().chain(above).collect())
+                }
+            }
+        )*
+    };
+}
+
+impl_range_value_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_range_value_float {
+    ($($float:ty),*) => {
+        $(
+            impl RangeValue for $float {
+                fn in_range(min: Option<Self>, max: Option<Self>) -> BoxedStrategy<Self> {
+                    (min.unwrap_or(<$float>::MIN)..=max.unwrap_or(<$float>::MAX)).boxed()
+                }
+
+                fn outside_range(min: Option<Self>, max: Option<Self>) -> Option<BoxedStrategy<Self>> {
+                    let below = min
+                        .filter(|min| *min > <$float>::MIN)
+                        .map(|min| (<$float>::MIN..min).boxed());
+                    let above = max.filter(|max| *max < <$float>::MAX).map(|max| {
+                        (max..=<$float>::MAX)
+                            .prop_filter("above range", move |value| *value > max)
+                            .boxed()
+                    });
+                    union(below.into_iter().chain(above).collect())
+                }
+            }
+        )*
+    };
+}
+
+impl_range_value_float!(f32, f64);
+
+/// Value validated with `length`.
+#[doc(hidden)]
+pub trait LengthValue: Sized + Debug + 'static {
+    fn with_length(length: RangeInclusive<usize>) -> BoxedStrategy<Self>;
+}
+
+impl LengthValue for String {
+    fn with_length(length: RangeInclusive<usize>) -> BoxedStrategy<Self> {
+        vec(::proptest::char::range('a', 'z'), length)
+            .prop_map(String::from_iter)
+            .boxed()
+    }
+}
+
+impl<T: Arbitrary + 'static> LengthValue for Vec<T> {
+    fn with_length(length: RangeInclusive<usize>) -> BoxedStrategy<Self> {
+        vec(any::<T>(), length).boxed()
+    }
+}
+
+/// Value validated with `char_length`.
+#[doc(hidden)]
+pub trait CharLengthValue: Sized + Debug + 'static {
+    fn with_char_length(length: RangeInclusive<usize>) -> BoxedStrategy<Self>;
+}
+
+impl CharLengthValue for String {
+    fn with_char_length(length: RangeInclusive<usize>) -> BoxedStrategy<Self> {
+        // Multi-byte characters tell character length apart from length.
+        let char = ::proptest::sample::select(&['a', 'z', 'é', 'ß', '日', '🦀'][..]);
+        vec(char, length).prop_map(String::from_iter).boxed()
+    }
+}
+
+/// Collection validated with `items`.
+#[doc(hidden)]
+pub trait CollectionValue: Sized + Debug + 'static {
+    type Item: Debug + 'static;
+
+    fn from_items(items: Vec<Self::Item>) -> Self;
+}
+
+impl<T: Debug + 'static> CollectionValue for Vec<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Self {
+        items
+    }
+}
+
+impl<T: Debug + 'static> CollectionValue for VecDeque<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Self {
+        items.into()
+    }
+}
+
+impl<T: Debug + Ord + 'static> CollectionValue for BTreeSet<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Self {
+        items.into_iter().collect()
+    }
+}
+
+#[doc(hidden)]
+pub fn pt_any<T: Arbitrary + 'static>() -> BoxedStrategy<T> {
+    any::<T>().boxed()
+}
+
+#[doc(hidden)]
+pub fn pt_nested<T: ArbitraryValid>() -> BoxedStrategy<T> {
+    T::arbitrary_valid()
+}
+
+#[doc(hidden)]
+pub fn pt_nested_invalid<T: ArbitraryValid>() -> Option<BoxedStrategy<T>> {
+    Some(T::arbitrary_invalid())
+}
+
+#[doc(hidden)]
+pub fn pt_range<T: RangeValue>(min: Option<T>, max: Option<T>) -> BoxedStrategy<T> {
+    T::in_range(min, max)
+}
+
+#[doc(hidden)]
+pub fn pt_range_invalid<T: RangeValue>(min: Option<T>, max: Option<T>) -> Option<BoxedStrategy<T>> {
+    T::outside_range(min, max)
+}
+
+#[doc(hidden)]
+pub fn pt_length<T: LengthValue>(
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> BoxedStrategy<T> {
+    T::with_length(length_range(min, max, equal))
+}
+
+#[doc(hidden)]
+pub fn pt_length_invalid<T: LengthValue>(
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> Option<BoxedStrategy<T>> {
+    union(
+        outside_length_ranges(min, max, equal)
+            .map(T::with_length)
+            .collect(),
+    )
+}
+
+#[doc(hidden)]
+pub fn pt_char_length<T: CharLengthValue>(
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> BoxedStrategy<T> {
+    T::with_char_length(length_range(min, max, equal))
+}
+
+#[doc(hidden)]
+pub fn pt_char_length_invalid<T: CharLengthValue>(
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> Option<BoxedStrategy<T>> {
+    union(
+        outside_length_ranges(min, max, equal)
+            .map(T::with_char_length)
+            .collect(),
+    )
+}
+
+#[doc(hidden)]
+pub fn pt_items<C: CollectionValue>(
+    item: BoxedStrategy<C::Item>,
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> BoxedStrategy<C> {
+    vec(item, length_range(min, max, equal))
+        .prop_map(C::from_items)
+        .boxed()
+}
+
+/// Generates collections of invalid length, or with invalid items.
+#[doc(hidden)]
+pub fn pt_items_invalid<C: CollectionValue>(
+    item: BoxedStrategy<C::Item>,
+    invalid_item: Option<BoxedStrategy<C::Item>>,
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> Option<BoxedStrategy<C>> {
+    let mut strategies: Vec<_> = outside_length_ranges(min, max, equal)
+        .map(|length| vec(item.clone(), length).prop_map(C::from_items).boxed())
+        .collect();
+    let length = length_range(min, max, equal);
+    if let Some(invalid_item) = invalid_item.filter(|_| *length.end() > 0) {
+        let length = (*length.start()).max(1)..=*length.end();
+        strategies.push(vec(invalid_item, length).prop_map(C::from_items).boxed());
+    }
+    union(strategies)
+}
+
+#[doc(hidden)]
+pub fn pt_some<T: Debug + 'static>(inner: BoxedStrategy<T>) -> BoxedStrategy<Option<T>> {
+    ::proptest::option::of(inner).boxed()
+}
+
+#[doc(hidden)]
+pub fn pt_some_invalid<T: Debug + 'static>(
+    inner: Option<BoxedStrategy<T>>,
+) -> Option<BoxedStrategy<Option<T>>> {
+    inner.map(|inner| inner.prop_map(Some).boxed())
+}
+
+/// Generates values from one of `alternatives`, or from `fallback` if there
+/// are none.
+#[doc(hidden)]
+pub fn pt_one_of<T: Debug + 'static>(
+    alternatives: Vec<BoxedStrategy<T>>,
+    fallback: impl FnOnce() -> BoxedStrategy<T>,
+) -> BoxedStrategy<T> {
+    union(alternatives).unwrap_or_else(fallback)
+}
+
+#[doc(hidden)]
+pub fn pt_just<T: Clone + Debug + 'static>(value: T) -> BoxedStrategy<T> {
+    Just(value).boxed()
+}
+
+fn union<T: Debug + 'static>(strategies: Vec<BoxedStrategy<T>>) -> Option<BoxedStrategy<T>> {
+    match strategies.len() {
+        0 => None,
+        1 => strategies.into_iter().next(),
+        _ => Some(Union::new(strategies).boxed()),
+    }
+}
+
+fn length_range(
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> RangeInclusive<usize> {
+    match (min, max, equal) {
+        (_, _, Some(equal)) => equal..=equal,
+        (min, max, None) => {
+            let min = min.unwrap_or(0);
+            min..=max.unwrap_or(min + EXTRA_LENGTH)
+        }
+    }
+}
+
+fn outside_length_ranges(
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> impl Iterator<Item = RangeInclusive<usize>> {
+    let (min, max) = match equal {
+        Some(equal) => (Some(equal), Some(equal)),
+        None => (min, max),
+    };
+    let below = min.filter(|min| *min > 0).map(|min| 0..=min - 1);
+    let above = max.map(|max| max + 1..=max + EXTRA_LENGTH);
+    below.into_iter().chain(above)
+}
@@ -0,0 +1,67 @@
+//! Bounds of `range` validator of the derive macro.
+
+/// Bound of `range` validator convertible to the type of validated values.
+/// Every type is a bound of itself. With `rust_decimal` and `bigdecimal`
+/// features, integers and floats are bounds of `Decimal` and `BigDecimal`, so
+/// decimal fields can be compared with literals. Floats convert through their
+/// shortest representation, e.g. `0.1` to exactly `0.1`.
+pub trait RangeBound<T> {
+    fn into_bound(self) -> T;
+}
+
+impl<T> RangeBound<T> for T {
+    fn into_bound(self) -> T {
+        self
+    }
+}
+
+/// Converts `bound` to the type of `value`, which infers types of literal
+/// bounds from the validated value.
+pub fn range_bound<T, B: RangeBound<T>>(_value: &T, bound: B) -> T {
+    bound.into_bound()
+}
+
+#[cfg(any(feature = "rust_decimal", feature = "bigdecimal"))]
+macro_rules! impl_range_bound {
+    ($target:ty, $($int:ty),*) => {
+        $(
+            impl RangeBound<$target> for $int {
+                fn into_bound(self) -> $target {
+                    <$target>::from(self)
+                }
+            }
+        )*
+
+        impl RangeBound<$target> for f32 {
+            fn into_bound(self) -> $target {
+                parse_float(self)
+            }
+        }
+
+        impl RangeBound<$target> for f64 {
+            fn into_bound(self) -> $target {
+                parse_float(self)
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "rust_decimal", feature = "bigdecimal"))]
+fn parse_float<F, T>(value: F) -> T
+where
+    F: core::fmt::Display,
+    T: core::str::FromStr,
+{
+    use alloc::string::ToString;
+
+    value
+        .to_string()
+        .parse()
+        .unwrap_or_else(|_| panic!("range bound {} is not a valid decimal", value))
+}
+
+#[cfg(feature = "rust_decimal")]
+impl_range_bound!(rust_decimal::Decimal, i8, i16, i32, i64, u8, u16, u32, u64);
+
+#[cfg(feature = "bigdecimal")]
+impl_range_bound!(bigdecimal::BigDecimal, i8, i16, i32, i64, u8, u16, u32, u64);
@@ -0,0 +1,137 @@
+//! Custom validators registered under names, referenced by `custom(name =
+//! "...")` derive attributes and `custom` rules of
+//! [dynamic](crate::dynamic) validators instead of function paths.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::any::{Any, TypeId};
+
+use crate::{codes, ValidationError, ValidationNode};
+
+type NamedValidator<T> = Arc<dyn Fn(&T) -> ValidationNode + Send + Sync>;
+
+/// Custom validators registered under names. A name can have validators of
+/// many value types, e.g. of `String` for derived types and of
+/// `serde_json::Value` for [dynamic](crate::dynamic) validators.
+/// ```
+/// # use not_so_fast::*;
+/// let mut registry = ValidatorRegistry::new();
+/// registry.register("no_profanity", |value: &String| {
+///     ValidationNode::error_if(value.contains("heck"), || ValidationError::with_code("profanity"))
+/// });
+///
+/// assert!(registry.validate("no_profanity", &"hello".to_string()).is_ok());
+/// assert_eq!(
+///     ".: profanity",
+///     registry.validate("no_profanity", &"oh heck".to_string()).to_string()
+/// );
+/// ```
+#[derive(Default)]
+pub struct ValidatorRegistry {
+    validators: BTreeMap<(String, TypeId), Box<dyn Any + Send + Sync>>,
+}
+
+impl ValidatorRegistry {
+    /// Creates an empty registry.
+    pub const fn new() -> Self {
+        Self {
+            validators: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `validator` of values of type `T` under `name`, replacing
+    /// the validator of `T` already registered under `name`.
+    pub fn register<T, F>(&mut self, name: impl Into<String>, validator: F) -> &mut Self
+    where
+        T: ?Sized + 'static,
+        F: Fn(&T) -> ValidationNode + Send + Sync + 'static,
+    {
+        let validator: NamedValidator<T> = Arc::new(validator);
+        self.validators
+            .insert((name.into(), TypeId::of::<T>()), Box::new(validator));
+        self
+    }
+
+    /// Tells if a validator of values of type `T` is registered under
+    /// `name`.
+    pub fn contains<T: ?Sized + 'static>(&self, name: &str) -> bool {
+        self.get::<T>(name).is_some()
+    }
+
+    /// Validates `value` with the validator of `T` registered under `name`.
+    /// Reports [codes::UNKNOWN_VALIDATOR] if there is no such validator.
+    pub fn validate<T: ?Sized + 'static>(&self, name: &str, value: &T) -> ValidationNode {
+        match self.get::<T>(name) {
+            Some(validator) => validator(value),
+            None => ValidationNode::error(unknown_validator_error(name)),
+        }
+    }
+
+    fn get<T: ?Sized + 'static>(&self, name: &str) -> Option<&NamedValidator<T>> {
+        self.validators
+            .get(&(String::from(name), TypeId::of::<T>()))
+            .and_then(|validator| validator.downcast_ref())
+    }
+}
+
+impl core::fmt::Debug for ValidatorRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set()
+            .entries(self.validators.keys().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+static REGISTRY: std::sync::OnceLock<ValidatorRegistry> = std::sync::OnceLock::new();
+
+/// Registers the global registry of validators referenced by `custom(name =
+/// "...")` derive attributes and [validate_named]. Returns `false`, leaving
+/// the registered registry, if a registry is already registered.
+/// ```
+/// # use not_so_fast::*;
+/// let mut registry = ValidatorRegistry::new();
+/// registry.register("even", |value: &u32| {
+///     ValidationNode::error_if(value % 2 == 1, || ValidationError::with_code("odd"))
+/// });
+/// assert!(set_validator_registry(registry));
+///
+/// #[derive(Validate)]
+/// struct Pair {
+///     #[validate(custom(name = "even"))]
+///     size: u32,
+/// }
+///
+/// assert!(Pair { size: 2 }.validate().is_ok());
+/// assert_eq!(".size: odd", Pair { size: 3 }.validate().to_string());
+/// assert!(!set_validator_registry(ValidatorRegistry::new()));
+/// ```
+#[cfg(feature = "std")]
+pub fn set_validator_registry(registry: ValidatorRegistry) -> bool {
+    REGISTRY.set(registry).is_ok()
+}
+
+/// Returns the global registry, or an empty one if no registry is
+/// registered.
+#[cfg(feature = "std")]
+pub fn validator_registry() -> &'static ValidatorRegistry {
+    static EMPTY: ValidatorRegistry = ValidatorRegistry::new();
+    REGISTRY.get().unwrap_or(&EMPTY)
+}
+
+/// Validates `value` with the validator of `T` registered under `name` in
+/// the global registry. Reports [codes::UNKNOWN_VALIDATOR] if there is no
+/// such validator.
+#[cfg(feature = "std")]
+pub fn validate_named<T: ?Sized + 'static>(name: &str, value: &T) -> ValidationNode {
+    validator_registry().validate(name, value)
+}
+
+fn unknown_validator_error(name: &str) -> ValidationError {
+    ValidationError::with_code(codes::UNKNOWN_VALIDATOR)
+        .and_message("Unknown validator")
+        .and_param("name", String::from(name))
+}
@@ -0,0 +1,34 @@
+//! Rejection shared by web framework integrations.
+
+use core::fmt::{self, Display};
+
+use crate::ValidationNode;
+
+/// Rejection of validating extractors and guards. Either the inner extractor
+/// failed, or the extracted value is invalid.
+#[derive(Debug)]
+pub enum ValidationRejection<R> {
+    /// The inner extractor rejected the request, e.g. because of malformed
+    /// JSON.
+    Extract(R),
+    /// The extracted value failed validation.
+    Invalid(ValidationNode),
+}
+
+impl<R: Display> Display for ValidationRejection<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationRejection::Extract(rejection) => rejection.fmt(f),
+            ValidationRejection::Invalid(errors) => errors.fmt(f),
+        }
+    }
+}
+
+impl<R: std::error::Error + 'static> std::error::Error for ValidationRejection<R> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ValidationRejection::Extract(rejection) => Some(rejection),
+            ValidationRejection::Invalid(errors) => Some(errors),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Framework-agnostic layer of web framework integrations: validation of
+//! extracted values and error responses of one shape and configurable body
+//! format.
+
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+use crate::{Validate, ValidationNode};
+
+/// Wrapper of values that passed validation, like extractors and guards of
+/// web framework integrations. Implementing
+/// [from_validated](TryFromUnvalidated::from_validated) is enough to get
+/// [try_from_unvalidated](TryFromUnvalidated::try_from_unvalidated), which
+/// rejects values with errors of [Severity::Error](crate::Severity::Error).
+/// ```
+/// # use not_so_fast::*;
+/// struct Valid<T>(T);
+///
+/// impl<T> TryFromUnvalidated<T> for Valid<T> {
+///     fn from_validated(value: T) -> Self {
+///         Valid(value)
+///     }
+/// }
+///
+/// #[derive(Validate)]
+/// struct Page {
+///     #[validate(range(max = 100))]
+///     per_page: u32,
+/// }
+///
+/// assert!(Valid::try_from_unvalidated(Page { per_page: 10 }).is_ok());
+/// assert!(Valid::try_from_unvalidated(Page { per_page: 1000 }).is_err());
+/// ```
+pub trait TryFromUnvalidated<T>: Sized {
+    /// Wraps a value that passed validation.
+    fn from_validated(value: T) -> Self;
+
+    /// Validates `value` and wraps it, or returns its errors if it has errors
+    /// of [Severity::Error](crate::Severity::Error). Warnings and infos don't
+    /// reject the value.
+    fn try_from_unvalidated(value: T) -> Result<Self, ValidationNode>
+    where
+        T: Validate,
+    {
+        let errors = value.validate();
+        if errors.has_errors() {
+            Err(errors)
+        } else {
+            Ok(Self::from_validated(value))
+        }
+    }
+}
+
+/// Format of bodies of [ValidationResponse]. Defaults to
+/// [ErrorBodyFormat::Plain].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ErrorBodyFormat {
+    /// Default serialization of [ValidationNode].
+    #[default]
+    Plain,
+    /// Serialization of [NodeAsTree](crate::NodeAsTree).
+    Tree,
+    /// Serialization of [NodeAsFlatList](crate::NodeAsFlatList).
+    FlatList,
+    /// Problem details of [ValidationNode::to_problem] with status 422,
+    /// served as `application/problem+json`.
+    Problem {
+        /// Title of the problem.
+        title: Cow<'static, str>,
+        /// URI identifying the problem type.
+        type_uri: Cow<'static, str>,
+    },
+}
+
+static FORMAT: OnceLock<ErrorBodyFormat> = OnceLock::new();
+
+/// Sets the format of error responses of web framework integrations. Returns
+/// `false`, leaving the format unchanged, if the format is already set.
+/// ```
+/// # use not_so_fast::*;
+/// assert!(set_error_body_format(ErrorBodyFormat::FlatList));
+/// assert!(!set_error_body_format(ErrorBodyFormat::Tree));
+/// assert_eq!(&ErrorBodyFormat::FlatList, error_body_format());
+/// ```
+pub fn set_error_body_format(format: ErrorBodyFormat) -> bool {
+    FORMAT.set(format).is_ok()
+}
+
+/// Returns the format set with [set_error_body_format], or
+/// [ErrorBodyFormat::Plain].
+pub fn error_body_format() -> &'static ErrorBodyFormat {
+    FORMAT.get_or_init(ErrorBodyFormat::default)
+}
+
+/// Error response of invalid request data, shared by web framework
+/// integrations. Custom integrations convert it to the response type of their
+/// framework.
+/// ```
+/// # use not_so_fast::*;
+/// let errors = ValidationNode::field("name", ValidationNode::error(ValidationError::with_code("length")));
+/// let response = errors.into_validation_response(&ErrorBodyFormat::FlatList);
+///
+/// assert_eq!(422, response.status);
+/// assert_eq!("application/json", response.content_type);
+/// assert_eq!(r#"[{"path":"name","code":"length"}]"#, response.body);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationResponse {
+    /// HTTP status code, `422 Unprocessable Entity`.
+    pub status: u16,
+    /// Media type of the body.
+    pub content_type: &'static str,
+    /// JSON body.
+    pub body: String,
+}
+
+/// Conversion of validation errors to [ValidationResponse].
+pub trait IntoValidationResponse {
+    /// Responds with errors in the given format. Web framework integrations
+    /// use [error_body_format].
+    fn into_validation_response(self, format: &ErrorBodyFormat) -> ValidationResponse;
+}
+
+impl IntoValidationResponse for ValidationNode {
+    fn into_validation_response(self, format: &ErrorBodyFormat) -> ValidationResponse {
+        (&self).into_validation_response(format)
+    }
+}
+
+impl IntoValidationResponse for &ValidationNode {
+    fn into_validation_response(self, format: &ErrorBodyFormat) -> ValidationResponse {
+        const STATUS: u16 = 422;
+        let (content_type, body) = match format {
+            ErrorBodyFormat::Plain => ("application/json", serde_json::to_string(self)),
+            ErrorBodyFormat::Tree => ("application/json", serde_json::to_string(&self.as_tree())),
+            ErrorBodyFormat::FlatList => (
+                "application/json",
+                serde_json::to_string(&self.as_flat_list()),
+            ),
+            ErrorBodyFormat::Problem { title, type_uri } => (
+                crate::Problem::CONTENT_TYPE,
+                serde_json::to_string(
+                    &self
+                        .to_problem(title.clone(), type_uri.clone())
+                        .with_status(STATUS),
+                ),
+            ),
+        };
+        ValidationResponse {
+            status: STATUS,
+            content_type,
+            body: body.expect("validation errors should serialize to JSON"),
+        }
+    }
+}
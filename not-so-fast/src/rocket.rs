@@ -0,0 +1,229 @@
+//! Rocket guards validating request data before it reaches the handler.
+//!
+//! Guards reject data with errors of [Severity::Error], warnings and infos
+//! don't fail the request. Rocket doesn't pass guard errors to catchers, so
+//! [Validated] data guard also stores errors of rejected data in the request
+//! cache, where catchers can find them with [validation_errors] and respond
+//! with their [ValidationResponse].
+//!
+//! [Severity::Error]: crate::Severity::Error
+
+use core::ops::{Deref, DerefMut};
+use std::io::Cursor;
+
+use ::rocket::data::{self, Data, FromData};
+use ::rocket::form::{self, DataField, FromForm, ValueField};
+use ::rocket::http::{ContentType, Status};
+use ::rocket::outcome::Outcome;
+use ::rocket::response::{self, Responder, Response};
+use ::rocket::Request;
+
+use crate::{
+    PathSegment, Severity, TryFromUnvalidated, Validate, ValidationNode, ValidationRejection,
+    ValidationResponse,
+};
+
+/// Value validated by a guard. As a data guard it wraps another data guard,
+/// e.g. `Validated<Json<T>>` or `Validated<Form<T>>`, and validates its
+/// dereferenced value. Invalid data is rejected with `422 Unprocessable
+/// Entity`.
+/// ```
+/// # use not_so_fast::*;
+/// use rocket::serde::json::Json;
+///
+/// #[derive(serde::Deserialize, Validate)]
+/// struct CreateUser {
+///     #[validate(char_length(min = 1, max = 30))]
+///     name: String,
+/// }
+///
+/// #[rocket::post("/users", data = "<user>")]
+/// fn create_user(user: Validated<Json<CreateUser>>) -> String {
+///     user.into_inner().into_inner().name
+/// }
+/// ```
+///
+/// As a form guard, e.g. `Form<Validated<T>>` or a form field of type
+/// `Validated<T>`, it validates the value after parsing it and reports errors
+/// as form errors named after their paths, like `address.city`.
+/// ```
+/// # use not_so_fast::*;
+/// use rocket::form::Form;
+///
+/// #[derive(rocket::FromForm, Validate)]
+/// struct Search {
+///     #[validate(char_length(max = 100))]
+///     query: String,
+/// }
+///
+/// #[rocket::get("/search?<search..>")]
+/// fn search(search: Validated<Search>) -> String {
+///     search.into_inner().query
+/// }
+///
+/// #[rocket::post("/search", data = "<search>")]
+/// fn search_form(search: Form<Validated<Search>>) -> String {
+///     search.into_inner().into_inner().query
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Validated<T>(pub T);
+
+impl<T> Validated<T> {
+    /// Returns the validated value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> TryFromUnvalidated<T> for Validated<T> {
+    fn from_validated(value: T) -> Self {
+        Validated(value)
+    }
+}
+
+impl<T> Deref for Validated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Validated<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Errors of data rejected by [Validated] data guard, kept in the request
+/// cache.
+struct CachedErrors(Option<ValidationNode>);
+
+/// Returns errors of data rejected by [Validated] data guard while handling
+/// `request`, e.g. to render them in a catcher.
+/// ```
+/// # use not_so_fast::*;
+/// use rocket::serde::json::{json, Value};
+/// use rocket::Request;
+///
+/// #[rocket::catch(422)]
+/// fn unprocessable(request: &Request<'_>) -> Value {
+///     match validation_errors(request) {
+///         Some(errors) => json!({ "errors": errors }),
+///         None => json!({ "errors": [] }),
+///     }
+/// }
+///
+/// #[rocket::catch(422)]
+/// fn unprocessable_formatted(request: &Request<'_>) -> Option<ValidationResponse> {
+///     validation_errors(request).map(|errors| errors.into_validation_response(error_body_format()))
+/// }
+/// ```
+pub fn validation_errors<'r>(request: &'r Request<'_>) -> Option<&'r ValidationNode> {
+    request.local_cache(|| CachedErrors(None)).0.as_ref()
+}
+
+#[::rocket::async_trait]
+impl<'r, D> FromData<'r> for Validated<D>
+where
+    D: FromData<'r> + Deref,
+    D::Target: Validate,
+{
+    type Error = ValidationRejection<D::Error>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        match D::from_data(req, data).await {
+            Outcome::Success(value) => {
+                let errors = value.validate();
+                if errors.has_errors() {
+                    req.local_cache(|| CachedErrors(Some(errors.clone())));
+                    Outcome::Error((
+                        Status::UnprocessableEntity,
+                        ValidationRejection::Invalid(errors),
+                    ))
+                } else {
+                    Outcome::Success(Validated(value))
+                }
+            }
+            Outcome::Error((status, error)) => {
+                Outcome::Error((status, ValidationRejection::Extract(error)))
+            }
+            Outcome::Forward(forward) => Outcome::Forward(forward),
+        }
+    }
+}
+
+#[::rocket::async_trait]
+impl<'r, T> FromForm<'r> for Validated<T>
+where
+    T: FromForm<'r> + Validate,
+{
+    type Context = T::Context;
+
+    fn init(opts: form::Options) -> Self::Context {
+        T::init(opts)
+    }
+
+    fn push_value(ctxt: &mut Self::Context, field: ValueField<'r>) {
+        T::push_value(ctxt, field)
+    }
+
+    async fn push_data(ctxt: &mut Self::Context, field: DataField<'r, '_>) {
+        T::push_data(ctxt, field).await
+    }
+
+    fn push_error(ctxt: &mut Self::Context, error: form::Error<'r>) {
+        T::push_error(ctxt, error)
+    }
+
+    fn finalize(ctxt: Self::Context) -> form::Result<'r, Self> {
+        let value = T::finalize(ctxt)?;
+        Validated::try_from_unvalidated(value).map_err(|errors| form_errors(&errors))
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ValidationResponse {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let content_type =
+            ContentType::parse_flexible(self.content_type).unwrap_or(ContentType::JSON);
+        Response::build()
+            .status(Status::new(self.status))
+            .header(content_type)
+            .sized_body(self.body.len(), Cursor::new(self.body))
+            .ok()
+    }
+}
+
+/// Converts errors to form errors with messages, or codes if errors have no
+/// messages, named after dot-separated paths of errors.
+fn form_errors<'v>(errors: &ValidationNode) -> form::Errors<'v> {
+    errors
+        .iter()
+        .filter(|(_, error)| error.severity() == Severity::Error)
+        .map(|(path, error)| {
+            let message = error.message().unwrap_or(error.code()).to_owned();
+            let error = form::Error::validation(message);
+            if path.is_root() {
+                error
+            } else {
+                error.with_name(form_name(path.segments()))
+            }
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn form_name(segments: &[PathSegment]) -> String {
+    let mut name = String::new();
+    for segment in segments {
+        if !name.is_empty() {
+            name.push('.');
+        }
+        match segment {
+            PathSegment::Field(field) | PathSegment::Key(field) => name.push_str(field),
+            PathSegment::Index(index) => name.push_str(&index.to_string()),
+        }
+    }
+    name
+}
@@ -0,0 +1,402 @@
+//! Reusable rules of strings, numbers and lists, for manual validators.
+//! Rules are built once, e.g. stored in variables or shared between
+//! validators, and report the same codes, messages and params as the
+//! validators of the derive macro.
+//!
+//! ```
+//! # use not_so_fast::*;
+//! use not_so_fast::rules::{ListRules, NumberRules, StringRules};
+//!
+//! struct User {
+//!     name: String,
+//!     age: u8,
+//!     pet_names: Vec<String>,
+//! }
+//!
+//! let name = StringRules::new().max_bytes(5);
+//! let age = NumberRules::new().min(3);
+//! let pet_names = ListRules::new()
+//!     .max_items(2)
+//!     .items(StringRules::new().max_bytes(10));
+//!
+//! let validate_user = |user: &User| {
+//!     ValidationNode::ok()
+//!         .and_field("name", name.validate(&user.name))
+//!         .and_field("age", age.validate(&user.age))
+//!         .and_field("pet_names", pet_names.validate(&user.pet_names))
+//! };
+//!
+//! let user = User { name: "Alexander".into(), age: 2, pet_names: vec!["Rex".into()] };
+//! assert_eq!(
+//!     ".age: range: Number not in range: min=3, value=2\n\
+//!      .name: length: Invalid length: max=5, value=9",
+//!     validate_user(&user).to_string(),
+//! );
+//! ```
+
+use alloc::sync::Arc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{codes, ParamValue, ValidationError, ValidationNode};
+
+/// Rules of values of type `T`. Implemented by [StringRules],
+/// [NumberRules], [ListRules] and functions taking `&T` and returning
+/// [ValidationNode].
+pub trait Rules<T: ?Sized> {
+    fn validate(&self, value: &T) -> ValidationNode;
+}
+
+impl<T: ?Sized, F: Fn(&T) -> ValidationNode> Rules<T> for F {
+    fn validate(&self, value: &T) -> ValidationNode {
+        self(value)
+    }
+}
+
+/// Rules accepting all values, used by [ListRules] without item rules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoRules;
+
+impl<T: ?Sized> Rules<T> for NoRules {
+    fn validate(&self, _value: &T) -> ValidationNode {
+        ValidationNode::ok()
+    }
+}
+
+/// Additional rule of a value, added with `custom`.
+type CustomRule<T> = Arc<dyn Fn(&T) -> ValidationNode + Send + Sync>;
+
+/// Rules of strings.
+/// ```
+/// # use not_so_fast::*;
+/// use not_so_fast::rules::StringRules;
+///
+/// let username = StringRules::new().min_chars(3).max_bytes(64).ascii();
+/// assert!(username.validate("alice").is_ok());
+/// assert_eq!(
+///     ".: char_length: Invalid character length: min=3, value=2\n\
+///      .: ascii: String contains non-ASCII characters",
+///     username.validate("ål").to_string(),
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct StringRules {
+    min_chars: Option<usize>,
+    max_chars: Option<usize>,
+    min_bytes: Option<usize>,
+    max_bytes: Option<usize>,
+    ascii: bool,
+    custom: Vec<CustomRule<str>>,
+}
+
+impl StringRules {
+    /// Creates rules accepting all strings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires at least `min` characters. Reports [codes::CHAR_LENGTH].
+    pub fn min_chars(mut self, min: usize) -> Self {
+        self.min_chars = Some(min);
+        self
+    }
+
+    /// Requires at most `max` characters. Reports [codes::CHAR_LENGTH].
+    pub fn max_chars(mut self, max: usize) -> Self {
+        self.max_chars = Some(max);
+        self
+    }
+
+    /// Requires at least `min` bytes. Reports [codes::LENGTH].
+    pub fn min_bytes(mut self, min: usize) -> Self {
+        self.min_bytes = Some(min);
+        self
+    }
+
+    /// Requires at most `max` bytes. Reports [codes::LENGTH].
+    pub fn max_bytes(mut self, max: usize) -> Self {
+        self.max_bytes = Some(max);
+        self
+    }
+
+    /// Requires ASCII characters only. Reports [codes::ASCII].
+    pub fn ascii(mut self) -> Self {
+        self.ascii = true;
+        self
+    }
+
+    /// Adds a rule checked after the other rules.
+    /// ```
+    /// # use not_so_fast::*;
+    /// use not_so_fast::rules::StringRules;
+    ///
+    /// let slug = StringRules::new().max_chars(20).custom(|slug| {
+    ///     ValidationNode::error_if(slug.contains(' '), || ValidationError::with_code("slug"))
+    /// });
+    /// assert!(slug.validate("hello world").is_err());
+    /// ```
+    pub fn custom(mut self, rule: impl Fn(&str) -> ValidationNode + Send + Sync + 'static) -> Self {
+        self.custom.push(Arc::new(rule));
+        self
+    }
+
+    /// Checks `value` against the rules.
+    pub fn validate(&self, value: &str) -> ValidationNode {
+        ValidationNode::ok()
+            .merge(length_node(
+                || value.chars().count(),
+                self.min_chars,
+                self.max_chars,
+                codes::CHAR_LENGTH,
+                "Invalid character length",
+            ))
+            .merge(length_node(
+                || value.len(),
+                self.min_bytes,
+                self.max_bytes,
+                codes::LENGTH,
+                "Invalid length",
+            ))
+            .and_error_if(self.ascii && !value.is_ascii(), || {
+                ValidationError::with_code(codes::ASCII)
+                    .and_message("String contains non-ASCII characters")
+            })
+            .merge(custom_node(&self.custom, value))
+    }
+}
+
+impl<T: AsRef<str> + ?Sized> Rules<T> for StringRules {
+    fn validate(&self, value: &T) -> ValidationNode {
+        StringRules::validate(self, value.as_ref())
+    }
+}
+
+impl core::fmt::Debug for StringRules {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StringRules")
+            .field("min_chars", &self.min_chars)
+            .field("max_chars", &self.max_chars)
+            .field("min_bytes", &self.min_bytes)
+            .field("max_bytes", &self.max_bytes)
+            .field("ascii", &self.ascii)
+            .field("custom", &self.custom.len())
+            .finish()
+    }
+}
+
+/// Rules of numbers, or other ordered values convertible to [ParamValue].
+/// ```
+/// # use not_so_fast::*;
+/// use not_so_fast::rules::NumberRules;
+///
+/// let percent = NumberRules::new().min(0.0).max(100.0);
+/// assert!(percent.validate(&42.0).is_ok());
+/// assert_eq!(
+///     ".: range: Number not in range: max=100, min=0, value=120.5",
+///     percent.validate(&120.5).to_string(),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct NumberRules<T> {
+    min: Option<T>,
+    max: Option<T>,
+    custom: Vec<CustomRule<T>>,
+}
+
+impl<T> Default for NumberRules<T> {
+    fn default() -> Self {
+        Self {
+            min: None,
+            max: None,
+            custom: Vec::new(),
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone + Into<ParamValue>> NumberRules<T> {
+    /// Creates rules accepting all numbers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires numbers not less than `min`. Reports [codes::RANGE].
+    pub fn min(mut self, min: T) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Requires numbers not greater than `max`. Reports [codes::RANGE].
+    pub fn max(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Adds a rule checked after the other rules.
+    pub fn custom(mut self, rule: impl Fn(&T) -> ValidationNode + Send + Sync + 'static) -> Self {
+        self.custom.push(Arc::new(rule));
+        self
+    }
+
+    /// Checks `value` against the rules.
+    pub fn validate(&self, value: &T) -> ValidationNode {
+        let below = self.min.as_ref().is_some_and(|min| value < min);
+        let above = self.max.as_ref().is_some_and(|max| value > max);
+        ValidationNode::error_if(below || above, || {
+            let mut error = ValidationError::with_code(codes::RANGE)
+                .and_message("Number not in range")
+                .and_param("value", value.clone());
+            if let Some(min) = &self.min {
+                error = error.and_param("min", min.clone());
+            }
+            if let Some(max) = &self.max {
+                error = error.and_param("max", max.clone());
+            }
+            error
+        })
+        .merge(custom_node(&self.custom, value))
+    }
+}
+
+impl<T: PartialOrd + Clone + Into<ParamValue>> Rules<T> for NumberRules<T> {
+    fn validate(&self, value: &T) -> ValidationNode {
+        NumberRules::validate(self, value)
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for NumberRules<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NumberRules")
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("custom", &self.custom.len())
+            .finish()
+    }
+}
+
+/// Rules of lists, and of their items with item rules `R`.
+/// ```
+/// # use not_so_fast::*;
+/// use not_so_fast::rules::{ListRules, NumberRules};
+///
+/// let scores = ListRules::new().min_items(1).items(NumberRules::new().max(10));
+/// assert!(scores.validate(&[1, 5]).is_ok());
+/// assert_eq!(
+///     ".[1]: range: Number not in range: max=10, value=12",
+///     scores.validate(&vec![3, 12]).to_string(),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct ListRules<R = NoRules> {
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    items: R,
+}
+
+impl ListRules {
+    /// Creates rules accepting all lists.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for ListRules {
+    fn default() -> Self {
+        Self {
+            min_items: None,
+            max_items: None,
+            items: NoRules,
+        }
+    }
+}
+
+impl<R> ListRules<R> {
+    /// Requires at least `min` items. Reports [codes::LENGTH].
+    pub fn min_items(mut self, min: usize) -> Self {
+        self.min_items = Some(min);
+        self
+    }
+
+    /// Requires at most `max` items. Reports [codes::LENGTH].
+    pub fn max_items(mut self, max: usize) -> Self {
+        self.max_items = Some(max);
+        self
+    }
+
+    /// Sets rules of items, replacing previous item rules. Errors of items
+    /// are reported at their indices.
+    pub fn items<I>(self, items: I) -> ListRules<I> {
+        ListRules {
+            min_items: self.min_items,
+            max_items: self.max_items,
+            items,
+        }
+    }
+
+    /// Checks `values` against the rules.
+    pub fn validate<T>(&self, values: &[T]) -> ValidationNode
+    where
+        R: Rules<T>,
+    {
+        length_node(
+            || values.len(),
+            self.min_items,
+            self.max_items,
+            codes::LENGTH,
+            "Invalid length",
+        )
+        .and_items(values.iter(), |_, item| self.items.validate(item))
+    }
+}
+
+impl<T, R: Rules<T>> Rules<[T]> for ListRules<R> {
+    fn validate(&self, values: &[T]) -> ValidationNode {
+        ListRules::validate(self, values)
+    }
+}
+
+impl<T, R: Rules<T>> Rules<Vec<T>> for ListRules<R> {
+    fn validate(&self, values: &Vec<T>) -> ValidationNode {
+        ListRules::validate(self, values)
+    }
+}
+
+impl<R: core::fmt::Debug> core::fmt::Debug for ListRules<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ListRules")
+            .field("min_items", &self.min_items)
+            .field("max_items", &self.max_items)
+            .field("items", &self.items)
+            .finish()
+    }
+}
+
+/// Reports an error with `code` if the length is out of bounds.
+fn length_node(
+    length: impl FnOnce() -> usize,
+    min: Option<usize>,
+    max: Option<usize>,
+    code: &'static str,
+    message: &'static str,
+) -> ValidationNode {
+    if min.is_none() && max.is_none() {
+        return ValidationNode::ok();
+    }
+    let length = length();
+    let invalid = min.is_some_and(|min| length < min) || max.is_some_and(|max| length > max);
+    ValidationNode::error_if(invalid, || {
+        let mut error = ValidationError::with_code(code)
+            .and_message(message)
+            .and_param("value", length);
+        if let Some(min) = min {
+            error = error.and_param("min", min);
+        }
+        if let Some(max) = max {
+            error = error.and_param("max", max);
+        }
+        error
+    })
+}
+
+fn custom_node<T: ?Sized>(custom: &[CustomRule<T>], value: &T) -> ValidationNode {
+    ValidationNode::all(custom.iter().map(|rule| rule(value)))
+}
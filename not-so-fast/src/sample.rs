@@ -0,0 +1,249 @@
+//! Generation of realistic valid values with `fake`, used by the `sample`
+//! flag of the derive macro.
+
+use alloc::collections::{BTreeSet, VecDeque};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use ::fake::faker::lorem::en::Word;
+use ::fake::{Dummy, Fake, Faker};
+use ::rand::Rng;
+
+use crate::{Validate, ValidationNode};
+
+/// Type generating realistic values that pass validation, e.g. to seed
+/// staging environments, implemented by the derive macro for types with
+/// `#[validate(sample)]`.
+pub trait Sample: Sized {
+    /// Generates a valid value with `rng`.
+    fn sample_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+    /// Generates a valid value with the thread-local generator of `rand`.
+    fn sample() -> Self {
+        Self::sample_with_rng(&mut ::rand::rng())
+    }
+}
+
+/// Attempts at generating a valid value before giving up.
+const MAX_ATTEMPTS: usize = 1000;
+
+/// Highest length above the lower bound of unbounded lengths, and distance
+/// from the bound of half-bounded ranges.
+const SPREAD: u8 = 24;
+
+/// Generates values with `generate` until one passes validation. Panics if
+/// no value passes validation in [MAX_ATTEMPTS] attempts.
+#[doc(hidden)]
+pub fn sa_valid<T, R>(rng: &mut R, mut generate: impl FnMut(&mut R) -> T) -> T
+where
+    T: Validate,
+    R: Rng + ?Sized,
+{
+    let mut errors = ValidationNode::ok();
+    for _ in 0..MAX_ATTEMPTS {
+        let value = generate(rng);
+        errors = value.validate();
+        if errors.is_ok() {
+            return value;
+        }
+    }
+    panic!(
+        "no valid value of {} sampled in {MAX_ATTEMPTS} attempts, last errors:\n{errors}",
+        core::any::type_name::<T>(),
+    );
+}
+
+/// Number validated with `range`.
+#[doc(hidden)]
+pub trait SampleRange: Sized {
+    fn sample_range<R: Rng + ?Sized>(rng: &mut R, min: Option<Self>, max: Option<Self>) -> Self;
+}
+
+macro_rules! impl_sample_range_int {
+    ($($int:ty),*) => {
+        $(
+            impl SampleRange for $int {
+                fn sample_range<R: Rng + ?Sized>(rng: &mut R, min: Option<Self>, max: Option<Self>) -> Self {
+                    let spread = SPREAD as $int;
+                    match (min, max) {
+                        (Some(min), Some(max)) => rng.random_range(min..=max.max(min)),
+                        (Some(min), None) => rng.random_range(min..=min.saturating_add(spread)),
+                        (None, Some(max)) => rng.random_range(max.saturating_sub(spread)..=max),
+                        (None, None) => Faker.fake_with_rng(rng),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_sample_range_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_sample_range_float {
+    ($($float:ty),*) => {
+        $(
+            impl SampleRange for $float {
+                fn sample_range<R: Rng + ?Sized>(rng: &mut R, min: Option<Self>, max: Option<Self>) -> Self {
+                    let spread = <$float>::from(SPREAD);
+                    match (min, max) {
+                        (Some(min), Some(max)) => rng.random_range(min..=max.max(min)),
+                        (Some(min), None) => rng.random_range(min..=min + spread),
+                        (None, Some(max)) => rng.random_range(max - spread..=max),
+                        (None, None) => Faker.fake_with_rng(rng),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_sample_range_float!(f32, f64);
+
+/// Value validated with `length`.
+#[doc(hidden)]
+pub trait SampleLength: Sized {
+    fn sample_length<R: Rng + ?Sized>(rng: &mut R, length: usize) -> Self;
+}
+
+impl SampleLength for String {
+    fn sample_length<R: Rng + ?Sized>(rng: &mut R, length: usize) -> Self {
+        text(rng, length)
+    }
+}
+
+impl<T: Dummy<Faker>> SampleLength for Vec<T> {
+    fn sample_length<R: Rng + ?Sized>(rng: &mut R, length: usize) -> Self {
+        (0..length).map(|_| Faker.fake_with_rng(rng)).collect()
+    }
+}
+
+/// Collection validated with `items`.
+#[doc(hidden)]
+pub trait SampleCollection: Sized {
+    type Item;
+
+    fn from_items(items: Vec<Self::Item>) -> Self;
+}
+
+impl<T> SampleCollection for Vec<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Self {
+        items
+    }
+}
+
+impl<T> SampleCollection for VecDeque<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Self {
+        items.into()
+    }
+}
+
+impl<T: Ord> SampleCollection for BTreeSet<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Self {
+        items.into_iter().collect()
+    }
+}
+
+impl<T: Eq + Hash> SampleCollection for HashSet<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Self {
+        items.into_iter().collect()
+    }
+}
+
+#[doc(hidden)]
+pub fn sa_any<T: Dummy<Faker>, R: Rng + ?Sized>(rng: &mut R) -> T {
+    Faker.fake_with_rng(rng)
+}
+
+#[doc(hidden)]
+pub fn sa_fake<T: Dummy<F>, F, R: Rng + ?Sized>(rng: &mut R, faker: &F) -> T {
+    T::dummy_with_rng(faker, rng)
+}
+
+#[doc(hidden)]
+pub fn sa_nested<T: Sample, R: Rng + ?Sized>(rng: &mut R) -> T {
+    T::sample_with_rng(rng)
+}
+
+#[doc(hidden)]
+pub fn sa_range<T: SampleRange, R: Rng + ?Sized>(rng: &mut R, min: Option<T>, max: Option<T>) -> T {
+    T::sample_range(rng, min, max)
+}
+
+#[doc(hidden)]
+pub fn sa_length<T: SampleLength, R: Rng + ?Sized>(
+    rng: &mut R,
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> T {
+    let length = length(rng, min, max, equal);
+    T::sample_length(rng, length)
+}
+
+#[doc(hidden)]
+pub fn sa_char_length<T: From<String>, R: Rng + ?Sized>(
+    rng: &mut R,
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> T {
+    let length = length(rng, min, max, equal);
+    text(rng, length).into()
+}
+
+#[doc(hidden)]
+pub fn sa_items<C: SampleCollection, R: Rng + ?Sized>(
+    rng: &mut R,
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+    mut item: impl FnMut(&mut R) -> C::Item,
+) -> C {
+    let length = length(rng, min, max, equal);
+    C::from_items((0..length).map(|_| item(rng)).collect())
+}
+
+#[doc(hidden)]
+pub fn sa_some<T, R: Rng + ?Sized>(rng: &mut R, inner: impl FnOnce(&mut R) -> T) -> Option<T> {
+    rng.random_bool(0.8).then(|| inner(rng))
+}
+
+fn length<R: Rng + ?Sized>(
+    rng: &mut R,
+    min: Option<usize>,
+    max: Option<usize>,
+    equal: Option<usize>,
+) -> usize {
+    match (min, max, equal) {
+        (_, _, Some(equal)) => equal,
+        (min, max, None) => {
+            let min = min.unwrap_or(0);
+            rng.random_range(min..=max.unwrap_or(min + usize::from(SPREAD)).max(min))
+        }
+    }
+}
+
+/// Generates lorem ipsum text of exactly `length` ASCII characters.
+fn text<R: Rng + ?Sized>(rng: &mut R, length: usize) -> String {
+    let mut text = String::new();
+    while text.len() < length {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(&Word().fake_with_rng::<String, _>(rng));
+    }
+    text.truncate(length);
+    if text.ends_with(' ') {
+        text.pop();
+        text.push('a');
+    }
+    text
+}
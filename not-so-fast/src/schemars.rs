@@ -0,0 +1,155 @@
+//! Constraints of validation attributes in JSON schemas generated with
+//! `schemars`.
+
+use ::schemars::Schema;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Type adding constraints of its validation attributes to its JSON schema.
+/// Implemented by the derive macro with `#[validate(schemars)]`, see
+/// [augment_schema].
+pub trait ValidationSchema {
+    /// Adds constraints of validation attributes to `schema`, the schema of
+    /// `Self` generated by `schemars`.
+    fn augment_schema(schema: &mut Schema);
+}
+
+/// Adds constraints of validation attributes of `T` to `schema`. Meant for
+/// `#[schemars(transform = not_so_fast::augment_schema::<Self>)]`, so one
+/// set of attributes feeds both validation and schema generation.
+///
+/// Constraints of `length`, `char_length` and `range` validators are added
+/// to properties of the fields, also inside `some`, `items` and `fields`.
+/// `length` of strings counts bytes, so `minLength` and `maxLength` match it
+/// only for ASCII strings. Other validators aren't expressed in schemas.
+/// Properties are matched with fields by name, or else ignoring case,
+/// underscores and hyphens, to follow renames of `serde`.
+/// ```
+/// # use not_so_fast::*;
+/// use schemars::{schema_for, JsonSchema};
+///
+/// #[derive(JsonSchema, Validate)]
+/// #[validate(schemars)]
+/// #[schemars(transform = not_so_fast::augment_schema::<Self>)]
+/// struct User {
+///     #[validate(char_length(min = 1, max = 30))]
+///     name: String,
+///     #[validate(items(range(min = 0, max = 100)))]
+///     scores: Vec<u32>,
+/// }
+///
+/// let schema = schema_for!(User);
+/// assert_eq!(30, schema.pointer("/properties/name/maxLength").unwrap().as_u64().unwrap());
+/// assert_eq!(100, schema.pointer("/properties/scores/items/maximum").unwrap().as_u64().unwrap());
+/// ```
+pub fn augment_schema<T: ValidationSchema + ?Sized>(schema: &mut Schema) {
+    T::augment_schema(schema);
+}
+
+/// Calls `f` with the schema of property `name` of an object schema.
+#[doc(hidden)]
+pub fn schema_property(schema: &mut Schema, name: &str, f: impl FnOnce(&mut Value)) {
+    let Some(Value::Object(properties)) = schema.get_mut("properties") else {
+        return;
+    };
+    if let Some(property) = find_property(properties, name) {
+        f(property);
+    }
+}
+
+/// Calls `f` with the schema of items of an array schema.
+#[doc(hidden)]
+pub fn schema_items(schema: &mut Value, f: impl FnOnce(&mut Value)) {
+    if let Some(items) = schema.get_mut("items") {
+        f(items);
+    }
+}
+
+/// Calls `f` with the schema of values of a map schema.
+#[doc(hidden)]
+pub fn schema_values(schema: &mut Value, f: impl FnOnce(&mut Value)) {
+    if let Some(values @ Value::Object(_)) = schema.get_mut("additionalProperties") {
+        f(values);
+    }
+}
+
+/// Adds bounds of `length` validator, keywords depending on the type of the
+/// schema.
+#[doc(hidden)]
+pub fn schema_length(schema: &mut Value, min: Option<usize>, max: Option<usize>) {
+    for (type_, suffix) in [
+        ("string", "Length"),
+        ("array", "Items"),
+        ("object", "Properties"),
+    ] {
+        if has_type(schema, type_) {
+            insert_bounds(schema, suffix, min, max);
+        }
+    }
+}
+
+/// Adds bounds of `char_length` validator.
+#[doc(hidden)]
+pub fn schema_char_length(schema: &mut Value, min: Option<usize>, max: Option<usize>) {
+    if has_type(schema, "string") {
+        insert_bounds(schema, "Length", min, max);
+    }
+}
+
+/// Adds `minimum` of `range` validator, if it serializes to a number.
+#[doc(hidden)]
+pub fn schema_minimum(schema: &mut Value, min: &impl Serialize) {
+    insert_number(schema, "minimum", min);
+}
+
+/// Adds `maximum` of `range` validator, if it serializes to a number.
+#[doc(hidden)]
+pub fn schema_maximum(schema: &mut Value, max: &impl Serialize) {
+    insert_number(schema, "maximum", max);
+}
+
+fn find_property<'a>(properties: &'a mut Map<String, Value>, name: &str) -> Option<&'a mut Value> {
+    if properties.contains_key(name) {
+        return properties.get_mut(name);
+    }
+    let name = normalize(name);
+    properties
+        .iter_mut()
+        .find(|(key, _)| normalize(key) == name)
+        .map(|(_, property)| property)
+}
+
+fn normalize(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn has_type(schema: &Value, type_: &str) -> bool {
+    match schema.get("type") {
+        Some(Value::String(value)) => value == type_,
+        Some(Value::Array(values)) => values.iter().any(|value| value == type_),
+        _ => false,
+    }
+}
+
+fn insert_bounds(schema: &mut Value, suffix: &str, min: Option<usize>, max: Option<usize>) {
+    let Value::Object(object) = schema else {
+        return;
+    };
+    if let Some(min) = min {
+        object.insert(format!("min{suffix}"), min.into());
+    }
+    if let Some(max) = max {
+        object.insert(format!("max{suffix}"), max.into());
+    }
+}
+
+fn insert_number(schema: &mut Value, key: &str, value: &impl Serialize) {
+    if let (Value::Object(object), Ok(value @ Value::Number(_))) =
+        (schema, serde_json::to_value(value))
+    {
+        object.insert(key.to_owned(), value);
+    }
+}
@@ -0,0 +1,73 @@
+//! Passing errors of derived types to [ErrorSink]s field by field, used by
+//! `validate_args_into` generated by the derive macro.
+
+use alloc::borrow::Cow;
+
+use crate::{ErrorSink, Path, PathSegment, ValidationError, ValidationNode};
+
+/// [ErrorSink] prepending a segment to paths of received errors, so that
+/// errors of a field or item are reported at the path of its parent.
+pub struct PrefixedSink<'a> {
+    segment: PathSegment,
+    sink: &'a mut dyn ErrorSink,
+}
+
+impl<'a> PrefixedSink<'a> {
+    /// Reports errors at field `name` of the node receiving errors of `sink`.
+    pub fn field(name: &'static str, sink: &'a mut dyn ErrorSink) -> Self {
+        Self {
+            segment: PathSegment::Field(Cow::Borrowed(name)),
+            sink,
+        }
+    }
+
+    /// Reports errors at item `index` of the node receiving errors of `sink`.
+    pub fn index(index: usize, sink: &'a mut dyn ErrorSink) -> Self {
+        Self {
+            segment: PathSegment::Index(index),
+            sink,
+        }
+    }
+}
+
+impl ErrorSink for PrefixedSink<'_> {
+    fn push(&mut self, mut path: Path, error: ValidationError) {
+        path.segments.insert(0, self.segment.clone());
+        self.sink.push(path, error);
+    }
+
+    fn is_done(&self) -> bool {
+        self.sink.is_done()
+    }
+}
+
+/// Passes errors of `node` to `sink` and checks if the sink is done.
+pub fn drain_root(node: ValidationNode, sink: &mut dyn ErrorSink) -> bool {
+    node.drain_into(sink);
+    sink.is_done()
+}
+
+/// Passes errors of `node` to `sink` at field `name` and checks if the sink
+/// is done.
+pub fn drain_field(node: ValidationNode, name: &'static str, sink: &mut dyn ErrorSink) -> bool {
+    node.drain_into(&mut PrefixedSink::field(name, sink));
+    sink.is_done()
+}
+
+/// Passes errors of `node` to `sink` at item `index` and checks if the sink
+/// is done.
+pub fn drain_item(node: ValidationNode, index: usize, sink: &mut dyn ErrorSink) -> bool {
+    node.drain_into(&mut PrefixedSink::index(index, sink));
+    sink.is_done()
+}
+
+/// Runs `validate` passing errors of a nested value to `sink` inside
+/// [ValidationNode::nested], so that limits of nesting depth apply. Checks
+/// if the sink is done.
+pub fn nested_into(sink: &mut dyn ErrorSink, validate: impl FnOnce(&mut dyn ErrorSink)) -> bool {
+    let node = ValidationNode::nested(|| {
+        validate(&mut *sink);
+        ValidationNode::ok()
+    });
+    drain_root(node, sink)
+}
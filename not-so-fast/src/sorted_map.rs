@@ -0,0 +1,246 @@
+//! Map storing entries in a `Vec` sorted by key. Nodes of validation trees
+//! usually have few children, for which binary search over a `Vec` is as
+//! fast as a `BTreeMap` lookup, and takes a single allocation.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+
+/// Map with entries sorted by key, supporting the subset of `BTreeMap` API
+/// used by [ValidationNode](crate::ValidationNode).
+#[derive(Clone, PartialEq)]
+pub(crate) struct SortedMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K: Ord, V> SortedMap<K, V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    fn find<Q: Ord + ?Sized>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.entries
+            .binary_search_by(|(entry_key, _)| entry_key.borrow().cmp(key))
+    }
+
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(crate) fn get<Q: Ord + ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        let index = self.find(key).ok()?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns value of the entry for which `f` returns `Ordering::Equal`.
+    /// `f` compares keys with the searched one, allowing lookups by keys
+    /// that can't be borrowed from `K`.
+    pub(crate) fn get_by(&self, mut f: impl FnMut(&K) -> core::cmp::Ordering) -> Option<&V> {
+        let index = self
+            .entries
+            .binary_search_by(|(entry_key, _)| f(entry_key))
+            .ok()?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns value of the entry found like in [get_by](SortedMap::get_by).
+    pub(crate) fn get_mut_by(
+        &mut self,
+        mut f: impl FnMut(&K) -> core::cmp::Ordering,
+    ) -> Option<&mut V> {
+        let index = self
+            .entries
+            .binary_search_by(|(entry_key, _)| f(entry_key))
+            .ok()?;
+        Some(&mut self.entries[index].1)
+    }
+
+    pub(crate) fn get_mut<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        let index = self.find(key).ok()?;
+        Some(&mut self.entries[index].1)
+    }
+
+    pub(crate) fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find(&key) {
+            Ok(index) => Some(core::mem::replace(&mut self.entries[index].1, value)),
+            Err(index) => {
+                self.entries.insert(index, (key, value));
+                None
+            }
+        }
+    }
+
+    pub(crate) fn remove<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        let index = self.find(key).ok()?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Removes the entry found like in [get_by](SortedMap::get_by).
+    pub(crate) fn remove_by(&mut self, mut f: impl FnMut(&K) -> core::cmp::Ordering) -> Option<V> {
+        let index = self
+            .entries
+            .binary_search_by(|(entry_key, _)| f(entry_key))
+            .ok()?;
+        Some(self.entries.remove(index).1)
+    }
+
+    pub(crate) fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.find(&key) {
+            Ok(index) => Entry::Occupied(OccupiedEntry {
+                entry: &mut self.entries[index],
+            }),
+            Err(index) => Entry::Vacant(VacantEntry {
+                entries: &mut self.entries,
+                index,
+                key,
+            }),
+        }
+    }
+
+    pub(crate) fn retain(&mut self, mut f: impl FnMut(&K, &mut V) -> bool) {
+        self.entries.retain_mut(|(key, value)| f(key, value));
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, K, V> {
+        Iter(self.entries.iter())
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> + '_ {
+        self.entries.iter_mut().map(|(key, value)| (&*key, value))
+    }
+
+    pub(crate) fn values(&self) -> impl DoubleEndedIterator<Item = &V> + '_ {
+        self.entries.iter().map(|(_, value)| value)
+    }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut V> + '_ {
+        self.entries.iter_mut().map(|(_, value)| value)
+    }
+}
+
+impl<K: Ord, V> Default for SortedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: core::fmt::Debug, V: core::fmt::Debug> core::fmt::Debug for SortedMap<K, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|(key, value)| (key, value)))
+            .finish()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SortedMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<K, V> IntoIterator for SortedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = alloc::vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SortedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter(self.entries.iter())
+    }
+}
+
+/// Iterator over entries of [SortedMap], in key order.
+#[derive(Clone)]
+pub(crate) struct Iter<'a, K, V>(core::slice::Iter<'a, (K, V)>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(key, value)| (key, value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// Entry of [SortedMap], like `btree_map::Entry`.
+pub(crate) enum Entry<'a, K, V> {
+    Vacant(VacantEntry<'a, K, V>),
+    Occupied(OccupiedEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub(crate) fn or_insert_with(self, f: impl FnOnce() -> V) -> &'a mut V {
+        match self {
+            Entry::Vacant(entry) => entry.insert(f()),
+            Entry::Occupied(entry) => entry.into_mut(),
+        }
+    }
+}
+
+pub(crate) struct VacantEntry<'a, K, V> {
+    entries: &'a mut Vec<(K, V)>,
+    index: usize,
+    key: K,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    pub(crate) fn insert(self, value: V) -> &'a mut V {
+        self.entries.insert(self.index, (self.key, value));
+        &mut self.entries[self.index].1
+    }
+}
+
+pub(crate) struct OccupiedEntry<'a, K, V> {
+    entry: &'a mut (K, V),
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub(crate) fn get_mut(&mut self) -> &mut V {
+        &mut self.entry.1
+    }
+
+    pub(crate) fn into_mut(self) -> &'a mut V {
+        &mut self.entry.1
+    }
+}
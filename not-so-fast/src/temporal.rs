@@ -0,0 +1,126 @@
+//! Support of date and time types for `before`, `after` and `within_last`
+//! validators of the derive macro.
+
+use core::time::Duration;
+
+/// Date or time type comparable with the current time. Implemented for
+/// `chrono` types `DateTime`, `NaiveDate` and `NaiveDateTime` with the `chrono`
+/// feature, and for `time` types `OffsetDateTime`, `Date` and
+/// `PrimitiveDateTime` with the `time` feature. The current time of naive and
+/// primitive types is taken in UTC.
+/// ```
+/// use not_so_fast::temporal::Temporal;
+/// use chrono::{NaiveDate, Utc};
+///
+/// let date = NaiveDate::from_ymd_opt(2020, 3, 10).unwrap();
+/// assert_eq!(Utc::now().date_naive(), date.now_like());
+/// assert_eq!(
+///     NaiveDate::from_ymd_opt(2020, 2, 29),
+///     date.checked_sub(std::time::Duration::from_secs(10 * 24 * 3600)),
+/// );
+/// ```
+pub trait Temporal: PartialOrd + Sized {
+    /// Returns the current time represented like `self`, e.g. in the time zone
+    /// of `self`.
+    fn now_like(&self) -> Self;
+
+    /// Returns `self` moved back by `duration`, or `None` on overflow. Dates
+    /// move back by whole days of `duration`.
+    fn checked_sub(&self, duration: Duration) -> Option<Self>;
+}
+
+/// Checks if `value` is not later than `now` and not earlier than `now`
+/// moved back by `duration`.
+/// ```
+/// use not_so_fast::temporal::is_within_last;
+/// use chrono::NaiveDate;
+/// use std::time::Duration;
+///
+/// let now = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+/// let month = Duration::from_secs(30 * 24 * 3600);
+/// assert!(is_within_last(&NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), &now, month));
+/// assert!(!is_within_last(&NaiveDate::from_ymd_opt(2024, 4, 30).unwrap(), &now, month));
+/// assert!(!is_within_last(&NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(), &now, month));
+/// ```
+pub fn is_within_last<T: Temporal>(value: &T, now: &T, duration: Duration) -> bool {
+    match now.checked_sub(duration) {
+        Some(start) => start <= *value && *value <= *now,
+        None => *value <= *now,
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use ::chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+
+    use super::*;
+
+    impl<Tz: TimeZone> Temporal for DateTime<Tz> {
+        fn now_like(&self) -> Self {
+            Utc::now().with_timezone(&self.timezone())
+        }
+
+        fn checked_sub(&self, duration: Duration) -> Option<Self> {
+            self.clone()
+                .checked_sub_signed(TimeDelta::from_std(duration).ok()?)
+        }
+    }
+
+    impl Temporal for NaiveDate {
+        fn now_like(&self) -> Self {
+            Utc::now().date_naive()
+        }
+
+        fn checked_sub(&self, duration: Duration) -> Option<Self> {
+            self.checked_sub_signed(TimeDelta::from_std(duration).ok()?)
+        }
+    }
+
+    impl Temporal for NaiveDateTime {
+        fn now_like(&self) -> Self {
+            Utc::now().naive_utc()
+        }
+
+        fn checked_sub(&self, duration: Duration) -> Option<Self> {
+            self.checked_sub_signed(TimeDelta::from_std(duration).ok()?)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use ::time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+    use super::*;
+
+    impl Temporal for OffsetDateTime {
+        fn now_like(&self) -> Self {
+            OffsetDateTime::now_utc().to_offset(self.offset())
+        }
+
+        fn checked_sub(&self, duration: Duration) -> Option<Self> {
+            OffsetDateTime::checked_sub(*self, duration.try_into().ok()?)
+        }
+    }
+
+    impl Temporal for Date {
+        fn now_like(&self) -> Self {
+            OffsetDateTime::now_utc().date()
+        }
+
+        fn checked_sub(&self, duration: Duration) -> Option<Self> {
+            Date::checked_sub(*self, duration.try_into().ok()?)
+        }
+    }
+
+    impl Temporal for PrimitiveDateTime {
+        fn now_like(&self) -> Self {
+            let now = OffsetDateTime::now_utc();
+            PrimitiveDateTime::new(now.date(), now.time())
+        }
+
+        fn checked_sub(&self, duration: Duration) -> Option<Self> {
+            PrimitiveDateTime::checked_sub(*self, duration.try_into().ok()?)
+        }
+    }
+}
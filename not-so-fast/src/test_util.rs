@@ -0,0 +1,101 @@
+//! Assertions of validation results for tests.
+
+use crate::ValidationNode;
+
+/// Asserts that a value implementing [Validate](crate::Validate) is valid.
+/// Panics with the error tree otherwise.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(Validate)]
+/// struct User {
+///     #[validate(range(max = 150))]
+///     age: u32,
+/// }
+///
+/// assert_valid!(User { age: 30 });
+/// ```
+#[macro_export]
+macro_rules! assert_valid {
+    ($value:expr $(,)?) => {{
+        use $crate::Validate as _;
+        $crate::__private::assert_valid(&($value).validate())
+    }};
+}
+
+/// Asserts that a value implementing [Validate](crate::Validate) is
+/// invalid. With `path => code` pairs, asserts that the value has exactly
+/// errors with these codes at these paths, in any order, ignoring messages
+/// and params. Panics with a diff of expected and actual errors, and the
+/// error tree, otherwise.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(Validate)]
+/// #[validate(custom = check_user)]
+/// struct User {
+///     #[validate(char_length(max = 5))]
+///     name: String,
+///     #[validate(range(max = 150))]
+///     age: u32,
+/// }
+///
+/// fn check_user(user: &User) -> ValidationNode {
+///     ValidationNode::error_if(user.name.is_empty() && user.age > 0, || {
+///         ValidationError::with_code("anonymous")
+///     })
+/// }
+///
+/// assert_invalid!(User { name: "Alexander".into(), age: 30 });
+/// assert_invalid!(
+///     User { name: String::new(), age: 200 },
+///     ".age" => "range",
+///     "." => "anonymous",
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_invalid {
+    ($value:expr $(,)?) => {{
+        use $crate::Validate as _;
+        $crate::__private::assert_invalid(&($value).validate())
+    }};
+    ($value:expr, $($path:expr => $code:expr),+ $(,)?) => {{
+        use $crate::Validate as _;
+        $crate::__private::assert_errors(&($value).validate(), &[$(($path, $code)),+])
+    }};
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_valid(node: &ValidationNode) {
+    if !node.is_ok() {
+        panic!("expected valid value, found errors:\n{node}");
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_invalid(node: &ValidationNode) {
+    if node.is_ok() {
+        panic!("expected invalid value, found no errors");
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_errors(node: &ValidationNode, expected: &[(&str, &str)]) {
+    let mut expected: Vec<_> = expected
+        .iter()
+        .map(|(path, code)| format!("{path}: {code}"))
+        .collect();
+    let mut actual: Vec<_> = node
+        .iter()
+        .map(|(path, error)| format!("{path}: {}", error.code()))
+        .collect();
+    expected.sort();
+    actual.sort();
+    pretty_assertions::assert_eq!(
+        expected.join("\n"),
+        actual.join("\n"),
+        "unexpected validation errors:\n{}",
+        node,
+    );
+}
@@ -0,0 +1,99 @@
+//! Logging of validation errors as structured `tracing` events.
+
+use core::fmt::{Display, Formatter};
+
+use ::tracing::Level;
+
+use crate::{ValidationError, ValidationNode};
+
+impl ValidationNode {
+    /// Emits one `tracing` event at `level` per error, with target
+    /// `not_so_fast`. Events have fields `path`, `code`, `params` and
+    /// `severity`, and the error message, or the code if the error has no
+    /// message, as the event message. Use [trace_errors](crate::trace_errors)
+    /// to emit events with another target.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let errors = ValidationNode::field(
+    ///     "age",
+    ///     ValidationNode::error(ValidationError::with_code("range").and_param("min", 18)),
+    /// );
+    /// // Emits event "range" with path=.age, code="range", params=min=18,
+    /// // severity="error".
+    /// errors.trace(tracing::Level::WARN);
+    /// ```
+    pub fn trace(&self, level: Level) {
+        crate::trace_errors!(target: "not_so_fast", level, self);
+    }
+}
+
+/// Emits one `tracing` event per error of a [ValidationNode], like
+/// [ValidationNode::trace], with a custom target. The target must be a
+/// constant, like targets of `tracing` macros.
+/// ```
+/// # use not_so_fast::*;
+/// let errors = ValidationNode::error(ValidationError::with_code("invalid"));
+/// trace_errors!(target: "app::signup", tracing::Level::INFO, &errors);
+/// trace_errors!(tracing::Level::INFO, &errors);
+/// ```
+#[macro_export]
+macro_rules! trace_errors {
+    (target: $target:expr, $level:expr, $node:expr) => {{
+        use $crate::__private::tracing::Level;
+        let level: Level = $level;
+        for (path, error) in $crate::ValidationNode::iter($node) {
+            let message = error.message().unwrap_or(error.code());
+            let params = $crate::__private::TracedParams(error);
+            let severity = error.severity().as_str();
+            if level == Level::ERROR {
+                $crate::__trace_error!($target, Level::ERROR, path, error, message, params, severity);
+            } else if level == Level::WARN {
+                $crate::__trace_error!($target, Level::WARN, path, error, message, params, severity);
+            } else if level == Level::INFO {
+                $crate::__trace_error!($target, Level::INFO, path, error, message, params, severity);
+            } else if level == Level::DEBUG {
+                $crate::__trace_error!($target, Level::DEBUG, path, error, message, params, severity);
+            } else {
+                $crate::__trace_error!($target, Level::TRACE, path, error, message, params, severity);
+            }
+        }
+    }};
+    ($level:expr, $node:expr) => {
+        $crate::trace_errors!(target: module_path!(), $level, $node)
+    };
+}
+
+/// Emits event of one error at a constant level, see [trace_errors].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_error {
+    ($target:expr, $level:expr, $path:ident, $error:ident, $message:ident, $params:ident, $severity:ident) => {
+        $crate::__private::tracing::event!(
+            target: $target,
+            $level,
+            path = %$path,
+            code = $error.code(),
+            params = %$params,
+            severity = $severity,
+            "{}",
+            $message,
+        )
+    };
+}
+
+/// Params of an error formatted like in [ValidationError] display, e.g.
+/// `max=10, value=12`.
+#[doc(hidden)]
+pub struct TracedParams<'a>(pub &'a ValidationError);
+
+impl Display for TracedParams<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (i, (name, value)) in self.0.params().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,421 @@
+//! Async counterparts of [Validate](crate::Validate) and
+//! [ValidateArgs](crate::ValidateArgs), and combinators of futures of
+//! validation nodes.
+
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::{ValidateArgs, ValidateDyn, ValidationNode};
+
+/// Trait describing types that can be validated asynchronously with
+/// arguments, e.g. with rules querying a database. Implemented by the derive
+/// macro for all types, awaiting `custom_async` validators. Sync
+/// implementations of [ValidateArgs] written by hand are bridged with
+/// [FromSync].
+/// ```
+/// # use not_so_fast::*;
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     let mut future = std::pin::pin!(future);
+/// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     loop {
+/// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// struct Username(String);
+///
+/// impl<'arg> ValidateAsyncArgs<'arg> for Username {
+///     type Args = &'arg [&'arg str];
+///
+///     async fn validate_args_async(&self, taken: Self::Args) -> ValidationNode {
+///         // Query the database here.
+///         ValidationNode::error_if(taken.contains(&self.0.as_str()), || {
+///             ValidationError::with_code("taken")
+///         })
+///     }
+/// }
+///
+/// let username = Username("admin".into());
+/// assert!(block_on(username.validate_args_async(&["admin"])).is_err());
+/// assert!(block_on(username.validate_args_async(&["root"])).is_ok());
+/// ```
+pub trait ValidateAsyncArgs<'arg> {
+    type Args;
+    fn validate_args_async(&self, args: Self::Args) -> impl Future<Output = ValidationNode>;
+}
+
+/// Trait describing types that can be validated asynchronously without
+/// arguments. Implemented for all types implementing [ValidateAsyncArgs]
+/// with `Args = ()`.
+/// ```
+/// # use not_so_fast::*;
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     let mut future = std::pin::pin!(future);
+/// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     loop {
+/// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// #[derive(Validate)]
+/// struct Order {
+///     #[validate(range(min = 1))]
+///     quantity: u32,
+/// }
+///
+/// assert!(block_on(Order { quantity: 0 }.validate_async()).is_err());
+/// ```
+pub trait ValidateAsync {
+    fn validate_async(&self) -> impl Future<Output = ValidationNode>;
+}
+
+impl<T> ValidateAsync for T
+where
+    T: for<'a> ValidateAsyncArgs<'a, Args = ()>,
+{
+    fn validate_async(&self) -> impl Future<Output = ValidationNode> {
+        self.validate_args_async(())
+    }
+}
+
+/// Bridge validating a value with its sync [ValidateArgs] implementation
+/// through [ValidateAsyncArgs], e.g. to pass types with hand-written sync
+/// validation where async validation is expected.
+/// ```
+/// # use not_so_fast::*;
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     let mut future = std::pin::pin!(future);
+/// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     loop {
+/// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// struct Even(u32);
+///
+/// impl<'arg> ValidateArgs<'arg> for Even {
+///     type Args = ();
+///
+///     fn validate_args(&self, _args: ()) -> ValidationNode {
+///         ValidationNode::error_if(self.0 % 2 == 1, || ValidationError::with_code("odd"))
+///     }
+/// }
+///
+/// assert!(block_on(FromSync(&Even(3)).validate_async()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FromSync<'a, T: ?Sized>(pub &'a T);
+
+impl<'arg, T> ValidateAsyncArgs<'arg> for FromSync<'_, T>
+where
+    T: ValidateArgs<'arg> + ?Sized,
+{
+    type Args = T::Args;
+
+    fn validate_args_async(&self, args: Self::Args) -> impl Future<Output = ValidationNode> {
+        core::future::ready(self.0.validate_args(args))
+    }
+}
+
+/// Implements [ValidateAsyncArgs] for pointers by validating the pointee,
+/// like [ValidateArgs] is implemented for them.
+macro_rules! impl_validate_async_args_pointer {
+    ($($pointer:ty),+) => {
+        $(
+            impl<'arg, T: ValidateAsyncArgs<'arg> + ?Sized> ValidateAsyncArgs<'arg> for $pointer {
+                type Args = T::Args;
+
+                fn validate_args_async(&self, args: Self::Args) -> impl Future<Output = ValidationNode> {
+                    (**self).validate_args_async(args)
+                }
+            }
+        )+
+    };
+}
+
+impl_validate_async_args_pointer!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+impl<'arg, T: ValidateAsyncArgs<'arg> + ToOwned + ?Sized> ValidateAsyncArgs<'arg> for Cow<'_, T> {
+    type Args = T::Args;
+
+    fn validate_args_async(&self, args: Self::Args) -> impl Future<Output = ValidationNode> {
+        (**self).validate_args_async(args)
+    }
+}
+
+/// Implements [ValidateAsyncArgs] without arguments for tuples, awaiting
+/// elements one by one and reporting their errors as items.
+macro_rules! impl_validate_async_args_tuple {
+    ($($element:ident $index:tt),+) => {
+        impl<'arg, $($element),+> ValidateAsyncArgs<'arg> for ($($element,)+)
+        where
+            $($element: ValidateAsyncArgs<'arg, Args = ()>,)+
+        {
+            type Args = ();
+
+            async fn validate_args_async(&self, _args: Self::Args) -> ValidationNode {
+                ValidationNode::ok()
+                    $(.and_item($index, self.$index.validate_args_async(()).await))+
+            }
+        }
+    };
+}
+
+impl_validate_async_args_tuple!(A 0);
+impl_validate_async_args_tuple!(A 0, B 1);
+impl_validate_async_args_tuple!(A 0, B 1, C 2);
+impl_validate_async_args_tuple!(A 0, B 1, C 2, D 3);
+impl_validate_async_args_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_validate_async_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_validate_async_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_validate_async_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_validate_async_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_validate_async_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_validate_async_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_validate_async_args_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+/// Trait objects are validated synchronously.
+macro_rules! impl_validate_async_args_dyn {
+    ($($bounds:tt)*) => {
+        impl<'arg> ValidateAsyncArgs<'arg> for dyn ValidateDyn $($bounds)* + '_ {
+            type Args = ();
+
+            fn validate_args_async(&self, (): ()) -> impl Future<Output = ValidationNode> {
+                core::future::ready(self.validate_dyn())
+            }
+        }
+    };
+}
+
+impl_validate_async_args_dyn!();
+impl_validate_async_args_dyn!(+ Send);
+impl_validate_async_args_dyn!(+ Send + Sync);
+
+/// Wrapper of a nested value validated by the derive macro in async
+/// expansion. Values implementing [ValidateAsyncArgs] are validated with it,
+/// others with [ValidateArgs], see [NestedAsyncArgs] and [NestedSyncArgs].
+#[doc(hidden)]
+pub struct NestedAsync<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait NestedAsyncArgs<'arg> {
+    type Args;
+    fn validate_nested_async(&self, args: Self::Args) -> impl Future<Output = ValidationNode>;
+}
+
+impl<'arg, T: ValidateAsyncArgs<'arg> + ?Sized> NestedAsyncArgs<'arg> for NestedAsync<'_, T> {
+    type Args = T::Args;
+
+    fn validate_nested_async(&self, args: Self::Args) -> impl Future<Output = ValidationNode> {
+        self.0.validate_args_async(args)
+    }
+}
+
+/// Fallback of [NestedAsyncArgs] for hand-written sync implementations,
+/// picked by method resolution through one more reference.
+#[doc(hidden)]
+pub trait NestedSyncArgs<'arg> {
+    type Args;
+    fn validate_nested_async(&self, args: Self::Args) -> impl Future<Output = ValidationNode>;
+}
+
+impl<'arg, T: ValidateArgs<'arg> + ?Sized> NestedSyncArgs<'arg> for &NestedAsync<'_, T> {
+    type Args = T::Args;
+
+    fn validate_nested_async(&self, args: Self::Args) -> impl Future<Output = ValidationNode> {
+        core::future::ready(self.0.validate_args(args))
+    }
+}
+
+/// Future of [join_all].
+#[must_use = "futures do nothing unless polled"]
+pub struct JoinAll<F: Future> {
+    futures: Vec<Option<Pin<Box<F>>>>,
+    outputs: Vec<Option<F::Output>>,
+}
+
+/// Polls `futures` concurrently, resolving to their outputs in the order of
+/// `futures`. Unlike awaiting futures one by one, slow lookups of one value
+/// don't delay starting lookups of the others.
+/// ```
+/// # use not_so_fast::*;
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     let mut future = std::pin::pin!(future);
+/// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     loop {
+/// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// async fn check(value: u32) -> ValidationNode {
+///     ValidationNode::error_if(value > 10, || ValidationError::with_code("too_big"))
+/// }
+///
+/// let nodes = block_on(join_all([check(5), check(50)]));
+/// assert!(nodes[0].is_ok());
+/// assert!(nodes[1].is_err());
+/// ```
+pub fn join_all<I>(futures: I) -> JoinAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    let futures: Vec<_> = futures
+        .into_iter()
+        .map(|future| Some(Box::pin(future)))
+        .collect();
+    let outputs = futures.iter().map(|_| None).collect();
+    JoinAll { futures, outputs }
+}
+
+// Futures are boxed and outputs are never pinned.
+impl<F: Future> Unpin for JoinAll<F> {}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = Pin::into_inner(self);
+        let mut pending = false;
+        for (slot, output) in this.futures.iter_mut().zip(this.outputs.iter_mut()) {
+            if let Some(future) = slot {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(value) => {
+                        *output = Some(value);
+                        *slot = None;
+                    }
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(this.outputs.drain(..).map(Option::unwrap).collect())
+        }
+    }
+}
+
+impl ValidationNode {
+    /// Awaits `futures` concurrently and merges their nodes, like
+    /// [all](ValidationNode::all) does.
+    /// ```
+    /// # use not_so_fast::*;
+    /// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    /// async fn check_unique(email: &str) -> ValidationNode {
+    ///     ValidationNode::error_if(email == "taken@example.com", || ValidationError::with_code("unique"))
+    /// }
+    ///
+    /// async fn check_not_banned(email: &str) -> ValidationNode {
+    ///     ValidationNode::error_if(email.ends_with("@spam.com"), || ValidationError::with_code("banned"))
+    /// }
+    ///
+    /// let email = "taken@example.com";
+    /// let errors = block_on(ValidationNode::all_async([
+    ///     Box::pin(check_unique(email)) as std::pin::Pin<Box<dyn std::future::Future<Output = _>>>,
+    ///     Box::pin(check_not_banned(email)),
+    /// ]));
+    /// assert_eq!(".: unique", errors.to_string());
+    /// ```
+    pub async fn all_async<F>(futures: impl IntoIterator<Item = F>) -> Self
+    where
+        F: Future<Output = ValidationNode>,
+    {
+        Self::all(join_all(futures).await)
+    }
+
+    /// Validates `items` concurrently with async `f`, attaching errors at
+    /// item indexes, like [items](ValidationNode::items) does.
+    /// ```
+    /// # use not_so_fast::*;
+    /// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    /// async fn check_exists(id: &u32) -> ValidationNode {
+    ///     ValidationNode::error_if(*id > 100, || ValidationError::with_code("not_found"))
+    /// }
+    ///
+    /// let ids = vec![1, 200, 3];
+    /// let errors = block_on(ValidationNode::items_async(ids.iter(), |_, id| check_exists(id)));
+    /// assert_eq!(".[1]: not_found", errors.to_string());
+    /// ```
+    pub async fn items_async<'a, T: 'a, F>(
+        items: impl Iterator<Item = &'a T>,
+        mut f: impl FnMut(usize, &'a T) -> F,
+    ) -> Self
+    where
+        F: Future<Output = ValidationNode>,
+    {
+        let nodes = join_all(items.enumerate().map(|(index, item)| f(index, item))).await;
+        nodes
+            .into_iter()
+            .enumerate()
+            .fold(Self::ok(), |acc, (index, node)| acc.and_item(index, node))
+    }
+
+    /// Validates values of `iterator` concurrently with async `f`, attaching
+    /// errors at keys, like [fields](ValidationNode::fields) does.
+    /// ```
+    /// # use not_so_fast::*;
+    /// # use std::collections::BTreeMap;
+    /// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    /// async fn check_sku(sku: &String) -> ValidationNode {
+    ///     ValidationNode::error_if(sku.is_empty(), || ValidationError::with_code("empty"))
+    /// }
+    ///
+    /// let skus = BTreeMap::from([("shirt", String::new()), ("hat", "H-1".to_string())]);
+    /// let errors = block_on(ValidationNode::fields_async(skus.iter(), |_, sku| check_sku(sku)));
+    /// assert_eq!(".shirt: empty", errors.to_string());
+    /// ```
+    pub async fn fields_async<'a, K, V: 'a, F>(
+        iterator: impl Iterator<Item = (&'a K, &'a V)>,
+        mut f: impl FnMut(&'a K, &'a V) -> F,
+    ) -> Self
+    where
+        K: ToString + 'a,
+        F: Future<Output = ValidationNode>,
+    {
+        let (keys, futures): (Vec<_>, Vec<_>) =
+            iterator.map(|(key, value)| (key, f(key, value))).unzip();
+        let nodes = join_all(futures).await;
+        keys.into_iter()
+            .zip(nodes)
+            .fold(Self::ok(), |acc, (key, node)| {
+                acc.and_field(key.to_string(), node)
+            })
+    }
+}
@@ -0,0 +1,175 @@
+//! Conversions between [ValidationNode] and errors of the `validator` crate,
+//! for code bases using both libraries.
+//!
+//! Struct-level errors, which `validator` keeps under the `__all__` key, are
+//! direct errors of [ValidationNode]. Severity, tags and sources of errors
+//! have no `validator` counterpart and are dropped.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::{ItemKey, Message, ParamValue, ValidationError, ValidationNode};
+
+/// Key of struct-level errors in [ValidationErrors].
+const ALL: &str = "__all__";
+
+impl From<validator::ValidationError> for ValidationError {
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut error = validator::ValidationError::new("range");
+    /// error.add_param("max".into(), &10);
+    ///
+    /// assert_eq!("range: max=10", ValidationError::from(error).to_string());
+    /// ```
+    fn from(error: validator::ValidationError) -> Self {
+        let mut result = ValidationError::with_code(error.code);
+        if let Some(message) = error.message {
+            result = result.and_message(message);
+        }
+        for (name, value) in error.params {
+            result = result.and_param(name, param_from_json(value));
+        }
+        result
+    }
+}
+
+impl From<ValidationError> for validator::ValidationError {
+    /// ```
+    /// # use not_so_fast::*;
+    /// let error = validator::ValidationError::from(ValidationError::with_code("range").and_param("max", 10));
+    /// assert_eq!("range", error.code);
+    /// assert_eq!(Some(&serde_json::json!(10)), error.params.get("max"));
+    /// ```
+    fn from(error: ValidationError) -> Self {
+        validator::ValidationError {
+            code: error.code,
+            message: error.message.map(Message::into_text),
+            params: error
+                .details
+                .map(|details| details.params)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, value)| (name, param_to_json(value)))
+                .collect(),
+        }
+    }
+}
+
+impl From<ValidationErrors> for ValidationNode {
+    /// Converts `validator` errors to a node with the same paths.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let mut errors = validator::ValidationErrors::new();
+    /// errors.add("name", validator::ValidationError::new("length"));
+    /// errors.add("__all__", validator::ValidationError::new("invariant"));
+    ///
+    /// assert_eq!(".: invariant\n.name: length", ValidationNode::from(errors).to_string());
+    /// ```
+    fn from(errors: ValidationErrors) -> Self {
+        errors
+            .into_errors()
+            .into_iter()
+            .fold(ValidationNode::ok(), |node, (name, kind)| {
+                let child = match kind {
+                    ValidationErrorsKind::Struct(errors) => ValidationNode::from(*errors),
+                    ValidationErrorsKind::List(items) => items
+                        .into_iter()
+                        .fold(ValidationNode::ok(), |node, (index, errors)| {
+                            node.and_item(index, ValidationNode::from(*errors))
+                        }),
+                    ValidationErrorsKind::Field(errors) => {
+                        ValidationNode::errors(errors.into_iter().map(ValidationError::from))
+                    }
+                };
+                if name == ALL {
+                    node.merge(child)
+                } else {
+                    node.and_field(name, child)
+                }
+            })
+    }
+}
+
+impl From<ValidationNode> for ValidationErrors {
+    /// Converts a node to `validator` errors. Nodes with errors only become
+    /// field errors, nodes with indexed items only become lists, and other
+    /// nodes become structs, with items stored under their indexes or keys.
+    /// ```
+    /// # use not_so_fast::*;
+    /// let node = ValidationNode::field(
+    ///     "tags",
+    ///     ValidationNode::item(1, ValidationNode::error(ValidationError::with_code("length"))),
+    /// );
+    ///
+    /// let errors = validator::ValidationErrors::from(node);
+    /// assert!(matches!(
+    ///     errors.errors().get("tags"),
+    ///     Some(validator::ValidationErrorsKind::List(_)),
+    /// ));
+    /// ```
+    fn from(node: ValidationNode) -> Self {
+        let mut errors = HashMap::new();
+        if !node.errors.is_empty() {
+            errors.insert(Cow::Borrowed(ALL), errors_kind(node.errors));
+        }
+        for (name, field) in node.fields {
+            errors.insert(name, node_kind(field));
+        }
+        for (key, item) in node.items {
+            let name = match key {
+                ItemKey::Index(index) => Cow::Owned(index.to_string()),
+                ItemKey::Key(key) => key,
+            };
+            errors.insert(name, node_kind(item));
+        }
+        ValidationErrors(errors)
+    }
+}
+
+fn node_kind(node: ValidationNode) -> ValidationErrorsKind {
+    if node.fields.is_empty() && node.items.is_empty() {
+        return errors_kind(node.errors);
+    }
+    let indexed = node
+        .items
+        .iter()
+        .all(|(key, _)| matches!(key, ItemKey::Index(_)));
+    if !indexed || !node.errors.is_empty() || !node.fields.is_empty() {
+        return ValidationErrorsKind::Struct(Box::new(node.into()));
+    }
+    let items = node.items.into_iter().filter_map(|(key, item)| match key {
+        ItemKey::Index(index) => Some((index, Box::new(item.into()))),
+        ItemKey::Key(_) => None,
+    });
+    ValidationErrorsKind::List(items.collect())
+}
+
+fn errors_kind(errors: impl IntoIterator<Item = ValidationError>) -> ValidationErrorsKind {
+    ValidationErrorsKind::Field(errors.into_iter().map(Into::into).collect())
+}
+
+fn param_from_json(value: serde_json::Value) -> ParamValue {
+    match value {
+        serde_json::Value::Bool(value) => ParamValue::Bool(value),
+        serde_json::Value::String(value) => ParamValue::String(value.into()),
+        serde_json::Value::Number(number) => {
+            if let Some(value) = number.as_u64() {
+                ParamValue::U64(value)
+            } else if let Some(value) = number.as_i64() {
+                ParamValue::I64(value)
+            } else {
+                ParamValue::Json(serde_json::Value::Number(number))
+            }
+        }
+        value => ParamValue::Json(value),
+    }
+}
+
+fn param_to_json(value: ParamValue) -> serde_json::Value {
+    match value {
+        ParamValue::Json(value) => value,
+        value => serde_json::to_value(&value).unwrap_or(serde_json::Value::Null),
+    }
+}
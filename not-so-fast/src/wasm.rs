@@ -0,0 +1,39 @@
+//! Validation of JavaScript values with `wasm-bindgen`.
+
+use ::wasm_bindgen::JsValue;
+use serde::de::DeserializeOwned;
+
+use crate::Validate;
+
+/// Deserializes a JavaScript value into `T` and validates it. Returns an array
+/// of errors serialized as [NodeAsFlatList](crate::NodeAsFlatList), which is
+/// empty for valid values. Values that can't be deserialized into `T` are
+/// rejected with a JavaScript error.
+///
+/// Derive macro generates functions exported to JavaScript that call
+/// `validate_js` with `#[validate(wasm = function_name)]` type attribute.
+/// ```
+/// # use not_so_fast::*;
+/// #[derive(serde::Deserialize, Validate)]
+/// #[validate(wasm = validate_user)]
+/// struct User {
+///     #[validate(char_length(min = 1, max = 30))]
+///     name: String,
+/// }
+///
+/// // In JavaScript: `validate_user({ name: "" })` returns
+/// // `[{ path: "name", code: "char_length", message: "Invalid character length", params: { min: 1, max: 30, value: 0 } }]`
+/// let _: fn(wasm_bindgen::JsValue) -> Result<wasm_bindgen::JsValue, wasm_bindgen::JsValue> = validate_user;
+/// ```
+pub fn validate_js<T>(value: JsValue) -> Result<JsValue, JsValue>
+where
+    T: DeserializeOwned + Validate,
+{
+    let value: T = serde_wasm_bindgen::from_value(value)?;
+    let errors = value.validate();
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    Ok(serde::Serialize::serialize(
+        &errors.as_flat_list(),
+        &serializer,
+    )?)
+}
@@ -0,0 +1,121 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use not_so_fast::*;
+
+/// Allocator counting allocations made by the current thread, so that tests
+/// running in parallel don't affect each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+fn tree() -> ValidationNode {
+    ValidationNode::ok()
+        .and_field(
+            "users".to_owned(),
+            ValidationNode::ok().and_keyed_item(
+                "abc".to_owned(),
+                ValidationNode::field(
+                    "name".to_owned(),
+                    ValidationNode::error(ValidationError::with_code("length")),
+                ),
+            ),
+        )
+        .and_field(
+            "limit".to_owned(),
+            ValidationNode::error(ValidationError::with_code("range")),
+        )
+}
+
+#[test]
+fn lookup_by_keyed_path_does_not_allocate() {
+    let node = tree();
+    let path: Path = ".users[\"abc\"].name".parse().unwrap();
+    let (found, count) = allocations(|| node.at(&path).is_some());
+    assert!(found);
+    assert_eq!(0, count);
+}
+
+#[test]
+fn get_keyed_item_does_not_allocate() {
+    let node = tree();
+    let users = node.get_field("users").unwrap();
+    let (found, count) = allocations(|| users.get_keyed_item("abc").is_some());
+    assert!(found);
+    assert_eq!(0, count);
+}
+
+#[test]
+fn push_at_existing_path_does_not_allocate() {
+    let mut node = tree();
+    let path: Path = ".users[\"abc\"]".parse().unwrap();
+    let error = ValidationError::with_code("duplicate");
+    let ((), count) = allocations(|| ErrorSink::push(&mut node, path, error));
+    assert_eq!(0, count);
+    assert_eq!(
+        ".limit: range\n.users[\"abc\"]: duplicate\n.users[\"abc\"].name: length",
+        node.to_string()
+    );
+}
+
+#[test]
+fn take_at_keyed_path_does_not_allocate() {
+    let mut node = tree();
+    let path: Path = ".users[\"abc\"]".parse().unwrap();
+    let (taken, count) = allocations(|| node.take_at(&path));
+    assert_eq!(".name: length", taken.unwrap().to_string());
+    assert_eq!(0, count);
+    assert_eq!(".limit: range", node.to_string());
+}
+
+#[test]
+fn insertion_order_does_not_allocate() {
+    let node = tree();
+    let (node, count) = allocations(|| node.with_insertion_order());
+    assert_eq!(0, count);
+    assert_eq!(
+        ".limit: range\n.users[\"abc\"].name: length",
+        node.to_string()
+    );
+}
+
+#[test]
+fn fields_allocate_only_keys_with_errors() {
+    let map: std::collections::BTreeMap<String, u32> = [
+        ("a".to_owned(), 1),
+        ("b".to_owned(), 2),
+        ("c".to_owned(), 3),
+    ]
+    .into_iter()
+    .collect();
+    let (node, count) = allocations(|| {
+        ValidationNode::fields(map.iter(), |_, value| {
+            ValidationNode::error_if(*value == 2, || ValidationError::with_code("two"))
+        })
+    });
+    assert_eq!(".b: two", node.to_string());
+    // The key and the fields vector; the error is stored inline.
+    assert_eq!(2, count);
+}
@@ -0,0 +1,74 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+
+#[test]
+fn arena_node_matches_heap_node() {
+    let bump = bumpalo::Bump::new();
+    let arena = Arena::new(&bump);
+    let errors = arena
+        .error(arena.with_code("invariant"))
+        .and_field(
+            "name",
+            arena.error(
+                arena
+                    .with_code("length")
+                    .and_message("Invalid length")
+                    .and_param("max", 10)
+                    .and_param("max", 20),
+            ),
+        )
+        .and_field("ok", arena.ok())
+        .and_field(
+            "tags",
+            arena
+                .ok()
+                .and_item(2, arena.error(arena.with_code("empty")))
+                .and_keyed_item(
+                    arena.format(format_args!("tag-{}", 3)),
+                    arena.error(arena.with_code("dup")),
+                ),
+        )
+        .and_field("name", arena.error(arena.with_code("unique")));
+
+    let expected = ValidationNode::error(ValidationError::with_code("invariant"))
+        .and_field(
+            "name",
+            ValidationNode::error(
+                ValidationError::with_code("length")
+                    .and_message("Invalid length")
+                    .and_param("max", 20),
+            )
+            .and_error(ValidationError::with_code("unique")),
+        )
+        .and_field(
+            "tags",
+            ValidationNode::item(
+                2,
+                ValidationNode::error(ValidationError::with_code("empty")),
+            )
+            .and_keyed_item(
+                "tag-3",
+                ValidationNode::error(ValidationError::with_code("dup")),
+            ),
+        );
+    assert_eq!(expected, errors.to_node());
+    assert_eq!(expected.to_string(), errors.to_string());
+}
+
+#[test]
+fn arena_is_reused_after_reset() {
+    let mut bump = bumpalo::Bump::new();
+    for round in 0..3 {
+        {
+            let arena = Arena::new(&bump);
+            let errors = arena
+                .ok()
+                .and_item(round, arena.error(arena.with_code("abc")));
+            assert_eq!(format!(".[{round}]: abc"), errors.to_string());
+        }
+        bump.reset();
+    }
+    assert!(Arena::new(&bump).ok().is_ok());
+}
@@ -0,0 +1,113 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::routing::{get, post};
+use axum::Router;
+use not_so_fast::*;
+use serde::Deserialize;
+use tower::ServiceExt;
+
+#[derive(Deserialize, Validate)]
+struct User {
+    #[validate(char_length(min = 1, max = 5))]
+    name: String,
+    #[validate(custom = warn_if_young)]
+    age: u8,
+}
+
+fn warn_if_young(age: &u8) -> ValidationNode {
+    ValidationNode::error_if(*age < 18, || {
+        ValidationError::with_code("young").and_severity(Severity::Warning)
+    })
+}
+
+fn app() -> Router {
+    Router::new()
+        .route(
+            "/users",
+            post(|ValidatedJson(user): ValidatedJson<User>| async move { user.name }),
+        )
+        .route(
+            "/search",
+            get(|ValidatedQuery(user): ValidatedQuery<User>| async move { user.name }),
+        )
+        .route(
+            "/users/{name}/{age}",
+            get(|ValidatedPath(user): ValidatedPath<User>| async move { user.name }),
+        )
+}
+
+async fn send(request: Request<Body>) -> (StatusCode, String) {
+    let response = app().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn post_json(body: &str) -> Request<Body> {
+    Request::post("/users")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_owned()))
+        .unwrap()
+}
+
+fn get_uri(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn validated_json() {
+    assert_eq!(
+        (StatusCode::OK, "tom".to_owned()),
+        send(post_json(r#"{"name": "tom", "age": 30}"#)).await,
+    );
+    assert_eq!(
+        (StatusCode::OK, "tom".to_owned()),
+        send(post_json(r#"{"name": "tom", "age": 10}"#)).await,
+    );
+    assert_eq!(
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            r#"{"name":{"errors":["char_length: Invalid character length: max=5, min=1, value=0"]}}"#.to_owned(),
+        ),
+        send(post_json(r#"{"name": "", "age": 30}"#)).await,
+    );
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        send(post_json(r#"{"name": "tom""#)).await.0,
+    );
+}
+
+#[tokio::test]
+async fn validated_query() {
+    assert_eq!(
+        (StatusCode::OK, "tom".to_owned()),
+        send(get_uri("/search?name=tom&age=30")).await,
+    );
+    assert_eq!(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        send(get_uri("/search?name=thomas&age=30")).await.0,
+    );
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        send(get_uri("/search?name=tom")).await.0,
+    );
+}
+
+#[tokio::test]
+async fn validated_path() {
+    assert_eq!(
+        (StatusCode::OK, "tom".to_owned()),
+        send(get_uri("/users/tom/30")).await,
+    );
+    assert_eq!(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        send(get_uri("/users/thomas/30")).await.0,
+    );
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        send(get_uri("/users/tom/old")).await.0,
+    );
+}
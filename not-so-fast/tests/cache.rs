@@ -0,0 +1,138 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+use std::cell::Cell;
+
+thread_local! {
+    static VALIDATED: Cell<usize> = const { Cell::new(0) };
+}
+
+fn validated() -> usize {
+    VALIDATED.with(Cell::get)
+}
+
+#[derive(Clone, Validate)]
+#[validate(custom = count)]
+struct Snapshot {
+    version: u64,
+    #[validate(items(range(max = 100)))]
+    prices: Vec<u32>,
+}
+
+fn count(_: &Snapshot) -> ValidationNode {
+    VALIDATED.with(|validated| validated.set(validated.get() + 1));
+    ValidationNode::ok()
+}
+
+impl ValidationKey for Snapshot {
+    fn validation_key(&self) -> u64 {
+        self.version
+    }
+}
+
+#[derive(Validate)]
+struct Update {
+    #[validate(char_length(max = 5))]
+    author: String,
+    #[validate(nested(cached))]
+    snapshot: Snapshot,
+    #[validate(items(nested(cached)))]
+    previous: Vec<Snapshot>,
+}
+
+fn snapshot(version: u64, prices: Vec<u32>) -> Snapshot {
+    Snapshot { version, prices }
+}
+
+#[test]
+fn nested_results_are_reused() {
+    let mut cache = ValidationCache::new();
+    let update = Update {
+        author: "Tom".into(),
+        snapshot: snapshot(2, vec![50, 500]),
+        previous: vec![snapshot(1, vec![]), snapshot(2, vec![50, 500])],
+    };
+    let before = validated();
+    let errors = update.validate_cached(&mut cache);
+    assert_eq!(2, validated() - before);
+    assert_eq!(2, cache.len());
+    assert_eq!(update.validate(), errors);
+    assert_eq!(
+        [
+            ".previous[1].prices[1]: range: Number not in range: max=100, value=500",
+            ".snapshot.prices[1]: range: Number not in range: max=100, value=500",
+        ]
+        .join("\n"),
+        errors.to_string()
+    );
+
+    let update = Update {
+        author: "Alexander".into(),
+        ..update
+    };
+    let before = validated();
+    assert_eq!(
+        [
+            ".author: char_length: Invalid character length: max=5, value=9",
+            ".previous[1].prices[1]: range: Number not in range: max=100, value=500",
+            ".snapshot.prices[1]: range: Number not in range: max=100, value=500",
+        ]
+        .join("\n"),
+        update.validate_cached(&mut cache).to_string()
+    );
+    assert_eq!(0, validated() - before);
+}
+
+#[test]
+fn without_cache_nested_values_are_validated() {
+    let update = Update {
+        author: "Tom".into(),
+        snapshot: snapshot(1, vec![]),
+        previous: vec![snapshot(1, vec![])],
+    };
+    let before = validated();
+    assert!(update.validate().is_ok());
+    assert!(update.validate().is_ok());
+    assert_eq!(4, validated() - before);
+}
+
+#[test]
+fn top_level_results_are_reused() {
+    let mut cache = ValidationCache::new();
+    assert!(cache.is_empty());
+    let before = validated();
+    assert!(cache.validate(&snapshot(1, vec![1000])).is_err());
+    assert!(cache.validate(&snapshot(1, vec![1000])).is_err());
+    assert_eq!(1, validated() - before);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert!(cache.validate(&snapshot(1, vec![10])).is_ok());
+    assert_eq!(2, validated() - before);
+}
+
+#[test]
+fn cache_is_restored_after_panic() {
+    #[derive(Validate)]
+    #[validate(custom = explode)]
+    struct Exploding {
+        #[validate(nested(cached))]
+        snapshot: Snapshot,
+    }
+
+    fn explode(_: &Exploding) -> ValidationNode {
+        panic!("explode");
+    }
+
+    let mut cache = ValidationCache::new();
+    cache.validate(&snapshot(1, vec![]));
+    let exploding = Exploding {
+        snapshot: snapshot(2, vec![]),
+    };
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        exploding.validate_cached(&mut cache)
+    }));
+    assert!(result.is_err());
+    assert_eq!(1, cache.len());
+}
@@ -0,0 +1,154 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+fn audited_errors() -> ValidationNode {
+    ValidationNode::ok()
+        .and_error(
+            ValidationError::with_code("conflict")
+                .and_message("Document changed")
+                .and_param("bool", true)
+                .and_param("i8", -8_i8)
+                .and_param("i128", i128::MIN)
+                .and_param("u16", 16_u16)
+                .and_param("u128", u128::MAX)
+                .and_param("usize", 7_usize)
+                .and_param("f32", 1.5_f32)
+                .and_param("f64", -0.25_f64)
+                .and_param("char", 'ß')
+                .and_param("string", "text")
+                .and_param("raw", ParamValue::Raw(Cow::Borrowed("^[a-z]+$")))
+                .and_param("bytes", ParamValue::Bytes(Cow::Borrowed(&[0, 1, 255])))
+                .and_param("list", vec![1_u8, 2, 3])
+                .and_param("map", BTreeMap::from([("a", 1_i32), ("b", 2)]))
+                .and_param("duration", Duration::new(90, 5))
+                .and_param("json", serde_json::json!({ "id": 1, "tags": ["x"] }))
+                .and_severity(Severity::Warning)
+                .and_tag("security")
+                .and_tag("audit"),
+        )
+        .and_field(
+            "lines",
+            ValidationNode::ok()
+                .and_item(
+                    3,
+                    ValidationNode::error(ValidationError::with_code("range")),
+                )
+                .and_keyed_item(
+                    "sku-1",
+                    ValidationNode::error(
+                        ValidationError::with_code("stock").and_severity(Severity::Info),
+                    ),
+                ),
+        )
+        .and_field(
+            "customer",
+            ValidationNode::ok()
+                .with_insertion_order()
+                .and_field(
+                    "name",
+                    ValidationNode::error(ValidationError::with_code("length")),
+                )
+                .and_field(
+                    "email",
+                    ValidationNode::error(ValidationError::with_code("email")),
+                )
+                .with_order(0),
+        )
+}
+
+#[test]
+fn json_round_trip_restores_equal_node() {
+    let errors = audited_errors();
+    let json = errors.to_canonical_json();
+    let restored = ValidationNode::from_canonical_json(&json).unwrap();
+    assert_eq!(errors, restored);
+    assert_eq!(errors.to_string(), restored.to_string());
+    assert_eq!(json, restored.to_canonical_json());
+}
+
+#[test]
+fn cbor_round_trip_restores_equal_node() {
+    let errors = audited_errors();
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&errors.as_canonical(), &mut bytes).unwrap();
+    let restored: CanonicalNode = ciborium::from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(errors, restored.0);
+}
+
+#[test]
+fn encoding_lists_all_members_in_fixed_order() {
+    let errors = ValidationNode::field(
+        "abc",
+        ValidationNode::item(
+            4,
+            ValidationNode::error(ValidationError::with_code("length").and_param("max", 20_u32)),
+        ),
+    );
+    assert_eq!(
+        concat!(
+            r#"{"version":1,"node":{"errors":[],"fields":{"abc":{"errors":[],"fields":{},"#,
+            r#""items":[[{"index":4},{"errors":[{"code":"length","message":null,"#,
+            r#""params":{"max":{"u32":20}},"severity":"error","tags":[]}],"fields":{},"#,
+            r#""items":[],"order":null,"insertion_order":false}]],"order":null,"#,
+            r#""insertion_order":false}},"items":[],"order":null,"insertion_order":false}}"#,
+        ),
+        errors.to_canonical_json(),
+    );
+}
+
+#[test]
+fn equal_nodes_built_in_different_order_encode_equally() {
+    let first = ValidationNode::ok()
+        .and_field("b", ValidationNode::error(ValidationError::with_code("2")))
+        .and_field("a", ValidationNode::error(ValidationError::with_code("1")))
+        .and_keyed_item("y", ValidationNode::error(ValidationError::with_code("4")))
+        .and_item(0, ValidationNode::error(ValidationError::with_code("3")));
+    let second = ValidationNode::ok()
+        .and_item(0, ValidationNode::error(ValidationError::with_code("3")))
+        .and_keyed_item("y", ValidationNode::error(ValidationError::with_code("4")))
+        .and_field("a", ValidationNode::error(ValidationError::with_code("1")))
+        .and_field("b", ValidationNode::error(ValidationError::with_code("2")));
+    assert_eq!(first.to_canonical_json(), second.to_canonical_json());
+}
+
+#[test]
+fn lazy_messages_are_stored_as_text() {
+    let errors = ValidationNode::error(
+        ValidationError::with_code("length").and_message_with(|| format!("At most {} items", 3)),
+    );
+    let restored = ValidationNode::from_canonical_json(&errors.to_canonical_json()).unwrap();
+    assert_eq!(
+        Some("At most 3 items"),
+        restored.iter().next().unwrap().1.message()
+    );
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    let json = ValidationNode::ok()
+        .to_canonical_json()
+        .replace(r#""version":1"#, r#""version":2"#);
+    let error = ValidationNode::from_canonical_json(&json).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("unsupported canonical encoding version 2, expected 1"),
+        "{error}",
+    );
+}
+
+#[test]
+fn malformed_encodings_are_rejected() {
+    assert!(ValidationNode::from_canonical_json(r#"{"version":1}"#).is_err());
+    assert!(ValidationNode::from_canonical_json(r#"{"node":{}}"#).is_err());
+    assert!(ValidationNode::from_canonical_json(
+        r#"{"version":1,"node":{"errors":[],"fields":{},"items":[],"order":null,"insertion_order":false,"extra":1}}"#
+    )
+    .is_err());
+    assert!(ValidationNode::from_canonical_json(r#"{"errors":[]}"#).is_err());
+}
@@ -0,0 +1,151 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{Args, Parser};
+use not_so_fast::*;
+
+#[derive(Parser, Validate)]
+#[command(name = "greet")]
+#[validate(custom = validate_cli)]
+struct Cli {
+    #[arg(long)]
+    #[validate(char_length(min = 1, max = 5))]
+    name: String,
+    #[arg(long, default_value_t = 30)]
+    #[validate(custom = warn_if_young)]
+    age: u8,
+    #[validate(length(max = 3), items(char_length(max = 5)))]
+    tags: Vec<String>,
+    #[command(flatten)]
+    #[validate(nested)]
+    output: Output,
+}
+
+#[derive(Args, Validate)]
+struct Output {
+    #[arg(short, long, default_value_t = 80)]
+    #[validate(range(min = 20))]
+    width: u16,
+}
+
+fn validate_cli(cli: &Cli) -> ValidationNode {
+    ValidationNode::error_if(cli.tags.contains(&cli.name), || {
+        ValidationError::with_code("name_tag").and_message("Name must not be a tag")
+    })
+}
+
+fn warn_if_young(age: &u8) -> ValidationNode {
+    ValidationNode::error_if(*age < 18, || {
+        ValidationError::with_code("young").and_severity(Severity::Warning)
+    })
+}
+
+fn first_line(error: &clap::Error) -> String {
+    error.to_string().lines().next().unwrap().to_owned()
+}
+
+#[test]
+fn valid_args() {
+    let cli =
+        Cli::try_parse_validated_from(["greet", "--name", "tom", "--age", "10", "a", "b"]).unwrap();
+    assert_eq!("tom", cli.name);
+    assert_eq!(vec!["a", "b"], cli.tags);
+    assert_eq!(80, cli.output.width);
+}
+
+#[test]
+fn invalid_args() {
+    let error = Cli::try_parse_validated_from(["greet", "--name", "Thomas"])
+        .err()
+        .unwrap();
+    assert_eq!(ErrorKind::ValueValidation, error.kind());
+    assert_eq!(2, error.exit_code());
+    assert_eq!(
+        "error: invalid value 'Thomas' for '--name <NAME>': char_length: Invalid character length: max=5, min=1, value=6",
+        first_line(&error),
+    );
+    assert_eq!(
+        Some(&ContextValue::String("--name <NAME>".to_owned())),
+        error.get(ContextKind::InvalidArg),
+    );
+    assert_eq!(
+        Some(&ContextValue::String("Thomas".to_owned())),
+        error.get(ContextKind::InvalidValue),
+    );
+    assert!(error.to_string().contains("Usage: greet"));
+
+    let error = Cli::try_parse_validated_from(["greet", "--name", "tom", "a", "abcdef"])
+        .err()
+        .unwrap();
+    assert_eq!(
+        "error: invalid value 'abcdef' for '[TAGS]...': char_length: Invalid character length: max=5, value=6",
+        first_line(&error),
+    );
+
+    let error = Cli::try_parse_validated_from(["greet", "--name", "tom", "a", "b", "c", "d"])
+        .err()
+        .unwrap();
+    assert_eq!(
+        "error: invalid value 'a b c d' for '[TAGS]...': length: Invalid length: max=3, value=4",
+        first_line(&error),
+    );
+}
+
+#[test]
+fn attributes_errors_of_flattened_and_default_args() {
+    let error = Cli::try_parse_validated_from(["greet", "--name", "tom", "-w", "10"])
+        .err()
+        .unwrap();
+    assert_eq!(
+        "error: invalid value '10' for '--width <WIDTH>': range: Number not in range: min=20, value=10",
+        first_line(&error),
+    );
+}
+
+#[test]
+fn reports_errors_without_args() {
+    let error = Cli::try_parse_validated_from(["greet", "--name", "tom", "tom"])
+        .err()
+        .unwrap();
+    assert_eq!(
+        "error: name_tag: Name must not be a tag",
+        first_line(&error)
+    );
+    assert_eq!(None, error.get(ContextKind::InvalidArg));
+}
+
+#[test]
+fn converts_all_errors() {
+    let mut command = <Cli as clap::CommandFactory>::command();
+    let matches = command
+        .clone()
+        .get_matches_from(["greet", "--name", "", "--age", "5", "-w", "1"]);
+    let errors = ValidationNode::ok()
+        .and_field(
+            "name",
+            ValidationNode::error(ValidationError::with_code("a")),
+        )
+        .and_field(
+            "age",
+            ValidationNode::error(ValidationError::with_code("b").and_severity(Severity::Warning)),
+        )
+        .and_field(
+            "output",
+            ValidationNode::field(
+                "width",
+                ValidationNode::error(ValidationError::with_code("c")),
+            ),
+        );
+    let errors: Vec<_> = clap_errors(&mut command, &matches, &errors)
+        .iter()
+        .map(first_line)
+        .collect();
+    assert_eq!(
+        vec![
+            "error: invalid value '' for '--name <NAME>': a",
+            "error: invalid value '1' for '--width <WIDTH>': c",
+        ],
+        errors,
+    );
+}
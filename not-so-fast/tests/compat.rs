@@ -0,0 +1,28 @@
+#![allow(deprecated)]
+
+use not_so_fast::*;
+
+#[test]
+fn aliases_name_renamed_types() {
+    fn validate_name(name: &str) -> ValidationErrors {
+        ValidationErrors::ok().and_field(
+            "name",
+            ValidationErrors::error_if(name.is_empty(), || Error::with_code("required")),
+        )
+    }
+
+    let errors: ValidationNode = validate_name("");
+
+    assert_eq!(".name: required", errors.to_string());
+    assert!(validate_name("Alice").is_ok());
+}
+
+#[test]
+fn constructor_shims_match_current_constructors() {
+    assert_eq!(ValidationNode::ok(), ValidationErrors::new());
+    assert_eq!(ValidationError::with_code("range"), Error::new("range"));
+    assert_eq!(
+        ValidationNode::error(ValidationError::with_code("abc")),
+        ValidationErrors::new().and_error(Error::new("abc")),
+    );
+}
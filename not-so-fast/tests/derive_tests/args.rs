@@ -62,6 +62,7 @@ fn struct_routing_args() {
         b: Nested,
     }
 
+    #[allow(clippy::ptr_arg)]
     fn validate_a(value: &String, a: u64, b: &str) -> ValidationNode {
         assert!(a == 10);
         assert!(b == "x");
@@ -0,0 +1,64 @@
+use not_so_fast::*;
+
+#[test]
+fn struct_codes() {
+    #[derive(Validate)]
+    struct Data {
+        #[validate(some(length(max = 3), items(char_length(max = 5))))]
+        names: Option<Vec<String>>,
+        #[validate(fields(range(min = 1)))]
+        scores: std::collections::BTreeMap<String, u32>,
+        #[validate(nested)]
+        nested: Nested,
+    }
+
+    #[derive(Validate)]
+    struct Nested {
+        #[validate(range(max = 10))]
+        value: u32,
+    }
+
+    assert_eq!(
+        vec![codes::CHAR_LENGTH, codes::LENGTH, codes::RANGE],
+        CodeRegistry::of::<Data>().iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn enum_codes() {
+    #[derive(Validate)]
+    enum Data {
+        A(#[validate(length(max = 3))] Vec<u8>),
+        B {
+            #[validate(custom = custom)]
+            value: u32,
+        },
+    }
+
+    fn custom(_value: &u32) -> ValidationNode {
+        ValidationNode::error(ValidationError::with_code("custom"))
+    }
+
+    assert_eq!(
+        vec![codes::LENGTH],
+        CodeRegistry::of::<Data>().iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn generic_codes() {
+    #[derive(Validate)]
+    struct Data<'a, T> {
+        #[validate(char_length(max = 3))]
+        name: &'a str,
+        value: T,
+    }
+
+    assert_eq!(
+        vec![codes::CHAR_LENGTH],
+        CodeRegistry::of::<Data<'static, ()>>()
+            .iter()
+            .collect::<Vec<_>>()
+    );
+    assert!(CodeRegistry::of::<Data<'static, ()>>().contains("char_length"));
+}
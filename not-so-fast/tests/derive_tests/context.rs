@@ -0,0 +1,127 @@
+use not_so_fast::*;
+
+struct RequestCtx {
+    max_len: usize,
+    banned: Vec<&'static str>,
+}
+
+fn validate_name(value: &str, ctx: &RequestCtx) -> ValidationNode {
+    ValidationNode::error_if(value.len() > ctx.max_len, || {
+        ValidationError::with_code("length").and_param("max", ctx.max_len)
+    })
+}
+
+fn validate_not_banned(value: &str, ctx: &RequestCtx, code: &'static str) -> ValidationNode {
+    ValidationNode::error_if(ctx.banned.contains(&value), || {
+        ValidationError::with_code(code)
+    })
+}
+
+#[test]
+fn struct_context_custom() {
+    #[derive(Validate)]
+    #[validate(context = RequestCtx)]
+    struct Struct {
+        #[validate(custom(function = validate_name, context))]
+        a: String,
+        #[validate(custom(function = validate_not_banned, context, args("banned")))]
+        b: String,
+    }
+
+    let ctx = RequestCtx {
+        max_len: 3,
+        banned: vec!["root"],
+    };
+    let value = Struct {
+        a: "abcd".into(),
+        b: "root".into(),
+    };
+    assert_eq!(
+        ".a: length: max=3\n.b: banned",
+        value.validate_with(&ctx).to_string()
+    );
+    assert_eq!(
+        ".a: length: max=3\n.b: banned",
+        value.validate_args((&ctx,)).to_string()
+    );
+}
+
+#[test]
+fn struct_context_type_custom() {
+    #[derive(Validate)]
+    #[validate(context = RequestCtx, custom(function = validate_struct, context))]
+    struct Struct {
+        a: String,
+    }
+    fn validate_struct(value: &Struct, ctx: &RequestCtx) -> ValidationNode {
+        ValidationNode::field("a", validate_name(&value.a, ctx))
+    }
+
+    let ctx = RequestCtx {
+        max_len: 3,
+        banned: vec![],
+    };
+    assert_eq!(
+        "",
+        Struct { a: "abc".into() }.validate_with(&ctx).to_string()
+    );
+    assert_eq!(
+        ".a: length: max=3",
+        Struct { a: "abcd".into() }.validate_with(&ctx).to_string()
+    );
+}
+
+#[test]
+fn struct_context_nested() {
+    #[derive(Validate)]
+    #[validate(context = RequestCtx)]
+    struct Child {
+        #[validate(custom(function = validate_name, context))]
+        name: String,
+    }
+
+    #[derive(Validate)]
+    #[validate(context = RequestCtx)]
+    struct Parent {
+        #[validate(nested(context))]
+        child: Child,
+        #[validate(items(nested(context)))]
+        children: Vec<Child>,
+        #[validate(some(custom(function = validate_name, context)))]
+        nickname: Option<String>,
+    }
+
+    let ctx = RequestCtx {
+        max_len: 2,
+        banned: vec![],
+    };
+    let value = Parent {
+        child: Child { name: "abc".into() },
+        children: vec![Child { name: "a".into() }, Child { name: "abc".into() }],
+        nickname: Some("abc".into()),
+    };
+    assert_eq!(
+        ".child.name: length: max=2\n.children[1].name: length: max=2\n.nickname: length: max=2",
+        value.validate_with(&ctx).to_string()
+    );
+}
+
+#[test]
+fn enum_context() {
+    #[derive(Validate)]
+    #[validate(context = RequestCtx)]
+    enum Enum {
+        A(#[validate(custom(function = validate_name, context))] String),
+        B,
+    }
+
+    let ctx = RequestCtx {
+        max_len: 2,
+        banned: vec![],
+    };
+    assert_eq!(
+        ".[0]: length: max=2",
+        Enum::A("abc".into()).validate_with(&ctx).to_string()
+    );
+    assert_eq!("", Enum::B.validate_with(&ctx).to_string());
+}
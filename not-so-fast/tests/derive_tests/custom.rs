@@ -1,3 +1,6 @@
+// `is_multiple_of` is not available in older Rust versions.
+#![allow(clippy::manual_is_multiple_of)]
+
 use not_so_fast::*;
 
 #[test]
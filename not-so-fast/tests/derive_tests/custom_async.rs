@@ -0,0 +1,340 @@
+use not_so_fast::*;
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::task::{Context, Poll, Waker};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn struct_custom_async_field() {
+    #[derive(Validate)]
+    struct Struct {
+        #[validate(custom_async = validate_email)]
+        email: String,
+    }
+    async fn validate_email(value: &str) -> ValidationNode {
+        ValidationNode::error_if(value == "taken@example.com", || {
+            ValidationError::with_code("unique")
+        })
+    }
+
+    let free = Struct {
+        email: "free@example.com".into(),
+    };
+    let taken = Struct {
+        email: "taken@example.com".into(),
+    };
+
+    assert_eq!("", block_on(free.validate_async()).to_string());
+    assert_eq!(
+        ".email: unique",
+        block_on(taken.validate_async()).to_string()
+    );
+    // Sync validation skips async validators.
+    assert_eq!("", taken.validate().to_string());
+}
+
+#[test]
+fn struct_custom_async_with_sync_validators() {
+    #[derive(Validate)]
+    struct Struct {
+        #[validate(char_length(max = 5), custom_async = validate_async)]
+        a: String,
+        #[validate(range(max = 10))]
+        b: u32,
+    }
+    async fn validate_async(value: &str) -> ValidationNode {
+        ValidationNode::error_if(value.starts_with('x'), || ValidationError::with_code("x"))
+    }
+
+    let value = Struct {
+        a: "xxxxxxx".into(),
+        b: 20,
+    };
+    assert_eq!(
+        ".a: char_length: Invalid character length: max=5, value=7\n.a: x\n.b: range: Number not in range: max=10, value=20",
+        block_on(value.validate_async()).to_string()
+    );
+    assert_eq!(
+        ".a: char_length: Invalid character length: max=5, value=7\n.b: range: Number not in range: max=10, value=20",
+        value.validate().to_string()
+    );
+}
+
+#[test]
+fn struct_custom_async_with_args() {
+    #[derive(Validate)]
+    #[validate(args(forbidden: &'arg str))]
+    struct Struct {
+        #[validate(custom_async(function = validate_async, args(forbidden)))]
+        a: String,
+    }
+    async fn validate_async(value: &str, forbidden: &str) -> ValidationNode {
+        ValidationNode::error_if(value == forbidden, || ValidationError::with_code("x"))
+    }
+
+    let value = Struct { a: "abc".into() };
+    assert_eq!(
+        "",
+        block_on(value.validate_args_async(("def",))).to_string()
+    );
+    assert_eq!(
+        ".a: x",
+        block_on(value.validate_args_async(("abc",))).to_string()
+    );
+}
+
+#[test]
+fn struct_custom_async_in_collections() {
+    #[derive(Validate)]
+    struct Struct {
+        #[validate(items(custom_async = validate_async))]
+        list: Vec<String>,
+        #[validate(fields(custom_async = validate_async))]
+        map: std::collections::BTreeMap<u32, String>,
+        #[validate(some(custom_async = validate_async))]
+        option: Option<String>,
+    }
+    async fn validate_async(value: &str) -> ValidationNode {
+        ValidationNode::error_if(value.starts_with('x'), || ValidationError::with_code("x"))
+    }
+
+    let value = Struct {
+        list: vec!["a".into(), "x".into()],
+        map: [(1, "x".into()), (2, "b".into())].into_iter().collect(),
+        option: Some("x".into()),
+    };
+    assert_eq!(
+        ".list[1]: x\n.map.1: x\n.option: x",
+        block_on(value.validate_async()).to_string()
+    );
+}
+
+#[test]
+fn struct_custom_async_in_keyed_items() {
+    #[derive(Validate)]
+    struct Struct {
+        #[validate(items(key = |item| item.to_uppercase(), custom_async = validate_async))]
+        list: Vec<String>,
+    }
+    async fn validate_async(value: &str) -> ValidationNode {
+        ValidationNode::error_if(value.starts_with('x'), || ValidationError::with_code("x"))
+    }
+
+    let value = Struct {
+        list: vec!["a".into(), "xy".into()],
+    };
+    assert_eq!(
+        ".list[\"XY\"]: x",
+        block_on(value.validate_async()).to_string()
+    );
+}
+
+#[test]
+fn struct_custom_async_in_nested() {
+    #[derive(Validate)]
+    struct Child {
+        #[validate(custom_async = never_ok)]
+        name: String,
+    }
+    #[derive(Validate)]
+    struct Parent {
+        #[validate(nested)]
+        child: Child,
+        #[validate(items(nested))]
+        children: Vec<std::sync::Arc<Child>>,
+    }
+    async fn never_ok(_value: &str) -> ValidationNode {
+        ValidationNode::error(ValidationError::with_code("x"))
+    }
+
+    let value = Parent {
+        child: Child { name: "a".into() },
+        children: vec![std::sync::Arc::new(Child { name: "b".into() })],
+    };
+    assert_eq!(
+        ".child.name: x\n.children[0].name: x",
+        block_on(value.validate_args_async(())).to_string()
+    );
+    // Sync validation skips async validators of nested values.
+    assert_eq!("", value.validate().to_string());
+}
+
+#[test]
+fn recursive_custom_async_in_nested() {
+    #[derive(Validate)]
+    struct Node {
+        #[validate(custom_async = validate_async)]
+        value: u32,
+        #[validate(items(nested))]
+        children: Vec<Node>,
+    }
+    async fn validate_async(value: &u32) -> ValidationNode {
+        ValidationNode::error_if(*value > 9, || ValidationError::with_code("x"))
+    }
+
+    let node = Node {
+        value: 1,
+        children: vec![Node {
+            value: 10,
+            children: vec![],
+        }],
+    };
+    assert_eq!(
+        ".children[0].value: x",
+        block_on(node.validate_async()).to_string()
+    );
+}
+
+#[test]
+fn struct_type_custom_async() {
+    #[derive(Validate)]
+    #[validate(custom = validate_sync, custom_async = validate_async)]
+    struct Struct {
+        a: u8,
+    }
+    fn validate_sync(value: &Struct) -> ValidationNode {
+        ValidationNode::error_if(value.a.is_multiple_of(2), || {
+            ValidationError::with_code("sync")
+        })
+    }
+    async fn validate_async(value: &Struct) -> ValidationNode {
+        ValidationNode::error_if(value.a.is_multiple_of(3), || {
+            ValidationError::with_code("async")
+        })
+    }
+
+    assert_eq!(
+        ".: sync\n.: async",
+        block_on(Struct { a: 6 }.validate_async()).to_string()
+    );
+    assert_eq!(".: sync", Struct { a: 6 }.validate().to_string());
+}
+
+#[test]
+fn enum_custom_async() {
+    #[derive(Validate)]
+    enum Enum {
+        A(#[validate(custom_async = validate_async)] String),
+        B {
+            #[validate(custom_async = validate_async)]
+            b: String,
+        },
+    }
+    async fn validate_async(value: &str) -> ValidationNode {
+        ValidationNode::error_if(value.starts_with('x'), || ValidationError::with_code("x"))
+    }
+
+    assert_eq!(
+        ".[0]: x",
+        block_on(Enum::A("x".into()).validate_async()).to_string()
+    );
+    assert_eq!(
+        ".b: x",
+        block_on(Enum::B { b: "x".into() }.validate_async()).to_string()
+    );
+    assert_eq!("", Enum::B { b: "x".into() }.validate().to_string());
+}
+
+/// Database handle, like a connection pool, borrowed by async validators.
+struct Pool {
+    emails: Vec<&'static str>,
+}
+
+impl Pool {
+    async fn email_taken(&self, email: &str) -> bool {
+        self.emails.contains(&email)
+    }
+}
+
+/// Repository used as a trait object.
+trait UserRepo {
+    fn username_taken<'a>(&'a self, username: &'a str) -> Pin<Box<dyn Future<Output = bool> + 'a>>;
+}
+
+impl UserRepo for Pool {
+    fn username_taken<'a>(&'a self, username: &'a str) -> Pin<Box<dyn Future<Output = bool> + 'a>> {
+        Box::pin(async move { username == "admin" && !self.emails.is_empty() })
+    }
+}
+
+#[derive(Validate)]
+#[validate(args(pool: &'arg Pool, repo: &'arg dyn UserRepo))]
+struct SignUp {
+    #[validate(custom_async(function = check_email, args(pool)))]
+    email: String,
+    #[validate(char_length(max = 10), custom_async(function = check_username, args(repo)))]
+    username: String,
+    #[validate(items(custom_async(function = check_email, args(pool))))]
+    backup_emails: Vec<String>,
+}
+
+async fn check_email(email: &str, pool: &Pool) -> ValidationNode {
+    ValidationNode::error_if(pool.email_taken(email).await, || {
+        ValidationError::with_code("email_taken")
+    })
+}
+
+async fn check_username(username: &str, repo: &dyn UserRepo) -> ValidationNode {
+    ValidationNode::error_if(repo.username_taken(username).await, || {
+        ValidationError::with_code("username_taken")
+    })
+}
+
+#[test]
+fn struct_custom_async_with_borrowed_handles() {
+    let pool = Pool {
+        emails: vec!["taken@example.com"],
+    };
+    let sign_up = SignUp {
+        email: "taken@example.com".into(),
+        username: "admin".into(),
+        backup_emails: vec!["free@example.com".into(), "taken@example.com".into()],
+    };
+    assert_eq!(
+        ".backup_emails[1]: email_taken\n.email: email_taken\n.username: username_taken",
+        block_on(sign_up.validate_args_async((&pool, &pool))).to_string()
+    );
+    assert!(sign_up.validate_args((&pool, &pool)).is_ok());
+
+    // Handles borrowed for a shorter time than the validated value.
+    let sign_up = {
+        let pool = Pool { emails: Vec::new() };
+        let node = block_on(ValidateAsyncArgs::validate_args_async(
+            &sign_up,
+            (&pool, &pool as &dyn UserRepo),
+        ));
+        assert!(node.is_ok());
+        sign_up
+    };
+    assert_eq!("admin", sign_up.username);
+}
+
+#[derive(Validate)]
+#[validate(context = Pool)]
+struct ChangeEmail {
+    #[validate(custom_async(function = check_email, context))]
+    email: String,
+}
+
+#[test]
+fn struct_custom_async_with_borrowed_context() {
+    let pool = Pool {
+        emails: vec!["taken@example.com"],
+    };
+    let change = ChangeEmail {
+        email: "taken@example.com".into(),
+    };
+    assert_eq!(
+        ".email: email_taken",
+        block_on(change.validate_with_async(&pool)).to_string()
+    );
+}
@@ -0,0 +1,121 @@
+use bigdecimal::BigDecimal;
+use not_so_fast::*;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+const MAX_QUANTITY: u32 = 100;
+const MAX_PRICE: Decimal = dec!(999.99);
+
+fn big(value: &str) -> BigDecimal {
+    value.parse().unwrap()
+}
+
+#[test]
+fn decimal_range() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(range(min = 0.01))]
+        a: Decimal,
+        #[validate(range(max = MAX_PRICE))]
+        b: Decimal,
+        #[validate(range(min = 1, max = MAX_QUANTITY))]
+        c: Decimal,
+        #[validate(some(range(min = -10, max = 10.5)))]
+        d: Option<Decimal>,
+        #[validate(items(range(max = 1)))]
+        e: Vec<Decimal>,
+    }
+
+    let valid = S {
+        a: dec!(0.01),
+        b: dec!(999.99),
+        c: dec!(100),
+        d: Some(dec!(-10)),
+        e: vec![dec!(0.5), dec!(1.000)],
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: dec!(0.009),
+        b: dec!(1000.00),
+        c: dec!(100.01),
+        d: Some(dec!(10.51)),
+        e: vec![dec!(1), dec!(1.0001)],
+    };
+    assert_eq!(
+        ".a: range: Number not in range: min=0.01, value=0.009
+.b: range: Number not in range: max=999.99, value=1000.00
+.c: range: Number not in range: max=100, min=1, value=100.01
+.d: range: Number not in range: max=10.5, min=-10, value=10.51
+.e[1]: range: Number not in range: max=1, value=1.0001",
+        invalid.validate().to_string(),
+    );
+}
+
+#[test]
+fn big_decimal_range() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(range(min = 0.01, max = MAX_QUANTITY))]
+        a: BigDecimal,
+        #[validate(items(range(min = 0)))]
+        b: Vec<BigDecimal>,
+    }
+
+    let valid = S {
+        a: big("0.01"),
+        b: vec![big("0"), big("123456789012345678901234567890.5")],
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: big("100.000000000000000000000001"),
+        b: vec![big("-0.000000000000000000000000001")],
+    };
+    assert_eq!(
+        ".a: range: Number not in range: max=100, min=0.01, value=100.000000000000000000000001
+.b[0]: range: Number not in range: min=0, value=-1E-27",
+        invalid.validate().to_string(),
+    );
+}
+
+#[test]
+fn decimal_ord_range() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(ord_range(min = dec!(0.50), max = MAX_PRICE))]
+        a: Decimal,
+        #[validate(ord_range(max = big("1e3")))]
+        b: BigDecimal,
+    }
+
+    assert!(S {
+        a: dec!(0.5),
+        b: big("1000")
+    }
+    .validate()
+    .is_ok());
+    assert_eq!(
+        ".a: range: Value not in range: max=\"999.99\", min=\"0.50\", value=\"0.49\"
+.b: range: Value not in range: max=\"1000\", value=\"1000.1\"",
+        S {
+            a: dec!(0.49),
+            b: big("1000.1")
+        }
+        .validate()
+        .to_string(),
+    );
+}
+
+#[test]
+fn decimal_params_serialize_as_strings() {
+    let errors = ValidationNode::error(
+        ValidationError::with_code("a")
+            .and_param("max", big("10.4999999999999999999999999"))
+            .and_param("value", dec!(10.50)),
+    );
+    assert_eq!(
+        r#"[{"path":"","code":"a","params":{"max":"10.4999999999999999999999999","value":"10.50"}}]"#,
+        serde_json::to_string(&errors.as_flat_list()).unwrap(),
+    );
+}
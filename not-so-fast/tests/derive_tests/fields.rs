@@ -114,6 +114,8 @@ fn different_types() {
 
     struct CustomKey;
 
+    // Keys need only `ToString`, not `Display`.
+    #[allow(clippy::to_string_trait_impl)]
     impl ToString for CustomKey {
         fn to_string(&self) -> String {
             "hello".into()
@@ -152,3 +154,49 @@ fn different_types() {
     .validate()
     .is_ok());
 }
+
+#[test]
+fn field_validate_fields_key_display() {
+    #[derive(Validate)]
+    struct Parent {
+        #[validate(fields(key_display = redact, range(max = 10)))]
+        field: HashMap<String, i32>,
+    }
+    #[allow(clippy::ptr_arg)]
+    fn redact(key: &String) -> String {
+        format!("{}...", &key[..3])
+    }
+
+    assert_eq!(
+        ".field.\"tok...\": range: Number not in range: max=10, value=11",
+        Parent {
+            field: map! { "token-secret".to_string() => 11, "other".to_string() => 1 }
+        }
+        .validate()
+        .to_string()
+    );
+}
+
+#[test]
+fn field_validate_fields_key_display_nested() {
+    #[derive(Validate)]
+    struct Child(#[validate(range(max = 10))] i32);
+
+    #[derive(Validate)]
+    struct Parent {
+        #[validate(fields(key_display = redact))]
+        field: HashMap<i32, Child>,
+    }
+    fn redact(_key: &i32) -> String {
+        "x".into()
+    }
+
+    assert_eq!(
+        ".field.x[0]: range: Number not in range: max=10, value=11",
+        Parent {
+            field: map! { 1 => Child(11) }
+        }
+        .validate()
+        .to_string()
+    );
+}
@@ -0,0 +1,72 @@
+use not_so_fast::*;
+
+#[derive(Validate)]
+struct Address {
+    #[validate(char_length(max = 5))]
+    city: String,
+    #[validate(range(max = 99))]
+    number: u32,
+}
+
+#[test]
+fn struct_flatten() {
+    #[derive(Validate)]
+    struct User {
+        #[validate(char_length(max = 5))]
+        name: String,
+        #[validate(flatten)]
+        address: Address,
+    }
+
+    let user = User {
+        name: "Alexander".into(),
+        address: Address {
+            city: "London".into(),
+            number: 221,
+        },
+    };
+    assert_eq!(
+        [
+            ".city: char_length: Invalid character length: max=5, value=6",
+            ".name: char_length: Invalid character length: max=5, value=9",
+            ".number: range: Number not in range: max=99, value=221",
+        ]
+        .join("\n"),
+        user.validate().to_string()
+    );
+}
+
+#[test]
+fn struct_flatten_with_validators() {
+    #[derive(Validate)]
+    struct User {
+        #[validate(flatten, custom = validate_address)]
+        address: Address,
+    }
+    fn validate_address(address: &Address) -> ValidationNode {
+        ValidationNode::error_if(address.number == 0, || ValidationError::with_code("zero"))
+    }
+
+    let user = User {
+        address: Address {
+            city: "London".into(),
+            number: 0,
+        },
+    };
+    assert_eq!(".: zero", user.validate().to_string());
+}
+
+#[test]
+fn tuple_struct_flatten() {
+    #[derive(Validate)]
+    struct Wrapper(#[validate(flatten)] Address);
+
+    let wrapper = Wrapper(Address {
+        city: "London".into(),
+        number: 1,
+    });
+    assert_eq!(
+        ".city: char_length: Invalid character length: max=5, value=6",
+        wrapper.validate().to_string()
+    );
+}
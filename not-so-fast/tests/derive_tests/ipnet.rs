@@ -0,0 +1,82 @@
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+use not_so_fast::*;
+
+const MAX_V6_PREFIX: u8 = 64;
+
+#[test]
+fn cidr() {
+    #[derive(Validate)]
+    struct S<'a> {
+        #[validate(cidr)]
+        a: String,
+        #[validate(cidr)]
+        b: &'a str,
+        #[validate(some(cidr))]
+        c: Option<String>,
+        #[validate(items(cidr))]
+        d: Vec<String>,
+    }
+
+    let valid = S {
+        a: "10.0.0.0/8".into(),
+        b: "2001:db8::/32",
+        c: Some("192.168.1.1/24".into()),
+        d: vec!["0.0.0.0/0".into(), "::/0".into()],
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: "10.0.0.0".into(),
+        b: "10.0.0.0/33",
+        c: Some("example.com/24".into()),
+        d: vec!["10.0.0.0/8".into(), " 10.0.0.0/8".into()],
+    };
+    assert_eq!(
+        ".a: cidr: Invalid CIDR notation
+.b: cidr: Invalid CIDR notation
+.c: cidr: Invalid CIDR notation
+.d[1]: cidr: Invalid CIDR notation",
+        invalid.validate().to_string(),
+    );
+}
+
+#[test]
+fn prefix_len() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(prefix_len(min = 8))]
+        a: IpNet,
+        #[validate(prefix_len(max = 24))]
+        b: Ipv4Net,
+        #[validate(prefix_len(min = 48, max = MAX_V6_PREFIX))]
+        c: Ipv6Net,
+        #[validate(items(prefix_len(equal = 32)))]
+        d: Vec<IpNet>,
+    }
+
+    let valid = S {
+        a: "10.0.0.0/8".parse().unwrap(),
+        b: "0.0.0.0/0".parse().unwrap(),
+        c: "2001:db8::/64".parse().unwrap(),
+        d: vec!["10.0.0.1/32".parse().unwrap()],
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: "0.0.0.0/0".parse().unwrap(),
+        b: "10.0.0.0/25".parse().unwrap(),
+        c: "2001:db8::/32".parse().unwrap(),
+        d: vec!["10.0.0.1/32".parse().unwrap(), "::1/128".parse().unwrap()],
+    };
+    assert_eq!(
+        ".a: prefix_len: Invalid prefix length: min=8, value=0
+.b: prefix_len: Invalid prefix length: max=24, value=25
+.c: prefix_len: Invalid prefix length: max=64, min=48, value=32
+.d[1]: prefix_len: Invalid prefix length: equal=32, value=128",
+        invalid.validate().to_string(),
+    );
+    assert_eq!(
+        vec![codes::PREFIX_LEN],
+        CodeRegistry::of::<S>().iter().collect::<Vec<_>>()
+    );
+}
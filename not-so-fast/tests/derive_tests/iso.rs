@@ -0,0 +1,72 @@
+use not_so_fast::*;
+
+#[test]
+fn country_code() {
+    #[derive(Validate)]
+    struct S<'a> {
+        #[validate(country_code)]
+        a: String,
+        #[validate(country_code)]
+        b: &'a str,
+        #[validate(some(country_code))]
+        c: Option<String>,
+        #[validate(items(country_code))]
+        d: Vec<&'a str>,
+    }
+
+    let valid = S {
+        a: "US".into(),
+        b: "GBR",
+        c: Some("AX".into()),
+        d: vec!["ZW", "ZWE", "AD", "AND"],
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: "us".into(),
+        b: "UK",
+        c: Some("U".into()),
+        d: vec!["PL", "POLAND", "ZZZ"],
+    };
+    assert_eq!(
+        ".a: country_code: Invalid country code
+.b: country_code: Invalid country code
+.c: country_code: Invalid country code
+.d[1]: country_code: Invalid country code
+.d[2]: country_code: Invalid country code",
+        invalid.validate().to_string(),
+    );
+    assert_eq!(
+        vec![codes::COUNTRY_CODE],
+        CodeRegistry::of::<S>().iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn currency_code() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(currency_code)]
+        a: String,
+        #[validate(items(currency_code))]
+        b: Vec<String>,
+    }
+
+    let valid = S {
+        a: "CHF".into(),
+        b: vec!["AED".into(), "XAU".into(), "ZWG".into()],
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: "chf".into(),
+        b: vec!["USD".into(), "DEM".into(), "US".into(), "USDT".into()],
+    };
+    assert_eq!(
+        ".a: currency_code: Invalid currency code
+.b[1]: currency_code: Invalid currency code
+.b[2]: currency_code: Invalid currency code
+.b[3]: currency_code: Invalid currency code",
+        invalid.validate().to_string(),
+    );
+}
@@ -115,3 +115,126 @@ fn different_types() {
     .validate()
     .is_ok());
 }
+
+#[test]
+fn field_validate_items_key_field() {
+    #[derive(Validate)]
+    struct User {
+        id: String,
+        #[validate(range(max = 150))]
+        age: u32,
+    }
+
+    #[derive(Validate)]
+    struct Parent {
+        #[validate(items(key_field = id))]
+        users: Vec<User>,
+    }
+    let user = |id: &str, age| User { id: id.into(), age };
+    assert!(Parent { users: vec![] }.validate().is_ok());
+    assert!(Parent {
+        users: vec![user("a", 10), user("b", 20)]
+    }
+    .validate()
+    .is_ok());
+    assert_eq!(
+        Parent {
+            users: vec![user("a", 10), user("b", 200), user("c", 300)]
+        }
+        .validate()
+        .to_string(),
+        ".users[\"b\"].age: range: Number not in range: max=150, value=200\n\
+         .users[\"c\"].age: range: Number not in range: max=150, value=300",
+    );
+}
+
+#[test]
+fn field_validate_items_key_expression() {
+    #[derive(Validate)]
+    struct Entry {
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Validate)]
+    struct Parent {
+        #[validate(items(key = |entry| entry.id * 10, custom = check_name))]
+        entries: Vec<Entry>,
+    }
+
+    fn check_name(entry: &Entry) -> ValidationNode {
+        ValidationNode::error_if(entry.name.is_empty(), || {
+            ValidationError::with_code("empty")
+        })
+    }
+
+    assert!(Parent {
+        entries: vec![Entry {
+            id: 1,
+            name: "a".into()
+        }]
+    }
+    .validate()
+    .is_ok());
+    assert_eq!(
+        Parent {
+            entries: vec![
+                Entry {
+                    id: 2,
+                    name: "".into()
+                },
+                Entry {
+                    id: 1,
+                    name: "a".into()
+                }
+            ]
+        }
+        .validate()
+        .to_string(),
+        ".entries[\"20\"]: empty",
+    );
+}
+
+#[test]
+fn items_parallel() {
+    use rayon::iter::IntoParallelRefIterator;
+
+    #[derive(Validate)]
+    struct Input {
+        #[validate(items(parallel, range(max = 10)))]
+        numbers: Vec<u32>,
+        #[validate(some(items(parallel, range(max = 10))))]
+        maybe_numbers: Option<[u32; 3]>,
+    }
+
+    let numbers: Vec<u32> = (0..5000).map(|index| index as u32 % 1000).collect();
+    let sequential = ValidationNode::items(numbers.iter(), |_index, number| {
+        ValidationNode::error_if(*number > 10, || ValidationError::with_code("range"))
+    });
+    let parallel = ValidationNode::par_items(numbers.par_iter(), |_index, number| {
+        ValidationNode::error_if(*number > 10, || ValidationError::with_code("range"))
+    });
+    assert_eq!(sequential, parallel);
+
+    assert!(Input {
+        numbers: vec![1; 1000],
+        maybe_numbers: Some([1, 2, 3]),
+    }
+    .validate()
+    .is_ok());
+
+    let mut numbers = vec![1; 1000];
+    numbers[0] = 11;
+    numbers[999] = 12;
+    assert_eq!(
+        Input {
+            numbers,
+            maybe_numbers: Some([1, 20, 3]),
+        }
+        .validate()
+        .to_string(),
+        ".maybe_numbers[1]: range: Number not in range: max=10, value=20\n\
+         .numbers[0]: range: Number not in range: max=10, value=11\n\
+         .numbers[999]: range: Number not in range: max=10, value=12",
+    );
+}
@@ -0,0 +1,95 @@
+use not_so_fast::*;
+
+#[test]
+fn language_tag() {
+    #[derive(Validate)]
+    struct S<'a> {
+        #[validate(language_tag)]
+        a: String,
+        #[validate(language_tag)]
+        b: &'a str,
+        #[validate(some(language_tag))]
+        c: Option<String>,
+        #[validate(items(language_tag))]
+        d: Vec<&'a str>,
+    }
+
+    let valid = S {
+        a: "en-US".into(),
+        b: "zh-Hant-TW",
+        c: Some("sr-Latn".into()),
+        d: vec!["de", "es-419", "EN-gb"],
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: "en_US".into(),
+        b: "",
+        c: Some("en-US-".into()),
+        d: vec!["fr", "français"],
+    };
+    assert_eq!(
+        ".a: language_tag: Invalid language tag
+.b: language_tag: Invalid language tag
+.c: language_tag: Invalid language tag
+.d[1]: language_tag: Invalid language tag",
+        invalid.validate().to_string(),
+    );
+    assert_eq!(
+        vec![codes::LANGUAGE_TAG],
+        CodeRegistry::of::<S>().iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn well_formed_language_tags() {
+    for tag in [
+        "en",
+        "ast",
+        "zh-yue-HK",
+        "sgn-ase",
+        "az-Arab-IR",
+        "de-CH-1901",
+        "sl-rozaj-biske",
+        "hy-Latn-IT-arevela",
+        "en-US-u-islamcal",
+        "de-DE-u-co-phonebk",
+        "en-a-bbb-x-a-ccc",
+        "qaa-Qaaa-QM-x-southern",
+        "x-private",
+        "i-klingon",
+        "en-GB-oed",
+    ] {
+        assert!(is_language_tag(tag), "{tag}");
+    }
+}
+
+#[test]
+fn malformed_language_tags() {
+    for tag in [
+        "",
+        "-",
+        "e",
+        "en--US",
+        "en-",
+        "englishlanguage",
+        "en-US-u",
+        "en-x",
+        "de-419-DE",
+        "a-DE",
+        "ar-a-aaa-b-bbb-a-ccc-",
+        "en-verylongsubtag",
+        "zh-abc-def-ghi-jkl",
+        "x-toolongsubtag",
+    ] {
+        assert!(!is_language_tag(tag), "{tag}");
+    }
+}
+
+#[cfg(feature = "language-tags")]
+#[test]
+fn registered_language_tags() {
+    assert!(is_language_tag("en-US"));
+    assert!(!is_language_tag("zz-ZZ"));
+    assert!(!is_language_tag("de-DE-1996-1996"));
+}
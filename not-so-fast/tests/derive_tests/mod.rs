@@ -1,11 +1,26 @@
 mod args;
 mod basic;
 mod char_length;
+mod codes;
+mod context;
 mod custom;
+mod custom_async;
+mod decimal;
 mod fields;
+mod flatten;
 mod generics;
+mod ipnet;
+mod iso;
 mod items;
+mod language_tag;
 mod length;
 mod nested;
+mod order;
 mod range;
+mod remote;
 mod some;
+mod temporal;
+mod transparent;
+mod url;
+mod uuid;
+mod wasm;
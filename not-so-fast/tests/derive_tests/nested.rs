@@ -42,3 +42,117 @@ fn field_validate_nested_args() {
     assert!(Parent { field: Child(10) }.validate().is_ok());
     assert!(Parent { field: Child(11) }.validate().is_err());
 }
+
+#[test]
+fn nested_limits() {
+    #[derive(Validate)]
+    struct Node {
+        #[validate(range(max = 9))]
+        value: u32,
+        #[validate(items(nested))]
+        children: Vec<Node>,
+    }
+
+    fn chain(depth: u32) -> Node {
+        Node {
+            value: 10,
+            children: match depth {
+                0 => vec![],
+                _ => vec![chain(depth - 1)],
+            },
+        }
+    }
+
+    let node = chain(2);
+    assert_eq!(
+        [
+            ".children[0].children[0]: depth_exceeded: Maximum depth exceeded: max=1",
+            ".children[0].value: range: Number not in range: max=9, value=10",
+            ".value: range: Number not in range: max=9, value=10",
+        ]
+        .join("\n"),
+        ValidationLimits::new()
+            .with_max_depth(1)
+            .run(|| node.validate())
+            .to_string()
+    );
+    assert_eq!(
+        3,
+        ValidationLimits::new()
+            .run(|| node.validate())
+            .error_count()
+    );
+
+    // Siblings are skipped once nested validations report enough errors.
+    let node = Node {
+        value: 0,
+        children: vec![chain(0), chain(0), chain(0)],
+    };
+    assert_eq!(
+        [
+            ".: truncated: Too many errors: omitted=0, skipped=1",
+            ".children[0].value: range: Number not in range: max=9, value=10",
+            ".children[1].value: range: Number not in range: max=9, value=10",
+        ]
+        .join("\n"),
+        ValidationLimits::new()
+            .with_max_errors(2)
+            .run(|| node.validate())
+            .to_string()
+    );
+    // Limits are removed after the run.
+    assert_eq!(3, node.validate().error_count());
+}
+
+#[test]
+fn nested_into_sink() {
+    use std::cell::Cell;
+
+    thread_local! {
+        static VALIDATED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Validate)]
+    #[validate(custom = count_validated)]
+    struct Child {
+        #[validate(range(max = 10))]
+        value: i32,
+    }
+
+    fn count_validated(_child: &Child) -> ValidationNode {
+        VALIDATED.with(|validated| validated.set(validated.get() + 1));
+        ValidationNode::ok()
+    }
+
+    #[derive(Validate)]
+    struct Parent {
+        #[validate(range(max = 10))]
+        a: i32,
+        #[validate(nested)]
+        b: Child,
+        #[validate(nested)]
+        c: Child,
+    }
+
+    let parent = Parent {
+        a: 11,
+        b: Child { value: 11 },
+        c: Child { value: 11 },
+    };
+    let mut first = FirstError::default();
+    parent.validate_into(&mut first);
+    assert_eq!(".a", first.path().unwrap().to_string());
+    assert_eq!(0, VALIDATED.with(Cell::get));
+
+    let parent = Parent { a: 10, ..parent };
+    let mut first = FirstError::default();
+    parent.validate_into(&mut first);
+    assert_eq!(".b.value", first.path().unwrap().to_string());
+    assert_eq!(1, VALIDATED.with(Cell::get));
+
+    // Sinks that are never done receive all errors.
+    let mut count = ErrorCount::default();
+    parent.validate_into(&mut count);
+    assert_eq!(2, count.count());
+    assert_eq!(3, VALIDATED.with(Cell::get));
+}
@@ -0,0 +1,113 @@
+use not_so_fast::*;
+
+#[test]
+fn struct_field_order() {
+    #[derive(Validate)]
+    struct Struct {
+        #[validate(range(max = 1))]
+        a: u32,
+        #[validate(range(max = 1), order = 1)]
+        b: u32,
+        #[validate(range(max = 1), order = 0)]
+        c: u32,
+    }
+
+    assert_eq!(
+        [
+            ".c: range: Number not in range: max=1, value=2",
+            ".b: range: Number not in range: max=1, value=2",
+            ".a: range: Number not in range: max=1, value=2",
+        ]
+        .join("\n"),
+        Struct { a: 2, b: 2, c: 2 }.validate().to_string()
+    );
+}
+
+#[test]
+fn struct_declaration_order() {
+    #[derive(Validate)]
+    #[validate(declaration_order)]
+    struct Struct {
+        #[validate(range(max = 1))]
+        zip_code: u32,
+        #[validate(range(max = 1), range(max = 0))]
+        street: u32,
+        #[validate(range(max = 1))]
+        city: u32,
+    }
+
+    assert_eq!(
+        [
+            ".zip_code: range: Number not in range: max=1, value=2",
+            ".street: range: Number not in range: max=1, value=2",
+            ".street: range: Number not in range: max=0, value=2",
+            ".city: range: Number not in range: max=1, value=2",
+        ]
+        .join("\n"),
+        Struct {
+            zip_code: 2,
+            street: 2,
+            city: 2
+        }
+        .validate()
+        .to_string()
+    );
+}
+
+#[test]
+fn enum_declaration_order() {
+    #[derive(Validate)]
+    #[validate(declaration_order)]
+    enum Enum {
+        A {
+            #[validate(range(max = 1))]
+            b: u32,
+            #[validate(range(max = 1))]
+            a: u32,
+        },
+    }
+
+    assert_eq!(
+        ".b: range: Number not in range: max=1, value=2\n.a: range: Number not in range: max=1, value=2",
+        Enum::A { b: 2, a: 2 }.validate().to_string()
+    );
+}
+
+#[test]
+fn sink_order() {
+    #[derive(Validate)]
+    #[validate(custom = check_struct)]
+    struct Struct {
+        #[validate(range(max = 1))]
+        a: u32,
+        #[validate(range(max = 1), order = 0)]
+        b: u32,
+        #[validate(nested)]
+        c: Enum,
+    }
+
+    #[derive(Validate)]
+    #[validate(declaration_order)]
+    enum Enum {
+        A {
+            #[validate(range(max = 1))]
+            b: u32,
+            #[validate(range(max = 1))]
+            a: u32,
+        },
+    }
+
+    fn check_struct(_value: &Struct) -> ValidationNode {
+        ValidationNode::error(ValidationError::with_code("struct"))
+    }
+
+    let value = Struct {
+        a: 2,
+        b: 2,
+        c: Enum::A { b: 2, a: 2 },
+    };
+    let mut errors = Vec::new();
+    value.validate_into(&mut errors);
+    let expected: Vec<_> = value.validate().into_iter().collect();
+    assert_eq!(expected, errors);
+}
@@ -0,0 +1,82 @@
+use not_so_fast::*;
+
+/// Stands in for a crate whose types can't be annotated.
+mod other_crate {
+    pub struct HttpConfig {
+        pub host: String,
+        pub port: u16,
+        pub upstreams: Vec<String>,
+        pub tls: Tls,
+    }
+
+    pub struct Tls {
+        pub cert_path: String,
+    }
+
+    pub struct Port(pub u16);
+}
+
+#[derive(Validate)]
+#[validate(remote = "other_crate::HttpConfig", custom = check_config)]
+#[allow(dead_code)]
+struct HttpConfig {
+    #[validate(char_length(min = 1, max = 10))]
+    host: String,
+    #[validate(range(min = 1024))]
+    port: u16,
+    #[validate(length(max = 2), items(char_length(max = 5)))]
+    upstreams: Vec<String>,
+    #[validate(custom = validate_tls)]
+    tls: other_crate::Tls,
+}
+
+#[derive(Validate)]
+#[validate(remote = "other_crate::Tls")]
+#[allow(dead_code)]
+struct Tls {
+    #[validate(char_length(min = 1))]
+    cert_path: String,
+}
+
+#[derive(Validate)]
+#[validate(remote = "other_crate::Port", transparent)]
+#[allow(dead_code)]
+struct Port(#[validate(range(max = 9000))] u16);
+
+fn check_config(config: &other_crate::HttpConfig) -> ValidationNode {
+    ValidationNode::error_if(config.upstreams.contains(&config.host), || {
+        ValidationError::with_code("self_upstream")
+    })
+}
+
+#[test]
+fn remote_struct() {
+    let config = other_crate::HttpConfig {
+        host: "localhost".into(),
+        port: 80,
+        upstreams: vec!["localhost".into(), "b".into(), "c".into()],
+        tls: other_crate::Tls {
+            cert_path: String::new(),
+        },
+    };
+    assert_eq!(
+        [
+            ".: self_upstream",
+            ".port: range: Number not in range: min=1024, value=80",
+            ".tls.cert_path: char_length: Invalid character length: min=1, value=0",
+            ".upstreams: length: Invalid length: max=2, value=3",
+            ".upstreams[0]: char_length: Invalid character length: max=5, value=9",
+        ]
+        .join("\n"),
+        validate_http_config(&config).to_string()
+    );
+}
+
+#[test]
+fn remote_tuple_struct() {
+    assert!(validate_port(&other_crate::Port(8080)).is_ok());
+    assert_eq!(
+        ".: range: Number not in range: max=9000, value=9999",
+        validate_port(&other_crate::Port(9999)).to_string()
+    );
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use not_so_fast::*;
+use time::macros::{date, datetime};
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+const EPOCH: NaiveDate = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+
+fn codes(node: &ValidationNode) -> Vec<(String, &str)> {
+    node.iter()
+        .map(|(path, error)| (path.to_string(), error.code()))
+        .collect()
+}
+
+#[test]
+fn before_and_after() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(before = now, after = EPOCH)]
+        a: NaiveDate,
+        #[validate(after = now)]
+        b: DateTime<FixedOffset>,
+        #[validate(some(before = now))]
+        c: Option<NaiveDateTime>,
+    }
+
+    let tomorrow = Utc::now() + Duration::days(1);
+    let valid = S {
+        a: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+        b: tomorrow.with_timezone(&FixedOffset::east_opt(3600).unwrap()),
+        c: Some(Utc::now().naive_utc() - Duration::minutes(1)),
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: EPOCH,
+        b: (Utc::now() - Duration::minutes(1)).fixed_offset(),
+        c: Some(tomorrow.naive_utc()),
+    };
+    assert_eq!(
+        vec![
+            (".a".to_owned(), "after"),
+            (".b".to_owned(), "after"),
+            (".c".to_owned(), "before"),
+        ],
+        codes(&invalid.validate()),
+    );
+
+    let invalid = S {
+        a: tomorrow.date_naive(),
+        ..valid
+    };
+    assert_eq!(
+        vec![(".a".to_owned(), "before")],
+        codes(&invalid.validate())
+    );
+}
+
+#[test]
+fn limit_params() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(before = date!(2000 - 01 - 01))]
+        a: time::Date,
+    }
+
+    assert_eq!(
+        r#".a: before: Value not before limit: limit="2000-01-01", value="2000-01-01""#,
+        S {
+            a: date!(2000 - 01 - 01)
+        }
+        .validate()
+        .to_string(),
+    );
+}
+
+#[test]
+fn within_last() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(within_last(days = 30))]
+        a: NaiveDate,
+        #[validate(within_last(hours = 1, minutes = 30))]
+        b: OffsetDateTime,
+        #[validate(within_last(seconds = 60))]
+        c: PrimitiveDateTime,
+    }
+
+    let now = OffsetDateTime::now_utc();
+    let valid = S {
+        a: Utc::now().date_naive() - Duration::days(30),
+        b: now - time::Duration::minutes(89),
+        c: PrimitiveDateTime::new(now.date(), now.time()),
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: Utc::now().date_naive() - Duration::days(31),
+        b: now - time::Duration::minutes(91),
+        c: datetime!(2000-01-01 0:00),
+    };
+    assert_eq!(
+        vec![
+            (".a".to_owned(), "within_last"),
+            (".b".to_owned(), "within_last"),
+            (".c".to_owned(), "within_last"),
+        ],
+        codes(&invalid.validate()),
+    );
+
+    let future = S {
+        a: Utc::now().date_naive() + Duration::days(1),
+        ..valid
+    };
+    let errors = future.validate();
+    assert_eq!(vec![(".a".to_owned(), "within_last")], codes(&errors));
+    let (_, error) = errors.iter().next().unwrap();
+    assert_eq!(Some(&ParamValue::from(30)), error.param("days"));
+}
+
+#[test]
+fn ord_range() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(ord_range(min = "b".to_owned(), max = "d".to_owned()))]
+        a: String,
+        #[validate(ord_range(min = EPOCH))]
+        b: NaiveDate,
+        #[validate(items(ord_range(max = 'z')))]
+        c: Vec<char>,
+    }
+
+    let valid = S {
+        a: "c".into(),
+        b: EPOCH,
+        c: vec!['a', 'z'],
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: "e".into(),
+        b: NaiveDate::from_ymd_opt(1969, 12, 31).unwrap(),
+        c: vec!['~'],
+    };
+    assert_eq!(
+        r#".a: range: Value not in range: max="d", min="b", value="e"
+.b: range: Value not in range: min="1970-01-01", value="1969-12-31"
+.c[0]: range: Value not in range: max="z", value="~""#,
+        invalid.validate().to_string(),
+    );
+}
+
+#[test]
+fn temporal_codes() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(before = now, ord_range(min = EPOCH))]
+        a: NaiveDate,
+        #[validate(after = now, within_last(days = 1))]
+        b: NaiveDate,
+    }
+
+    assert_eq!(
+        vec![
+            codes::AFTER,
+            codes::BEFORE,
+            codes::RANGE,
+            codes::WITHIN_LAST
+        ],
+        CodeRegistry::of::<S>().iter().collect::<Vec<_>>()
+    );
+}
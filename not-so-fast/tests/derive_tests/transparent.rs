@@ -0,0 +1,53 @@
+use not_so_fast::*;
+
+#[derive(Validate)]
+#[validate(transparent)]
+struct EmailAddress(#[validate(char_length(max = 10), custom = validate_at)] String);
+
+fn validate_at(value: &str) -> ValidationNode {
+    ValidationNode::error_if(!value.contains('@'), || ValidationError::with_code("email"))
+}
+
+#[test]
+fn tuple_struct_transparent() {
+    assert_eq!("", EmailAddress("a@b.c".into()).validate().to_string());
+    assert_eq!(
+        ".: char_length: Invalid character length: max=10, value=12\n.: email",
+        EmailAddress("not an email".into()).validate().to_string()
+    );
+}
+
+#[test]
+fn tuple_struct_transparent_nested() {
+    #[derive(Validate)]
+    struct User {
+        #[validate]
+        email: EmailAddress,
+        #[validate(items)]
+        other_emails: Vec<EmailAddress>,
+    }
+
+    let user = User {
+        email: EmailAddress("abc".into()),
+        other_emails: vec![EmailAddress("a@b.c".into()), EmailAddress("def".into())],
+    };
+    assert_eq!(
+        ".email: email\n.other_emails[1]: email",
+        user.validate().to_string()
+    );
+}
+
+#[test]
+fn named_struct_transparent() {
+    #[derive(Validate)]
+    #[validate(transparent)]
+    struct Wrapper {
+        #[validate(range(max = 10))]
+        value: u32,
+    }
+
+    assert_eq!(
+        ".: range: Number not in range: max=10, value=11",
+        Wrapper { value: 11 }.validate().to_string()
+    );
+}
@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+use not_so_fast::*;
+use uuid::Uuid;
+
+const V7: usize = 7;
+
+const RANDOM: Uuid = Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8);
+const TIME_ORDERED: Uuid = Uuid::from_u128(0x01890a5d_ac96_774b_bcce_b302099a8057);
+
+#[test]
+fn uuid_version() {
+    #[derive(Validate)]
+    struct S {
+        #[validate(uuid_version = 4)]
+        a: Uuid,
+        #[validate(some(uuid_version = V7))]
+        b: Option<Uuid>,
+        #[validate(items(uuid_version = 4))]
+        c: Vec<Uuid>,
+    }
+
+    let valid = S {
+        a: RANDOM,
+        b: Some(TIME_ORDERED),
+        c: vec![RANDOM],
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = S {
+        a: TIME_ORDERED,
+        b: Some(RANDOM),
+        c: vec![RANDOM, Uuid::nil()],
+    };
+    assert_eq!(
+        ".a: uuid_version: Invalid UUID version: value=01890a5d-ac96-774b-bcce-b302099a8057, version=4
+.b: uuid_version: Invalid UUID version: value=67e55044-10b1-426f-9247-bb680e5fe0c8, version=7
+.c[1]: uuid_version: Invalid UUID version: value=00000000-0000-0000-0000-000000000000, version=4",
+        invalid.validate().to_string(),
+    );
+    assert_eq!(
+        vec![codes::UUID_VERSION],
+        CodeRegistry::of::<S>().iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn uuid_keys() {
+    #[derive(Validate)]
+    struct Item {
+        #[validate(uuid_version = 4)]
+        id: Uuid,
+    }
+
+    #[derive(Validate)]
+    struct S {
+        #[validate(fields(range(max = 10)))]
+        scores: BTreeMap<Uuid, u32>,
+        #[validate(items(key_field = id, nested))]
+        items: Vec<Item>,
+    }
+
+    let invalid = S {
+        scores: BTreeMap::from([(RANDOM, 5), (TIME_ORDERED, 20)]),
+        items: vec![Item { id: TIME_ORDERED }],
+    };
+    assert_eq!(
+        r#".items["01890a5d-ac96-774b-bcce-b302099a8057"].id: uuid_version: Invalid UUID version: value=01890a5d-ac96-774b-bcce-b302099a8057, version=4
+.scores."01890a5d-ac96-774b-bcce-b302099a8057": range: Number not in range: max=10, value=20"#,
+        invalid.validate().to_string(),
+    );
+}
+
+#[test]
+fn uuid_params_serialize_as_strings() {
+    let errors = ValidationNode::error(ValidationError::with_code("a").and_param("id", RANDOM));
+    assert_eq!(
+        r#"[{"path":"","code":"a","params":{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8"}}]"#,
+        serde_json::to_string(&errors.as_flat_list()).unwrap(),
+    );
+}
@@ -0,0 +1,38 @@
+use not_so_fast::*;
+use serde::Deserialize;
+use wasm_bindgen::JsValue;
+
+#[derive(Deserialize, Validate)]
+#[validate(wasm = validate_user, wasm = check_user)]
+struct User {
+    #[validate(char_length(max = 5))]
+    name: String,
+}
+
+#[derive(Deserialize, Validate)]
+#[validate(wasm = validate_shape)]
+enum Shape {
+    Circle {
+        #[validate(range(min = 0.0))]
+        radius: f64,
+    },
+}
+
+#[test]
+fn wasm_functions() {
+    // JavaScript values exist only in wasm, so check signatures of the
+    // exported functions and that types keep validating as usual.
+    let _: fn(JsValue) -> Result<JsValue, JsValue> = validate_user;
+    let _: fn(JsValue) -> Result<JsValue, JsValue> = check_user;
+    let _: fn(JsValue) -> Result<JsValue, JsValue> = validate_shape;
+
+    assert_eq!(
+        ".name: char_length: Invalid character length: max=5, value=6",
+        User {
+            name: "Thomas".into()
+        }
+        .validate()
+        .to_string(),
+    );
+    assert!(Shape::Circle { radius: 1.0 }.validate().is_ok());
+}
@@ -0,0 +1,136 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::dynamic::{Bounds, DynValidator, Rule};
+use not_so_fast::*;
+use serde_json::json;
+
+const DOCUMENT: &str = r#"{
+    "fields": {
+        "name": ["required", { "char_length": { "min": 1, "max": 5 } }],
+        "age": [{ "type": "integer" }, { "range": { "min": 18, "max": 100 } }],
+        "role": [{ "one_of": ["admin", "user"] }],
+        "tags": [{ "length": { "max": 2 } }, { "items": [{ "char_length": { "max": 3 } }] }],
+        "address": [{ "fields": { "city": ["required"] } }],
+        "limits": [{ "values": [{ "range": { "max": 1.5 } }] }]
+    }
+}"#;
+
+#[test]
+fn valid_input() {
+    let validator = DynValidator::from_json(DOCUMENT).unwrap();
+    let input = json!({
+        "name": "Tom",
+        "age": 30,
+        "role": "user",
+        "tags": ["a", "b"],
+        "address": { "city": "Oslo" },
+        "limits": { "cpu": 1.0 },
+    });
+    assert!(validator.validate(&input).is_ok());
+}
+
+#[test]
+fn invalid_input() {
+    let validator = DynValidator::from_json(DOCUMENT).unwrap();
+    let input = json!({
+        "age": 12.5,
+        "role": "root",
+        "tags": ["a", "bbbb", "c"],
+        "address": { "city": null },
+        "limits": { "cpu": 2, "memory": 1 },
+    });
+    assert_eq!(
+        [
+            ".address.city: required: Value is required",
+            ".age: type: Invalid type: expected=\"integer\"",
+            ".age: range: Number not in range: max=100, min=18, value=12.5",
+            ".limits.cpu: range: Number not in range: max=1.5, value=2",
+            ".name: required: Value is required",
+            ".role: one_of: Value not allowed: allowed=[\"admin\",\"user\"], value=\"root\"",
+            ".tags: length: Invalid length: max=2, value=3",
+            ".tags[1]: char_length: Invalid character length: max=3, value=4",
+        ]
+        .join("\n"),
+        validator.validate(&input).to_string(),
+    );
+}
+
+#[test]
+fn rules_of_other_types_are_skipped() {
+    let validator = DynValidator::from_json(DOCUMENT).unwrap();
+    let input = json!({ "name": "Tom", "age": "thirty", "tags": 5 });
+    assert_eq!(
+        ".age: type: Invalid type: expected=\"integer\"",
+        validator.validate(&input).to_string(),
+    );
+}
+
+#[test]
+fn errors_match_derive() {
+    #[derive(Validate)]
+    struct User {
+        #[validate(char_length(min = 1, max = 5))]
+        name: String,
+        #[validate(length(equal = 2))]
+        tags: Vec<String>,
+    }
+
+    let validator = DynValidator::from_json(
+        r#"{ "fields": {
+            "name": [{ "char_length": { "min": 1, "max": 5 } }],
+            "tags": [{ "length": { "equal": 2 } }]
+        } }"#,
+    )
+    .unwrap();
+    let user = User {
+        name: "Alexander".into(),
+        tags: vec!["a".into()],
+    };
+    let input = json!({ "name": user.name, "tags": user.tags });
+    assert_eq!(
+        user.validate().to_string(),
+        validator.validate(&input).to_string()
+    );
+}
+
+#[test]
+fn root_rules() {
+    let validator = DynValidator {
+        rules: vec![
+            Rule::Length(Bounds {
+                max: Some(1),
+                ..Bounds::default()
+            }),
+            Rule::Items(vec![Rule::Required]),
+        ],
+        ..DynValidator::default()
+    };
+    assert_eq!(
+        ".: length: Invalid length: max=1, value=2\n.[1]: required: Value is required",
+        validator.validate(&json!([1, null])).to_string(),
+    );
+}
+
+#[test]
+fn document_round_trip() {
+    let validator = DynValidator::from_json(DOCUMENT).unwrap();
+    let serialized = serde_json::to_value(&validator).unwrap();
+    assert_eq!(
+        json!(["required", { "char_length": { "min": 1, "max": 5 } }]),
+        serialized["fields"]["name"],
+    );
+    assert_eq!(
+        validator,
+        serde_json::from_value::<DynValidator>(serialized).unwrap()
+    );
+}
+
+#[test]
+fn invalid_documents() {
+    assert!(DynValidator::from_json(r#"{ "fields": { "a": [{ "regex": "x" }] } }"#).is_err());
+    assert!(
+        DynValidator::from_json(r#"{ "fields": { "a": [{ "length": { "mni": 1 } }] } }"#).is_err()
+    );
+    assert!(DynValidator::from_json(r#"{ "field": {} }"#).is_err());
+}
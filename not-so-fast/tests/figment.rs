@@ -0,0 +1,136 @@
+// `Jail` closures return `figment::Error`.
+#![allow(clippy::result_large_err)]
+
+#[macro_use]
+extern crate pretty_assertions;
+
+use figment::providers::{Env, Format, Serialized, Toml};
+use figment::{Figment, Jail};
+use not_so_fast::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Validate)]
+struct Config {
+    #[validate(nested)]
+    server: Server,
+    #[validate(length(max = 2), items(nested))]
+    upstreams: Vec<Upstream>,
+    #[serde(default = "default_workers")]
+    #[validate(range(max = 64))]
+    workers: u32,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "kebab-case")]
+struct Server {
+    #[validate(char_length(min = 1))]
+    host: String,
+    #[validate(range(min = 1))]
+    port: u16,
+    #[validate(range(max = 1000), custom = warn_if_many)]
+    max_connections: u32,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+struct Upstream {
+    #[validate(char_length(min = 1))]
+    base_url: String,
+}
+
+fn default_workers() -> u32 {
+    100
+}
+
+fn warn_if_many(connections: &u32) -> ValidationNode {
+    ValidationNode::error_if(*connections > 500, || {
+        ValidationError::with_code("many").and_severity(Severity::Warning)
+    })
+}
+
+const CONFIG: &str = r#"
+[server]
+host = "localhost"
+port = 8080
+max-connections = 600
+
+[[upstreams]]
+baseUrl = "http://a"
+
+[[upstreams]]
+baseUrl = "http://b"
+"#;
+
+fn figment() -> Figment {
+    Figment::new()
+        .merge(Toml::file("Config.toml"))
+        .merge(Env::prefixed("APP_").split("__"))
+}
+
+#[test]
+fn valid_config() {
+    Jail::expect_with(|jail| {
+        jail.create_file("Config.toml", CONFIG)?;
+        jail.set_env("APP_WORKERS", 8);
+
+        let config: Config = figment().extract_validated().unwrap();
+        assert_eq!(8080, config.server.port);
+        assert_eq!(8, config.workers);
+        Ok(())
+    });
+}
+
+#[test]
+fn invalid_config() {
+    Jail::expect_with(|jail| {
+        jail.create_file(
+            "Config.toml",
+            &CONFIG
+                .replace("max-connections = 600", "max-connections = 2000")
+                .replace(r#"baseUrl = "http://b""#, r#"baseUrl = """#),
+        )?;
+        jail.set_env("APP_SERVER__PORT", 0);
+
+        let Err(ConfigError::Invalid(keys)) = figment().extract_validated::<Config>() else {
+            panic!("config should be invalid");
+        };
+        let paths: Vec<_> = keys.iter().map(|key| key.path.join(".")).collect();
+        assert_eq!(
+            vec![
+                "server.max-connections",
+                "server.port",
+                "upstreams.1.baseUrl",
+                "workers",
+            ],
+            paths,
+        );
+        let messages: Vec<_> = keys.iter().map(|key| key.to_string()).collect();
+        assert_eq!(
+            vec![
+                r#"range: Number not in range: max=1000, value=2000 for key "default.server.max-connections" in Config.toml TOML file"#,
+                r#"range: Number not in range: min=1, value=0 for key "SERVER.PORT" in `APP_` environment variable(s)"#,
+                r#"char_length: Invalid character length: min=1, value=0 for key "default.upstreams.1.baseUrl" in Config.toml TOML file"#,
+                r#"range: Number not in range: max=64, value=100 for key "workers""#,
+            ],
+            messages,
+        );
+        assert!(keys[0]
+            .metadata
+            .as_ref()
+            .unwrap()
+            .source
+            .as_ref()
+            .unwrap()
+            .file_path()
+            .is_some());
+        assert_eq!(None, keys[3].metadata);
+        Ok(())
+    });
+}
+
+#[test]
+fn extraction_errors() {
+    let figment = Figment::from(Serialized::default("workers", "many"));
+    let error = figment.extract_validated::<Config>().err().unwrap();
+    assert!(matches!(error, ConfigError::Extract(_)));
+}
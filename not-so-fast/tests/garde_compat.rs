@@ -0,0 +1,69 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+
+#[test]
+fn garde_round_trip() {
+    let node = ValidationNode::error(ValidationError::with_code("invariant"))
+        .and_field(
+            "name",
+            ValidationNode::error(ValidationError::with_code("length")),
+        )
+        .and_field(
+            "tags",
+            ValidationNode::item(
+                2,
+                ValidationNode::error(ValidationError::with_code("empty")),
+            ),
+        )
+        .and_field(
+            "address",
+            ValidationNode::field(
+                "city",
+                ValidationNode::error(ValidationError::with_code("unknown")),
+            ),
+        );
+
+    let report = garde::Report::from(node.clone());
+    assert_eq!(
+        "invariant\naddress.city: unknown\nname: length\ntags[2]: empty\n",
+        report.to_string()
+    );
+    let expected = node
+        .map_messages(|error| Some(error.code().to_owned().into()))
+        .map_codes(|_| codes::GARDE.into());
+    assert_eq!(expected, ValidationNode::from(report));
+}
+
+#[test]
+fn garde_report_of_derived_type() {
+    use garde::Validate as _;
+
+    #[derive(garde::Validate)]
+    struct User {
+        #[garde(length(min = 3))]
+        name: String,
+        #[garde(dive)]
+        pets: Vec<Pet>,
+    }
+
+    #[derive(garde::Validate)]
+    struct Pet {
+        #[garde(range(max = 30))]
+        age: u8,
+    }
+
+    let user = User {
+        name: "ab".into(),
+        pets: vec![Pet { age: 1 }, Pet { age: 40 }],
+    };
+    let node = ValidationNode::from(user.validate().unwrap_err());
+    assert_eq!(
+        vec![".name", ".pets[1].age"],
+        node.iter()
+            .map(|(path, _)| path.to_string())
+            .collect::<Vec<_>>()
+    );
+    assert!(node.iter().all(|(_, error)| error.code() == codes::GARDE));
+}
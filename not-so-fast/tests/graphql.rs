@@ -0,0 +1,84 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use async_graphql::{EmptyMutation, EmptySubscription, InputObject, Object, Schema};
+use not_so_fast::*;
+
+#[derive(InputObject, Validate)]
+struct AddressInput {
+    #[validate(char_length(min = 1))]
+    city: String,
+}
+
+#[derive(InputObject, Validate)]
+struct UserInput {
+    #[validate(char_length(max = 5))]
+    name: String,
+    #[validate(custom = warn_if_young)]
+    age: u8,
+    #[validate(items(nested))]
+    addresses: Vec<AddressInput>,
+}
+
+fn warn_if_young(age: &u8) -> ValidationNode {
+    ValidationNode::error_if(*age < 18, || {
+        ValidationError::with_code("young").and_severity(Severity::Warning)
+    })
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn user(
+        &self,
+        #[graphql(validator(custom = "ValidateInput"))] user: UserInput,
+    ) -> String {
+        user.name
+    }
+}
+
+async fn execute(query: &str) -> async_graphql::Response {
+    Schema::new(Query, EmptyMutation, EmptySubscription)
+        .execute(query)
+        .await
+}
+
+#[tokio::test]
+async fn valid_input() {
+    let response =
+        execute(r#"{ user(user: { name: "tom", age: 10, addresses: [{ city: "Oslo" }] }) }"#).await;
+    assert!(response.errors.is_empty());
+    assert_eq!(r#"{user: "tom"}"#, response.data.to_string());
+}
+
+#[tokio::test]
+async fn invalid_input() {
+    let response = execute(
+        r#"{ user(user: { name: "thomas", age: 10, addresses: [{ city: "Oslo" }, { city: "" }] }) }"#,
+    )
+    .await;
+    assert_eq!(1, response.errors.len());
+    let error = &response.errors[0];
+    assert_eq!(
+        concat!(
+            r#"Failed to parse "UserInput": "#,
+            ".addresses[1].city: char_length: Invalid character length: min=1, value=0\n",
+            ".name: char_length: Invalid character length: max=5, value=6",
+        ),
+        error.message,
+    );
+    assert_eq!(
+        concat!(
+            r#"[{path: ["addresses", 1, "city"], code: "char_length", message: "Invalid character length"}, "#,
+            r#"{path: ["name"], code: "char_length", message: "Invalid character length"}]"#,
+        ),
+        error
+            .extensions
+            .as_ref()
+            .unwrap()
+            .get("validation")
+            .unwrap()
+            .to_string(),
+    );
+}
@@ -0,0 +1,461 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+
+fn tree() -> ValidationNode {
+    ValidationNode::error(ValidationError::with_code("one"))
+        .and_field(
+            "b",
+            ValidationNode::error(ValidationError::with_code("two")).with_order(0),
+        )
+        .and_field(
+            "a",
+            ValidationNode::ok()
+                .and_item(
+                    1,
+                    ValidationNode::error(ValidationError::with_code("three")),
+                )
+                .and_keyed_item(
+                    "x y",
+                    ValidationNode::error(ValidationError::with_code("four")),
+                ),
+        )
+        .and_item(
+            0,
+            ValidationNode::field(
+                "c",
+                ValidationNode::error(ValidationError::with_code("five")),
+            ),
+        )
+}
+
+#[test]
+fn iter_follows_display_order() {
+    let node = tree();
+    let lines: Vec<_> = node
+        .iter()
+        .map(|(path, error)| format!("{}: {}", path, error.code()))
+        .collect();
+    assert_eq!(node.to_string(), lines.join("\n"));
+}
+
+#[test]
+fn into_iter_matches_iter() {
+    let borrowed: Vec<_> = tree()
+        .iter()
+        .map(|(path, error)| (path, error.code().to_string()))
+        .collect();
+    let owned: Vec<_> = tree()
+        .into_iter()
+        .map(|(path, error)| (path, error.code().to_string()))
+        .collect();
+    assert_eq!(borrowed, owned);
+    assert_eq!(
+        &[
+            PathSegment::Field("a".into()),
+            PathSegment::Key("x y".into())
+        ],
+        owned[3].0.segments(),
+    );
+}
+
+#[test]
+fn iter_ok_node() {
+    assert_eq!(0, ValidationNode::ok().iter().count());
+    assert_eq!(0, ValidationNode::ok().into_iter().count());
+}
+
+#[test]
+fn metrics() {
+    let node = tree();
+    assert_eq!(5, node.error_count());
+    assert_eq!(node.iter().count(), node.error_count());
+    assert_eq!(4, node.leaf_count());
+    assert_eq!(2, node.depth());
+}
+
+#[test]
+fn accessors() {
+    let node = tree();
+    assert_eq!(
+        vec!["one"],
+        node.direct_errors()
+            .iter()
+            .map(ValidationError::code)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["b", "a"],
+        node.field_nodes().map(|(name, _)| name).collect::<Vec<_>>()
+    );
+    let a = node.get_field("a").unwrap();
+    assert_eq!("three", a.get_item(1).unwrap().direct_errors()[0].code());
+    assert_eq!(
+        "four",
+        a.get_keyed_item("x y").unwrap().direct_errors()[0].code()
+    );
+    assert!(a.get_item(0).is_none());
+    assert_eq!(
+        vec![PathSegment::Index(0)],
+        node.item_nodes()
+            .map(|(segment, _)| segment)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn flatten() {
+    let flat: Vec<_> = tree()
+        .flatten()
+        .into_iter()
+        .map(|(path, errors)| {
+            let codes: Vec<_> = errors.iter().map(|e| e.code().to_string()).collect();
+            (path, codes)
+        })
+        .collect();
+    let flat_ref: Vec<_> = tree()
+        .flatten_ref()
+        .into_iter()
+        .map(|(path, errors)| {
+            let codes: Vec<_> = errors.iter().map(|e| e.code().to_string()).collect();
+            (path, codes)
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("".to_string(), vec!["one".to_string()]),
+            ("[0].c".to_string(), vec!["five".to_string()]),
+            ("a[\"x y\"]".to_string(), vec!["four".to_string()]),
+            ("a[1]".to_string(), vec!["three".to_string()]),
+            ("b".to_string(), vec!["two".to_string()]),
+        ],
+        flat
+    );
+    assert_eq!(flat, flat_ref);
+}
+
+#[test]
+fn std_traits() {
+    let node = tree();
+    let copy = node.clone();
+    assert_eq!(node, copy);
+    assert_ne!(node, ValidationNode::ok());
+    assert_ne!(
+        ValidationNode::error(ValidationError::with_code("a").and_param("x", 1)),
+        ValidationNode::error(ValidationError::with_code("a").and_param("x", 2)),
+    );
+    assert_eq!(
+        ValidationNode::field("a", ValidationNode::error(ValidationError::with_code("a"))),
+        ValidationNode::ok().with_insertion_order().and_field(
+            "a",
+            ValidationNode::error(ValidationError::with_code("a")).with_order(3)
+        ),
+    );
+
+    let error: Box<dyn std::error::Error> = Box::new(node);
+    assert_eq!(copy.to_string(), error.to_string());
+    let error: Box<dyn std::error::Error> = Box::new(ValidationError::with_code("a"));
+    assert_eq!("a", error.to_string());
+}
+
+#[test]
+fn map_errors_receives_paths() {
+    let paths: Vec<_> = tree().iter().map(|(path, _)| path).collect();
+    let mut visited = Vec::new();
+    let mapped = tree().map_errors(|path, error| {
+        visited.push(path.clone());
+        ValidationError::with_code(format!("{}!", error.code()))
+    });
+    visited.sort();
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(sorted, visited);
+    assert_eq!(
+        vec!["one!", "two!", "three!", "four!", "five!"],
+        mapped
+            .iter()
+            .map(|(_, error)| error.code())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn retain_prunes_empty_nodes() {
+    let mut node = tree();
+    node.retain(|path, _| path.segments().len() < 2);
+    assert_eq!(".: one\n.b: two", node.to_string());
+    assert!(node.get_field("a").is_none());
+    assert!(node.get_item(0).is_none());
+
+    node.retain(|_, _| false);
+    assert!(node.is_ok());
+    assert_eq!(ValidationNode::ok(), node);
+}
+
+#[test]
+fn subtrees_at_paths() {
+    let mut node = tree();
+    let path = |s: &str| s.parse::<Path>().unwrap();
+
+    assert_eq!(
+        "four",
+        node.at(&path(".a[\"x y\"]")).unwrap().direct_errors()[0].code()
+    );
+    assert!(node.at(&path(".a[\"x\"]")).is_none());
+    assert!(node.at(&path(".b.c")).is_none());
+
+    assert!(node.take_at(&path(".a.b")).is_none());
+    assert_eq!(
+        ".: four",
+        node.take_at(&path(".a[\"x y\"]")).unwrap().to_string()
+    );
+    assert!(node.remove_at(&path(".a[1]")));
+    // `.a` became empty and was pruned.
+    assert!(node.at(&path(".a")).is_none());
+    assert!(node.remove_at(&path(".[0].c")));
+    assert!(node.get_item(0).is_none());
+
+    let root = node.take_at(&Path::root()).unwrap();
+    assert_eq!(".: one\n.b: two", root.to_string());
+    assert!(node.is_ok());
+    assert!(node.take_at(&Path::root()).is_none());
+}
+
+#[test]
+fn first_error_matches_iter() {
+    let node = tree();
+    let (path, error) = node.first_error().unwrap();
+    let (iter_path, iter_error) = node.iter().next().unwrap();
+    assert_eq!(iter_path, path);
+    assert!(std::ptr::eq(iter_error, error));
+
+    let mut node = node;
+    node.retain(|path, _| !path.is_root());
+    let (path, error) = node.first_error().unwrap();
+    assert_eq!(".b", path.to_string());
+    assert_eq!("two", error.code());
+}
+
+#[test]
+fn first_matches_iter() {
+    let node = tree();
+    let (path, error) = node.first_error().unwrap();
+    assert_eq!(
+        format!("{path}: {}", error.code()),
+        node.clone().first().to_string()
+    );
+
+    let mut node = node;
+    node.retain(|path, _| !path.is_root());
+    assert_eq!(".b: two", node.first().to_string());
+
+    let node = ValidationNode::ok()
+        .with_insertion_order()
+        .and_field(
+            "zeta",
+            ValidationNode::error(ValidationError::with_code("1")),
+        )
+        .and_field(
+            "alpha",
+            ValidationNode::error(ValidationError::with_code("2")),
+        );
+    assert_eq!(".zeta: 1", node.first().to_string());
+
+    let node = ValidationNode::ok()
+        .and_item(
+            0,
+            ValidationNode::ok()
+                .with_insertion_order()
+                .and_field(
+                    "zeta",
+                    ValidationNode::error(ValidationError::with_code("1")),
+                )
+                .and_field(
+                    "alpha",
+                    ValidationNode::error(ValidationError::with_code("2")),
+                ),
+        )
+        .and_item(1, ValidationNode::error(ValidationError::with_code("3")));
+    assert_eq!(".[0].zeta: 1", node.first().to_string());
+}
+
+#[test]
+fn truncate_keeps_display_order() {
+    let mut node = tree();
+    node.truncate(3);
+    assert_eq!(
+        ".: one\n.: truncated: Too many errors: omitted=2\n.b: two\n.a[1]: three",
+        node.to_string()
+    );
+
+    let mut node = tree();
+    node.truncate(5);
+    assert_eq!(tree(), node);
+
+    let mut node = tree();
+    node.truncate(0);
+    assert_eq!(".: truncated: Too many errors: omitted=5", node.to_string());
+}
+
+#[test]
+fn dedupe_after_merge() {
+    let mut node = tree().merge(tree());
+    assert_eq!(10, node.error_count());
+    node.dedupe();
+    assert_eq!(tree(), node);
+}
+
+#[test]
+fn error_source() {
+    use std::error::Error;
+
+    let parse_error = "x".parse::<i32>().unwrap_err();
+    let node = ValidationNode::field(
+        "n",
+        ValidationNode::error(ValidationError::with_code("number").and_source(parse_error.clone())),
+    );
+    let copy = node.clone();
+    let (_, error) = copy.first_error().unwrap();
+    assert_eq!(parse_error.to_string(), error.source().unwrap().to_string());
+    assert_eq!(
+        ValidationNode::field(
+            "n",
+            ValidationNode::error(ValidationError::with_code("number"))
+        ),
+        node
+    );
+    assert!(ValidationError::with_code("number").source().is_none());
+}
+
+#[test]
+fn insertion_order() {
+    let error = |code: &'static str| ValidationNode::error(ValidationError::with_code(code));
+
+    // Fields added before the mode is enabled keep their position.
+    let errors = ValidationNode::ok()
+        .and_field("z", error("1"))
+        .and_field("b", error("2"))
+        .with_insertion_order()
+        .and_field("a", error("3"))
+        .and_at(".c.x", ValidationError::with_code("4"))
+        .merge(ValidationNode::field("e", error("5")).and_field("d", error("6")))
+        .and_field("d", error("7"));
+    let paths: Vec<_> = errors.iter().map(|(path, _)| path.to_string()).collect();
+    assert_eq!(vec![".b", ".z", ".a", ".c.x", ".d", ".d", ".e"], paths);
+
+    // Explicit order takes precedence.
+    let errors = ValidationNode::ok()
+        .with_insertion_order()
+        .and_field("a", error("1"))
+        .and_field("b", error("2").with_order(0));
+    assert_eq!(".a: 1\n.b: 2", errors.to_string());
+}
+
+#[test]
+fn walk() {
+    #[derive(Default)]
+    struct PathCollector {
+        path: Path,
+        errors: Vec<(String, String)>,
+    }
+
+    impl Visit for PathCollector {
+        fn enter_field(&mut self, name: &str) {
+            self.path.push(PathSegment::Field(name.to_owned().into()));
+        }
+        fn leave_field(&mut self, _name: &str) {
+            self.path.pop();
+        }
+        fn enter_item(&mut self, item: &PathSegment) {
+            self.path.push(item.clone());
+        }
+        fn leave_item(&mut self, _item: &PathSegment) {
+            self.path.pop();
+        }
+        fn visit_error(&mut self, error: &ValidationError) {
+            self.errors
+                .push((self.path.to_string(), error.code().to_owned()));
+        }
+    }
+
+    let errors = tree().and_keyed_item("k", ValidationNode::error(ValidationError::with_code("x")));
+    let mut collector = PathCollector::default();
+    errors.walk(&mut collector);
+
+    let expected: Vec<_> = errors
+        .iter()
+        .map(|(path, error)| (path.to_string(), error.code().to_owned()))
+        .collect();
+    assert_eq!(expected, collector.errors);
+    assert!(collector.path.is_root());
+}
+
+#[test]
+fn diff() {
+    let error = |code: &'static str| ValidationError::with_code(code);
+
+    let old = ValidationNode::error(error("a"))
+        .and_error(error("a"))
+        .and_error(error("b"))
+        .and_field("x", ValidationNode::error(error("c")));
+    let new = ValidationNode::error(error("a"))
+        .and_error(error("b"))
+        .and_error(error("b"))
+        .and_item(0, ValidationNode::error(error("c")));
+
+    let diff = old.diff(&new);
+    let added: Vec<_> = diff
+        .added()
+        .iter()
+        .map(|(path, error)| (path.to_string(), error.code()))
+        .collect();
+    let removed: Vec<_> = diff
+        .removed()
+        .iter()
+        .map(|(path, error)| (path.to_string(), error.code()))
+        .collect();
+    assert_eq!(
+        vec![(".".to_string(), "b"), (".[0]".to_string(), "c")],
+        added
+    );
+    assert_eq!(
+        vec![(".".to_string(), "a"), (".x".to_string(), "c")],
+        removed
+    );
+
+    assert!(ValidationNode::ok().diff(&ValidationNode::ok()).is_empty());
+    assert_eq!("", old.diff(&old.clone()).to_string());
+    assert_eq!(
+        "- .: a\n- .: a\n- .: b\n- .x: c",
+        old.diff(&ValidationNode::ok()).to_string()
+    );
+}
+
+#[test]
+fn error_sinks() {
+    let mut first = FirstError::default();
+    tree().drain_into(&mut first);
+    assert_eq!(
+        tree().first_error().map(|(path, _)| path),
+        first.path().cloned()
+    );
+    assert_eq!("one", first.error().unwrap().code());
+
+    struct Tree;
+
+    impl Validate for Tree {
+        fn validate(&self) -> ValidationNode {
+            tree()
+        }
+    }
+
+    let mut count = ErrorCount::default();
+    Tree.validate_into(&mut count);
+    assert_eq!(tree().error_count(), count.count());
+
+    let mut list = Vec::new();
+    tree().drain_into(&mut list);
+    let expected: Vec<_> = tree().into_iter().collect();
+    assert_eq!(expected, list);
+}
@@ -0,0 +1,98 @@
+use std::cell::Cell;
+
+use not_so_fast::*;
+
+fn error(code: &'static str) -> ValidationNode {
+    ValidationNode::error(ValidationError::with_code(code))
+}
+
+#[test]
+fn children_are_evaluated_without_limits() {
+    let calls = Cell::new(0);
+    let child = |code| {
+        calls.set(calls.get() + 1);
+        error(code)
+    };
+
+    let errors = ValidationNode::ok()
+        .and_field_with("a", || child("abc"))
+        .and_item_with(3, || child("def"))
+        .and_field_with("b", ValidationNode::ok);
+
+    assert_eq!(2, calls.get());
+    assert_eq!(".a: abc\n.[3]: def", errors.to_string());
+}
+
+#[test]
+fn children_are_evaluated_under_budget() {
+    let errors = ValidationLimits::new().with_max_errors(3).run(|| {
+        error("abc")
+            .and_field_with("a", || error("def"))
+            .and_item_with(0, || error("ghi"))
+    });
+
+    assert_eq!(".: abc\n.a: def\n.[0]: ghi", errors.to_string());
+}
+
+#[test]
+fn children_are_skipped_when_budget_is_exhausted() {
+    let calls = Cell::new(0);
+    let errors = ValidationLimits::new().with_max_errors(2).run(|| {
+        ValidationNode::ok()
+            .and_field_with("a", || error("abc").merge(error("def")))
+            .and_field_with("b", || {
+                calls.set(calls.get() + 1);
+                error("ghi")
+            })
+            .and_item_with(0, || {
+                calls.set(calls.get() + 1);
+                error("jkl")
+            })
+    });
+
+    assert_eq!(0, calls.get());
+    assert_eq!(
+        ".: truncated: Too many errors: omitted=0, skipped=2\n.a: abc\n.a: def",
+        errors.to_string()
+    );
+}
+
+#[test]
+fn errors_of_nested_values_count_towards_budget() {
+    let calls = Cell::new(0);
+    let errors = ValidationLimits::new().with_max_errors(1).run(|| {
+        ValidationNode::ok()
+            .and_field("a", ValidationNode::nested(|| error("abc")))
+            .and_field_with("b", || {
+                calls.set(calls.get() + 1);
+                error("def")
+            })
+    });
+
+    assert_eq!(0, calls.get());
+    assert_eq!(
+        ".: truncated: Too many errors: omitted=0, skipped=1\n.a: abc",
+        errors.to_string()
+    );
+}
+
+#[test]
+fn panicking_nested_validation_leaves_its_level() {
+    let errors = ValidationLimits::new()
+        .with_max_depth(1)
+        .with_max_errors(2)
+        .run(|| {
+            let result = std::panic::catch_unwind(|| {
+                ValidationNode::nested(|| {
+                    let _ = ValidationNode::field("a", error("abc"));
+                    panic!("validator bug");
+                })
+            });
+            assert!(result.is_err());
+            ValidationNode::ok()
+                .and_field("a", ValidationNode::nested(|| error("def")))
+                .and_field_with("b", || error("ghi"))
+        });
+
+    assert_eq!(".a: def\n.b: ghi", errors.to_string());
+}
@@ -0,0 +1,135 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+
+#[test]
+fn validation_error_with_code_only() {
+    assert_eq!(
+        ValidationError::with_code("invalid"),
+        validation_error!("invalid")
+    );
+}
+
+#[test]
+fn validation_error_with_message_and_params() {
+    let max = 5;
+    let error = validation_error!(
+        "length",
+        message = "Invalid length",
+        value = "abcdefg".len(),
+        max = max,
+    );
+
+    assert_eq!(
+        ValidationError::with_code("length")
+            .and_message("Invalid length")
+            .and_param("value", 7usize)
+            .and_param("max", 5),
+        error
+    );
+    assert_eq!("Invalid length", error.message().unwrap());
+}
+
+#[test]
+fn validation_error_with_severity() {
+    let error = validation_error!("deprecated", severity = Severity::Info, since = "1.2");
+
+    assert_eq!(Severity::Info, error.severity());
+    assert_eq!(
+        ".: deprecated: since=\"1.2\"",
+        ValidationNode::error(error).to_string()
+    );
+}
+
+#[test]
+fn empty_node_is_ok() {
+    let node = node! {};
+
+    assert!(node.is_ok());
+}
+
+#[test]
+fn node_matches_builder() {
+    let built = node! {
+        validation_error!("conflict"),
+        .name => { validation_error!("length", max = 5) },
+        .pet_names => {
+            validation_error!("length", max = 2),
+            [0] => { validation_error!("length", max = 10), validation_error!("ascii") },
+            [2] => { validation_error!("length", max = 10) },
+        },
+        .tags => {
+            ["rust"] => { validation_error!("duplicate") },
+            [String::from("go")] => { validation_error!("unknown") },
+        },
+    };
+    let expected = ValidationNode::error(ValidationError::with_code("conflict"))
+        .and_field(
+            "name",
+            ValidationNode::error(ValidationError::with_code("length").and_param("max", 5)),
+        )
+        .and_field(
+            "pet_names",
+            ValidationNode::error(ValidationError::with_code("length").and_param("max", 2))
+                .and_item(
+                    0,
+                    ValidationNode::error(
+                        ValidationError::with_code("length").and_param("max", 10),
+                    )
+                    .and_error(ValidationError::with_code("ascii")),
+                )
+                .and_item(
+                    2,
+                    ValidationNode::error(
+                        ValidationError::with_code("length").and_param("max", 10),
+                    ),
+                ),
+        )
+        .and_field(
+            "tags",
+            ValidationNode::ok()
+                .and_keyed_item(
+                    "rust",
+                    ValidationNode::error(ValidationError::with_code("duplicate")),
+                )
+                .and_keyed_item(
+                    "go",
+                    ValidationNode::error(ValidationError::with_code("unknown")),
+                ),
+        );
+
+    assert_eq!(expected, built);
+}
+
+#[test]
+fn node_with_expressions_and_merges() {
+    let address = node! { .city => { validation_error!("required") } };
+    let extra = node! { .zip => { validation_error!("format") } };
+    let index = 3;
+
+    let node = node! {
+        .address => address,
+        ."first-name" => ValidationNode::error(validation_error!("required")),
+        .items => { [index] => node! { validation_error!("range") } },
+        ..extra,
+    };
+
+    assert_eq!(
+        ".address.city: required\n\
+         .\"first-name\": required\n\
+         .items[3]: range\n\
+         .zip: format",
+        node.to_string()
+    );
+}
+
+#[test]
+fn node_with_ok_children_is_ok() {
+    let node = node! {
+        .name => {},
+        [0] => ValidationNode::ok(),
+    };
+
+    assert!(node.is_ok());
+}
@@ -91,3 +91,48 @@ fn stateful_item_validation() {
     assert!(validate_unique_numbers(&[1, 4, 5, 6, 8, 9]).is_ok());
     assert!(validate_unique_numbers(&[1, 2, 3, 2, 4, 5, 6, 7]).is_err());
 }
+
+#[test]
+fn lazy_user() {
+    struct User {
+        name: String,
+        emails: Vec<String>,
+    }
+
+    fn validate_email(email: &str) -> ValidationNode {
+        ValidationNode::error_if(!email.contains('@'), || ValidationError::with_code("email"))
+    }
+
+    // Deferred validations borrow the validated value.
+    fn validate_user(user: &User) -> LazyNode<'_> {
+        ValidationNode::field(
+            "name",
+            ValidationNode::error_if(user.name.is_empty(), || ValidationError::with_code("empty")),
+        )
+        .and_field_lazy("emails", || {
+            ValidationNode::items(user.emails.iter(), |_, email| validate_email(email))
+        })
+    }
+
+    let user = User {
+        name: "".into(),
+        emails: vec!["a@b.c".into(), "abc".into()],
+    };
+    assert_eq!(
+        ".emails[1]: email\n.name: empty",
+        validate_user(&user).evaluate().to_string()
+    );
+    assert_eq!(
+        ".name: empty",
+        validate_user(&user).evaluate_fail_fast().to_string()
+    );
+
+    let user = User {
+        name: "Tom".into(),
+        emails: vec!["abc".into()],
+    };
+    assert_eq!(
+        ".emails[0]: email",
+        validate_user(&user).evaluate_fail_fast().to_string()
+    );
+}
@@ -0,0 +1,112 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use not_so_fast::*;
+
+#[derive(Validate)]
+struct Order {
+    #[validate(range(min = 1))]
+    quantity: u32,
+    #[validate(items(char_length(max = 5)))]
+    tags: Vec<String>,
+}
+
+fn order(quantity: u32, tags: &[&str]) -> Order {
+    Order {
+        quantity,
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+    }
+}
+
+/// Observer recording calls as lines of text.
+#[derive(Default, Clone)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl ValidationObserver for Recorder {
+    fn on_validated(&self, type_name: &str, ok: bool, error_count: usize, _: Duration) {
+        self.0.lock().unwrap().push(format!(
+            "validated {type_name} ok={ok} errors={error_count}"
+        ));
+    }
+
+    fn on_error(&self, type_name: &str, path: &Path, error: &ValidationError) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("error {type_name} {path} {}", error.code()));
+    }
+}
+
+#[test]
+fn validate_observed_by_reports_outcomes() {
+    let recorder = Recorder::default();
+    assert!(order(1, &["new"]).validate_observed_by(&recorder).is_ok());
+    let errors = order(0, &["new", "discounted"]).validate_observed_by(&recorder);
+    assert_eq!(2, errors.error_count());
+    assert_eq!(
+        vec![
+            "validated observer::Order ok=true errors=0",
+            "error observer::Order .quantity range",
+            "error observer::Order .tags[1] char_length",
+            "validated observer::Order ok=false errors=2",
+        ],
+        *recorder.0.lock().unwrap(),
+    );
+}
+
+#[test]
+fn validate_observed_reports_to_global_observer() {
+    let recorder = Recorder::default();
+    assert!(set_observer(recorder.clone()));
+    assert!(order(0, &[]).validate_observed().is_err());
+    assert_eq!(
+        vec![
+            "error observer::Order .quantity range",
+            "validated observer::Order ok=false errors=1",
+        ],
+        *recorder.0.lock().unwrap(),
+    );
+}
+
+#[test]
+fn metrics_observer_records_counters() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        let observer = MetricsObserver::with_prefix("shop");
+        order(1, &[]).validate_observed_by(&observer);
+        order(0, &["discounted"]).validate_observed_by(&observer);
+        order(0, &[]).validate_observed_by(&observer);
+    });
+
+    let mut metrics: Vec<_> = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value)| {
+            let labels: Vec<_> = key
+                .key()
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect();
+            let value = match value {
+                DebugValue::Counter(value) => value.to_string(),
+                DebugValue::Histogram(values) => format!("{} samples", values.len()),
+                DebugValue::Gauge(value) => value.to_string(),
+            };
+            format!("{}{{{}}} {}", key.key().name(), labels.join(","), value)
+        })
+        .collect();
+    metrics.sort();
+    assert_eq!(
+        vec![
+            "shop_validation_duration_seconds{type=observer::Order} 3 samples",
+            "shop_validation_errors_total{type=observer::Order,code=char_length,severity=error} 1",
+            "shop_validation_errors_total{type=observer::Order,code=range,severity=error} 2",
+            "shop_validations_total{type=observer::Order,outcome=invalid} 2",
+            "shop_validations_total{type=observer::Order,outcome=valid} 1",
+        ],
+        metrics,
+    );
+}
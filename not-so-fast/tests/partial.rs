@@ -0,0 +1,136 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[validate(custom = check_profile)]
+struct Profile {
+    #[validate(char_length(min = 1, max = 10))]
+    name: String,
+    #[validate(range(max = 150))]
+    age: u32,
+    #[validate(items(char_length(max = 5)))]
+    tags: Vec<String>,
+    #[validate(nested)]
+    address: Address,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[validate(custom = check_address)]
+struct Address {
+    #[validate(char_length(max = 10))]
+    street: String,
+    #[validate(char_length(max = 10))]
+    city: String,
+    country: String,
+}
+
+fn check_profile(profile: &Profile) -> ValidationNode {
+    ValidationNode::error_if(profile.age < 18 && !profile.tags.is_empty(), || {
+        ValidationError::with_code("minor_tags")
+    })
+}
+
+fn check_address(address: &Address) -> ValidationNode {
+    ValidationNode::error_if(address.country == "NL" && address.city == "Paris", || {
+        ValidationError::with_code("city_country")
+    })
+}
+
+/// Stored profile, already invalid before the update.
+fn stored() -> Profile {
+    Profile {
+        name: "Tom".into(),
+        age: 200,
+        tags: vec![],
+        address: Address {
+            street: "Very long street name".into(),
+            city: "Amsterdam".into(),
+            country: "NL".into(),
+        },
+    }
+}
+
+#[test]
+fn errors_of_absent_fields_are_dropped() {
+    let mut profile = stored();
+    profile.name = "Thomas Anderson".into();
+    assert_eq!(
+        ".name: char_length: Invalid character length: max=10, min=1, value=15",
+        profile
+            .validate_partial(&FieldMask::new().with_field("name"))
+            .to_string()
+    );
+    assert!(stored().validate_partial(&FieldMask::new()).is_ok());
+}
+
+#[test]
+fn cross_field_invariants_of_parents_are_kept() {
+    let mut profile = stored();
+    profile.address.city = "Paris".into();
+    profile.tags = vec!["a".into(), "toolong".into()];
+    profile.age = 10;
+    let present = FieldMask::new()
+        .with_path(".address.city".parse().unwrap())
+        .with_field("tags");
+    assert_eq!(
+        [
+            ".: minor_tags",
+            ".address: city_country",
+            ".tags[1]: char_length: Invalid character length: max=5, value=7",
+        ]
+        .join("\n"),
+        profile.validate_partial(&present).to_string()
+    );
+}
+
+#[test]
+fn mask_from_patch_body() {
+    let body = json!({ "age": 300, "address": { "street": "Short" } });
+    let mask = FieldMask::from_json(&body);
+    assert_eq!(
+        vec![".address.street", ".age"],
+        mask.paths()
+            .map(|path| path.to_string())
+            .collect::<Vec<_>>()
+    );
+
+    let mut profile = stored();
+    profile.age = 300;
+    profile.address.street = "Short".into();
+    assert_eq!(
+        ".age: range: Number not in range: max=150, value=300",
+        profile.validate_partial(&mask).to_string()
+    );
+
+    assert!(FieldMask::from_json(&json!({})).paths().next().is_none());
+    assert_eq!(
+        vec![".address"],
+        FieldMask::from_json(&json!({ "address": {} }))
+            .paths()
+            .map(|path| path.to_string())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn retain_present_with_args() {
+    #[derive(Validate)]
+    #[validate(args(max: u32))]
+    struct Limits {
+        #[validate(range(max = max))]
+        a: u32,
+        #[validate(range(max = max))]
+        b: u32,
+    }
+
+    let mut errors = Limits { a: 5, b: 5 }.validate_args((1,));
+    errors.retain_present(&[".b".parse().unwrap()].into_iter().collect());
+    assert_eq!(
+        ".b: range: Number not in range: max=1, value=5",
+        errors.to_string()
+    );
+}
@@ -0,0 +1,126 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+
+#[test]
+fn display_and_parse_round_trip() {
+    let paths = [
+        Path::root(),
+        Path::root().and_field("a"),
+        Path::root().and_index(0).and_field("a_b1"),
+        Path::root().and_key("abc").and_index(12),
+        Path::root().and_field("").and_field("with space"),
+        Path::root().and_field("quote\"backslash\\").and_key("\"\\"),
+        Path::root().and_field("zażółć").and_key("ünï"),
+    ];
+    for path in paths {
+        let text = path.to_string();
+        assert_eq!(Ok(path), text.parse::<Path>(), "{}", text);
+    }
+}
+
+#[test]
+fn paths_from_iter_match_display() {
+    let errors = ValidationNode::field(
+        "a b",
+        ValidationNode::keyed_item(
+            "\"k\"",
+            ValidationNode::error(ValidationError::with_code("x")),
+        ),
+    )
+    .and_item(2, ValidationNode::error(ValidationError::with_code("y")));
+    for (line, (path, _)) in errors.to_string().lines().zip(errors.iter()) {
+        let (text, _) = line.split_once(": ").unwrap();
+        assert_eq!(path, text.parse::<Path>().unwrap());
+    }
+}
+
+#[test]
+fn parse_errors() {
+    for (input, position) in [
+        ("", 0),
+        ("a", 0),
+        (".a..b", 3),
+        (".a.", 3),
+        (".[", 2),
+        (".[x]", 2),
+        (".[1", 3),
+        (".a b", 2),
+        (".\"abc", 5),
+        (".\"\\x\"", 3),
+    ] {
+        assert_eq!(
+            position,
+            input.parse::<Path>().unwrap_err().position(),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn ordering() {
+    let mut paths: Vec<Path> = [".b", ".a[1]", ".a", ".a[0]", "."]
+        .iter()
+        .map(|path| path.parse().unwrap())
+        .collect();
+    paths.sort();
+    assert_eq!(
+        vec![".", ".a", ".a[0]", ".a[1]", ".b"],
+        paths.iter().map(ToString::to_string).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn insert_at_path() {
+    let errors = ValidationNode::field(
+        "a",
+        ValidationNode::item(1, ValidationNode::error(ValidationError::with_code("abc"))),
+    )
+    .and_at(r#".a[1]["k"]"#, ValidationError::with_code("def"))
+    .and_at(".a[1]", ValidationError::with_code("ghi"));
+
+    let expected = ValidationNode::field(
+        "a",
+        ValidationNode::item(
+            1,
+            ValidationNode::error(ValidationError::with_code("abc"))
+                .and_error(ValidationError::with_code("ghi"))
+                .and_keyed_item(
+                    "k",
+                    ValidationNode::error(ValidationError::with_code("def")),
+                ),
+        ),
+    );
+    assert_eq!(expected, errors);
+
+    // Errors inserted at a path are found at the same path.
+    for path in [".x", ".x[0]", r#".x["y"].z"#] {
+        let path: Path = path.parse().unwrap();
+        let errors = ValidationNode::ok().and_at(path.clone(), ValidationError::with_code("abc"));
+        assert_eq!(Some(path), errors.first_error().map(|(path, _)| path));
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid path")]
+fn insert_at_invalid_path() {
+    let _ = ValidationNode::ok().and_at("a.b", ValidationError::with_code("abc"));
+}
+
+#[test]
+fn try_insert_at_path() {
+    let errors = ValidationNode::ok()
+        .try_and_at(r#".a[1]["k"]"#, ValidationError::with_code("abc"))
+        .unwrap();
+    assert_eq!(
+        ValidationNode::ok().and_at(r#".a[1]["k"]"#, ValidationError::with_code("abc")),
+        errors
+    );
+
+    let error = ValidationNode::ok()
+        .try_and_at(".a[x]", ValidationError::with_code("abc"))
+        .unwrap_err();
+    assert_eq!(3, error.position());
+}
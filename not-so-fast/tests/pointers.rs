@@ -0,0 +1,79 @@
+use not_so_fast::*;
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Validate)]
+struct Pet {
+    #[validate(char_length(max = 5))]
+    name: String,
+}
+
+#[derive(Validate)]
+#[validate(args(max_age: u32))]
+struct Owner {
+    #[validate(custom(function = check_age, args(max_age)))]
+    age: u32,
+}
+
+fn check_age(age: &u32, max_age: u32) -> ValidationNode {
+    ValidationNode::error_if(*age > max_age, || ValidationError::with_code("age"))
+}
+
+fn errors<T: Validate>(value: T) -> String {
+    value.validate().to_string()
+}
+
+#[test]
+fn pointers_validate_pointed_to_value() {
+    let pet = Pet {
+        name: "Alexander".into(),
+    };
+    let expected = ".name: char_length: Invalid character length: max=5, value=9";
+    assert_eq!(expected, errors(&pet));
+    assert_eq!(expected, errors::<&mut Pet>(&mut pet.clone()));
+    assert_eq!(expected, errors(Box::new(pet.clone())));
+    assert_eq!(expected, errors(Rc::new(pet.clone())));
+    assert_eq!(expected, errors(Arc::new(pet.clone())));
+    assert_eq!(expected, errors(Cow::Borrowed(&pet)));
+    assert_eq!(expected, errors(Cow::<Pet>::Owned(pet.clone())));
+    assert_eq!(expected, errors::<&&Box<Pet>>(&&Box::new(pet)));
+}
+
+#[test]
+fn pointers_pass_args_to_pointed_to_value() {
+    let owner = Owner { age: 40 };
+    assert!(owner.validate_args((30,)).is_err());
+    assert!(<&Owner>::validate_args(&&owner, (30,)).is_err());
+    assert!(Box::new(&owner).validate_args((30,)).is_err());
+    assert!(Arc::new(owner).validate_args((50,)).is_ok());
+}
+
+#[derive(Validate)]
+struct Household<'a> {
+    #[validate(nested)]
+    first: Box<Pet>,
+    #[validate(items(nested))]
+    others: Vec<Rc<Pet>>,
+    #[validate(nested)]
+    borrowed: &'a Pet,
+}
+
+#[test]
+fn nested_pointers_are_validated() {
+    let pet = Pet { name: "Rex".into() };
+    let long = Pet {
+        name: "Alexander".into(),
+    };
+    let household = Household {
+        first: Box::new(long.clone()),
+        others: vec![Rc::new(pet.clone()), Rc::new(long.clone())],
+        borrowed: &long,
+    };
+    assert_eq!(
+        ".borrowed.name: char_length: Invalid character length: max=5, value=9\n\
+         .first.name: char_length: Invalid character length: max=5, value=9\n\
+         .others[1].name: char_length: Invalid character length: max=5, value=9",
+        household.validate().to_string(),
+    );
+}
@@ -0,0 +1,115 @@
+use not_so_fast::*;
+use proptest::prelude::*;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Validate)]
+#[validate(proptest)]
+#[allow(dead_code)]
+struct Order {
+    #[validate(char_length(min = 1, max = 8))]
+    customer: String,
+    #[validate(range(min = 1, max = 100))]
+    quantity: u32,
+    #[validate(range(min = -1.5, max = 1.5))]
+    discount: f64,
+    #[validate(some(length(equal = 4)))]
+    coupon: Option<String>,
+    #[validate(length(max = 3), items(nested))]
+    lines: Vec<Line>,
+    #[validate(items(range(max = 10)))]
+    tags: BTreeSet<u8>,
+    #[validate(nested)]
+    address: Address,
+    note: bool,
+}
+
+#[derive(Debug, Validate)]
+#[validate(proptest)]
+struct Line(
+    #[validate(range(min = 1))] u16,
+    #[validate(length(min = 1, max = 2))] Vec<u8>,
+);
+
+#[derive(Debug, Validate)]
+#[validate(proptest, custom = check_address)]
+struct Address {
+    #[validate(char_length(max = 5))]
+    city: String,
+    #[validate(length(equal = 2))]
+    country: String,
+}
+
+fn check_address(address: &Address) -> ValidationNode {
+    ValidationNode::error_if(address.city == address.country, || {
+        ValidationError::with_code("same")
+    })
+}
+
+#[derive(Debug, Validate)]
+#[validate(proptest)]
+#[allow(dead_code)]
+struct Unconstrained {
+    flag: bool,
+}
+
+proptest! {
+    #[test]
+    fn valid_values(order in Order::arbitrary_valid()) {
+        prop_assert!(order.validate().is_ok(), "{}", order.validate());
+    }
+
+    #[test]
+    fn invalid_values(order in Order::arbitrary_invalid()) {
+        prop_assert!(order.validate().is_err());
+    }
+
+    #[test]
+    fn invalid_nested_values(address in Address::arbitrary_invalid(), line in Line::arbitrary_invalid()) {
+        prop_assert!(address.validate().is_err());
+        prop_assert!(line.validate().is_err());
+    }
+
+    #[test]
+    fn unconstrained_values(value in Unconstrained::arbitrary_valid()) {
+        prop_assert!(value.validate().is_ok());
+    }
+}
+
+#[test]
+fn invalid_values_violate_each_constraint() {
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    let mut runner = TestRunner::deterministic();
+    let mut paths = BTreeSet::new();
+    for _ in 0..500 {
+        let order = Order::arbitrary_invalid()
+            .new_tree(&mut runner)
+            .unwrap()
+            .current();
+        for (path, _) in order.validate().iter() {
+            paths.insert(
+                path.segments()[..1]
+                    .iter()
+                    .cloned()
+                    .collect::<Path>()
+                    .to_string(),
+            );
+        }
+    }
+    assert_eq!(
+        [
+            ".address",
+            ".coupon",
+            ".customer",
+            ".discount",
+            ".lines",
+            ".quantity",
+            ".tags",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect::<BTreeSet<_>>(),
+        paths
+    );
+}
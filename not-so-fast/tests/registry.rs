@@ -0,0 +1,113 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::dynamic::DynValidator;
+use not_so_fast::*;
+use serde_json::{json, Value};
+use std::sync::Once;
+
+fn no_profanity(text: &str) -> ValidationNode {
+    ValidationNode::error_if(text.contains("heck"), || {
+        ValidationError::with_code("profanity").and_message("Profanity not allowed")
+    })
+}
+
+/// Registers the global registry shared by all tests of this binary.
+fn install_registry() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let mut registry = ValidatorRegistry::new();
+        registry
+            .register("no_profanity", |text: &String| no_profanity(text))
+            .register("no_profanity", |value: &Value| {
+                value.as_str().map_or_else(ValidationNode::ok, no_profanity)
+            })
+            .register("balanced", |pair: &Pair| {
+                ValidationNode::error_if(pair.left != pair.right, || {
+                    ValidationError::with_code("unbalanced")
+                })
+            });
+        assert!(set_validator_registry(registry));
+    });
+}
+
+#[derive(Validate)]
+struct Comment {
+    #[validate(custom(name = "no_profanity"))]
+    text: String,
+    #[validate(custom(name = "not_registered"))]
+    author: String,
+}
+
+#[derive(Validate)]
+#[validate(custom(name = "balanced"))]
+struct Pair {
+    left: u32,
+    right: u32,
+}
+
+#[test]
+fn local_registry() {
+    let mut registry = ValidatorRegistry::new();
+    registry.register("no_profanity", |text: &String| no_profanity(text));
+
+    assert!(registry.contains::<String>("no_profanity"));
+    assert!(!registry.contains::<Value>("no_profanity"));
+    assert!(registry
+        .validate("no_profanity", &"hello".to_string())
+        .is_ok());
+    assert_eq!(
+        ".: profanity: Profanity not allowed",
+        registry
+            .validate("no_profanity", &"oh heck".to_string())
+            .to_string()
+    );
+    // Validators are registered per value type.
+    assert_eq!(
+        ".: unknown_validator: Unknown validator: name=\"no_profanity\"",
+        registry
+            .validate("no_profanity", &json!("oh heck"))
+            .to_string()
+    );
+}
+
+#[test]
+fn derive_uses_global_registry() {
+    install_registry();
+    let comment = Comment {
+        text: "what the heck".into(),
+        author: "Tom".into(),
+    };
+    assert_eq!(
+        [
+            ".author: unknown_validator: Unknown validator: name=\"not_registered\"",
+            ".text: profanity: Profanity not allowed",
+        ]
+        .join("\n"),
+        comment.validate().to_string()
+    );
+    assert!(Pair { left: 1, right: 1 }.validate().is_ok());
+    assert_eq!(
+        ".: unbalanced",
+        Pair { left: 1, right: 2 }.validate().to_string()
+    );
+}
+
+#[test]
+fn dynamic_rules_use_registry() {
+    install_registry();
+    let validator =
+        DynValidator::from_json(r#"{ "fields": { "text": [{ "custom": "no_profanity" }] } }"#)
+            .unwrap();
+    let input = json!({ "text": "oh heck" });
+    assert_eq!(
+        ".text: profanity: Profanity not allowed",
+        validator.validate(&input).to_string()
+    );
+    assert_eq!(
+        ".text: unknown_validator: Unknown validator: name=\"no_profanity\"",
+        validator
+            .validate_with(&input, &ValidatorRegistry::new())
+            .to_string()
+    );
+}
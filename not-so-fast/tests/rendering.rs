@@ -4,6 +4,7 @@ extern crate pretty_assertions;
 use not_so_fast::*;
 
 #[test]
+#[allow(clippy::useless_vec)]
 fn simple() {
     let errors = ValidationNode::ok()
         .and_error(
@@ -128,4 +129,606 @@ fn simple() {
         ].join("\n"),
         errors_text.to_string()
     );
+
+    assert_eq!(
+        [
+            "- one: Test message one: param1=\"value1\"",
+            "field_a:",
+            "  - two",
+            "  - three",
+            "field_b:",
+            "  [0]:",
+            "    - four",
+            "  [1]:",
+            "    - five",
+            "    - six",
+            "\"field_c_~!@#$%^&*()_+\":",
+            "  - seven",
+            "[0]:",
+            "  - eight",
+            "[1]:",
+            "  [2]:",
+            "    - nine",
+            "[2]:",
+            "  - c: p01=true, p02=1, p03=1, p04=1, p05=1, p06=1, p07=1, p08=1, p09=1, p10=1, p11=1, p12=1, p13=1.1, p14=1.1, p15='\\n', p16=\"one\\ntwo\", p17=\"three\\nfour\", p18=five\nsix"
+        ]
+        .join("\n"),
+        format!("{:#}", errors)
+    );
+    assert_eq!("", format!("{:#}", ValidationNode::ok()));
+}
+
+#[test]
+fn tree_format() {
+    let errors = ValidationNode::error(
+        ValidationError::with_code("one")
+            .and_message("Test message one")
+            .and_param("param1", "value1"),
+    )
+    .and_field(
+        "errors",
+        ValidationNode::error(ValidationError::with_code("two")).with_order(1),
+    )
+    .and_field(
+        "field_b",
+        ValidationNode::ok()
+            .and_item(
+                1,
+                ValidationNode::error(ValidationError::with_code("three")),
+            )
+            .and_keyed_item(
+                "1",
+                ValidationNode::error(ValidationError::with_code("four").and_tag("pii")),
+            ),
+    )
+    .and_item(
+        0,
+        ValidationNode::error(
+            ValidationError::with_code("five")
+                .and_param("max", 10u8)
+                .and_param("value", 12.5)
+                .and_param("flag", true)
+                .and_param("char", 'x'),
+        ),
+    );
+
+    assert_eq!(
+        serde_json::json!({
+            "errors": [
+                { "code": "one", "message": "Test message one", "params": { "param1": "value1" } }
+            ],
+            "fields": {
+                "errors": {
+                    "errors": [{ "code": "two" }],
+                    "order": 1
+                },
+                "field_b": {
+                    "items": {
+                        "1": { "errors": [{ "code": "three" }] }
+                    },
+                    "keyed_items": {
+                        "1": { "errors": [{ "code": "four", "tags": ["pii"] }] }
+                    }
+                }
+            },
+            "items": {
+                "0": {
+                    "errors": [
+                        { "code": "five", "params": { "char": "x", "flag": true, "max": 10, "value": 12.5 } }
+                    ]
+                }
+            }
+        }),
+        serde_json::to_value(errors.as_tree()).unwrap()
+    );
+    assert_eq!(
+        serde_json::json!({}),
+        serde_json::to_value(ValidationNode::ok().as_tree()).unwrap()
+    );
+}
+
+#[test]
+fn tree_format_round_trip() {
+    let errors = ValidationNode::error(
+        ValidationError::with_code("one")
+            .and_message("Test message one")
+            .and_param("param1", "value1"),
+    )
+    .and_field(
+        "b",
+        ValidationNode::error(ValidationError::with_code("two").and_severity(Severity::Warning))
+            .with_order(0),
+    )
+    .and_field(
+        "a",
+        ValidationNode::ok()
+            .and_item(
+                1,
+                ValidationNode::error(ValidationError::with_code("three")),
+            )
+            .and_keyed_item(
+                "1",
+                ValidationNode::error(ValidationError::with_code("four").and_tag("pii")),
+            ),
+    )
+    .and_item(
+        0,
+        ValidationNode::error(
+            ValidationError::with_code("five")
+                .and_param("max", 10u8)
+                .and_param("min", -10)
+                .and_param("value", 12.5)
+                .and_param("flag", true),
+        ),
+    );
+
+    let json = serde_json::to_string(&errors.as_tree()).unwrap();
+    let deserialized: ValidationNode = serde_json::from_str(&json).unwrap();
+    assert_eq!(errors.to_string(), deserialized.to_string());
+    assert_eq!(
+        json,
+        serde_json::to_string(&deserialized.as_tree()).unwrap()
+    );
+    assert!(json.contains(r#""severity":"warning""#));
+    assert!(deserialized.has_warnings());
+    assert!(json.contains(r#""tags":["pii"]"#));
+    assert!(deserialized.has_tag("pii"));
+    assert!(!deserialized
+        .at(&".b".parse().unwrap())
+        .unwrap()
+        .has_errors());
+
+    let empty: ValidationNode =
+        serde_json::from_str(r#"{"fields": {"a": {}}, "items": {"3": {"errors": []}}}"#).unwrap();
+    assert!(empty.is_ok());
+
+    assert!(serde_json::from_str::<ValidationError>(r#"{"message": "no code"}"#).is_err());
+}
+
+#[test]
+fn tree_format_round_trip_with_nulls() {
+    let errors = ValidationNode::error(
+        ValidationError::with_code("one")
+            .and_param("document", serde_json::json!({"a": null, "b": [1, null]}))
+            .and_param("missing", serde_json::Value::Null),
+    );
+
+    let json = serde_json::to_string(&errors.as_tree()).unwrap();
+    let deserialized: ValidationNode = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        json,
+        serde_json::to_string(&deserialized.as_tree()).unwrap()
+    );
+    assert_eq!(
+        Some(&ParamValue::Json(serde_json::Value::Null)),
+        deserialized.first_error().unwrap().1.param("missing")
+    );
+}
+
+#[test]
+fn rich_params() {
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    let error = ValidationError::with_code("c")
+        .and_param("bytes", ParamValue::Bytes(b"a\x00\"".as_slice().into()))
+        .and_param("duration", Duration::from_millis(1500))
+        .and_param("list", vec![ParamValue::from(1), ParamValue::from("x")])
+        .and_param("map", BTreeMap::from([("k", vec![true])]));
+
+    assert_eq!(
+        r#"c: bytes=b"a\x00\"", duration=1.5s, list=[1, "x"], map={"k": [true]}"#,
+        error.to_string()
+    );
+    assert_eq!(
+        serde_json::json!({
+            "code": "c",
+            "params": {
+                "bytes": [97, 0, 34],
+                "duration": 1.5,
+                "list": [1, "x"],
+                "map": { "k": [true] }
+            }
+        }),
+        serde_json::to_value(&error).unwrap()
+    );
+
+    let deserialized: ValidationError =
+        serde_json::from_value(serde_json::to_value(&error).unwrap()).unwrap();
+    assert_eq!(
+        r#"c: bytes=[97, 0, 34], duration=1.5, list=[1, "x"], map={"k": [true]}"#,
+        deserialized.to_string()
+    );
+}
+
+#[test]
+fn json_param() {
+    let error = ValidationError::with_code("c").and_param(
+        "document",
+        serde_json::json!({ "name": "x", "tags": [1, 2] }),
+    );
+    assert_eq!(
+        r#"c: document={"name":"x","tags":[1,2]}"#,
+        error.to_string()
+    );
+    assert_eq!(
+        serde_json::json!({
+            "code": "c",
+            "params": { "document": { "name": "x", "tags": [1, 2] } }
+        }),
+        serde_json::to_value(&error).unwrap()
+    );
+}
+
+#[test]
+fn display_options() {
+    let errors = ValidationNode::error(ValidationError::with_code("root"))
+        .and_field(
+            "a/b~c",
+            ValidationNode::keyed_item(
+                "k.1",
+                ValidationNode::error(ValidationError::with_code("x").and_message("X")),
+            ),
+        )
+        .and_item(
+            2,
+            ValidationNode::error(ValidationError::with_code("y").and_param("max", 1)),
+        );
+
+    assert_eq!(
+        errors.to_string(),
+        errors.display_with(DisplayOptions::new()).to_string()
+    );
+    assert_eq!(
+        "root\n/a~1b~0c/k.1: x: X\n/2: y: max=1",
+        errors
+            .display_with(DisplayOptions::new().with_path_style(PathStyle::JsonPointer))
+            .to_string()
+    );
+    assert_eq!(
+        "root | a/b~c.k.1: x | 2: y",
+        errors
+            .display_with(
+                DisplayOptions::new()
+                    .with_path_style(PathStyle::Dotted)
+                    .with_separator(" | ")
+                    .with_messages(false)
+                    .with_params(false)
+            )
+            .to_string()
+    );
+    assert_eq!(
+        ".: root\n... 2 more",
+        errors
+            .display_with(DisplayOptions::new().with_max_errors(1))
+            .to_string()
+    );
+    assert_eq!(
+        "... 3 more",
+        errors
+            .display_with(DisplayOptions::new().with_max_errors(0))
+            .to_string()
+    );
+}
+
+#[test]
+fn write_to_reports_io_errors() {
+    struct Failing;
+
+    impl std::io::Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "closed",
+            ))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let errors = ValidationNode::error(ValidationError::with_code("abc"));
+    let error = errors
+        .write_to(&mut Failing, &DisplayOptions::new())
+        .unwrap_err();
+    assert_eq!(std::io::ErrorKind::BrokenPipe, error.kind());
+
+    let mut buffer = Vec::new();
+    errors
+        .write_to(&mut buffer, &DisplayOptions::new().with_separator(";"))
+        .unwrap();
+    assert_eq!(errors.to_string().as_bytes(), buffer.as_slice());
+}
+
+#[test]
+fn budgeted_rendering() {
+    let errors = ValidationNode::items([1, 2, 3].iter(), |index, _value| {
+        ValidationNode::error(ValidationError::with_code("x".repeat(index + 1)))
+    });
+    // ".[0]: x" = 7, ".[1]: xx" = 8, ".[2]: xxx" = 9 bytes
+    let full = errors.to_string();
+    assert_eq!(26, full.len());
+    assert_eq!(full, errors.to_string_budgeted(26));
+    // The second error has to make room for the marker.
+    assert_eq!(".[0]: x\n... 2 more", errors.to_string_budgeted(25));
+    assert_eq!(".[0]: x\n... 2 more", errors.to_string_budgeted(18));
+    assert_eq!("... 3 more", errors.to_string_budgeted(10));
+    assert_eq!("", errors.to_string_budgeted(9));
+    assert_eq!("", ValidationNode::ok().to_string_budgeted(0));
+    for budget in 0..30 {
+        assert!(errors.to_string_budgeted(budget).len() <= budget);
+    }
+}
+
+#[test]
+fn resolved_messages() {
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    let errors = ValidationNode::field(
+        "a",
+        ValidationNode::error(ValidationError::with_code("known").and_message("Known")),
+    )
+    .and_field(
+        "b",
+        ValidationNode::error(ValidationError::with_code("unknown").and_message("Unknown")),
+    )
+    .and_field(
+        "c",
+        ValidationNode::error(ValidationError::with_code("known")),
+    );
+    let resolver = |code: &str, _: &BTreeMap<Cow<'static, str>, ParamValue>| {
+        (code == "known").then(|| "Znany".to_string())
+    };
+
+    assert_eq!(
+        ".a: known: Znany\n.b: unknown: Unknown\n.c: known: Znany",
+        errors.display_resolved(&resolver).to_string()
+    );
+    // Resolved messages are not printed when messages are disabled.
+    let options = DisplayOptions::new()
+        .with_messages(false)
+        .with_max_errors(2);
+    assert_eq!(
+        ".a: known\n.b: unknown\n... 1 more",
+        errors
+            .display_with(options)
+            .with_resolver(&resolver)
+            .to_string()
+    );
+}
+
+#[test]
+fn message_catalog() {
+    let catalog = MessageCatalog::new()
+        .with_template("a", "{missing} {x}{y} {{x}} }{ {x")
+        .with_template("b", "char {c}, list {l}, empty {}");
+    let errors = ValidationNode::field(
+        "a",
+        ValidationNode::error(
+            ValidationError::with_code("a")
+                .and_param("x", 1)
+                .and_param("y", "two"),
+        ),
+    )
+    .and_field(
+        "b",
+        ValidationNode::error(
+            ValidationError::with_code("b")
+                .and_param("c", 'c')
+                .and_param("l", vec![1, 2]),
+        ),
+    );
+    let options = DisplayOptions::new().with_params(false);
+
+    assert_eq!(
+        ".a: a: {missing} 1two {x} }{ {x\n.b: b: char c, list [1, 2], empty {}",
+        errors
+            .display_with(options)
+            .with_resolver(&catalog)
+            .to_string()
+    );
+}
+
+#[test]
+fn any_alternatives() {
+    let errors = ValidationNode::any([
+        ValidationNode::error(ValidationError::with_code("a").and_param("max", 3)),
+        ValidationNode::field("x", ValidationNode::error(ValidationError::with_code("b")))
+            .and_error(ValidationError::with_code("c")),
+    ]);
+    assert_eq!(
+        r#".: any: None of the alternatives is valid: alternatives=[["a: max=3"], ["c", ".x: b"]]"#,
+        errors.to_string()
+    );
+    assert_eq!(
+        ".: any: None of the alternatives is valid: alternatives=[]",
+        ValidationNode::any([]).to_string()
+    );
+}
+
+#[test]
+fn lazy_messages() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let errors = ValidationNode::field(
+        "name",
+        ValidationNode::error(
+            ValidationError::with_code("length")
+                .and_param("max", 3)
+                .and_message_with(move || {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    format!("Longer than {} characters", 3)
+                }),
+        ),
+    );
+
+    assert!(errors.is_err());
+    assert_eq!(1, errors.error_count());
+    assert_eq!(0, calls.load(Ordering::SeqCst));
+
+    let copy = errors.clone();
+    assert_eq!(
+        ".name: length: Longer than 3 characters: max=3",
+        errors.to_string()
+    );
+    assert_eq!(errors.to_string(), copy.to_string());
+    assert_eq!(1, calls.load(Ordering::SeqCst));
+
+    assert_eq!(
+        ValidationNode::field(
+            "name",
+            ValidationNode::error(
+                ValidationError::with_code("length")
+                    .and_param("max", 3)
+                    .and_message("Longer than 3 characters"),
+            ),
+        ),
+        errors
+    );
+}
+
+#[test]
+fn problem_details() {
+    let errors = ValidationNode::error(ValidationError::with_code("invariant"))
+        .and_field(
+            "a/b",
+            ValidationNode::keyed_item(
+                "x~y",
+                ValidationNode::error(ValidationError::with_code("abc").and_message("Bad value")),
+            ),
+        )
+        .and_field(
+            "tags",
+            ValidationNode::item(
+                3,
+                ValidationNode::error(ValidationError::with_code("empty")),
+            ),
+        );
+    let problem = errors
+        .to_problem(
+            "Your request is invalid",
+            "https://example.com/problems/invalid",
+        )
+        .with_status(400)
+        .with_detail("3 errors")
+        .with_instance("/orders/7");
+
+    assert_eq!(Problem::CONTENT_TYPE, "application/problem+json");
+    assert_eq!(
+        serde_json::json!({
+            "type": "https://example.com/problems/invalid",
+            "title": "Your request is invalid",
+            "status": 400,
+            "detail": "3 errors",
+            "instance": "/orders/7",
+            "errors": [
+                { "path": "", "code": "invariant" },
+                { "path": "/a~1b/x~0y", "code": "abc", "message": "Bad value" },
+                { "path": "/tags/3", "code": "empty" },
+            ],
+        }),
+        serde_json::to_value(&problem).unwrap(),
+    );
+}
+
+#[test]
+fn flat_list() {
+    let errors = ValidationNode::error(ValidationError::with_code("invariant"))
+        .and_field(
+            "user",
+            ValidationNode::field(
+                "first name",
+                ValidationNode::error(
+                    ValidationError::with_code("length")
+                        .and_message("Too long")
+                        .and_param("max", 10),
+                ),
+            ),
+        )
+        .and_field(
+            "tags",
+            ValidationNode::item(
+                3,
+                ValidationNode::error(
+                    ValidationError::with_code("empty")
+                        .and_severity(Severity::Warning)
+                        .and_tag("ui"),
+                ),
+            ),
+        );
+
+    assert_eq!(
+        serde_json::json!([
+            { "path": "", "code": "invariant" },
+            { "path": "tags[3]", "code": "empty", "severity": "warning", "tags": ["ui"] },
+            {
+                "path": "user.\"first name\"",
+                "code": "length",
+                "message": "Too long",
+                "params": { "max": 10 },
+            },
+        ]),
+        serde_json::to_value(errors.as_flat_list()).unwrap(),
+    );
+    assert_eq!(
+        "[]",
+        serde_json::to_string(&ValidationNode::ok().as_flat_list()).unwrap(),
+    );
+}
+
+#[test]
+fn colored() {
+    let errors = ValidationNode::error(ValidationError::with_code("one"))
+        .and_field(
+            "a",
+            ValidationNode::error(
+                ValidationError::with_code("two")
+                    .and_message("Two")
+                    .and_param("min", 1)
+                    .and_param("max", 5),
+            ),
+        )
+        .and_item(
+            3,
+            ValidationNode::error(ValidationError::with_code("three").and_message("Three")),
+        );
+
+    assert_eq!(
+        "\x1b[36m.\x1b[0m: \x1b[31mone\x1b[0m\n\
+         \x1b[36m.a\x1b[0m: \x1b[31mtwo\x1b[0m: Two: \x1b[2mmax=5, min=1\x1b[0m\n\
+         \x1b[36m.[3]\x1b[0m: \x1b[31mthree\x1b[0m: Three",
+        errors.display_colored().to_string()
+    );
+}
+
+#[test]
+fn colored_with_options() {
+    let errors = ValidationNode::error(ValidationError::with_code("one").and_param("x", 1))
+        .and_field(
+            "a",
+            ValidationNode::error(ValidationError::with_code("two").and_param("y", 2)),
+        )
+        .and_field(
+            "b",
+            ValidationNode::error(ValidationError::with_code("three")),
+        );
+    let options = DisplayOptions::new()
+        .with_path_style(PathStyle::Dotted)
+        .with_params(false)
+        .with_max_errors(2)
+        .with_colors(true);
+
+    assert_eq!(
+        "\x1b[31mone\x1b[0m\n\x1b[36ma\x1b[0m: \x1b[31mtwo\x1b[0m\n... 1 more",
+        errors.display_with(options.clone()).to_string()
+    );
+    assert_eq!(
+        "one\na: two\n... 1 more",
+        errors.display_with(options.with_colors(false)).to_string()
+    );
 }
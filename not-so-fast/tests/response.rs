@@ -0,0 +1,112 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use not_so_fast::*;
+use serde::Deserialize;
+use tower::ServiceExt;
+
+#[derive(Deserialize, Validate)]
+struct Comment {
+    #[validate(char_length(min = 1, max = 5))]
+    text: String,
+    #[validate(custom = warn_if_anonymous)]
+    author: Option<String>,
+}
+
+fn warn_if_anonymous(author: &Option<String>) -> ValidationNode {
+    ValidationNode::error_if(author.is_none(), || {
+        ValidationError::with_code("anonymous").and_severity(Severity::Warning)
+    })
+}
+
+struct Valid<T>(T);
+
+impl<T> TryFromUnvalidated<T> for Valid<T> {
+    fn from_validated(value: T) -> Self {
+        Valid(value)
+    }
+}
+
+fn comment(text: &str) -> Comment {
+    Comment {
+        text: text.to_owned(),
+        author: None,
+    }
+}
+
+#[test]
+fn try_from_unvalidated_rejects_only_errors() {
+    let Valid(valid) = Valid::try_from_unvalidated(comment("hi")).ok().unwrap();
+    assert_eq!("hi", valid.text);
+
+    let errors = Valid::try_from_unvalidated(comment("")).err().unwrap();
+    assert_eq!(
+        ".author: anonymous\n.text: char_length: Invalid character length: max=5, min=1, value=0",
+        errors.to_string(),
+    );
+}
+
+#[test]
+fn response_formats() {
+    let errors = comment("").validate();
+
+    let plain = (&errors).into_validation_response(&ErrorBodyFormat::Plain);
+    assert_eq!(422, plain.status);
+    assert_eq!("application/json", plain.content_type);
+    assert_eq!(serde_json::to_string(&errors).unwrap(), plain.body);
+
+    let tree = (&errors).into_validation_response(&ErrorBodyFormat::Tree);
+    assert_eq!("application/json", tree.content_type);
+    assert_eq!(serde_json::to_string(&errors.as_tree()).unwrap(), tree.body);
+
+    let flat = (&errors).into_validation_response(&ErrorBodyFormat::FlatList);
+    assert_eq!("application/json", flat.content_type);
+    assert_eq!(
+        r#"[{"path":"author","code":"anonymous","severity":"warning"},{"path":"text","code":"char_length","message":"Invalid character length","params":{"max":5,"min":1,"value":0}}]"#,
+        flat.body,
+    );
+
+    let problem = errors.into_validation_response(&ErrorBodyFormat::Problem {
+        title: "Invalid comment".into(),
+        type_uri: "about:blank".into(),
+    });
+    assert_eq!(422, problem.status);
+    assert_eq!("application/problem+json", problem.content_type);
+    assert_eq!(
+        r#"{"type":"about:blank","title":"Invalid comment","status":422,"errors":[{"path":"/author","code":"anonymous"},{"path":"/text","code":"char_length","message":"Invalid character length"}]}"#,
+        problem.body,
+    );
+}
+
+#[tokio::test]
+async fn axum_rejection_uses_global_format() {
+    assert!(set_error_body_format(ErrorBodyFormat::Problem {
+        title: "Invalid comment".into(),
+        type_uri: "about:blank".into(),
+    }));
+
+    let app = Router::new().route(
+        "/comments",
+        post(|ValidatedJson(comment): ValidatedJson<Comment>| async move { comment.text }),
+    );
+    let request = Request::post("/comments")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"text":"too long","author":"me"}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+    assert_eq!(
+        "application/problem+json",
+        response.headers()[header::CONTENT_TYPE]
+    );
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(
+        r#"{"type":"about:blank","title":"Invalid comment","status":422,"errors":[{"path":"/text","code":"char_length","message":"Invalid character length"}]}"#,
+        String::from_utf8(body.to_vec()).unwrap(),
+    );
+}
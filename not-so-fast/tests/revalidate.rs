@@ -0,0 +1,153 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+
+#[derive(Validate)]
+#[validate(incremental, custom = check_period)]
+struct Event {
+    #[validate(char_length(min = 1, max = 10))]
+    title: String,
+    start: u32,
+    #[validate(range(max = 100))]
+    end: u32,
+    #[validate(items(char_length(max = 5)))]
+    tags: Vec<String>,
+    #[validate(nested)]
+    venue: Venue,
+}
+
+#[derive(Validate)]
+struct Venue {
+    #[validate(range(min = 1))]
+    capacity: u32,
+}
+
+fn check_period(event: &Event) -> ValidationNode {
+    ValidationNode::ok().and_field(
+        "end",
+        ValidationNode::error_if(event.end < event.start, || {
+            ValidationError::with_code("before_start")
+        }),
+    )
+}
+
+fn event() -> Event {
+    Event {
+        title: "Conference".into(),
+        start: 10,
+        end: 20,
+        tags: vec!["rust".into()],
+        venue: Venue { capacity: 100 },
+    }
+}
+
+fn paths(paths: &[&str]) -> Vec<Path> {
+    paths.iter().map(|path| path.parse().unwrap()).collect()
+}
+
+#[test]
+fn revalidation_matches_full_validation() {
+    let mut event = event();
+    let mut revalidation = Revalidation::new(&event);
+    assert!(revalidation.node().is_ok());
+
+    event.tags.push("conference".into());
+    event.venue.capacity = 0;
+    assert_eq!(
+        &event.validate(),
+        revalidation.revalidate(&event, &paths(&[".tags[1]", ".venue.capacity"]))
+    );
+    assert_eq!(
+        [
+            ".tags[1]: char_length: Invalid character length: max=5, value=10",
+            ".venue.capacity: range: Number not in range: min=1, value=0",
+        ]
+        .join("\n"),
+        revalidation.node().to_string()
+    );
+
+    event.tags.pop();
+    revalidation.revalidate(&event, &paths(&[".tags"]));
+    assert_eq!(&event.validate(), revalidation.node());
+}
+
+#[test]
+fn unchanged_fields_keep_errors() {
+    let mut event = event();
+    event.title = String::new();
+    let mut revalidation = Revalidation::new(&event);
+
+    // Changes not reported as changed are not revalidated.
+    event.title = "Meetup".into();
+    event.end = 200;
+    revalidation.revalidate(&event, &paths(&[".end"]));
+    assert_eq!(
+        [
+            ".end: range: Number not in range: max=100, value=200",
+            ".title: char_length: Invalid character length: max=10, min=1, value=0",
+        ]
+        .join("\n"),
+        revalidation.node().to_string()
+    );
+}
+
+#[test]
+fn cross_field_errors_are_invalidated() {
+    let mut event = event();
+    event.start = 30;
+    let mut revalidation = Revalidation::new(&event);
+    assert_eq!(".end: before_start", revalidation.node().to_string());
+
+    // The error is reported at `.end`, but depends on `.start`.
+    event.start = 15;
+    revalidation.revalidate(&event, &paths(&[".start"]));
+    assert!(revalidation.node().is_ok());
+
+    event.start = 200;
+    event.end = 150;
+    assert_eq!(
+        [
+            ".end: before_start",
+            ".end: range: Number not in range: max=100, value=150",
+        ]
+        .join("\n"),
+        event.revalidate(&paths(&[".end"])).to_string()
+    );
+}
+
+#[test]
+fn whole_value_changes() {
+    let mut event = event();
+    let mut revalidation = Revalidation::new(&event);
+    event.title = String::new();
+    event.venue.capacity = 0;
+    revalidation.revalidate(&event, &[Path::root()]);
+    assert_eq!(&event.validate(), revalidation.node());
+    assert_eq!(event.validate(), revalidation.into_node());
+}
+
+#[derive(Validate)]
+#[validate(incremental)]
+struct Listing {
+    #[validate(char_length(max = 5))]
+    name: String,
+    #[validate(flatten)]
+    venue: Venue,
+}
+
+#[test]
+fn flattened_fields_revalidate_on_any_change() {
+    assert_eq!(&[Some("name"), None], Listing::FIELDS);
+    let mut listing = Listing {
+        name: "Hall".into(),
+        venue: Venue { capacity: 10 },
+    };
+    let mut revalidation = Revalidation::new(&listing);
+    listing.venue.capacity = 0;
+    revalidation.revalidate(&listing, &paths(&[".capacity"]));
+    assert_eq!(
+        ".capacity: range: Number not in range: min=1, value=0",
+        revalidation.node().to_string()
+    );
+}
@@ -0,0 +1,132 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+use rocket::form::Form;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::{json, Json, Value};
+use rocket::{catch, catchers, get, post, routes, FromForm, Request};
+use serde::Deserialize;
+
+#[derive(Deserialize, FromForm, Validate)]
+struct User {
+    #[validate(char_length(min = 1, max = 5))]
+    name: String,
+    #[validate(custom = warn_if_young)]
+    age: u8,
+    #[validate(length(max = 2))]
+    tags: Vec<String>,
+}
+
+fn warn_if_young(age: &u8) -> ValidationNode {
+    ValidationNode::error_if(*age < 18, || {
+        ValidationError::with_code("young").and_severity(Severity::Warning)
+    })
+}
+
+#[post("/json", data = "<user>")]
+fn json_user(user: Validated<Json<User>>) -> String {
+    user.into_inner().into_inner().name
+}
+
+#[post("/form", data = "<user>")]
+fn form_user(user: Form<Validated<User>>) -> String {
+    user.into_inner().into_inner().name
+}
+
+#[get("/query?<user..>")]
+fn query_user(user: Validated<User>) -> String {
+    user.into_inner().name
+}
+
+#[catch(422)]
+fn unprocessable(request: &Request<'_>) -> Value {
+    json!({ "errors": validation_errors(request) })
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![json_user, form_user, query_user])
+        .register("/", catchers![unprocessable]);
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn validated_json() {
+    let client = client();
+    let post = |body: &str| {
+        let response = client
+            .post("/json")
+            .header(ContentType::JSON)
+            .body(body)
+            .dispatch();
+        (response.status(), response.into_string().unwrap())
+    };
+    assert_eq!(
+        (Status::Ok, "tom".to_owned()),
+        post(r#"{"name": "tom", "age": 10, "tags": []}"#),
+    );
+    assert_eq!(
+        (
+            Status::UnprocessableEntity,
+            r#"{"errors":{"name":{"errors":["char_length: Invalid character length: max=5, min=1, value=0"]}}}"#.to_owned(),
+        ),
+        post(r#"{"name": "", "age": 30, "tags": []}"#),
+    );
+    assert_eq!(Status::BadRequest, post(r#"{"name": "tom""#).0);
+}
+
+#[test]
+fn validated_form() {
+    let client = client();
+    let response = client
+        .post("/form")
+        .header(ContentType::Form)
+        .body("name=tom&age=10&tags=a")
+        .dispatch();
+    assert_eq!(Status::Ok, response.status());
+    assert_eq!("tom", response.into_string().unwrap());
+
+    let response = client
+        .post("/form")
+        .header(ContentType::Form)
+        .body("name=thomas&age=30&tags=a&tags=b&tags=c")
+        .dispatch();
+    assert_eq!(Status::UnprocessableEntity, response.status());
+}
+
+#[test]
+fn validated_query() {
+    let client = client();
+    let response = client.get("/query?name=tom&age=30").dispatch();
+    assert_eq!(Status::Ok, response.status());
+    assert_eq!("tom", response.into_string().unwrap());
+
+    let response = client.get("/query?name=thomas&age=30").dispatch();
+    assert_eq!(Status::UnprocessableEntity, response.status());
+    assert_eq!(r#"{"errors":null}"#, response.into_string().unwrap());
+}
+
+#[test]
+fn form_errors() {
+    let errors = Form::<Validated<User>>::parse("name=thomas&age=10&tags=a&tags=b&tags=c")
+        .map(|_| ())
+        .unwrap_err();
+    let errors: Vec<_> = errors
+        .iter()
+        .map(|error| {
+            (
+                error.name.as_ref().unwrap().to_string(),
+                error.kind.to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("name".to_owned(), "Invalid character length".to_owned()),
+            ("tags".to_owned(), "Invalid length".to_owned()),
+        ],
+        errors,
+    );
+}
@@ -0,0 +1,179 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::rules::{ListRules, NumberRules, Rules, StringRules};
+use not_so_fast::*;
+
+#[test]
+fn user() {
+    struct User {
+        name: String,
+        age: u8,
+        pet_names: Vec<String>,
+    }
+
+    #[derive(Validate)]
+    struct DerivedUser {
+        #[validate(length(max = 5))]
+        name: String,
+        #[validate(range(min = 3))]
+        age: u8,
+        #[validate(length(max = 2), items(length(max = 10)))]
+        pet_names: Vec<String>,
+    }
+
+    let name = StringRules::new().max_bytes(5);
+    let age = NumberRules::new().min(3);
+    let pet_names = ListRules::new()
+        .max_items(2)
+        .items(StringRules::new().max_bytes(10));
+    let validate_user = |user: &User| {
+        ValidationNode::ok()
+            .and_field("name", name.validate(&user.name))
+            .and_field("age", age.validate(&user.age))
+            .and_field("pet_names", pet_names.validate(&user.pet_names))
+    };
+
+    let pets = vec![
+        "asdjaiu sdhuyags ydgaysd".to_string(),
+        "aisud 8asydahsbdjabsd".to_string(),
+        "a8 7d8diu h788yhkahsd78".to_string(),
+    ];
+    let errors = validate_user(&User {
+        name: "easdiuasd&&&&&".into(),
+        age: 2,
+        pet_names: pets.clone(),
+    });
+    let derived_errors = DerivedUser {
+        name: "easdiuasd&&&&&".into(),
+        age: 2,
+        pet_names: pets,
+    }
+    .validate();
+
+    assert_eq!(
+        ".age: range: Number not in range: min=3, value=2
+.name: length: Invalid length: max=5, value=14
+.pet_names: length: Invalid length: max=2, value=3
+.pet_names[0]: length: Invalid length: max=10, value=24
+.pet_names[1]: length: Invalid length: max=10, value=21
+.pet_names[2]: length: Invalid length: max=10, value=23",
+        errors.to_string()
+    );
+    assert_eq!(derived_errors.to_string(), errors.to_string());
+}
+
+#[test]
+fn string_rules() {
+    let rules = StringRules::new().min_chars(3).max_bytes(6).ascii();
+
+    assert!(rules.validate("abc").is_ok());
+    assert_eq!(
+        ".: char_length: Invalid character length: min=3, value=2",
+        rules.validate("ab").to_string()
+    );
+    assert_eq!(
+        ".: length: Invalid length: max=6, value=7",
+        rules.validate("abcdefg").to_string()
+    );
+    assert_eq!(
+        ".: length: Invalid length: max=6, value=7\n\
+         .: ascii: String contains non-ASCII characters",
+        rules.validate("żółw").to_string()
+    );
+}
+
+#[test]
+fn char_and_byte_bounds_are_separate() {
+    let rules = StringRules::new().max_chars(3).max_bytes(4);
+
+    assert!(rules.validate("日").is_ok());
+    assert_eq!(
+        ".: length: Invalid length: max=4, value=6",
+        rules.validate("日本").to_string()
+    );
+    assert_eq!(
+        ".: char_length: Invalid character length: max=3, value=4",
+        rules.validate("abcd").to_string()
+    );
+}
+
+#[test]
+fn number_rules() {
+    let rules = NumberRules::new().min(-5).max(5);
+
+    assert!(rules.validate(&-5).is_ok());
+    assert!(rules.validate(&5).is_ok());
+    assert_eq!(
+        ".: range: Number not in range: max=5, min=-5, value=6",
+        rules.validate(&6).to_string()
+    );
+}
+
+#[test]
+fn custom_rules_run_after_built_in_rules() {
+    let even = NumberRules::new().max(10u32).custom(|value| {
+        ValidationNode::error_if(value % 2 == 1, || ValidationError::with_code("even"))
+    });
+    let slug = StringRules::new().max_chars(4).custom(|value| {
+        ValidationNode::error_if(value.contains(' '), || ValidationError::with_code("slug"))
+    });
+
+    assert!(even.validate(&4).is_ok());
+    assert_eq!(
+        ".: range: Number not in range: max=10, value=11\n.: even",
+        even.validate(&11).to_string()
+    );
+    assert_eq!(
+        ".: char_length: Invalid character length: max=4, value=5\n.: slug",
+        slug.validate("a b c").to_string()
+    );
+}
+
+#[test]
+fn nested_list_rules() {
+    let matrix = ListRules::new().min_items(1).items(
+        ListRules::new()
+            .max_items(2)
+            .items(NumberRules::new().min(0)),
+    );
+
+    assert!(matrix.validate(&[vec![0, 1], vec![]]).is_ok());
+    assert_eq!(
+        ".: length: Invalid length: min=1, value=0",
+        matrix.validate::<Vec<i32>>(&[]).to_string()
+    );
+    assert_eq!(
+        ".[0]: length: Invalid length: max=2, value=3\n.[1][0]: range: Number not in range: min=0, value=-1",
+        matrix.validate(&[vec![1, 2, 3], vec![-1]]).to_string()
+    );
+}
+
+#[test]
+fn functions_as_item_rules() {
+    let names = ListRules::new().items(|name: &String| {
+        ValidationNode::error_if(name.is_empty(), || ValidationError::with_code("required"))
+    });
+
+    assert_eq!(
+        ".[1]: required",
+        names
+            .validate(&["a".to_string(), String::new()])
+            .to_string()
+    );
+}
+
+#[test]
+fn rules_are_shared_between_validators() {
+    fn check<T: ?Sized>(rules: &dyn Rules<T>, value: &T) -> ValidationNode {
+        rules.validate(value)
+    }
+
+    let name = StringRules::new().max_chars(3);
+    let cloned = name.clone();
+    let in_thread = std::thread::spawn(move || cloned.validate("abcd").is_err());
+
+    assert!(in_thread.join().unwrap());
+    assert!(check::<String>(&name, &"abcd".to_string()).is_err());
+    assert!(check::<str>(&name, "abc").is_ok());
+}
@@ -0,0 +1,99 @@
+use fake::faker::internet::en::SafeEmail;
+use fake::faker::name::en::Name;
+use not_so_fast::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Validate)]
+#[validate(sample)]
+#[allow(dead_code)]
+struct Customer {
+    #[validate(char_length(min = 1, max = 40), sample = Name())]
+    name: String,
+    #[validate(custom = check_email, sample = SafeEmail())]
+    email: String,
+    #[validate(range(min = 18, max = 120))]
+    age: u8,
+    #[validate(range(min = -1.5, max = 1.5))]
+    discount: f64,
+    #[validate(some(length(equal = 4)))]
+    coupon: Option<String>,
+    #[validate(length(max = 3), items(nested))]
+    orders: Vec<Order>,
+    #[validate(items(range(max = 10)))]
+    tags: BTreeSet<u8>,
+    #[validate(nested)]
+    address: Address,
+    vip: bool,
+}
+
+#[derive(Debug, Validate)]
+#[validate(sample)]
+struct Order(
+    #[validate(range(min = 1))] u16,
+    #[validate(length(min = 1, max = 2))] Vec<u8>,
+);
+
+#[derive(Debug, Validate)]
+#[validate(sample, custom = check_address)]
+struct Address {
+    #[validate(char_length(max = 5))]
+    city: String,
+    #[validate(length(equal = 2))]
+    country: String,
+    #[validate(sample = 1000..2000)]
+    zip: u32,
+}
+
+fn check_email(email: &str) -> ValidationNode {
+    ValidationNode::error_if(!email.contains('@'), || ValidationError::with_code("email"))
+}
+
+fn check_address(address: &Address) -> ValidationNode {
+    ValidationNode::error_if(address.city == address.country, || {
+        ValidationError::with_code("same")
+    })
+}
+
+#[test]
+fn sampled_values_are_valid() {
+    for _ in 0..100 {
+        let customer = Customer::sample();
+        assert!(customer.validate().is_ok(), "{customer:?}");
+        assert!(customer.email.contains('@'));
+        assert!((1000..2000).contains(&customer.address.zip));
+    }
+}
+
+#[test]
+fn sampling_is_reproducible_with_seeded_rng() {
+    let first = Customer::sample_with_rng(&mut StdRng::seed_from_u64(7));
+    let second = Customer::sample_with_rng(&mut StdRng::seed_from_u64(7));
+    assert_eq!(format!("{first:?}"), format!("{second:?}"));
+}
+
+#[test]
+fn sampled_lengths_are_within_bounds() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..100 {
+        let customer = Customer::sample_with_rng(&mut rng);
+        assert!(customer.orders.len() <= 3);
+        assert!(customer.coupon.is_none_or(|coupon| coupon.len() == 4));
+        assert_eq!(2, customer.address.country.len());
+        assert!((18..=120).contains(&customer.age));
+    }
+}
+
+#[derive(Debug, Validate)]
+#[validate(sample)]
+struct Impossible {
+    #[validate(range(min = 10, max = 5))]
+    value: u32,
+}
+
+#[test]
+#[should_panic(expected = "no valid value of sample::Impossible sampled in 1000 attempts")]
+fn unsatisfiable_validators_panic() {
+    Impossible::sample();
+}
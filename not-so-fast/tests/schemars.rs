@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use not_so_fast::*;
+use pretty_assertions::assert_eq;
+use schemars::{schema_for, JsonSchema};
+use serde_json::json;
+
+const MAX_TAGS: usize = 10;
+
+#[derive(JsonSchema, Validate)]
+#[validate(schemars)]
+#[schemars(transform = not_so_fast::augment_schema::<Self>)]
+struct Product {
+    #[validate(char_length(min = 1, max = 50))]
+    name: String,
+    #[validate(length(max = MAX_TAGS), items(char_length(equal = 3)))]
+    tags: Vec<String>,
+    #[validate(range(min = 0.5, max = 100.0))]
+    price: f64,
+    #[validate(some(range(max = 5)))]
+    rating: Option<u8>,
+    #[validate(fields(length(min = 2)))]
+    attributes: BTreeMap<String, String>,
+    #[validate(custom = validate_sku)]
+    sku: String,
+}
+
+fn validate_sku(_sku: &str) -> ValidationNode {
+    ValidationNode::ok()
+}
+
+#[test]
+fn schema_has_constraints_of_validators() {
+    let schema = schema_for!(Product);
+    let properties = schema.get("properties").unwrap();
+    assert_eq!(
+        json!({ "type": "string", "minLength": 1, "maxLength": 50 }),
+        properties["name"],
+    );
+    assert_eq!(
+        json!({
+            "type": "array",
+            "maxItems": 10,
+            "items": { "type": "string", "minLength": 3, "maxLength": 3 },
+        }),
+        properties["tags"],
+    );
+    assert_eq!(0.5, properties["price"]["minimum"]);
+    assert_eq!(100.0, properties["price"]["maximum"]);
+    assert_eq!(5, properties["rating"]["maximum"]);
+    assert_eq!(
+        json!({ "type": "string", "minLength": 2 }),
+        properties["attributes"]["additionalProperties"],
+    );
+    assert_eq!(json!({ "type": "string" }), properties["sku"]);
+}
+
+#[derive(JsonSchema, Validate)]
+#[validate(schemars)]
+#[schemars(transform = not_so_fast::augment_schema::<Self>)]
+#[serde(rename_all = "camelCase")]
+struct Renamed {
+    #[validate(length(max = 3))]
+    first_names: Vec<String>,
+    #[validate(char_length(max = 20))]
+    r#type: String,
+}
+
+#[test]
+fn schema_follows_renamed_fields() {
+    let schema = schema_for!(Renamed);
+    let properties = schema.get("properties").unwrap();
+    assert_eq!(3, properties["firstNames"]["maxItems"]);
+    assert_eq!(20, properties["type"]["maxLength"]);
+}
+
+#[derive(JsonSchema, Validate)]
+#[validate(schemars)]
+#[schemars(transform = not_so_fast::augment_schema::<Self>)]
+struct Wrapper<T> {
+    #[validate(length(min = 1))]
+    values: Vec<T>,
+}
+
+#[test]
+fn schema_of_generic_type() {
+    let schema = schema_for!(Wrapper<u32>);
+    assert_eq!(1, *schema.pointer("/properties/values/minItems").unwrap());
+}
+
+#[test]
+fn validation_is_unchanged() {
+    let product = Product {
+        name: String::new(),
+        tags: vec!["abcd".into()],
+        price: 0.0,
+        rating: Some(6),
+        attributes: BTreeMap::from([("color".into(), "r".into())]),
+        sku: "x".into(),
+    };
+    assert_eq!(
+        vec![
+            ".attributes.color",
+            ".name",
+            ".price",
+            ".rating",
+            ".tags[0]"
+        ],
+        product
+            .validate()
+            .iter()
+            .map(|(path, _)| path.to_string())
+            .collect::<Vec<_>>(),
+    );
+}
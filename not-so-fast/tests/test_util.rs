@@ -0,0 +1,69 @@
+use not_so_fast::*;
+
+#[derive(Validate)]
+struct Signup {
+    #[validate(char_length(min = 3))]
+    name: String,
+    #[validate(range(min = 18))]
+    age: u32,
+    #[validate(items(char_length(max = 5)))]
+    tags: Vec<String>,
+}
+
+fn signup() -> Signup {
+    Signup {
+        name: "Tom".into(),
+        age: 30,
+        tags: vec!["rust".into()],
+    }
+}
+
+#[test]
+fn valid_values() {
+    assert_valid!(signup());
+    let signup = signup();
+    assert_valid!(&signup);
+}
+
+#[test]
+fn invalid_values() {
+    let signup = Signup {
+        name: "T".into(),
+        age: 12,
+        tags: vec!["rust".into(), "databases".into()],
+    };
+    assert_invalid!(signup);
+    assert_invalid!(
+        signup,
+        ".tags[1]" => "char_length",
+        ".age" => "range",
+        ".name" => "char_length",
+    );
+}
+
+#[test]
+#[should_panic(expected = "expected valid value, found errors:\n.age: range")]
+fn valid_assertion_of_invalid_value() {
+    assert_valid!(Signup {
+        age: 10,
+        ..signup()
+    });
+}
+
+#[test]
+#[should_panic(expected = "expected invalid value, found no errors")]
+fn invalid_assertion_of_valid_value() {
+    assert_invalid!(signup());
+}
+
+#[test]
+#[should_panic(expected = "unexpected validation errors:\n.age: range: Number not in range")]
+fn missing_errors() {
+    assert_invalid!(Signup { age: 10, ..signup() }, ".age" => "range", ".name" => "char_length");
+}
+
+#[test]
+#[should_panic(expected = "unexpected validation errors")]
+fn unexpected_errors() {
+    assert_invalid!(Signup { age: 10, name: String::new(), ..signup() }, ".age" => "range");
+}
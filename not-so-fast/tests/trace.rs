@@ -0,0 +1,149 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use not_so_fast::*;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Subscriber recording events and spans as lines of text.
+#[derive(Default, Clone)]
+struct Recorder {
+    lines: Arc<Mutex<Vec<String>>>,
+    spans: Arc<Mutex<Vec<String>>>,
+    entered: Arc<Mutex<Vec<u64>>>,
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        self.0.push_str(&format!("{}={:?}", field.name(), value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(String::new());
+        span.record(&mut fields);
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(format!("{}{{{}}}", span.metadata().name(), fields.0));
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        let spans = self.spans.lock().unwrap();
+        let scope: Vec<_> = self
+            .entered
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|id| spans[*id as usize - 1].clone())
+            .collect();
+        self.lines.lock().unwrap().push(format!(
+            "{} {} [{}] {}",
+            event.metadata().level(),
+            event.metadata().target(),
+            scope.join(":"),
+            fields.0,
+        ));
+    }
+
+    fn enter(&self, span: &Id) {
+        self.entered.lock().unwrap().push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.entered.lock().unwrap().pop();
+    }
+}
+
+fn record(f: impl FnOnce()) -> Vec<String> {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), f);
+    let lines = recorder.lines.lock().unwrap().clone();
+    lines
+}
+
+fn errors() -> ValidationNode {
+    ValidationNode::field(
+        "age",
+        ValidationNode::error(
+            ValidationError::with_code("range")
+                .and_message("Number not in range")
+                .and_param("min", 18)
+                .and_param("value", 16),
+        ),
+    )
+    .and_item(
+        2,
+        ValidationNode::error(ValidationError::with_code("stale").and_severity(Severity::Warning)),
+    )
+}
+
+#[test]
+fn trace_emits_event_per_error() {
+    let lines = record(|| errors().trace(Level::WARN));
+    assert_eq!(
+        vec![
+            r#"WARN not_so_fast [] message=Number not in range path=.age code="range" params=min=18, value=16 severity="error""#,
+            r#"WARN not_so_fast [] message=stale path=.[2] code="stale" params= severity="warning""#,
+        ],
+        lines,
+    );
+}
+
+#[test]
+fn trace_errors_with_target() {
+    let lines = record(|| {
+        trace_errors!(target: "app::signup", Level::ERROR, &errors());
+        trace_errors!(
+            Level::DEBUG,
+            &ValidationNode::error(ValidationError::with_code("a"))
+        );
+    });
+    assert_eq!(
+        vec![
+            r#"ERROR app::signup [] message=Number not in range path=.age code="range" params=min=18, value=16 severity="error""#,
+            r#"ERROR app::signup [] message=stale path=.[2] code="stale" params= severity="warning""#,
+            r#"DEBUG trace [] message=a path=. code="a" params= severity="error""#,
+        ],
+        lines,
+    );
+}
+
+#[test]
+fn derive_trace_runs_validation_in_span() {
+    fn check_name(name: &str) -> ValidationNode {
+        tracing::info!(name, "checking name");
+        ValidationNode::ok()
+    }
+
+    #[derive(Validate)]
+    #[validate(trace)]
+    struct User {
+        #[validate(custom = check_name)]
+        name: String,
+    }
+
+    let lines = record(|| {
+        User { name: "Tom".into() }.validate();
+    });
+    assert_eq!(
+        vec![r#"INFO trace [validate{type_name="User"}] message=checking name name="Tom""#],
+        lines,
+    );
+}
@@ -0,0 +1,76 @@
+use not_so_fast::*;
+
+#[derive(Debug, Validate)]
+struct Port(#[validate(range(min = 1024))] u16);
+
+#[derive(Debug, Validate)]
+struct Host(#[validate(char_length(min = 1, max = 10))] String);
+
+fn parse_address(address: &str) -> (Host, Port) {
+    let (host, port) = address.split_once(':').unwrap();
+    (Host(host.into()), Port(port.parse().unwrap()))
+}
+
+#[test]
+fn valid_tuple() {
+    assert!(parse_address("localhost:8080").validate().is_ok());
+}
+
+#[test]
+fn errors_of_elements_are_reported_as_items() {
+    assert_eq!(
+        ".[0][0]: char_length: Invalid character length: max=10, min=1, value=0\n\
+         .[1][0]: range: Number not in range: min=1024, value=80",
+        parse_address(":80").validate().to_string(),
+    );
+}
+
+#[test]
+fn nested_tuples_and_pointers() {
+    let value = (Port(1), (Box::new(Port(2)), &Host(String::new())));
+    assert_eq!(
+        vec![".[0][0]", ".[1][0][0]", ".[1][1][0]"],
+        value
+            .validate()
+            .iter()
+            .map(|(path, _)| path.to_string())
+            .collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn largest_tuple() {
+    let port = || Port(1);
+    let value = (
+        port(),
+        port(),
+        port(),
+        port(),
+        port(),
+        port(),
+        port(),
+        port(),
+        port(),
+        port(),
+        port(),
+        port(),
+    );
+    assert_eq!(12, value.validate().iter().count());
+}
+
+#[derive(Validate)]
+struct Route {
+    #[validate(nested)]
+    endpoint: (Host, Port),
+}
+
+#[test]
+fn tuple_fields_are_nested() {
+    let route = Route {
+        endpoint: parse_address("example:22"),
+    };
+    assert_eq!(
+        ".endpoint[1][0]: range: Number not in range: min=1024, value=22",
+        route.validate().to_string(),
+    );
+}
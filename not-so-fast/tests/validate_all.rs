@@ -0,0 +1,77 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+
+#[derive(Validate)]
+struct Item {
+    #[validate(char_length(max = 3))]
+    name: String,
+}
+
+#[derive(Validate)]
+#[validate(args(reserved: &'arg [&'arg str]))]
+struct Username {
+    #[validate(custom(function = check_reserved, args(reserved)))]
+    name: String,
+}
+
+fn check_reserved(name: &str, reserved: &[&str]) -> ValidationNode {
+    ValidationNode::error_if(reserved.contains(&name), || {
+        ValidationError::with_code("reserved")
+    })
+}
+
+fn item(name: &str) -> Item {
+    Item { name: name.into() }
+}
+
+#[test]
+fn empty_iterator_is_ok() {
+    assert!(validate_all(Vec::<Item>::new()).is_ok());
+}
+
+#[test]
+fn owned_and_borrowed_elements() {
+    let items = vec![item("abcd"), item("ab"), item("abcde")];
+    let expected = ".[0].name: char_length: Invalid character length: max=3, value=4\n\
+                    .[2].name: char_length: Invalid character length: max=3, value=5";
+
+    assert_eq!(expected, validate_all(&items).to_string());
+    assert_eq!(expected, validate_all(items.iter()).to_string());
+    assert_eq!(expected, validate_all(items).to_string());
+}
+
+#[test]
+fn indices_count_yielded_elements() {
+    let items = [item("a"), item("abcd"), item("b"), item("abcde")];
+
+    let errors = validate_all(items.iter().skip(1).step_by(2));
+
+    assert_eq!(
+        ".[0].name: char_length: Invalid character length: max=3, value=4\n\
+         .[1].name: char_length: Invalid character length: max=3, value=5",
+        errors.to_string()
+    );
+}
+
+#[test]
+fn args_are_passed_to_every_element() {
+    let users = [
+        Username {
+            name: "admin".into(),
+        },
+        Username {
+            name: "alice".into(),
+        },
+        Username {
+            name: "root".into(),
+        },
+    ];
+
+    assert_eq!(
+        ".[0].name: reserved\n.[2].name: reserved",
+        validate_all_args(&users, (&["admin", "root"][..],)).to_string()
+    );
+    assert!(validate_all_args(&users, (&[][..],)).is_ok());
+}
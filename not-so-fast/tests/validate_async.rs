@@ -0,0 +1,152 @@
+use not_so_fast::*;
+use std::cell::Cell;
+use std::future::{poll_fn, Future};
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// Validates any type generically through the trait.
+async fn validate_generic<T: ValidateAsync>(value: &T) -> ValidationNode {
+    value.validate_async().await
+}
+
+#[derive(Validate)]
+struct SyncOnly {
+    #[validate(range(max = 10))]
+    a: u32,
+}
+
+#[derive(Validate)]
+struct WithAsync {
+    #[validate(range(max = 10), custom_async = check_taken)]
+    a: u32,
+}
+
+async fn check_taken(a: &u32) -> ValidationNode {
+    ValidationNode::error_if(*a == 7, || ValidationError::with_code("taken"))
+}
+
+#[derive(Validate)]
+#[validate(args(limit: u32))]
+struct WithArgs {
+    #[validate(custom_async(function = check_limit, args(limit)))]
+    a: u32,
+}
+
+async fn check_limit(a: &u32, limit: u32) -> ValidationNode {
+    ValidationNode::error_if(*a > limit, || ValidationError::with_code("limit"))
+}
+
+struct Manual(u32);
+
+impl<'arg> ValidateArgs<'arg> for Manual {
+    type Args = ();
+
+    fn validate_args(&self, _args: ()) -> ValidationNode {
+        ValidationNode::error_if(self.0 == 0, || ValidationError::with_code("zero"))
+    }
+}
+
+#[test]
+fn derived_types_implement_validate_async() {
+    assert_eq!(
+        ".a: range: Number not in range: max=10, value=11",
+        block_on(validate_generic(&SyncOnly { a: 11 })).to_string()
+    );
+    assert_eq!(
+        ".a: taken",
+        block_on(validate_generic(&WithAsync { a: 7 })).to_string()
+    );
+    assert!(block_on(validate_generic(&WithAsync { a: 5 })).is_ok());
+    assert_eq!(
+        ".a: limit",
+        block_on(ValidateAsyncArgs::validate_args_async(
+            &WithArgs { a: 5 },
+            (3,)
+        ))
+        .to_string()
+    );
+}
+
+#[test]
+fn from_sync_bridges_sync_impls() {
+    assert_eq!(
+        ".: zero",
+        block_on(validate_generic(&FromSync(&Manual(0)))).to_string()
+    );
+    assert!(block_on(validate_generic(&FromSync(&Manual(1)))).is_ok());
+}
+
+/// Future pending for `polls` polls before resolving to a node, counting
+/// polls in `counter`.
+async fn delayed(polls: usize, counter: &Cell<usize>, node: ValidationNode) -> ValidationNode {
+    let mut remaining = polls;
+    poll_fn(|_| {
+        counter.set(counter.get() + 1);
+        if remaining == 0 {
+            Poll::Ready(())
+        } else {
+            remaining -= 1;
+            Poll::Pending
+        }
+    })
+    .await;
+    node
+}
+
+#[test]
+fn join_all_polls_futures_concurrently() {
+    let counter = Cell::new(0);
+    let error = |code| ValidationNode::error(ValidationError::with_code(code));
+    let nodes = block_on(join_all([
+        delayed(2, &counter, error("a")),
+        delayed(0, &counter, error("b")),
+        delayed(1, &counter, error("c")),
+    ]));
+    assert_eq!(
+        vec![".: a", ".: b", ".: c"],
+        nodes
+            .iter()
+            .map(|node| node.to_string())
+            .collect::<Vec<_>>()
+    );
+    // Each future is polled until ready, not once more.
+    assert_eq!(3 + 1 + 2, counter.get());
+}
+
+#[test]
+fn items_async_and_all_async() {
+    let counter = Cell::new(0);
+    let items = [3, 0, 1];
+    let errors = block_on(ValidationNode::items_async(items.iter(), |_, polls| {
+        delayed(
+            *polls,
+            &counter,
+            ValidationNode::error_if(*polls > 0, || ValidationError::with_code("slow")),
+        )
+    }));
+    assert_eq!(".[0]: slow\n.[2]: slow", errors.to_string());
+
+    let errors = block_on(ValidationNode::all_async([
+        delayed(
+            1,
+            &counter,
+            ValidationNode::error(ValidationError::with_code("a")),
+        ),
+        delayed(
+            0,
+            &counter,
+            ValidationNode::error(ValidationError::with_code("b")),
+        ),
+    ]));
+    assert_eq!(".: a\n.: b", errors.to_string());
+}
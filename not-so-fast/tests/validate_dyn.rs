@@ -0,0 +1,87 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use std::sync::Arc;
+
+use not_so_fast::*;
+
+#[derive(Validate)]
+struct Name {
+    #[validate(char_length(max = 3))]
+    value: String,
+}
+
+#[derive(Validate)]
+struct Age {
+    #[validate(range(max = 150))]
+    value: u32,
+}
+
+struct Manual(bool);
+
+impl ValidateArgs<'_> for Manual {
+    type Args = ();
+
+    fn validate_args(&self, (): ()) -> ValidationNode {
+        ValidationNode::error_if(!self.0, || ValidationError::with_code("manual"))
+    }
+}
+
+#[test]
+fn heterogeneous_values_are_validated() {
+    let values: Vec<Box<dyn ValidateDyn>> = vec![
+        Box::new(Name {
+            value: "Alice".into(),
+        }),
+        Box::new(Age { value: 30 }),
+        Box::new(Manual(false)),
+    ];
+
+    let errors = ValidationNode::items(values.iter(), |_, value| value.validate_dyn());
+
+    assert_eq!(
+        ".[0].value: char_length: Invalid character length: max=3, value=5\n.[2]: manual",
+        errors.to_string()
+    );
+}
+
+#[test]
+fn trait_objects_implement_validate() {
+    fn validate(value: impl Validate) -> ValidationNode {
+        value.validate()
+    }
+
+    let boxed: Box<dyn ValidateDyn> = Box::new(Age { value: 200 });
+    let shared: Arc<dyn ValidateDyn + Send + Sync> = Arc::new(Manual(false));
+    let sendable: &(dyn ValidateDyn + Send) = &Manual(true);
+
+    assert_eq!(
+        ".value: range: Number not in range: max=150, value=200",
+        validate(boxed).to_string()
+    );
+    assert_eq!(".: manual", validate(shared).to_string());
+    assert!(validate(sendable).is_ok());
+}
+
+#[test]
+fn trait_objects_are_nested_values() {
+    #[derive(Validate)]
+    struct Form {
+        #[validate(nested)]
+        main: Box<dyn ValidateDyn>,
+        #[validate(items(nested))]
+        extra: Vec<Arc<dyn ValidateDyn + Send + Sync>>,
+    }
+
+    let form = Form {
+        main: Box::new(Name {
+            value: "Bob".into(),
+        }),
+        extra: vec![Arc::new(Manual(true)), Arc::new(Age { value: 151 })],
+    };
+
+    assert_eq!(
+        ".extra[1].value: range: Number not in range: max=150, value=151",
+        form.validate().to_string()
+    );
+}
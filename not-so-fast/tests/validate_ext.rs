@@ -0,0 +1,54 @@
+use not_so_fast::*;
+
+#[derive(Debug, PartialEq, Validate)]
+struct Pair(
+    #[validate(range(max = 10))] u32,
+    #[validate(range(max = 10))] u32,
+);
+
+fn parse_pair(input: &str) -> Pair {
+    let (a, b) = input.split_once(',').unwrap();
+    Pair(a.parse().unwrap(), b.parse().unwrap())
+}
+
+fn sum(input: &str) -> Result<u32, ValidationNode> {
+    let pair = parse_pair(input)
+        .validate_owned()
+        .map_err(|(_, errors)| errors)?;
+    Ok(pair.0 + pair.1)
+}
+
+#[test]
+fn validate_or_returns_valid_value() {
+    let pair = Pair(1, 2);
+    assert!(std::ptr::eq(&pair, pair.validate_or().unwrap()));
+    assert_eq!(
+        ".[1]: range: Number not in range: max=10, value=11",
+        Pair(1, 11).validate_or().unwrap_err().to_string(),
+    );
+}
+
+#[test]
+fn validate_owned_returns_value_with_errors() {
+    assert_eq!(Ok(3), sum("1,2"));
+    assert_eq!(
+        ".[0]: range: Number not in range: max=10, value=12",
+        sum("12,2").unwrap_err().to_string(),
+    );
+    let (pair, errors) = Pair(20, 30).validate_owned().unwrap_err();
+    assert_eq!(Pair(20, 30), pair);
+    assert_eq!(2, errors.iter().count());
+}
+
+#[test]
+fn ensure_valid_returns_errors() {
+    assert_eq!(Ok(()), Pair(1, 2).ensure_valid());
+    assert_eq!(Err(Pair(1, 11).validate()), Pair(1, 11).ensure_valid());
+}
+
+#[test]
+fn works_with_references_and_pointers() {
+    let pair = Pair(1, 2);
+    assert!(<&Pair>::ensure_valid(&&pair).is_ok());
+    assert!(Box::new(Pair(1, 20)).validate_owned().is_err());
+}
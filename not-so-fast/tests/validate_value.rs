@@ -0,0 +1,191 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::dynamic::DynValidator;
+use not_so_fast::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Serialize, Validate)]
+#[validate(dynamic)]
+struct Order {
+    #[validate(char_length(min = 1, max = 8))]
+    customer: String,
+    #[validate(length(max = 2), items(nested))]
+    lines: Vec<Line>,
+    #[validate(some(length(equal = 4)))]
+    coupon: Option<String>,
+    #[validate(fields(range(max = 1.5)))]
+    discounts: BTreeMap<String, f64>,
+    #[validate(nested)]
+    shipping: Address,
+    // Not expressible as a rule, skipped.
+    #[validate(custom = not_empty)]
+    note: String,
+}
+
+#[derive(Serialize, Validate)]
+#[validate(dynamic)]
+struct Line {
+    #[validate(range(min = 1, max = 100))]
+    quantity: u32,
+    #[validate(fields(char_length(max = 3)))]
+    attributes: HashMap<String, String>,
+}
+
+#[derive(Serialize, serde::Deserialize, Validate)]
+#[validate(dynamic)]
+struct Address {
+    #[validate(char_length(max = 5))]
+    city: String,
+}
+
+fn not_empty(value: &str) -> ValidationNode {
+    ValidationNode::error_if(value.is_empty(), || ValidationError::with_code("empty"))
+}
+
+#[test]
+fn value_errors_match_typed_errors() {
+    let order = Order {
+        customer: "Alexander the Great".into(),
+        lines: vec![
+            Line {
+                quantity: 0,
+                attributes: HashMap::from([("color".into(), "green".into())]),
+            },
+            Line {
+                quantity: 5,
+                attributes: HashMap::new(),
+            },
+            Line {
+                quantity: 500,
+                attributes: HashMap::new(),
+            },
+        ],
+        coupon: Some("SPRING".into()),
+        discounts: BTreeMap::from([("vip".into(), 2.5), ("new".into(), 0.5)]),
+        shipping: Address {
+            city: "London".into(),
+        },
+        note: String::new(),
+    };
+    let value = serde_json::to_value(&order).unwrap();
+    assert_eq!(
+        [
+            ".coupon: length: Invalid length: equal=4, value=6",
+            ".customer: char_length: Invalid character length: max=8, min=1, value=19",
+            ".discounts.vip: range: Number not in range: max=1.5, value=2.5",
+            ".lines: length: Invalid length: max=2, value=3",
+            ".lines[0].attributes.color: char_length: Invalid character length: max=3, value=5",
+            ".lines[0].quantity: range: Number not in range: max=100, min=1, value=0",
+            ".lines[2].quantity: range: Number not in range: max=100, min=1, value=500",
+            ".note: empty",
+            ".shipping.city: char_length: Invalid character length: max=5, value=6",
+        ]
+        .join("\n"),
+        order.validate().to_string()
+    );
+    assert_eq!(
+        order
+            .validate()
+            .to_string()
+            .lines()
+            .filter(|line| !line.starts_with(".note"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Order::validate_value(&value).to_string()
+    );
+}
+
+#[test]
+fn partial_values() {
+    let value = json!({
+        "customer": "Tom",
+        "coupon": null,
+        "lines": [{ "quantity": 1000, "price": 3 }],
+        "forwarded_by": "gateway",
+    });
+    assert_eq!(
+        ".lines[0].quantity: range: Number not in range: max=100, min=1, value=1000",
+        Order::validate_value(&value).to_string()
+    );
+    assert!(Order::validate_value(&json!({})).is_ok());
+}
+
+#[test]
+fn stored_validator() {
+    let validator = DynValidator::of::<Address>();
+    assert_eq!(
+        json!({ "rules": [{ "fields": { "city": [{ "char_length": { "max": 5 } }] } }] }),
+        serde_json::to_value(&validator).unwrap()
+    );
+    assert!(validator.validate(&json!({ "city": "Oslo" })).is_ok());
+}
+
+#[derive(serde::Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+#[validate(dynamic)]
+#[allow(dead_code)]
+struct Renamed {
+    #[validate(range(max = 1))]
+    first_value: u32,
+    #[serde(rename = "SECOND")]
+    #[validate(range(max = 1))]
+    second_value: u32,
+    #[serde(rename(serialize = "thirdOut", deserialize = "thirdIn"))]
+    #[validate(range(max = 1))]
+    third_value: u32,
+    #[serde(flatten)]
+    #[validate(flatten)]
+    address: Address,
+}
+
+#[test]
+fn serde_names() {
+    let value = json!({ "firstValue": 2, "SECOND": 2, "thirdIn": 2, "city": "Amsterdam" });
+    assert_eq!(
+        [
+            ".SECOND: range: Number not in range: max=1, value=2",
+            ".city: char_length: Invalid character length: max=5, value=9",
+            ".firstValue: range: Number not in range: max=1, value=2",
+            ".thirdIn: range: Number not in range: max=1, value=2",
+        ]
+        .join("\n"),
+        Renamed::validate_value(&value).to_string()
+    );
+}
+
+#[derive(Validate)]
+#[validate(dynamic, custom(name = "consistent"))]
+#[allow(dead_code)]
+struct Named {
+    #[validate(custom(name = "no_profanity"))]
+    text: String,
+}
+
+#[test]
+fn named_validators() {
+    let mut registry = ValidatorRegistry::new();
+    registry
+        .register("no_profanity", |value: &Value| {
+            ValidationNode::error_if(value == "heck", || ValidationError::with_code("profanity"))
+        })
+        .register("consistent", |value: &Value| {
+            ValidationNode::error_if(value.get("text").is_none(), || {
+                ValidationError::with_code("inconsistent")
+            })
+        });
+    assert_eq!(
+        ".: inconsistent",
+        DynValidator::of::<Named>()
+            .validate_with(&json!({}), &registry)
+            .to_string()
+    );
+    assert_eq!(
+        ".text: profanity",
+        DynValidator::of::<Named>()
+            .validate_with(&json!({ "text": "heck" }), &registry)
+            .to_string()
+    );
+}
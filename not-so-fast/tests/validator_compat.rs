@@ -0,0 +1,34 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+use not_so_fast::*;
+
+#[test]
+fn validator_round_trip() {
+    let node = ValidationNode::error(ValidationError::with_code("invariant"))
+        .and_field(
+            "name",
+            ValidationNode::error(
+                ValidationError::with_code("length")
+                    .and_message("Invalid length")
+                    .and_param("max", 10u64),
+            ),
+        )
+        .and_field(
+            "tags",
+            ValidationNode::item(
+                2,
+                ValidationNode::error(ValidationError::with_code("empty")),
+            ),
+        )
+        .and_field(
+            "address",
+            ValidationNode::error(ValidationError::with_code("incomplete")).and_field(
+                "city",
+                ValidationNode::error(ValidationError::with_code("unknown")),
+            ),
+        );
+
+    let errors = validator::ValidationErrors::from(node.clone());
+    assert_eq!(node, ValidationNode::from(errors));
+}